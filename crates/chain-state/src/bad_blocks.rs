@@ -0,0 +1,125 @@
+//! Tracking of blocks that were rejected as invalid.
+
+use parking_lot::RwLock;
+use reth_primitives::SealedBlock;
+use reth_primitives_traits::Block;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Default number of bad blocks that are retained.
+///
+/// This matches geth's `badBlockLimit`.
+pub const DEFAULT_BAD_BLOCK_LIMIT: usize = 10;
+
+/// A bounded store of the most recently observed blocks that failed validation.
+///
+/// Once the limit is reached, the oldest block is evicted. Inserting a block that is already
+/// tracked is a no-op.
+#[derive(Debug)]
+pub struct BadBlockStore<B: Block> {
+    /// Tracked bad blocks, oldest first.
+    blocks: RwLock<VecDeque<Arc<SealedBlock<B>>>>,
+    /// Maximum number of tracked bad blocks.
+    limit: usize,
+}
+
+impl<B: Block> BadBlockStore<B> {
+    /// Creates a new store that retains at most `limit` blocks.
+    pub fn new(limit: usize) -> Self {
+        Self { blocks: RwLock::new(VecDeque::with_capacity(limit)), limit }
+    }
+
+    /// Inserts a bad block, evicting the oldest tracked block if the store is full.
+    pub fn insert(&self, block: SealedBlock<B>) {
+        if self.limit == 0 {
+            return
+        }
+
+        let mut blocks = self.blocks.write();
+        if blocks.iter().any(|b| b.hash() == block.hash()) {
+            return
+        }
+        if blocks.len() >= self.limit {
+            blocks.pop_front();
+        }
+        blocks.push_back(Arc::new(block));
+    }
+
+    /// Returns all tracked bad blocks, most recently inserted first.
+    pub fn blocks(&self) -> Vec<Arc<SealedBlock<B>>> {
+        self.blocks.read().iter().rev().cloned().collect()
+    }
+
+    /// Returns the number of tracked bad blocks.
+    pub fn len(&self) -> usize {
+        self.blocks.read().len()
+    }
+
+    /// Returns `true` if no bad blocks are tracked.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.read().is_empty()
+    }
+
+    /// Removes all tracked bad blocks.
+    pub fn clear(&self) {
+        self.blocks.write().clear()
+    }
+}
+
+impl<B: Block> Default for BadBlockStore<B> {
+    fn default() -> Self {
+        Self::new(DEFAULT_BAD_BLOCK_LIMIT)
+    }
+}
+
+/// A type that provides access to the blocks that were rejected as invalid by the engine.
+pub trait BadBlockProvider: Send + Sync {
+    /// The block type.
+    type Block: Block;
+
+    /// Returns the most recently observed bad blocks, most recent first.
+    fn bad_blocks(&self) -> Vec<Arc<SealedBlock<Self::Block>>>;
+}
+
+impl<T: BadBlockProvider> BadBlockProvider for &T {
+    type Block = T::Block;
+
+    fn bad_blocks(&self) -> Vec<Arc<SealedBlock<Self::Block>>> {
+        (*self).bad_blocks()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+
+    fn block(number: u64) -> SealedBlock<reth_primitives::Block> {
+        SealedBlock::seal_parts(Header { number, ..Default::default() }, Default::default())
+    }
+
+    #[test]
+    fn evicts_oldest() {
+        let store = BadBlockStore::new(2);
+        store.insert(block(1));
+        store.insert(block(2));
+        store.insert(block(3));
+
+        let numbers = store.blocks().iter().map(|b| b.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![3, 2]);
+    }
+
+    #[test]
+    fn ignores_duplicates() {
+        let store = BadBlockStore::default();
+        store.insert(block(1));
+        store.insert(block(1));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn zero_limit() {
+        let store = BadBlockStore::new(0);
+        store.insert(block(1));
+        assert!(store.is_empty());
+    }
+}
@@ -1,12 +1,12 @@
 //! Types for tracking the canonical chain state in memory.
 
 use crate::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    BadBlockStore, CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
     ChainInfoTracker, MemoryOverlayStateProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
-use alloy_primitives::{map::HashMap, TxHash, B256};
+use alloy_primitives::{map::HashMap, BlockNumber, TxHash, B256};
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_execution_types::{Chain, ExecutionOutcome};
@@ -137,6 +137,8 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// Recently observed blocks that failed validation.
+    pub(crate) bad_blocks: BadBlockStore<N::Block>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -190,6 +192,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                bad_blocks: BadBlockStore::default(),
             }),
         }
     }
@@ -214,6 +217,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            bad_blocks: BadBlockStore::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -230,6 +234,16 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             .map(|block| block.block_ref().recovered_block().clone_sealed_header())
     }
 
+    /// Records a block that failed validation.
+    pub fn insert_bad_block(&self, block: SealedBlock<N::Block>) {
+        self.inner.bad_blocks.insert(block)
+    }
+
+    /// Returns the most recently observed bad blocks, most recent first.
+    pub fn bad_blocks(&self) -> Vec<Arc<SealedBlock<N::Block>>> {
+        self.inner.bad_blocks.blocks()
+    }
+
     /// Clears all entries in the in memory state.
     pub fn clear_state(&self) {
         self.inner.clear()
@@ -302,6 +316,28 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         }
     }
 
+    /// Removes all blocks above the given height from the in memory state, including the pending
+    /// block.
+    ///
+    /// This is used when the canonical chain is rewound to an older block.
+    pub fn remove_blocks_above(&self, number: BlockNumber) {
+        {
+            // acquire locks, starting with the numbers lock
+            let mut numbers = self.inner.in_memory_state.numbers.write();
+            let mut blocks = self.inner.in_memory_state.blocks.write();
+
+            for (_, hash) in numbers.split_off(&(number + 1)) {
+                blocks.remove(&hash);
+            }
+
+            // remove the pending state
+            self.inner.in_memory_state.pending.send_modify(|p| {
+                p.take();
+            });
+        }
+        self.inner.in_memory_state.update_metrics();
+    }
+
     /// Removes blocks from the in memory state that are persisted to the given height.
     ///
     /// This will update the links between blocks and remove all blocks that are [..
@@ -1287,6 +1323,33 @@ mod tests {
         assert_eq!(state.inner.in_memory_state.block_count(), 1);
     }
 
+    #[test]
+    fn test_in_memory_state_remove_blocks_above() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut test_block_builder: TestBlockBuilder = TestBlockBuilder::default();
+        let block1 = test_block_builder.get_executed_block_with_number(1, B256::random());
+        let block2 =
+            test_block_builder.get_executed_block_with_number(2, block1.recovered_block().hash());
+        let block3 =
+            test_block_builder.get_executed_block_with_number(3, block2.recovered_block().hash());
+
+        let chain =
+            NewCanonicalChain::Commit { new: vec![block1.clone(), block2.clone(), block3.clone()] };
+        state.update_chain(chain);
+        state.set_pending_block(block3);
+
+        state.remove_blocks_above(1);
+
+        assert_eq!(state.inner.in_memory_state.block_count(), 1);
+        assert_eq!(
+            state.head_state().unwrap().block_ref().recovered_block().hash(),
+            block1.recovered_block().hash()
+        );
+        assert!(state.state_by_hash(block2.recovered_block().hash()).is_none());
+        assert!(state.state_by_number(3).is_none());
+        assert!(state.pending_state().is_none());
+    }
+
     #[test]
     fn test_in_memory_state_set_pending_block() {
         let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
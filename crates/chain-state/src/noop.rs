@@ -1,11 +1,13 @@
 //! Noop impls for testing.
 
 use crate::{
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
+    BadBlockProvider, CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications,
     ForkChoiceSubscriptions,
 };
+use reth_primitives::SealedBlock;
 use reth_primitives_traits::NodePrimitives;
 use reth_storage_api::noop::NoopProvider;
+use std::sync::Arc;
 use tokio::sync::{broadcast, watch};
 
 impl<C: Send + Sync, N: NodePrimitives> CanonStateSubscriptions for NoopProvider<C, N> {
@@ -27,3 +29,11 @@ impl<C: Send + Sync, N: NodePrimitives> ForkChoiceSubscriptions for NoopProvider
         ForkChoiceNotifications(rx)
     }
 }
+
+impl<C: Send + Sync, N: NodePrimitives> BadBlockProvider for NoopProvider<C, N> {
+    type Block = N::Block;
+
+    fn bad_blocks(&self) -> Vec<Arc<SealedBlock<N::Block>>> {
+        Vec::new()
+    }
+}
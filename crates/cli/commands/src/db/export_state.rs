@@ -0,0 +1,415 @@
+use crate::common::CliNodeTypes;
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{Address, BlockNumber, B256};
+use clap::Parser;
+use itertools::Itertools;
+use reth_chainspec::EthereumHardforks;
+use reth_db::{tables, DatabaseEnv};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{storage_sharded_key::StorageShardedKey, IntegerList, ShardedKey},
+    transaction::DbTx,
+};
+use reth_db_common::{
+    init::{GenesisAccountWithAddress, StateRoot},
+    DbTool,
+};
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_provider::{
+    AccountReader, BlockNumReader, DBProvider, HeaderProvider, ProviderError,
+    StageCheckpointReader, StateProvider, TryIntoHistoricalStateProvider,
+};
+use reth_stages::StageId;
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Number of exported accounts between progress logs.
+const LOG_INTERVAL: usize = 100_000;
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db export-state` command
+pub struct Command {
+    /// The block number to export the state at.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    block: BlockNumber,
+
+    /// The JSONL file to write the state dump to, in the format consumed by `reth init-state`.
+    #[arg(long, short, value_name = "STATE_DUMP_FILE")]
+    output: PathBuf,
+
+    /// Also write the state as a geth-style genesis `alloc` JSON file.
+    ///
+    /// The file contains a single object mapping addresses to accounts, which can be used as the
+    /// `alloc` field of a genesis file.
+    #[arg(long, value_name = "ALLOC_FILE")]
+    alloc: Option<PathBuf>,
+
+    /// Continue an interrupted export by appending to the existing state dump file.
+    ///
+    /// Accounts are exported in ascending address order, so the export continues after the last
+    /// complete account in the file.
+    #[arg(long)]
+    resume: bool,
+}
+
+impl Command {
+    /// Execute `db export-state` command
+    pub fn execute<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+        self,
+        tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    ) -> eyre::Result<()> {
+        let provider = tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+
+        let last_block_number = provider.last_block_number()?;
+        eyre::ensure!(
+            self.block <= last_block_number,
+            "Block {} is above the last block {last_block_number}",
+            self.block
+        );
+        let state_root = provider
+            .header_by_number(self.block)?
+            .ok_or(ProviderError::HeaderNotFound(self.block.into()))?
+            .state_root();
+
+        let (file, last_address) = if self.resume && self.output.exists() {
+            resume_output(&self.output, state_root)?
+        } else {
+            eyre::ensure!(
+                !self.output.exists(),
+                "State dump file already exists: {:?}. Use --resume to continue the export.",
+                self.output
+            );
+            (reth_fs_util::create_file(&self.output)?, None)
+        };
+        let mut writer = BufWriter::new(file);
+        if last_address.is_none() && writer.get_ref().metadata()?.len() == 0 {
+            serde_json::to_writer(&mut writer, &StateRoot { root: state_root })?;
+            writer.write_all(b"\n")?;
+        }
+        if let Some(address) = last_address {
+            info!(target: "reth::cli", %address, "Resuming state export");
+        }
+
+        // Accounts and storage slots that were changed after the block are either in the plain
+        // state with a different value, or not in the plain state anymore. They're found in the
+        // last shards of the history indices, which hold the latest blocks that changed them, so
+        // both are walked in address order next to the plain state.
+        for stage in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+            eyre::ensure!(
+                checkpoint >= last_block_number,
+                "The {stage} checkpoint {checkpoint} is behind the last block {last_block_number}"
+            );
+        }
+
+        let history = tool
+            .provider_factory
+            .provider()?
+            .disable_long_read_transaction_safety()
+            .try_into_history_at_block(self.block)?;
+
+        info!(target: "reth::cli", block = self.block, root = %state_root, "Exporting state");
+        let mut accounts_cursor = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+        let mut accounts_history_cursor =
+            provider.tx_ref().cursor_read::<tables::AccountsHistory>()?;
+        let mut storage_cursor = provider.tx_ref().cursor_read::<tables::PlainStorageState>()?;
+        let mut storages_history_cursor =
+            provider.tx_ref().cursor_read::<tables::StoragesHistory>()?;
+
+        let plain_addresses =
+            accounts_cursor.walk(last_address)?.map(|entry| entry.map(|(address, _)| address));
+        let changed_addresses = accounts_history_cursor
+            .walk(last_address.map(|address| ShardedKey::new(address, 0)))?
+            .filter_map(|entry| {
+                entry
+                    .map(|(key, blocks)| {
+                        changed_after(key.highest_block_number, &blocks, self.block)
+                            .then_some(key.key)
+                    })
+                    .transpose()
+            });
+        let mut exported = 0;
+        for address in plain_addresses
+            .merge_by(changed_addresses, |a, b| a.as_ref().ok() <= b.as_ref().ok())
+            .dedup()
+        {
+            let address = address?;
+            if Some(address) <= last_address {
+                continue
+            }
+
+            let Some(account) = history.basic_account(&address)? else { continue };
+
+            // A dup walk needs an existing key, destroyed accounts have no plain storage
+            let plain_slots = storage_cursor
+                .walk_range(address..=address)?
+                .map(|entry| entry.map(|(_, entry)| entry.key));
+            let changed_slots = storages_history_cursor
+                .walk_range(
+                    StorageShardedKey::new(address, B256::ZERO, 0)..=
+                        StorageShardedKey::last(address, B256::repeat_byte(0xff)),
+                )?
+                .filter_map(|entry| {
+                    entry
+                        .map(|(key, blocks)| {
+                            changed_after(key.sharded_key.highest_block_number, &blocks, self.block)
+                                .then_some(key.sharded_key.key)
+                        })
+                        .transpose()
+                });
+            let mut storage = BTreeMap::new();
+            for slot in plain_slots
+                .merge_by(changed_slots, |a, b| a.as_ref().ok() <= b.as_ref().ok())
+                .dedup()
+            {
+                let slot = slot?;
+                if let Some(value) =
+                    history.storage(address, slot)?.filter(|value| !value.is_zero())
+                {
+                    storage.insert(slot, B256::from(value));
+                }
+            }
+
+            let code = match account.bytecode_hash.filter(|hash| *hash != KECCAK_EMPTY) {
+                Some(hash) => Some(
+                    history
+                        .bytecode_by_hash(&hash)?
+                        .ok_or_else(|| eyre::eyre!("Bytecode {hash} of {address} not found"))?
+                        .original_bytes(),
+                ),
+                None => None,
+            };
+
+            let genesis_account = GenesisAccount {
+                nonce: Some(account.nonce),
+                balance: account.balance,
+                code,
+                storage: (!storage.is_empty()).then_some(storage),
+                ..Default::default()
+            };
+            serde_json::to_writer(
+                &mut writer,
+                &GenesisAccountWithAddress { genesis_account, address },
+            )?;
+            writer.write_all(b"\n")?;
+
+            exported += 1;
+            if exported % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", exported, %address, "Exporting state");
+            }
+        }
+        writer.flush()?;
+
+        info!(target: "reth::cli", exported, path = ?self.output, "State exported");
+
+        if let Some(alloc) = self.alloc {
+            write_alloc(&self.output, &alloc)?;
+            info!(target: "reth::cli", path = ?alloc, "Genesis alloc written");
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether the history shard holds a change after the block.
+///
+/// Only the last shard of a key, which holds its latest changes, is considered.
+fn changed_after(
+    highest_block_number: BlockNumber,
+    blocks: &IntegerList,
+    block: BlockNumber,
+) -> bool {
+    highest_block_number == u64::MAX && blocks.max().is_some_and(|max| max > block)
+}
+
+/// Opens an existing state dump file for appending and returns the address of the last account in
+/// it.
+///
+/// A trailing incomplete line left by an interrupted export is truncated.
+fn resume_output(path: &Path, state_root: B256) -> eyre::Result<(File, Option<Address>)> {
+    let mut reader = BufReader::new(reth_fs_util::open(path)?);
+    let mut line = String::new();
+    let mut complete_len = 0;
+    let mut root = None;
+    let mut last_address = None;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 || !line.ends_with('\n') {
+            break
+        }
+
+        if root.is_none() {
+            root = Some(serde_json::from_str::<StateRoot>(&line)?.root);
+        } else {
+            last_address = Some(serde_json::from_str::<GenesisAccountWithAddress>(&line)?.address);
+        }
+        complete_len += read as u64;
+    }
+
+    if let Some(root) = root {
+        eyre::ensure!(
+            root == state_root,
+            "State dump file has state root {root}, but the state root at the block is {state_root}"
+        );
+    }
+
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.set_len(complete_len)?;
+    file.seek(SeekFrom::End(0))?;
+
+    Ok((file, last_address))
+}
+
+/// Converts the JSONL state dump into a geth-style genesis `alloc` JSON object.
+fn write_alloc(state_dump: &Path, alloc: &Path) -> eyre::Result<()> {
+    let reader = BufReader::new(reth_fs_util::open(state_dump)?);
+    let mut writer = BufWriter::new(reth_fs_util::create_file(alloc)?);
+
+    writer.write_all(b"{")?;
+    // The first line is the state root
+    for (index, line) in reader.lines().skip(1).enumerate() {
+        let GenesisAccountWithAddress { genesis_account, address } = serde_json::from_str(&line?)?;
+
+        writer.write_all(if index == 0 { b"\n  " } else { b",\n  " })?;
+        serde_json::to_writer(&mut writer, &address)?;
+        writer.write_all(b": ")?;
+        serde_json::to_writer(&mut writer, &genesis_account)?;
+    }
+    writer.write_all(b"\n}\n")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_config::config::EtlConfig;
+    use reth_db::{init_db, mdbx::DatabaseArguments};
+    use reth_db_api::models::ClientVersion;
+    use reth_db_common::init::init_from_state_dump;
+    use reth_provider::{
+        providers::StaticFileProvider,
+        test_utils::{blocks::BlockchainTestData, create_test_provider_factory, MockNodeTypes},
+        BlockWriter, DatabaseProviderFactory, ProviderFactory, StorageLocation,
+    };
+    use std::fs;
+
+    type TestNodeTypes = NodeTypesWithDBAdapter<MockNodeTypes, Arc<DatabaseEnv>>;
+
+    /// Returns the test blocks up to the block that destroys accounts.
+    ///
+    /// The last test block recreates the destroyed accounts without their reverts, so the history
+    /// doesn't match the state at the blocks before it.
+    fn test_data() -> BlockchainTestData {
+        let mut data = BlockchainTestData::default();
+        data.blocks.truncate(4);
+        data
+    }
+
+    /// Creates a node with the test blocks, which create, change and destroy accounts and storage.
+    fn create_node(path: &Path, data: &BlockchainTestData) -> DbTool<TestNodeTypes> {
+        let db = init_db(
+            path.join("db"),
+            DatabaseArguments::new(ClientVersion::default()).with_growth_step(Some(1024 * 1024)),
+        )
+        .unwrap();
+        let factory = ProviderFactory::<TestNodeTypes>::new(
+            Arc::new(db),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(path.join("static_files")).unwrap(),
+        );
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(data.genesis.clone().try_recover().unwrap(), StorageLocation::Database)
+            .unwrap();
+        let mut execution_outcome = data.blocks[0].1.clone();
+        for (_, outcome) in &data.blocks[1..] {
+            execution_outcome.extend(outcome.clone());
+        }
+        provider_rw
+            .append_blocks_with_state(
+                data.blocks.iter().map(|(block, _)| block.clone()).collect(),
+                &execution_outcome,
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        DbTool::new(factory).unwrap()
+    }
+
+    fn export(tool: &DbTool<TestNodeTypes>, block: BlockNumber, output: &Path, resume: bool) {
+        Command { block, output: output.to_path_buf(), alloc: None, resume }
+            .execute::<MockNodeTypes>(tool)
+            .unwrap();
+    }
+
+    /// Initializes a node without state at the block from the state dump, which checks the state
+    /// root of the dump and of the initialized state against the block header.
+    fn init_state(data: &BlockchainTestData, block: BlockNumber, state_dump: &Path) {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.database_provider_rw().unwrap();
+        provider_rw
+            .insert_block(data.genesis.clone().try_recover().unwrap(), StorageLocation::Database)
+            .unwrap();
+        for (block, _) in &data.blocks[..block as usize] {
+            provider_rw.insert_block(block.clone(), StorageLocation::Database).unwrap();
+        }
+
+        let hash = init_from_state_dump(
+            BufReader::new(File::open(state_dump).unwrap()),
+            &provider_rw,
+            EtlConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(hash, data.blocks[block as usize - 1].0.hash());
+    }
+
+    #[test]
+    fn export_and_init_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = test_data();
+        let tool = create_node(&dir.path().join("node"), &data);
+
+        for block in 1..=data.blocks.len() as BlockNumber {
+            let output = dir.path().join(format!("state-{block}.jsonl"));
+            export(&tool, block, &output, false);
+            init_state(&data, block, &output);
+        }
+    }
+
+    #[test]
+    fn resume_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = test_data();
+        let tool = create_node(&dir.path().join("node"), &data);
+        // Accounts destroyed in the last blocks are only found through the history indices
+        let block = 3;
+
+        let complete = dir.path().join("complete.jsonl");
+        export(&tool, block, &complete, false);
+        let expected = fs::read_to_string(&complete).unwrap();
+
+        // Interrupt the export in the middle of an account
+        let interrupted = dir.path().join("interrupted.jsonl");
+        let lines = expected.lines().collect::<Vec<_>>();
+        let partial = format!("{}\n{}", lines[..10].join("\n"), &lines[10][..20]);
+        fs::write(&interrupted, partial).unwrap();
+
+        export(&tool, block, &interrupted, true);
+        assert_eq!(fs::read_to_string(&interrupted).unwrap(), expected);
+        init_state(&data, block, &interrupted);
+    }
+}
@@ -3,11 +3,15 @@ use alloy_primitives::{hex, BlockHash};
 use clap::Parser;
 use reth_db::{
     static_file::{
-        ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, TransactionMask,
+        AccountChangeSetMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderWithHashMask,
+        ReceiptMask, StorageChangeSetMask, TransactionMask,
     },
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
-use reth_db_api::table::{Decompress, DupSort, Table};
+use reth_db_api::{
+    models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
+    table::{Decompress, DupSort, Table},
+};
 use reth_db_common::DbTool;
 use reth_node_api::{ReceiptTy, TxTy};
 use reth_node_builder::NodeTypesWithDB;
@@ -73,6 +77,13 @@ impl Command {
                         (table_key::<tables::Receipts>(&key)?, <ReceiptMask<ReceiptTy<N>>>::MASK)
                     }
                     StaticFileSegment::BlockMeta => todo!(),
+                    // Both changeset segments have a row per block.
+                    StaticFileSegment::AccountChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, AccountChangeSetMask::MASK)
+                    }
+                    StaticFileSegment::StorageChangeSets => {
+                        (table_key::<tables::AccountChangeSets>(&key)?, StorageChangeSetMask::MASK)
+                    }
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -117,6 +128,18 @@ impl Command {
                                 StaticFileSegment::BlockMeta => {
                                     todo!()
                                 }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let changeset = StaticFileAccountChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let changeset = StaticFileStorageChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&changeset)?);
+                                }
                             }
                         }
                     }
@@ -9,8 +9,11 @@ use std::io::{self, Write};
 mod checksum;
 mod clear;
 mod diff;
+mod export_state;
 mod get;
 mod list;
+mod query;
+mod snapshot;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -50,6 +53,13 @@ pub enum Subcommands {
     Version,
     /// Returns the full database path
     Path,
+    /// Runs range, prefix and aggregate queries against the tables and static files
+    Query(query::Command),
+    /// Exports the state at a block as a JSONL state dump
+    ExportState(export_state::Command),
+    /// Creates and restores consistent snapshots of the database, static files and `ExEx` WAL
+    #[command(subcommand)]
+    Snapshot(snapshot::Subcommands),
 }
 
 /// `db_ro_exec` opens a database in read-only mode, and then execute with the provided command
@@ -69,6 +79,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
 
+        // restoring a snapshot populates an empty datadir, so it's handled before the checks
+        if let Subcommands::Snapshot(snapshot::Subcommands::Restore(command)) = self.command {
+            return command.execute(data_dir, self.env.chain.chain())
+        }
+
         // ensure the provided datadir exist
         eyre::ensure!(
             data_dir.data_dir().is_dir(),
@@ -148,6 +163,25 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            Subcommands::Query(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::ExportState(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool)?;
+                });
+            }
+            Subcommands::Snapshot(snapshot::Subcommands::Create(command)) => {
+                let chain = self.env.chain.chain();
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(data_dir, chain, &tool)?;
+                });
+            }
+            Subcommands::Snapshot(snapshot::Subcommands::Restore(_)) => {
+                unreachable!("snapshot restore is handled before opening the database")
+            }
         }
 
         Ok(())
@@ -172,4 +206,43 @@ mod tests {
         .unwrap();
         assert_eq!(cmd.env.datadir.resolve_datadir(cmd.env.chain.chain).as_ref(), Path::new(&path));
     }
+
+    #[test]
+    fn parse_export_state() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "export-state",
+            "--block",
+            "100",
+            "--output",
+            "state.jsonl",
+            "--alloc",
+            "alloc.json",
+            "--resume",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::ExportState(_)));
+    }
+
+    #[test]
+    fn parse_snapshot() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "snapshot",
+            "create",
+            "--compact",
+            "backup",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cmd.command,
+            Subcommands::Snapshot(snapshot::Subcommands::Create(snapshot::CreateCommand { .. }))
+        ));
+
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth", "snapshot", "restore", "backup",
+        ])
+        .unwrap();
+        assert!(matches!(cmd.command, Subcommands::Snapshot(snapshot::Subcommands::Restore(_))));
+    }
 }
@@ -0,0 +1,942 @@
+use crate::db::get::{maybe_json_value_parser, table_key};
+use alloy_primitives::{hex, BlockHash, Bytes};
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use reth_db::{
+    static_file::{
+        AccountChangeSetMask, BlockMetaMask, ColumnSelectorOne, ColumnSelectorThree,
+        ColumnSelectorTwo, HeaderWithHashMask, ReceiptMask, StorageChangeSetMask, TransactionMask,
+    },
+    BlockBodyIndices, RawKey, RawTable, TableRawRow, TableViewer, Tables,
+};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{
+        StaticFileAccountChangeSet, StaticFileBlockWithdrawals, StaticFileStorageChangeSet,
+        StoredBlockOmmers,
+    },
+    table::{Decompress, DupSort, Encode, Table},
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_db_common::DbTool;
+use reth_node_api::{HeaderTy, ReceiptTy, TxTy};
+use reth_node_builder::NodeTypesWithDB;
+use reth_provider::{providers::ProviderNodeTypes, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    cell::RefCell,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db query` command
+///
+/// Without arguments, queries are read from stdin, one per line. Run `help` for the list of
+/// queries.
+pub struct Command {
+    /// Read queries from a file, one per line, and stop at the first failing query.
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    #[arg(long, value_name = "FILE", conflicts_with = "execute")]
+    script: Option<PathBuf>,
+
+    /// Run a single query and exit.
+    #[arg(long, short, value_name = "QUERY")]
+    execute: Option<String>,
+}
+
+impl Command {
+    /// Execute `db query` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        if let Some(query) = self.execute {
+            return run_query(tool, &query, &mut io::stdout())
+        }
+
+        if let Some(script) = self.script {
+            let reader = io::BufReader::new(reth_fs_util::open(&script)?);
+            for (index, line) in reader.lines().enumerate() {
+                run_query(tool, &line?, &mut io::stdout())
+                    .wrap_err_with(|| format!("query on line {} failed", index + 1))?;
+            }
+            return Ok(())
+        }
+
+        let interactive = io::stdin().is_terminal();
+        if interactive {
+            println!("Type `help` for the list of queries, `exit` to quit.");
+        }
+        let mut lines = io::stdin().lock().lines();
+        loop {
+            if interactive {
+                print!("reth> ");
+                io::stdout().flush()?;
+            }
+            let Some(line) = lines.next() else { break };
+            let line = line?;
+            if matches!(line.trim(), "exit" | "quit") {
+                break
+            }
+            if let Err(err) = run_query(tool, &line, &mut io::stdout()) {
+                eprintln!("Error: {err:#}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A single query line.
+#[derive(Parser, Debug)]
+#[command(no_binary_name = true, disable_version_flag = true)]
+struct QueryLine {
+    #[command(subcommand)]
+    query: Query,
+}
+
+#[derive(Subcommand, Debug)]
+enum Query {
+    /// Scans a database table and prints the decoded entries as JSON lines
+    Scan(ScanArgs),
+    /// Scans the rows of a static file segment and prints the decoded rows as JSON lines
+    StaticFile(StaticFileArgs),
+}
+
+/// The arguments for the `scan` query.
+#[derive(Parser, Debug)]
+struct ScanArgs {
+    /// The table name
+    table: Tables,
+
+    /// The first key of the range, inclusive.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    from: Option<String>,
+
+    /// The last key of the range, inclusive.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    to: Option<String>,
+
+    /// Only scan the entries of this key. Same as `--from <KEY> --to <KEY>`.
+    #[arg(long, value_parser = maybe_json_value_parser, conflicts_with_all = ["from", "to"])]
+    key: Option<String>,
+
+    /// The first subkey of the dup-sort entries, inclusive. Only for dup-sort tables.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    subkey_from: Option<String>,
+
+    /// The last subkey of the dup-sort entries, inclusive. Only for dup-sort tables.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    subkey_to: Option<String>,
+
+    /// Only scan the entries whose encoded key starts with these hex encoded bytes.
+    #[arg(long, value_parser = Bytes::from_str)]
+    prefix: Option<Bytes>,
+
+    /// Iterate from the last entry of the range to the first one.
+    #[arg(long, short)]
+    reverse: bool,
+
+    /// Skip first N matching entries
+    #[arg(long, short, default_value_t = 0)]
+    skip: usize,
+
+    /// Maximum number of entries to print
+    #[arg(long, short, default_value_t = 10)]
+    limit: usize,
+
+    /// Print the number and the total size of all matching entries instead of the entries.
+    #[arg(long, short)]
+    count: bool,
+
+    /// Output bytes instead of human-readable decoded values
+    #[arg(long)]
+    raw: bool,
+}
+
+/// The arguments for the `static-file` query.
+#[derive(Parser, Debug)]
+struct StaticFileArgs {
+    /// The static file segment
+    segment: StaticFileSegment,
+
+    /// The first row of the range, inclusive. A block number for block based segments and a
+    /// transaction number for transaction based segments.
+    #[arg(long)]
+    from: Option<u64>,
+
+    /// The last row of the range, inclusive. Defaults to the highest row of the segment.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Iterate from the last row of the range to the first one.
+    #[arg(long, short)]
+    reverse: bool,
+
+    /// Skip first N rows
+    #[arg(long, short, default_value_t = 0)]
+    skip: usize,
+
+    /// Maximum number of rows to print
+    #[arg(long, short, default_value_t = 10)]
+    limit: usize,
+
+    /// Print the number and the total size of all rows instead of the rows.
+    #[arg(long, short)]
+    count: bool,
+
+    /// Output bytes instead of human-readable decoded values
+    #[arg(long)]
+    raw: bool,
+}
+
+/// Aggregate of the entries matched by a query with `--count`.
+#[derive(Debug, Default, Serialize)]
+struct Aggregate {
+    /// Number of matching entries.
+    count: usize,
+    /// Total size of the encoded keys in bytes.
+    key_bytes: usize,
+    /// Total size of the encoded values in bytes.
+    value_bytes: usize,
+    /// Key of the first matching entry.
+    first_key: Option<serde_json::Value>,
+    /// Key of the last matching entry.
+    last_key: Option<serde_json::Value>,
+}
+
+impl Aggregate {
+    fn add(&mut self, key: serde_json::Value, key_bytes: usize, value_bytes: usize) {
+        self.count += 1;
+        self.key_bytes += key_bytes;
+        self.value_bytes += value_bytes;
+        if self.first_key.is_none() {
+            self.first_key = Some(key.clone());
+        }
+        self.last_key = Some(key);
+    }
+}
+
+/// Parses and runs a single query line, writing the results to `out`.
+fn run_query<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    line: &str,
+    out: &mut dyn Write,
+) -> eyre::Result<()> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(())
+    }
+
+    let query = match QueryLine::try_parse_from(split_query(line)?) {
+        Ok(query_line) => query_line.query,
+        // Help is returned as an error, but it's not a failure
+        Err(err) if !err.use_stderr() => {
+            write!(out, "{err}")?;
+            return Ok(())
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    match query {
+        Query::Scan(args) => {
+            args.table.view(&ScanViewer { tool, args: &args, out: RefCell::new(out) })
+        }
+        Query::StaticFile(args) => scan_static_file(tool, &args, out),
+    }
+}
+
+/// Splits a query line into arguments.
+///
+/// Arguments are separated by whitespace, except inside single quotes, double quotes, braces and
+/// brackets, so JSON keys can be passed without quoting. Single quotes are removed.
+fn split_query(line: &str) -> eyre::Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut single_quoted = false;
+    let mut double_quoted = false;
+    let mut depth = 0usize;
+    let mut escaped = false;
+
+    for c in line.chars() {
+        if single_quoted {
+            if c == '\'' {
+                single_quoted = false;
+            } else {
+                current.push(c);
+            }
+            continue
+        }
+        if double_quoted {
+            current.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                double_quoted = false;
+            }
+            continue
+        }
+
+        match c {
+            '\'' => single_quoted = true,
+            '"' => {
+                double_quoted = true;
+                current.push(c);
+            }
+            '{' | '[' => {
+                depth += 1;
+                current.push(c);
+            }
+            '}' | ']' => {
+                depth = depth.checked_sub(1).ok_or_else(|| eyre::eyre!("Unbalanced `{c}`"))?;
+                current.push(c);
+            }
+            c if c.is_whitespace() && depth == 0 => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+                continue
+            }
+            c => current.push(c),
+        }
+        in_arg = true;
+    }
+
+    eyre::ensure!(!single_quoted && !double_quoted, "Unterminated quote");
+    eyre::ensure!(depth == 0, "Unbalanced braces or brackets");
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+struct ScanViewer<'a, N: NodeTypesWithDB> {
+    tool: &'a DbTool<N>,
+    args: &'a ScanArgs,
+    out: RefCell<&'a mut dyn Write>,
+}
+
+impl<N: ProviderNodeTypes> ScanViewer<'_, N> {
+    /// Scans the table in raw mode, so the range, prefix and subkey filters can be applied to the
+    /// encoded bytes, which are ordered the same way as in the database.
+    ///
+    /// The subkey of a dup-sort entry is encoded at the start of its value.
+    fn scan<T: Table>(
+        &self,
+        subkey_from: Option<Vec<u8>>,
+        subkey_to: Option<Vec<u8>>,
+    ) -> eyre::Result<()> {
+        let args = self.args;
+        let mut out = self.out.borrow_mut();
+        let (from, to) = match &args.key {
+            Some(key) => (Some(key), Some(key)),
+            None => (args.from.as_ref(), args.to.as_ref()),
+        };
+        let from = from.map(|key| encode_key::<T>(key)).transpose()?;
+        let to = to.map(|key| encode_key::<T>(key)).transpose()?;
+        let prefix = args.prefix.as_ref().map(|prefix| prefix.to_vec()).unwrap_or_default();
+
+        let mut tx = self.tool.provider_factory.db_ref().tx()?;
+        tx.disable_long_read_transaction_safety();
+        let mut cursor = tx.cursor_read::<RawTable<T>>()?;
+
+        let walker: Box<dyn Iterator<Item = Result<TableRawRow<T>, DatabaseError>> + '_> = if args
+            .reverse
+        {
+            // Start right after the last key of the range, so all of its duplicates are included.
+            let start = to.as_ref().map(|to| {
+                let mut start = to.clone();
+                start.push(0);
+                RawKey::from_vec(start)
+            });
+            let start = match start {
+                Some(start) if cursor.seek(start.clone())?.is_some() => Some(start),
+                _ => None,
+            };
+            Box::new(cursor.walk_back(start)?)
+        } else {
+            let start = match (&from, prefix.is_empty()) {
+                (Some(from), false) => Some(from.clone().max(prefix.clone())),
+                (Some(from), true) => Some(from.clone()),
+                (None, false) => Some(prefix.clone()),
+                (None, true) => None,
+            };
+            Box::new(cursor.walk(start.map(RawKey::from_vec))?)
+        };
+
+        let mut aggregate = Aggregate::default();
+        let mut skipped = 0;
+        let mut printed = 0;
+        for entry in walker {
+            let (key, value) = entry?;
+            let raw_key = key.raw_key().as_slice();
+
+            if args.reverse {
+                if from.as_deref().is_some_and(|from| raw_key < from) ||
+                    (!prefix.is_empty() && raw_key < prefix.as_slice())
+                {
+                    break
+                }
+                if to.as_deref().is_some_and(|to| raw_key > to) || !raw_key.starts_with(&prefix) {
+                    continue
+                }
+            } else {
+                if to.as_deref().is_some_and(|to| raw_key > to) ||
+                    (!raw_key.starts_with(&prefix) && raw_key > prefix.as_slice())
+                {
+                    break
+                }
+                if from.as_deref().is_some_and(|from| raw_key < from) ||
+                    !raw_key.starts_with(&prefix)
+                {
+                    continue
+                }
+            }
+
+            let raw_value = value.raw_value();
+            let value_subkey = |len: usize| &raw_value[..len.min(raw_value.len())];
+            if subkey_from.as_deref().is_some_and(|from| value_subkey(from.len()) < from) ||
+                subkey_to.as_deref().is_some_and(|to| value_subkey(to.len()) > to)
+            {
+                continue
+            }
+
+            if skipped < args.skip {
+                skipped += 1;
+                continue
+            }
+
+            if args.count {
+                let key_json = if args.raw {
+                    json!(hex::encode_prefixed(raw_key))
+                } else {
+                    serde_json::to_value(key.key()?)?
+                };
+                aggregate.add(key_json, raw_key.len(), raw_value.len());
+                continue
+            }
+
+            let row = if args.raw {
+                json!({
+                    "key": hex::encode_prefixed(raw_key),
+                    "value": hex::encode_prefixed(raw_value),
+                })
+            } else {
+                json!({ "key": key.key()?, "value": value.value()? })
+            };
+            writeln!(out, "{}", serde_json::to_string(&row)?)?;
+
+            printed += 1;
+            if printed >= args.limit {
+                break
+            }
+        }
+
+        if args.count {
+            writeln!(out, "{}", serde_json::to_string(&aggregate)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<N: ProviderNodeTypes> TableViewer<()> for ScanViewer<'_, N> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        eyre::ensure!(
+            self.args.subkey_from.is_none() && self.args.subkey_to.is_none(),
+            "Table {} is not a dup-sort table",
+            T::NAME
+        );
+        self.scan::<T>(None, None)
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        let subkey_from = self.args.subkey_from.as_deref().map(encode_subkey::<T>).transpose()?;
+        let subkey_to = self.args.subkey_to.as_deref().map(encode_subkey::<T>).transpose()?;
+        self.scan::<T>(subkey_from, subkey_to)
+    }
+}
+
+/// Parses a JSON key of the table and encodes it.
+fn encode_key<T: Table>(key: &str) -> eyre::Result<Vec<u8>> {
+    Ok(table_key::<T>(key)?.encode().as_ref().to_vec())
+}
+
+/// Parses a JSON subkey of the dup-sort table and encodes it.
+fn encode_subkey<T: DupSort>(subkey: &str) -> eyre::Result<Vec<u8>> {
+    let subkey: T::SubKey = serde_json::from_str(subkey).map_err(|e| eyre::eyre!(e))?;
+    Ok(subkey.encode().as_ref().to_vec())
+}
+
+/// Scans the rows of a static file segment.
+fn scan_static_file<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    args: &StaticFileArgs,
+    out: &mut dyn Write,
+) -> eyre::Result<()> {
+    let segment = args.segment;
+    let mask = match segment {
+        StaticFileSegment::Headers => <HeaderWithHashMask<HeaderTy<N>>>::MASK,
+        StaticFileSegment::Transactions => <TransactionMask<TxTy<N>>>::MASK,
+        StaticFileSegment::Receipts => <ReceiptMask<ReceiptTy<N>>>::MASK,
+        StaticFileSegment::BlockMeta => <BlockMetaMask<HeaderTy<N>>>::MASK,
+        StaticFileSegment::AccountChangeSets => AccountChangeSetMask::MASK,
+        StaticFileSegment::StorageChangeSets => StorageChangeSetMask::MASK,
+    };
+
+    let static_file_provider = tool.provider_factory.static_file_provider();
+    let highest = if segment.is_tx_based() {
+        static_file_provider.get_highest_static_file_tx(segment)
+    } else {
+        static_file_provider.get_highest_static_file_block(segment)
+    };
+    let Some(highest) = highest else {
+        if args.count {
+            writeln!(out, "{}", serde_json::to_string(&Aggregate::default())?)?;
+        }
+        return Ok(())
+    };
+    let range = args.from.unwrap_or_default()..=args.to.unwrap_or(highest).min(highest);
+    let rows: Box<dyn Iterator<Item = u64>> =
+        if args.reverse { Box::new(range.rev()) } else { Box::new(range) };
+
+    let mut aggregate = Aggregate::default();
+    let mut skipped = 0;
+    let mut printed = 0;
+    for row in rows {
+        let Some(columns) = static_file_provider.find_static_file(segment, |provider| {
+            let mut cursor = provider.cursor()?;
+            cursor.get(row.into(), mask).map(|result| {
+                result.map(|vec| vec.iter().map(|slice| slice.to_vec()).collect::<Vec<_>>())
+            })
+        })?
+        else {
+            continue
+        };
+
+        if skipped < args.skip {
+            skipped += 1;
+            continue
+        }
+
+        if args.count {
+            aggregate.add(json!(row), 0, columns.iter().map(Vec::len).sum());
+            continue
+        }
+
+        let value = if args.raw {
+            json!(columns.iter().map(hex::encode_prefixed).collect::<Vec<_>>())
+        } else {
+            decode_static_file_row::<N>(segment, &columns)?
+        };
+        writeln!(out, "{}", serde_json::to_string(&json!({ "key": row, "value": value }))?)?;
+
+        printed += 1;
+        if printed >= args.limit {
+            break
+        }
+    }
+
+    if args.count {
+        writeln!(out, "{}", serde_json::to_string(&aggregate)?)?;
+    }
+
+    Ok(())
+}
+
+/// Decodes the columns of a static file row.
+fn decode_static_file_row<N: ProviderNodeTypes>(
+    segment: StaticFileSegment,
+    columns: &[Vec<u8>],
+) -> eyre::Result<serde_json::Value> {
+    Ok(match segment {
+        StaticFileSegment::Headers => json!({
+            "header": <HeaderTy<N>>::decompress(columns[0].as_slice())?,
+            "hash": BlockHash::decompress(columns[1].as_slice())?,
+        }),
+        StaticFileSegment::Transactions => {
+            serde_json::to_value(<TxTy<N>>::decompress(columns[0].as_slice())?)?
+        }
+        StaticFileSegment::Receipts => {
+            serde_json::to_value(<ReceiptTy<N>>::decompress(columns[0].as_slice())?)?
+        }
+        StaticFileSegment::BlockMeta => json!({
+            "body_indices": <<BlockBodyIndices as Table>::Value>::decompress(columns[0].as_slice())?,
+            "ommers": <StoredBlockOmmers<HeaderTy<N>>>::decompress(columns[1].as_slice())?,
+            "withdrawals": StaticFileBlockWithdrawals::decompress(columns[2].as_slice())?,
+        }),
+        StaticFileSegment::AccountChangeSets => {
+            serde_json::to_value(StaticFileAccountChangeSet::decompress(columns[0].as_slice())?)?
+        }
+        StaticFileSegment::StorageChangeSets => {
+            serde_json::to_value(StaticFileStorageChangeSet::decompress(columns[0].as_slice())?)?
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256, U256};
+    use reth_db::tables;
+    use reth_db_api::{
+        models::{StoredBlockBodyIndices, StoredBlockWithdrawals},
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{Account, StorageEntry, TransactionSigned};
+    use reth_provider::{
+        providers::StaticFileWriter,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        DatabaseProviderFactory,
+    };
+    use serde_json::Value;
+
+    fn address(prefix: u8, last: u8) -> Address {
+        let mut address = Address::ZERO;
+        address[0] = prefix;
+        address[19] = last;
+        address
+    }
+
+    /// Creates a database with five accounts, two of them with three and one storage slots.
+    fn test_tool() -> DbTool<MockNodeTypesWithDB> {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        for (prefix, last) in [(0xaa, 1), (0xaa, 2), (0xaa, 3), (0xbb, 1), (0xbb, 2)] {
+            let account = Account { nonce: last as u64, ..Default::default() };
+            provider
+                .tx_ref()
+                .put::<tables::PlainAccountState>(address(prefix, last), account)
+                .unwrap();
+        }
+        for (address, slots) in [(address(0xaa, 1), 1..=3u8), (address(0xaa, 2), 1..=1)] {
+            for slot in slots {
+                let entry =
+                    StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+                provider.tx_ref().put::<tables::PlainStorageState>(address, entry).unwrap();
+            }
+        }
+        provider.commit().unwrap();
+        DbTool::new(factory).unwrap()
+    }
+
+    /// Runs the query and returns the printed rows.
+    fn query<N: ProviderNodeTypes>(tool: &DbTool<N>, line: &str) -> Vec<Value> {
+        let mut out = Vec::new();
+        run_query(tool, line, &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    /// Runs the query and returns the keys of the printed rows.
+    fn query_keys<N: ProviderNodeTypes>(tool: &DbTool<N>, line: &str) -> Vec<Value> {
+        query(tool, line).into_iter().map(|row| row["key"].clone()).collect()
+    }
+
+    fn keys(addresses: &[Address]) -> Vec<Value> {
+        addresses.iter().map(|address| json!(address)).collect()
+    }
+
+    /// Returns the storage slots of the printed rows.
+    fn slots<N: ProviderNodeTypes>(tool: &DbTool<N>, line: &str) -> Vec<(Address, u64)> {
+        query(tool, line)
+            .into_iter()
+            .map(|row| {
+                (
+                    serde_json::from_value(row["key"].clone()).unwrap(),
+                    serde_json::from_value::<U256>(row["value"]["value"].clone()).unwrap().to(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scan_range() {
+        let tool = test_tool();
+
+        assert_eq!(
+            query_keys(&tool, "scan PlainAccountState"),
+            keys(&[
+                address(0xaa, 1),
+                address(0xaa, 2),
+                address(0xaa, 3),
+                address(0xbb, 1),
+                address(0xbb, 2)
+            ])
+        );
+        // Both ends are inclusive.
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!(
+                    "scan PlainAccountState --from {} --to {}",
+                    address(0xaa, 2),
+                    address(0xbb, 1)
+                )
+            ),
+            keys(&[address(0xaa, 2), address(0xaa, 3), address(0xbb, 1)])
+        );
+        // The bounds don't have to exist.
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!("scan PlainAccountState --from {} --skip 1 --limit 2", address(0xab, 0))
+            ),
+            keys(&[address(0xbb, 2)])
+        );
+        assert_eq!(
+            query_keys(&tool, &format!("scan PlainAccountState --key {}", address(0xaa, 3))),
+            keys(&[address(0xaa, 3)])
+        );
+        assert_eq!(
+            query(&tool, &format!("scan PlainAccountState --to {} --count", address(0xaa, 3))),
+            vec![json!({
+                "count": 3,
+                "key_bytes": 60,
+                "value_bytes": 3 * 3,
+                "first_key": address(0xaa, 1),
+                "last_key": address(0xaa, 3),
+            })]
+        );
+    }
+
+    #[test]
+    fn scan_prefix() {
+        let tool = test_tool();
+
+        assert_eq!(
+            query_keys(&tool, "scan PlainAccountState --prefix 0xbb"),
+            keys(&[address(0xbb, 1), address(0xbb, 2)])
+        );
+        assert_eq!(
+            query_keys(&tool, "scan PlainAccountState --prefix 0xaa --reverse --limit 2"),
+            keys(&[address(0xaa, 3), address(0xaa, 2)])
+        );
+        // The range and the prefix are intersected.
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!("scan PlainAccountState --prefix 0xaa --from {}", address(0xaa, 2))
+            ),
+            keys(&[address(0xaa, 2), address(0xaa, 3)])
+        );
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!(
+                    "scan PlainAccountState --prefix 0xaa --to {} --reverse",
+                    address(0xbb, 1)
+                )
+            ),
+            keys(&[address(0xaa, 3), address(0xaa, 2), address(0xaa, 1)])
+        );
+        assert!(query_keys(&tool, "scan PlainAccountState --prefix 0xab").is_empty());
+        assert!(query_keys(&tool, "scan PlainAccountState --prefix 0xcc --reverse").is_empty());
+    }
+
+    #[test]
+    fn scan_reverse() {
+        let tool = test_tool();
+
+        assert_eq!(
+            query_keys(&tool, "scan PlainAccountState --reverse --limit 3"),
+            keys(&[address(0xbb, 2), address(0xbb, 1), address(0xaa, 3)])
+        );
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!(
+                    "scan PlainAccountState --from {} --to {} --reverse",
+                    address(0xaa, 2),
+                    address(0xbb, 1)
+                )
+            ),
+            keys(&[address(0xbb, 1), address(0xaa, 3), address(0xaa, 2)])
+        );
+        // The end of the range is past the last key.
+        assert_eq!(
+            query_keys(
+                &tool,
+                &format!("scan PlainAccountState --to {} --reverse --skip 1", address(0xcc, 0))
+            )
+            .len(),
+            4
+        );
+        // All duplicates of the last key are included.
+        assert_eq!(
+            slots(&tool, &format!("scan PlainStorageState --to {} --reverse", address(0xaa, 1))),
+            vec![(address(0xaa, 1), 3), (address(0xaa, 1), 2), (address(0xaa, 1), 1)]
+        );
+    }
+
+    #[test]
+    fn scan_subkey() {
+        let tool = test_tool();
+        let slot = |slot: u8| B256::with_last_byte(slot);
+
+        assert_eq!(
+            slots(
+                &tool,
+                &format!(
+                    "scan PlainStorageState --key {} --subkey-from {}",
+                    address(0xaa, 1),
+                    slot(2)
+                )
+            ),
+            vec![(address(0xaa, 1), 2), (address(0xaa, 1), 3)]
+        );
+        assert_eq!(
+            slots(
+                &tool,
+                &format!(
+                    "scan PlainStorageState --key {} --subkey-to {} --reverse",
+                    address(0xaa, 1),
+                    slot(2)
+                )
+            ),
+            vec![(address(0xaa, 1), 2), (address(0xaa, 1), 1)]
+        );
+        // The subkey range applies to every key.
+        assert_eq!(
+            slots(
+                &tool,
+                &format!(
+                    "scan PlainStorageState --subkey-from {} --subkey-to {}",
+                    slot(1),
+                    slot(1)
+                )
+            ),
+            vec![(address(0xaa, 1), 1), (address(0xaa, 2), 1)]
+        );
+        assert!(slots(
+            &tool,
+            &format!("scan PlainStorageState --key {} --subkey-from {}", address(0xaa, 2), slot(2))
+        )
+        .is_empty());
+
+        let mut out = Vec::new();
+        assert!(run_query(
+            &tool,
+            &format!("scan PlainAccountState --subkey-from {}", slot(1)),
+            &mut out
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn scan_static_files() {
+        let tool = test_tool();
+        let static_file_provider = tool.provider_factory.static_file_provider();
+
+        let transactions = [TransactionSigned::default(), TransactionSigned::default()];
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions).unwrap();
+        writer.increment_block(0).unwrap();
+        for (tx_num, transaction) in transactions.iter().enumerate() {
+            writer.append_transaction(tx_num as u64, transaction).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let body_indices = StoredBlockBodyIndices { first_tx_num: 0, tx_count: 2 };
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::BlockMeta).unwrap();
+        writer
+            .append_eth_block_meta(
+                &body_indices,
+                &Default::default(),
+                &StoredBlockWithdrawals::default(),
+                0,
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        assert_eq!(
+            query(&tool, "static-file transactions --reverse"),
+            vec![
+                json!({ "key": 1, "value": transactions[1] }),
+                json!({ "key": 0, "value": transactions[0] })
+            ]
+        );
+        assert_eq!(
+            query(&tool, "static-file block-meta"),
+            vec![json!({
+                "key": 0,
+                "value": {
+                    "body_indices": body_indices,
+                    "ommers": { "ommers": [] },
+                    "withdrawals": { "withdrawals": null },
+                }
+            })]
+        );
+    }
+
+    #[test]
+    fn split_query_args() {
+        assert_eq!(
+            split_query("scan  PlainStorageState --key 0x01 --limit 5").unwrap(),
+            vec!["scan", "PlainStorageState", "--key", "0x01", "--limit", "5"]
+        );
+        assert_eq!(
+            split_query(
+                r#"scan AccountsHistory --from { "key": "0x01", "highest_block_number": 1 }"#
+            )
+            .unwrap(),
+            vec![
+                "scan",
+                "AccountsHistory",
+                "--from",
+                r#"{ "key": "0x01", "highest_block_number": 1 }"#
+            ]
+        );
+        assert_eq!(
+            split_query(r#"scan StageCheckpoints --key 'Merkle Execution' --to "a b""#).unwrap(),
+            vec!["scan", "StageCheckpoints", "--key", "Merkle Execution", "--to", r#""a b""#]
+        );
+        assert!(split_query("scan Headers --from {").is_err());
+        assert!(split_query("scan Headers --from 'a").is_err());
+    }
+
+    #[test]
+    fn parse_queries() {
+        let query = QueryLine::try_parse_from(
+            split_query(
+                "scan PlainStorageState --key 0x0000000000000000000000000000000000000001 \
+                 --subkey-from 0x0000000000000000000000000000000000000000000000000000000000000001 \
+                 --reverse --count",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(query.query, Query::Scan(ScanArgs { reverse: true, count: true, .. })));
+
+        let query = QueryLine::try_parse_from(
+            split_query("static-file headers --from 10 --to 20 --limit 2").unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            query.query,
+            Query::StaticFile(StaticFileArgs {
+                segment: StaticFileSegment::Headers,
+                from: Some(10),
+                to: Some(20),
+                limit: 2,
+                ..
+            })
+        ));
+    }
+}
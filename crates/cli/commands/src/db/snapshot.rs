@@ -0,0 +1,635 @@
+use crate::common::CliNodeTypes;
+use alloy_primitives::hex;
+use clap::{Parser, Subcommand};
+use eyre::WrapErr;
+use reth_chainspec::{Chain, EthereumHardforks};
+use reth_db::{
+    mdbx::DatabaseArguments,
+    open_db_read_only, tables,
+    version::{db_version_file_path, DB_VERSION, DB_VERSION_FILE_NAME},
+    Database, DatabaseEnv,
+};
+use reth_db_api::{cursor::DbCursorRO, models::ClientVersion, transaction::DbTx};
+use reth_db_common::DbTool;
+use reth_node_builder::{NodePrimitives, NodeTypesWithDBAdapter};
+use reth_node_core::{
+    dirs::{ChainPath, DataDirPath},
+    version::SHORT_VERSION,
+};
+use reth_provider::{
+    providers::{StaticFileProvider, StaticFileWriter},
+    BlockHashReader,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// Name of the manifest file written to the root of a snapshot.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Name of the MDBX data file.
+const MDBX_DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the MDBX lock file.
+const MDBX_LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Directory of the database inside a snapshot.
+const DB_DIR: &str = "db";
+
+/// Directory of the static files inside a snapshot.
+const STATIC_FILES_DIR: &str = "static_files";
+
+/// Directory of the `ExEx` write-ahead log inside a snapshot.
+const EXEX_WAL_DIR: &str = "exex/wal";
+
+/// Number of times the database and static files are copied before giving up, if the static files
+/// change in a way that makes them inconsistent with the database copy.
+const MAX_COPY_ATTEMPTS: usize = 3;
+
+#[derive(Debug, Subcommand)]
+/// `reth db snapshot` subcommands
+pub enum Subcommands {
+    /// Creates a consistent snapshot of the database, static files and `ExEx` WAL
+    Create(CreateCommand),
+    /// Verifies a snapshot and restores it into the datadir
+    Restore(RestoreCommand),
+}
+
+/// The arguments for the `reth db snapshot create` command
+#[derive(Parser, Debug)]
+pub struct CreateCommand {
+    /// The directory to write the snapshot to. Must not exist or be empty.
+    #[arg(value_name = "SNAPSHOT_DIR")]
+    output: PathBuf,
+
+    /// Compact the database while copying it, omitting free pages.
+    ///
+    /// The copy is smaller but takes longer to produce.
+    #[arg(long)]
+    compact: bool,
+}
+
+impl CreateCommand {
+    /// Execute `db snapshot create` command
+    ///
+    /// The database is copied first from a single read transaction, then the static files are
+    /// copied next to it. The node keeps appending to the static files while they're copied, and
+    /// unwinds truncate them, so the copied static files are healed and verified against the
+    /// copied database. If they were truncated below the database or reorged while copying, both
+    /// copies are discarded and retried. Any rows above the database are healed by the
+    /// consistency check on startup.
+    pub fn execute<N: CliNodeTypes<ChainSpec: EthereumHardforks>>(
+        self,
+        data_dir: ChainPath<DataDirPath>,
+        chain: Chain,
+        tool: &DbTool<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+    ) -> eyre::Result<()> {
+        ensure_empty_dir(&self.output)?;
+
+        let db_dir = self.output.join(DB_DIR);
+        let static_files_dir = self.output.join(STATIC_FILES_DIR);
+        let has_receipts_pruning =
+            tool.provider_factory.provider()?.prune_modes_ref().has_receipts_pruning();
+
+        let mut attempt = 1;
+        let stage_checkpoints = loop {
+            reth_fs_util::create_dir_all(&db_dir)?;
+
+            info!(target: "reth::cli", path = ?db_dir, compact = self.compact, "Copying database");
+            tool.provider_factory.db_ref().copy(&db_dir.join(MDBX_DATA_FILE_NAME), self.compact)?;
+            fs::copy(db_version_file_path(data_dir.db()), db_dir.join(DB_VERSION_FILE_NAME))
+                .wrap_err("failed to copy database version file")?;
+
+            info!(target: "reth::cli", path = ?static_files_dir, "Copying static files");
+            let copied = copy_static_files(&data_dir.static_files(), &static_files_dir)?;
+
+            let verified = if copied {
+                verify_static_files::<N::Primitives>(
+                    &db_dir,
+                    &static_files_dir,
+                    &data_dir.static_files(),
+                    has_receipts_pruning,
+                )?
+            } else {
+                Err("static files were removed".to_string())
+            };
+
+            match verified {
+                Ok(stage_checkpoints) => break stage_checkpoints,
+                Err(reason) if attempt < MAX_COPY_ATTEMPTS => {
+                    warn!(target: "reth::cli", %reason, attempt, "Static files are inconsistent with the database copy, retrying");
+                    reth_fs_util::remove_dir_all(&db_dir)?;
+                    reth_fs_util::remove_dir_all(&static_files_dir)?;
+                    attempt += 1;
+                }
+                Err(reason) => {
+                    eyre::bail!("Static files are inconsistent with the database copy: {reason}")
+                }
+            }
+        };
+
+        let wal_dir = data_dir.exex_wal();
+        if wal_dir.is_dir() {
+            info!(target: "reth::cli", path = ?wal_dir, "Copying ExEx WAL");
+            copy_dir_lossy(&wal_dir, &self.output.join(EXEX_WAL_DIR))?;
+        }
+
+        info!(target: "reth::cli", "Computing checksums");
+        let mut files = Vec::new();
+        for path in collect_files(&self.output)? {
+            files.push(ManifestFile::new(&self.output, &path)?);
+        }
+
+        let manifest = Manifest {
+            client_version: SHORT_VERSION.to_string(),
+            chain: chain.to_string(),
+            db_version: DB_VERSION,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            stage_checkpoints,
+            files,
+        };
+        reth_fs_util::write_json_file(&self.output.join(MANIFEST_FILE_NAME), &manifest)?;
+
+        info!(target: "reth::cli", path = ?self.output, files = manifest.files.len(), "Snapshot created");
+        print_stage_checkpoints(&manifest.stage_checkpoints);
+
+        Ok(())
+    }
+}
+
+/// The arguments for the `reth db snapshot restore` command
+#[derive(Parser, Debug)]
+pub struct RestoreCommand {
+    /// The directory of the snapshot to restore.
+    #[arg(value_name = "SNAPSHOT_DIR")]
+    input: PathBuf,
+}
+
+impl RestoreCommand {
+    /// Execute `db snapshot restore` command
+    ///
+    /// The whole manifest is verified before anything is written to the datadir.
+    pub fn execute(self, data_dir: ChainPath<DataDirPath>, chain: Chain) -> eyre::Result<()> {
+        let manifest: Manifest =
+            reth_fs_util::read_json_file(&self.input.join(MANIFEST_FILE_NAME))?;
+
+        eyre::ensure!(
+            manifest.chain == chain.to_string(),
+            "Snapshot was created for chain {}, but restoring to {chain}",
+            manifest.chain
+        );
+        eyre::ensure!(
+            manifest.db_version == DB_VERSION,
+            "Snapshot database version {} does not match the current version {DB_VERSION}",
+            manifest.db_version
+        );
+
+        info!(target: "reth::cli", path = ?self.input, files = manifest.files.len(), "Verifying snapshot");
+        for file in &manifest.files {
+            file.verify(&self.input)?;
+        }
+
+        let targets = [
+            (DB_DIR, data_dir.db()),
+            (STATIC_FILES_DIR, data_dir.static_files()),
+            (EXEX_WAL_DIR, data_dir.exex_wal()),
+        ];
+        for (_, target) in &targets {
+            ensure_empty_dir(target)?;
+        }
+
+        for (dir, target) in &targets {
+            let prefix = Path::new(dir);
+            for file in
+                manifest.files.iter().filter(|file| Path::new(&file.path).starts_with(prefix))
+            {
+                let relative = Path::new(&file.path).strip_prefix(prefix)?;
+                let to = target.join(relative);
+                if let Some(parent) = to.parent() {
+                    reth_fs_util::create_dir_all(parent)?;
+                }
+                fs::copy(self.input.join(&file.path), &to)
+                    .wrap_err_with(|| format!("failed to copy {}", file.path))?;
+            }
+        }
+
+        info!(target: "reth::cli", path = ?data_dir.data_dir(), "Snapshot restored");
+        print_stage_checkpoints(&manifest.stage_checkpoints);
+
+        Ok(())
+    }
+}
+
+/// Snapshot manifest, stored as [`MANIFEST_FILE_NAME`] in the snapshot directory.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// Version of the client that created the snapshot.
+    client_version: String,
+    /// Chain the snapshot belongs to.
+    chain: String,
+    /// Version of the database schema.
+    db_version: u64,
+    /// UNIX timestamp of the snapshot creation, in seconds.
+    created_at: u64,
+    /// Stage checkpoint block numbers of the copied database.
+    stage_checkpoints: BTreeMap<String, u64>,
+    /// Every file of the snapshot.
+    files: Vec<ManifestFile>,
+}
+
+/// A file of the snapshot with its checksum.
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestFile {
+    /// Path relative to the snapshot directory.
+    path: String,
+    /// Size in bytes.
+    size: u64,
+    /// Hex encoded SHA-256 of the file contents.
+    sha256: String,
+}
+
+impl ManifestFile {
+    fn new(root: &Path, path: &Path) -> eyre::Result<Self> {
+        let (size, sha256) = hash_file(path)?;
+        let path = path
+            .strip_prefix(root)?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        Ok(Self { path, size, sha256 })
+    }
+
+    fn verify(&self, root: &Path) -> eyre::Result<()> {
+        let (size, sha256) = hash_file(&root.join(&self.path))?;
+        eyre::ensure!(
+            size == self.size,
+            "Size mismatch for {}: expected {}, got {size}",
+            self.path,
+            self.size
+        );
+        eyre::ensure!(
+            sha256 == self.sha256,
+            "Checksum mismatch for {}: expected {}, got {sha256}",
+            self.path,
+            self.sha256
+        );
+        Ok(())
+    }
+}
+
+/// Ensures that the directory doesn't exist or is empty.
+fn ensure_empty_dir(path: &Path) -> eyre::Result<()> {
+    if path.exists() {
+        eyre::ensure!(
+            reth_fs_util::read_dir(path)?.next().is_none(),
+            "Directory is not empty: {path:?}"
+        );
+    }
+    Ok(())
+}
+
+/// Copies the static files, skipping the lock file.
+///
+/// The configuration files are copied before the offsets and the offsets before the data. The
+/// files of a segment may still disagree on the number of rows if the node appended to or
+/// truncated them during the copy, which [`verify_static_files`] heals.
+///
+/// Returns `false` if a file was removed while copying.
+fn copy_static_files(from: &Path, to: &Path) -> eyre::Result<bool> {
+    reth_fs_util::create_dir_all(to)?;
+
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && entry.file_name() != "lock" {
+            files.push(entry.path());
+        }
+    }
+    files.sort_by_key(|path| match path.extension().and_then(|extension| extension.to_str()) {
+        Some("conf") => 0,
+        Some("off") => 1,
+        Some(_) => 2,
+        None => 3,
+    });
+
+    for file in files {
+        let name = file.file_name().expect("is a file");
+        match fs::copy(&file, to.join(name)) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err).wrap_err_with(|| format!("failed to copy {file:?}")),
+        }
+    }
+
+    Ok(true)
+}
+
+/// Heals the copied static files and verifies them against the copied database.
+///
+/// Every segment has to reach the block the database expects it to hold: the stage checkpoints for
+/// headers, transactions and receipts, and the pruned changesets for the changeset segments. The
+/// header at the headers checkpoint has to be the one of the database, and still has to be in the
+/// static files of the datadir, otherwise the chain was reorged while copying.
+///
+/// Returns the stage checkpoints of the copied database, or the reason why the copies are
+/// inconsistent. The lock file created by opening the copied database is removed.
+fn verify_static_files<N: NodePrimitives>(
+    db_dir: &Path,
+    static_files_dir: &Path,
+    datadir_static_files_dir: &Path,
+    has_receipts_pruning: bool,
+) -> eyre::Result<Result<BTreeMap<String, u64>, String>> {
+    let db = open_db_read_only(db_dir, DatabaseArguments::new(ClientVersion::default()))?;
+    let result = db.view(|tx| -> eyre::Result<_> {
+        let stage_checkpoints = tx
+            .cursor_read::<tables::StageCheckpoints>()?
+            .walk(None)?
+            .map(|entry| entry.map(|(stage, checkpoint)| (stage, checkpoint.block_number)))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        let stage_checkpoint =
+            |stage: StageId| stage_checkpoints.get(stage.as_str()).copied().unwrap_or_default();
+        let pruned_block = |segment: PruneSegment| -> eyre::Result<u64> {
+            Ok(tx
+                .get::<tables::PruneCheckpoints>(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .unwrap_or_default())
+        };
+
+        let mut expected_blocks = vec![
+            (StaticFileSegment::Headers, stage_checkpoint(StageId::Headers)),
+            (StaticFileSegment::Transactions, stage_checkpoint(StageId::Bodies)),
+            (
+                StaticFileSegment::AccountChangeSets,
+                pruned_block(PruneSegment::AccountChangeSets)?,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                pruned_block(PruneSegment::StorageChangeSets)?,
+            ),
+        ];
+        // Receipts are kept in the database if they're pruned.
+        if !has_receipts_pruning {
+            expected_blocks.push((StaticFileSegment::Receipts, stage_checkpoint(StageId::Execution)));
+        }
+
+        let static_file_provider = StaticFileProvider::<N>::read_write(static_files_dir)?;
+        for segment in StaticFileSegment::iter() {
+            if static_file_provider.get_highest_static_file_block(segment).is_some() {
+                // Fetching the writer heals the rows that were appended or truncated while copying.
+                static_file_provider.latest_writer(segment)?;
+            }
+        }
+
+        for (segment, block) in expected_blocks {
+            let highest_block = static_file_provider.get_highest_static_file_block(segment);
+            if block > 0 && highest_block.is_none_or(|highest_block| highest_block < block) {
+                return Ok(Err(format!(
+                    "static file segment {segment} is at block {highest_block:?}, behind block {block} of the database"
+                )))
+            }
+        }
+
+        let headers_checkpoint = stage_checkpoint(StageId::Headers);
+        if let Some(hash) = static_file_provider.block_hash(headers_checkpoint)? {
+            if tx.get::<tables::HeaderNumbers>(hash)? != Some(headers_checkpoint) {
+                return Ok(Err(format!(
+                    "header {headers_checkpoint} of the static files is not in the database"
+                )))
+            }
+            let datadir_hash = StaticFileProvider::<N>::read_only(datadir_static_files_dir, false)?
+                .block_hash(headers_checkpoint)?;
+            if datadir_hash != Some(hash) {
+                return Ok(Err(format!("block {headers_checkpoint} was reorged")))
+            }
+        }
+
+        Ok(Ok(stage_checkpoints))
+    })??;
+    drop(db);
+
+    let lock_file = db_dir.join(MDBX_LOCK_FILE_NAME);
+    if lock_file.exists() {
+        reth_fs_util::remove_file(lock_file)?;
+    }
+
+    Ok(result)
+}
+
+/// Copies a directory recursively, skipping files that are removed while copying.
+fn copy_dir_lossy(from: &Path, to: &Path) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(to)?;
+
+    for entry in reth_fs_util::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_lossy(&path, &target)?;
+        } else {
+            match fs::copy(&path, &target) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err).wrap_err_with(|| format!("failed to copy {path:?}")),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns all files in the directory recursively, sorted by path.
+fn collect_files(dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            files.extend(collect_files(&entry.path())?);
+        } else {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns the size and the hex encoded SHA-256 of the file.
+fn hash_file(path: &Path) -> eyre::Result<(u64, String)> {
+    let mut file = reth_fs_util::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 1024 * 1024];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, hex::encode(hasher.finalize())))
+}
+
+fn print_stage_checkpoints(stage_checkpoints: &BTreeMap<String, u64>) {
+    println!("Stage checkpoints:");
+    for (stage, block_number) in stage_checkpoints {
+        println!("  {stage}: {block_number}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_db::init_db;
+    use reth_db_common::init::init_genesis;
+    use reth_node_core::{
+        args::DatadirArgs,
+        dirs::{DataDirPath, MaybePlatformPath},
+    };
+    use reth_provider::{
+        test_utils::MockNodeTypes, DatabaseProviderFactory, ProviderFactory, StageCheckpointWriter,
+    };
+    use reth_stages::StageCheckpoint;
+
+    type TestNodeTypes = NodeTypesWithDBAdapter<MockNodeTypes, Arc<DatabaseEnv>>;
+
+    fn datadir(path: &Path) -> ChainPath<DataDirPath> {
+        MaybePlatformPath::<DataDirPath>::from(path.to_path_buf())
+            .unwrap_or_chain_default(Chain::mainnet(), DatadirArgs::default())
+    }
+
+    fn create_node(data_dir: &ChainPath<DataDirPath>) -> DbTool<TestNodeTypes> {
+        // Keep the database file small, it's hashed for the manifest.
+        let db = init_db(
+            data_dir.db(),
+            DatabaseArguments::new(ClientVersion::default()).with_growth_step(Some(1024 * 1024)),
+        )
+        .unwrap();
+        let factory = ProviderFactory::<TestNodeTypes>::new(
+            Arc::new(db),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(data_dir.static_files()).unwrap(),
+        );
+        init_genesis(&factory).unwrap();
+        DbTool::new(factory).unwrap()
+    }
+
+    fn create_snapshot(
+        data_dir: &ChainPath<DataDirPath>,
+        tool: &DbTool<TestNodeTypes>,
+        output: &Path,
+    ) -> eyre::Result<()> {
+        CreateCommand { output: output.to_path_buf(), compact: false }.execute::<MockNodeTypes>(
+            data_dir.clone(),
+            Chain::mainnet(),
+            tool,
+        )
+    }
+
+    #[test]
+    fn create_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = datadir(&dir.path().join("node"));
+        let tool = create_node(&data_dir);
+        let snapshot_dir = dir.path().join("snapshot");
+
+        create_snapshot(&data_dir, &tool, &snapshot_dir).unwrap();
+        let manifest: Manifest =
+            reth_fs_util::read_json_file(&snapshot_dir.join(MANIFEST_FILE_NAME)).unwrap();
+        assert!(manifest.files.iter().any(|file| file.path == "db/mdbx.dat"));
+        assert!(manifest.files.iter().all(|file| !file.path.ends_with("lock")));
+        assert!(!manifest.stage_checkpoints.is_empty());
+
+        let restored_dir = datadir(&dir.path().join("restored"));
+        RestoreCommand { input: snapshot_dir }
+            .execute(restored_dir.clone(), Chain::mainnet())
+            .unwrap();
+
+        // The static files are restored byte for byte.
+        for file in collect_files(&data_dir.static_files()).unwrap() {
+            let name = file.file_name().unwrap();
+            if name == "lock" {
+                continue
+            }
+            assert_eq!(
+                fs::read(&file).unwrap(),
+                fs::read(restored_dir.static_files().join(name)).unwrap()
+            );
+        }
+
+        // The restored database is readable and at the same checkpoints.
+        let db =
+            open_db_read_only(restored_dir.db(), DatabaseArguments::new(ClientVersion::default()))
+                .unwrap();
+        let stage_checkpoints = db
+            .view(|tx| {
+                tx.cursor_read::<tables::StageCheckpoints>()
+                    .unwrap()
+                    .walk(None)
+                    .unwrap()
+                    .map(|entry| entry.map(|(stage, checkpoint)| (stage, checkpoint.block_number)))
+                    .collect::<Result<BTreeMap<_, _>, _>>()
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(stage_checkpoints, manifest.stage_checkpoints);
+        let genesis_hash = MAINNET.genesis_hash();
+        assert_eq!(
+            db.view(|tx| tx.get::<tables::HeaderNumbers>(genesis_hash)).unwrap().unwrap(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn restore_rejects_tampered_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = datadir(&dir.path().join("node"));
+        let tool = create_node(&data_dir);
+        let snapshot_dir = dir.path().join("snapshot");
+        create_snapshot(&data_dir, &tool, &snapshot_dir).unwrap();
+
+        let manifest_path = snapshot_dir.join(MANIFEST_FILE_NAME);
+        let mut manifest: Manifest = reth_fs_util::read_json_file(&manifest_path).unwrap();
+        let file = manifest.files.iter_mut().find(|file| file.path == "db/mdbx.dat").unwrap();
+        file.sha256 = hex::encode([0u8; 32]);
+        reth_fs_util::write_json_file(&manifest_path, &manifest).unwrap();
+
+        let restored_dir = datadir(&dir.path().join("restored"));
+        let err = RestoreCommand { input: snapshot_dir }
+            .execute(restored_dir.clone(), Chain::mainnet())
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Checksum mismatch for db/mdbx.dat"), "{err}");
+        assert!(!restored_dir.db().exists());
+        assert!(!restored_dir.static_files().exists());
+    }
+
+    #[test]
+    fn create_rejects_static_files_behind_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_dir = datadir(&dir.path().join("node"));
+        let tool = create_node(&data_dir);
+
+        // The database expects a header that isn't in the static files.
+        let provider = tool.provider_factory.database_provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Headers, StageCheckpoint::new(1)).unwrap();
+        provider.commit().unwrap();
+
+        let err = create_snapshot(&data_dir, &tool, &dir.path().join("snapshot")).unwrap_err();
+        assert!(
+            err.to_string().contains("static file segment Headers is at block Some(0)"),
+            "{err}"
+        );
+    }
+}
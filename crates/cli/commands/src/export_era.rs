@@ -0,0 +1,161 @@
+//! Command that exports blocks to era1 archives.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::ReceiptWithBloom;
+use clap::Parser;
+use eyre::ensure;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era::{era1_file_name, BlockTuple, Era1Writer, MAX_BLOCKS_PER_ERA1};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{BlockBody, EthPrimitives, StaticFileSegment};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, ChainSpecProvider, HeaderProvider,
+    OmmersProvider, ProviderError, ProviderFactory, ReceiptProvider, StaticFileProviderFactory,
+    TransactionsProvider,
+};
+use std::{
+    io::BufWriter,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Exports blocks to era1 archives.
+///
+/// Every epoch of [`MAX_BLOCKS_PER_ERA1`] blocks is written to its own file, named after the
+/// network, the epoch and its accumulator root.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export, rounded down to the first block of its epoch.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0)]
+    from: u64,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the last block with receipts in static files, the last epoch file ends early if
+    /// this isn't the last block of an epoch.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: Option<u64>,
+
+    /// The directory to write the era1 files to.
+    #[arg(value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
+    /// Execute `export-era` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = EthPrimitives>,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let network = provider_factory.chain_spec().chain().to_string();
+
+        // receipts are only available for executed or imported blocks
+        let last_receipt_block = provider_factory
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Receipts)
+            .unwrap_or_default();
+        let to = self.to.unwrap_or(last_receipt_block);
+        ensure!(
+            to <= last_receipt_block,
+            "Receipts are only available up to block {last_receipt_block}"
+        );
+        ensure!(self.from <= to, "The first block {} is after the last block {to}", self.from);
+
+        reth_fs_util::create_dir_all(&self.path)?;
+
+        let epoch_len = MAX_BLOCKS_PER_ERA1 as u64;
+        for epoch in self.from / epoch_len..=to / epoch_len {
+            let first_block = epoch * epoch_len;
+            let last_block = (first_block + epoch_len - 1).min(to);
+
+            let file_path =
+                export_epoch(&provider_factory, &self.path, &network, first_block..=last_block)?;
+            info!(target: "reth::cli", path = %file_path.display(), first_block, last_block, "Exported era1 file");
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the blocks of an epoch to an era1 file in the directory, and returns its path.
+///
+/// The range starts at the first block of the epoch, and ends early if it is the last epoch to
+/// export.
+pub(crate) fn export_epoch<N>(
+    provider_factory: &ProviderFactory<N>,
+    dir: &Path,
+    network: &str,
+    blocks: RangeInclusive<u64>,
+) -> eyre::Result<PathBuf>
+where
+    N: ProviderNodeTypes<Primitives = EthPrimitives>,
+{
+    let provider = provider_factory.provider()?;
+    let static_file_provider = provider_factory.static_file_provider();
+    let epoch = blocks.start() / MAX_BLOCKS_PER_ERA1 as u64;
+
+    // the file is named after the accumulator root, which is known once all blocks are written
+    let tmp_path = dir.join(format!("{network}-{epoch:05}.era1.tmp"));
+    let mut writer =
+        Era1Writer::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?), *blocks.start())?;
+
+    for number in blocks {
+        let header = static_file_provider
+            .header_by_number(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        ensure!(
+            header.withdrawals_root.is_none(),
+            "Block {number} has withdrawals, era1 archives only hold pre-merge blocks"
+        );
+        let total_difficulty = static_file_provider
+            .header_td_by_number(number)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+        let indices = provider
+            .block_body_indices(number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+        let transactions = static_file_provider.transactions_by_tx_range(indices.tx_num_range())?;
+        let receipts = static_file_provider.receipts_by_tx_range(indices.tx_num_range())?;
+        ensure!(
+            transactions.len() == indices.tx_count as usize && receipts.len() == transactions.len(),
+            "Missing transactions or receipts of block {number} in static files"
+        );
+
+        let body = BlockBody {
+            transactions,
+            ommers: provider.ommers(number.into())?.unwrap_or_default(),
+            withdrawals: None,
+        };
+        let receipts = receipts.into_iter().map(ReceiptWithBloom::from).collect::<Vec<_>>();
+
+        writer.append(&BlockTuple::new(&header, &body, &receipts, total_difficulty)?)?;
+    }
+
+    let root = writer.finish()?;
+    let file_path = dir.join(era1_file_name(network, epoch, root));
+    reth_fs_util::rename(&tmp_path, &file_path)?;
+
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_era_command() {
+        let args: ExportEraCommand<EthereumChainSpecParser> =
+            ExportEraCommand::parse_from(["reth", "--to", "8191", "era"]);
+        assert_eq!(args.from, 0);
+        assert_eq!(args.to, Some(8191));
+        assert_eq!(args.path, PathBuf::from("era"));
+    }
+}
@@ -0,0 +1,403 @@
+//! Command that imports blocks from era1 archives.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{
+    proofs::{calculate_ommers_root, calculate_receipt_root, calculate_transaction_root},
+    Header, ReceiptWithBloom,
+};
+use alloy_primitives::B256;
+use clap::Parser;
+use eyre::{bail, ensure, OptionExt};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_era::{accumulator_root, Era1Reader, HeaderRecord};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{BlockBody, EthPrimitives, Receipt, StaticFileSegment};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockBodyIndicesProvider, BlockNumReader, BlockWriter,
+    DatabaseProviderFactory, HeaderProvider, ProviderError, ProviderFactory, StageCheckpointWriter,
+    StaticFileProviderFactory, StaticFileWriter, StorageLocation,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Imports blocks from era1 archives into static files.
+///
+/// The headers, transactions and receipts of the blocks are written to static files, and the
+/// headers and bodies stages are advanced to the last imported block. The receipts are kept ahead
+/// of the execution stage, which doesn't write them again.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The era1 files, or directories of era1 files, to import.
+    ///
+    /// Files are imported in the order of their names, which is the order of their epochs. Blocks
+    /// the node already has are skipped.
+    #[arg(value_name = "PATH", required = true, verbatim_doc_comment)]
+    paths: Vec<PathBuf>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraCommand<C> {
+    /// Execute `import-era` command
+    pub async fn execute<N>(self) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = EthPrimitives>,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let mut total_imported_blocks = 0;
+        let mut total_imported_txns = 0;
+
+        for path in era1_files(&self.paths)? {
+            let (imported_blocks, imported_txns) = import_era1_file(&provider_factory, &path)?;
+            total_imported_blocks += imported_blocks;
+            total_imported_txns += imported_txns;
+        }
+
+        info!(target: "reth::cli",
+            total_imported_blocks,
+            total_imported_txns,
+            "Era1 files imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Imports the blocks of the era1 file that extend the local chain.
+///
+/// Receipts are only imported if the receipts in static files reach the local tip, otherwise the
+/// execution stage creates them.
+///
+/// Returns the number of imported blocks and transactions.
+fn import_era1_file<N>(
+    provider_factory: &ProviderFactory<N>,
+    path: &Path,
+) -> eyre::Result<(u64, usize)>
+where
+    N: ProviderNodeTypes<Primitives = EthPrimitives>,
+{
+    let static_file_provider = provider_factory.static_file_provider();
+
+    let mut tip = provider_factory.last_block_number()?;
+    ensure!(
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Headers) == Some(tip),
+        "Headers and bodies are at different heights, finish or unwind the pipeline first"
+    );
+    let mut tip_hash =
+        provider_factory.sealed_header(tip)?.ok_or_eyre("missing header of the local tip")?.hash();
+
+    let records = verify_era1(path)?;
+    info!(target: "reth::cli", path = %path.display(), "Verified era1 file accumulator");
+
+    let provider_rw = provider_factory.database_provider_rw()?;
+    let import_receipts = !provider_rw.prune_modes_ref().has_receipts_pruning() &&
+        static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts) ==
+            Some(tip);
+    if !import_receipts {
+        info!(target: "reth::cli", path = %path.display(), "Receipts are behind the local tip, leaving them to the execution stage");
+    }
+
+    let mut next_tx_num = provider_rw
+        .block_body_indices(tip)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(tip))?
+        .next_tx_num();
+    let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+    let mut receipts_writer = import_receipts
+        .then(|| static_file_provider.latest_writer(StaticFileSegment::Receipts))
+        .transpose()?;
+    let mut imported_blocks = 0;
+    let mut imported_txns = 0;
+
+    for (block, record) in Era1Reader::open(path)?.zip(records) {
+        let block = block?;
+        let header: Header = block.decode_header()?;
+        if header.number <= tip {
+            ensure!(
+                header.number < tip || record.block_hash == tip_hash,
+                "Block {} of {} doesn't match the local chain",
+                header.number,
+                path.display()
+            );
+            continue
+        }
+        ensure!(
+            header.number == tip + 1 && header.parent_hash == tip_hash,
+            "Block {} of {} doesn't extend the local chain at block {tip}",
+            header.number,
+            path.display()
+        );
+
+        let body: BlockBody = block.decode_body()?;
+        let receipts: Vec<ReceiptWithBloom<Receipt>> = block.decode_receipts()?;
+        verify_block(&header, &body, &receipts)?;
+
+        headers_writer.append_header(&header, record.total_difficulty, &record.block_hash)?;
+        provider_rw.tx_ref().put::<tables::HeaderNumbers>(record.block_hash, header.number)?;
+
+        if let Some(writer) = receipts_writer.as_mut() {
+            writer.increment_block(header.number)?;
+            for (tx_num, receipt) in (next_tx_num..).zip(receipts) {
+                writer.append_receipt(tx_num, &receipt.receipt)?;
+            }
+        }
+
+        next_tx_num += body.transactions.len() as u64;
+        imported_txns += body.transactions.len();
+        provider_rw
+            .append_block_bodies(vec![(header.number, Some(body))], StorageLocation::StaticFiles)?;
+
+        tip = header.number;
+        tip_hash = record.block_hash;
+        imported_blocks += 1;
+    }
+    drop(headers_writer);
+    drop(receipts_writer);
+
+    for stage in [StageId::Headers, StageId::Bodies] {
+        provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(tip))?;
+    }
+
+    // Static files are committed first, in case of a crash they are unwound according to the
+    // database checkpoints.
+    static_file_provider.commit()?;
+    provider_rw.commit()?;
+
+    info!(target: "reth::cli", path = %path.display(), imported_blocks, tip, "Imported era1 file");
+
+    Ok((imported_blocks, imported_txns))
+}
+
+/// Returns the era1 files of the given paths, sorted by name.
+fn era1_files(paths: &[PathBuf]) -> eyre::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            for entry in reth_fs_util::read_dir(path)? {
+                let file = entry?.path();
+                if file.extension().is_some_and(|ext| ext == "era1") {
+                    files.push(file);
+                }
+            }
+        } else {
+            files.push(path.clone());
+        }
+    }
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    Ok(files)
+}
+
+/// Reads all blocks of the era1 file and verifies the accumulator root of the file.
+///
+/// Returns the header records of the blocks.
+fn verify_era1(path: &Path) -> eyre::Result<Vec<HeaderRecord>> {
+    let mut reader = Era1Reader::open(path)?;
+    let mut records = Vec::new();
+    for block in reader.by_ref() {
+        let block = block?;
+        records.push(HeaderRecord {
+            block_hash: block.header_hash()?,
+            total_difficulty: block.total_difficulty,
+        });
+    }
+
+    let expected = reader.accumulator().expect("all blocks are read");
+    let root = accumulator_root(&records);
+    if root != expected {
+        bail!("Accumulator root mismatch in {}: expected {expected}, got {root}", path.display())
+    }
+
+    Ok(records)
+}
+
+/// Verifies the body and receipts of the block against its header.
+fn verify_block(
+    header: &Header,
+    body: &BlockBody,
+    receipts: &[ReceiptWithBloom<Receipt>],
+) -> eyre::Result<()> {
+    let check = |name: &str, got: B256, expected: B256| {
+        ensure!(
+            got == expected,
+            "{name} root mismatch in block {}: expected {expected}, got {got}",
+            header.number
+        );
+        Ok(())
+    };
+
+    ensure!(
+        body.withdrawals.is_none(),
+        "Block {} has withdrawals, era1 archives only hold pre-merge blocks",
+        header.number
+    );
+    check(
+        "Transactions",
+        calculate_transaction_root(&body.transactions),
+        header.transactions_root,
+    )?;
+    check("Ommers", calculate_ommers_root(&body.ommers), header.ommers_hash)?;
+    check("Receipts", calculate_receipt_root(receipts), header.receipts_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export_era::export_epoch;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_primitives::SealedBlock;
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        ExecutionOutcome, OriginalValuesKnown, ReceiptProvider, StageCheckpointReader, StateWriter,
+        TransactionsProvider,
+    };
+    use reth_testing_utils::generators::{self, random_block, random_receipt, BlockParams};
+
+    /// Returns a chain of pre-merge blocks starting at genesis, with the receipts of every block.
+    fn test_chain(len: u64) -> Vec<(SealedBlock, Vec<Receipt>)> {
+        let mut rng = generators::rng();
+        let mut parent = None;
+        (0..len)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams {
+                        parent,
+                        tx_count: Some(number as u8 % 3),
+                        ommers_count: Some(0),
+                        ..Default::default()
+                    },
+                );
+                let receipts = block
+                    .body()
+                    .transactions
+                    .iter()
+                    .map(|tx| random_receipt(&mut rng, tx, Some(1)))
+                    .collect::<Vec<_>>();
+
+                let (mut header, body) = block.split_header_body();
+                header.receipts_root = calculate_receipt_root(
+                    &receipts.iter().cloned().map(ReceiptWithBloom::from).collect::<Vec<_>>(),
+                );
+                let block: SealedBlock = SealedBlock::seal_parts(header, body);
+                parent = Some(block.hash());
+                (block, receipts)
+            })
+            .collect()
+    }
+
+    /// Inserts the blocks and receipts, and advances the stages to the last block.
+    fn insert_chain(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        chain: &[(SealedBlock, Vec<Receipt>)],
+    ) {
+        let provider_rw = factory.provider_rw().unwrap();
+        for (block, _) in chain {
+            provider_rw
+                .insert_block(block.clone().try_recover().unwrap(), StorageLocation::StaticFiles)
+                .unwrap();
+        }
+        let outcome = ExecutionOutcome {
+            receipts: chain.iter().map(|(_, receipts)| receipts.clone()).collect(),
+            ..Default::default()
+        };
+        provider_rw
+            .write_state(&outcome, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)
+            .unwrap();
+
+        let last_block = chain.len() as u64 - 1;
+        for stage in [StageId::Headers, StageId::Bodies, StageId::Execution] {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(last_block)).unwrap();
+        }
+        factory.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    #[test]
+    fn parse_import_era_command() {
+        let args: ImportEraCommand<EthereumChainSpecParser> =
+            ImportEraCommand::parse_from(["reth", "a.era1", "dir"]);
+        assert_eq!(args.paths, vec![PathBuf::from("a.era1"), PathBuf::from("dir")]);
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let chain = test_chain(6);
+        let source = create_test_provider_factory();
+        insert_chain(&source, &chain);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = export_epoch(&source, dir.path(), "test", 0..=5).unwrap();
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("test-00000-"));
+
+        // the node only has the genesis block
+        let factory = create_test_provider_factory();
+        insert_chain(&factory, &chain[..1]);
+
+        let txns = chain.iter().map(|(block, _)| block.body().transactions.len()).sum();
+        assert_eq!(import_era1_file(&factory, &path).unwrap(), (5, txns));
+        // imported blocks are skipped
+        assert_eq!(import_era1_file(&factory, &path).unwrap(), (0, 0));
+
+        let check_chain = || {
+            for (block, receipts) in &chain {
+                let number = block.header().number;
+                assert_eq!(factory.sealed_header(number).unwrap().unwrap().hash(), block.hash());
+                assert_eq!(
+                    factory.transactions_by_block(number.into()).unwrap().as_ref(),
+                    Some(&block.body().transactions)
+                );
+                assert_eq!(
+                    factory.receipts_by_block(number.into()).unwrap().as_ref(),
+                    Some(receipts)
+                );
+            }
+        };
+        check_chain();
+
+        let provider = factory.provider().unwrap();
+        for (stage, block_number) in
+            [(StageId::Headers, 5), (StageId::Bodies, 5), (StageId::Execution, 0)]
+        {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage).unwrap().unwrap().block_number,
+                block_number
+            );
+        }
+
+        // the imported receipts are kept ahead of the execution stage
+        assert_eq!(
+            factory
+                .static_file_provider()
+                .check_consistency(&factory.database_provider_ro().unwrap(), false)
+                .unwrap(),
+            None
+        );
+
+        // and aren't written again by the execution
+        let provider_rw = factory.provider_rw().unwrap();
+        let outcome = ExecutionOutcome {
+            receipts: chain[1..].iter().map(|(_, receipts)| receipts.clone()).collect(),
+            first_block: 1,
+            ..Default::default()
+        };
+        provider_rw
+            .write_state(&outcome, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(5)).unwrap();
+        factory.static_file_provider().commit().unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_eq!(
+            factory.static_file_provider().get_highest_static_file_tx(StaticFileSegment::Receipts),
+            txns.checked_sub(1).map(|tx| tx as u64)
+        );
+        check_chain();
+    }
+}
@@ -24,12 +24,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
         info!(target: "reth::cli", "Copying data from database to static files...");
         let static_file_producer =
             StaticFileProducer::new(provider_factory.clone(), prune_config.segments.clone());
-        let lowest_static_file_height =
-            static_file_producer.lock().copy_to_static_files()?.min_block_num();
-        info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+        let highest_static_file_height =
+            static_file_producer.lock().copy_to_static_files()?.max_block_num();
+        info!(target: "reth::cli", ?highest_static_file_height, "Copied data from database to static files");
 
         // Delete data which has been copied to static files.
-        if let Some(prune_tip) = lowest_static_file_height {
+        if let Some(prune_tip) = highest_static_file_height {
             info!(target: "reth::cli", ?prune_tip, ?prune_config, "Pruning data from database...");
             // Run the pruner according to the configuration, and don't enforce any limits on it
             let mut pruner = PrunerBuilder::new(prune_config)
@@ -18,7 +18,8 @@ use reth_codecs::alloy::{
 };
 use reth_db::{
     models::{
-        AccountBeforeTx, StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockOmmers,
+        AccountBeforeTx, StaticFileAccountChangeSet, StaticFileBlockWithdrawals,
+        StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices, StoredBlockOmmers,
         StoredBlockWithdrawals,
     },
     ClientVersion,
@@ -114,12 +115,15 @@ compact_types!(
         StoredBlockBodyIndices,
         StoredBlockWithdrawals,
         StaticFileBlockWithdrawals,
+        StaticFileAccountChangeSet,
+        StaticFileStorageChangeSet,
         // Manual implementations
         TransactionSigned,
         // Bytecode, // todo revm arbitrary
         StorageEntry,
         // MerkleCheckpoint, // todo storedsubnode -> branchnodecompact arbitrary
         AccountBeforeTx,
+        StorageBeforeTx,
         ClientVersion,
         StoredNibbles,
         StoredNibblesSubKey,
@@ -1,5 +1,6 @@
 //! Configuration files.
 
+use alloy_primitives::Address;
 use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
@@ -31,6 +32,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the transaction pool.
+    pub txpool: TxPoolConfig,
 }
 
 impl Config {
@@ -120,6 +123,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Log Index stage configuration.
+    pub log_index: LogIndexConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -373,6 +378,25 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log Index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct LogIndexConfig {
+    /// Whether to build the log index in the pipeline.
+    ///
+    /// Blocks persisted by the engine are added to an index that was already built, disabling the
+    /// stage doesn't remove it. This is done with `reth stage drop log-index`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for LogIndexConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -449,6 +473,54 @@ where
     })
 }
 
+/// Transaction pool configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPoolConfig {
+    /// Admission policies that are checked before transactions are validated.
+    pub admission: AdmissionConfig,
+}
+
+/// Transaction pool admission policy configuration.
+///
+/// Settings that are not set here fall back to the node's cli arguments.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// Maximum number of transactions per sender and rate limit interval.
+    pub sender_rate_limit: Option<u32>,
+    /// Maximum number of transactions per origin and rate limit interval.
+    pub origin_rate_limit: Option<u32>,
+    /// Interval of the rate limits.
+    #[serde(
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub rate_limit_interval: Option<Duration>,
+    /// Senders whose transactions are rejected.
+    pub deny_from: Vec<Address>,
+    /// Recipients whose transactions are rejected.
+    pub deny_to: Vec<Address>,
+    /// Whether contract creations are rejected.
+    pub no_contract_creation: bool,
+    /// Minimum priority fee in wei of all transaction types without a minimum in
+    /// `min_tip_by_type`.
+    pub min_tip: Option<u64>,
+    /// Minimum priority fees of specific transaction types.
+    pub min_tip_by_type: Vec<MinTipConfig>,
+    /// Maximum calldata size in bytes.
+    pub max_calldata_size: Option<usize>,
+}
+
+/// Minimum priority fee of a transaction type.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct MinTipConfig {
+    /// The EIP-2718 transaction type.
+    pub tx_type: u8,
+    /// The minimum priority fee in wei.
+    pub min_tip: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Config, EXTENSION};
@@ -996,6 +1068,30 @@ connect_trusted_nodes_only = true
         assert!(conf.peers.trusted_nodes_only);
     }
 
+    #[test]
+    fn test_conf_txpool_admission() {
+        let reth_toml = r#"
+[txpool.admission]
+sender_rate_limit = 10
+rate_limit_interval = "2s"
+deny_to = ["0x0000000000000000000000000000000000000001"]
+no_contract_creation = true
+min_tip_by_type = [{ tx_type = 2, min_tip = 1000000000 }]
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let admission = &conf.txpool.admission;
+        assert_eq!(admission.sender_rate_limit, Some(10));
+        assert_eq!(admission.rate_limit_interval, Some(Duration::from_secs(2)));
+        assert_eq!(admission.deny_to, vec![Address::with_last_byte(1)]);
+        assert!(admission.no_contract_creation);
+        assert_eq!(admission.min_tip_by_type[0].min_tip, 1_000_000_000);
+
+        // round trip
+        let conf2: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(conf, conf2);
+    }
+
     #[test]
     fn test_can_support_dns_in_trusted_nodes() {
         let reth_toml = r#"
@@ -5,7 +5,6 @@ use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, FullNodeComponents};
 use reth_node_builder::{rpc::RpcRegistry, NodeTypes};
 use reth_provider::BlockReader;
-use reth_rpc_api::DebugApiServer;
 use reth_rpc_eth_api::{
     helpers::{EthApiSpec, EthTransactions, TraceExt},
     EthApiTypes,
@@ -31,7 +30,7 @@ where
 
     /// Retrieves a transaction envelope by its hash
     pub async fn envelope_by_hash(&self, hash: B256) -> eyre::Result<TxEnvelope> {
-        let tx = self.inner.debug_api().raw_transaction(hash).await?.unwrap();
+        let tx = self.inner.eth_api().raw_transaction_by_hash(hash).await?.unwrap();
         let tx = tx.to_vec();
         Ok(TxEnvelope::decode_2718(&mut tx.as_ref()).unwrap())
     }
@@ -1,4 +1,5 @@
 use alloc::boxed::Box;
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::ForkchoiceUpdateError;
 
 /// Represents all error cases when handling a new payload.
@@ -45,3 +46,33 @@ impl BeaconForkChoiceUpdateError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Represents error cases when rewinding the canonical chain with
+/// [`BeaconEngineMessage::SetHead`](crate::BeaconEngineMessage::SetHead).
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconSetHeadError {
+    /// Thrown when the target block is above the current canonical head.
+    #[error("block {target} is above the canonical head {head}")]
+    AboveHead {
+        /// The requested head.
+        target: BlockNumber,
+        /// The current canonical head.
+        head: BlockNumber,
+    },
+    /// Thrown when the head can't be changed because the engine is syncing.
+    #[error("can't set head while backfill sync is in progress")]
+    Syncing,
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// An internal error occurred while rewinding the chain.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl BeaconSetHeadError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
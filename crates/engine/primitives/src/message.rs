@@ -1,7 +1,10 @@
 use crate::{
-    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, EngineApiMessageVersion,
-    EngineTypes, ExecutionPayload, ForkchoiceStatus,
+    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, BeaconSetHeadError,
+    EngineApiMessageVersion, EngineTypes, ExecutionPayload, ForkchoiceStatus,
 };
+use alloc::boxed::Box;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -162,6 +165,16 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
     },
     /// Message with exchanged transition configuration.
     TransitionConfigurationExchanged,
+    /// Message to rewind the canonical chain to the given block.
+    ///
+    /// This is not part of the engine API, it is sent by the `debug_setHead` RPC method.
+    SetHead {
+        /// The number of the canonical block that becomes the new head.
+        number: BlockNumber,
+        /// The sender for returning the new canonical head, once the rewound blocks were removed
+        /// from disk.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    },
 }
 
 impl<Engine: EngineTypes> Display for BeaconEngineMessage<Engine> {
@@ -188,6 +201,9 @@ impl<Engine: EngineTypes> Display for BeaconEngineMessage<Engine> {
             Self::TransitionConfigurationExchanged => {
                 write!(f, "TransitionConfigurationExchanged")
             }
+            Self::SetHead { number, .. } => {
+                write!(f, "SetHead(number: {number})")
+            }
         }
     }
 }
@@ -268,4 +284,40 @@ where
     pub fn transition_configuration_exchanged(&self) {
         let _ = self.to_engine.send(BeaconEngineMessage::TransitionConfigurationExchanged);
     }
+
+    /// Sends a message to the beacon consensus engine to rewind the canonical chain to the given
+    /// block and waits for a response.
+    ///
+    /// All canonical blocks above the new head are discarded, including the ones that were already
+    /// persisted. The response is sent after these were removed from disk.
+    pub async fn set_head(&self, number: BlockNumber) -> Result<BlockNumHash, BeaconSetHeadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::SetHead { number, tx });
+        rx.await.map_err(|_| BeaconSetHeadError::EngineUnavailable)?
+    }
+}
+
+/// A type that can rewind the canonical chain.
+///
+/// This is a type erased version of [`BeaconConsensusEngineHandle::set_head`] for components that
+/// are not generic over the [`EngineTypes`], like the `debug` RPC namespace.
+pub trait ChainHeadSetter: fmt::Debug + Send + Sync {
+    /// Rewinds the canonical chain to the given block, see
+    /// [`BeaconConsensusEngineHandle::set_head`].
+    fn set_head(
+        &self,
+        number: BlockNumber,
+    ) -> Pin<Box<dyn Future<Output = Result<BlockNumHash, BeaconSetHeadError>> + Send + '_>>;
+}
+
+impl<Engine> ChainHeadSetter for BeaconConsensusEngineHandle<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn set_head(
+        &self,
+        number: BlockNumber,
+    ) -> Pin<Box<dyn Future<Output = Result<BlockNumHash, BeaconSetHeadError>> + Send + '_>> {
+        Box::pin(Self::set_head(self, number))
+    }
 }
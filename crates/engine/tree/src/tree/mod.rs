@@ -23,14 +23,15 @@ use error::{InsertBlockError, InsertBlockErrorKind, InsertBlockFatalError};
 use metrics::PrewarmThreadMetrics;
 use persistence_state::CurrentPersistenceAction;
 use reth_chain_state::{
-    CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates,
+    CanonStateNotification, CanonicalInMemoryState, ExecutedBlock, ExecutedBlockWithTrieUpdates,
     MemoryOverlayStateProvider, NewCanonicalChain,
 };
 use reth_consensus::{Consensus, FullConsensus, PostExecutionInput};
 pub use reth_engine_primitives::InvalidBlockHook;
 use reth_engine_primitives::{
-    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, EngineTypes,
-    EngineValidator, ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
+    BeaconConsensusEngineEvent, BeaconEngineMessage, BeaconOnNewPayloadError, BeaconSetHeadError,
+    EngineTypes, EngineValidator, ExecutionPayload, ForkchoiceStateTracker, ForkchoiceStatus,
+    OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_ethereum_primitives::EthPrimitives;
@@ -46,7 +47,7 @@ use reth_primitives_traits::{
     SignedTransaction,
 };
 use reth_provider::{
-    providers::ConsistentDbView, BlockReader, DBProvider, DatabaseProviderFactory,
+    providers::ConsistentDbView, BlockReader, Chain, DBProvider, DatabaseProviderFactory,
     ExecutionOutcome, HashedPostStateProvider, ProviderError, StateCommitmentProvider,
     StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
 };
@@ -284,6 +285,35 @@ impl<N: NodePrimitives> TreeState<N> {
         debug!(target: "engine::tree", ?upper_bound, ?last_persisted_hash, "Removed canonical blocks from the tree");
     }
 
+    /// Removes all canonical blocks above the given block number, together with all blocks that
+    /// descend from them.
+    ///
+    /// This also drops the persisted trie updates of the removed heights.
+    pub(crate) fn remove_canonical_above(&mut self, new_tip_num: BlockNumber) {
+        debug!(target: "engine::tree", ?new_tip_num, "Removing canonical blocks above the new tip from the tree");
+
+        // walk back the canonical chain and collect all blocks above the new tip
+        let mut blocks_to_remove = Vec::new();
+        let mut current_block = self.current_canonical_head.hash;
+        while let Some(executed) = self.blocks_by_hash.get(&current_block) {
+            if executed.recovered_block().number() <= new_tip_num {
+                break
+            }
+            blocks_to_remove.push(current_block);
+            current_block = executed.recovered_block().parent_hash();
+        }
+
+        // remove them and all of their descendants
+        while let Some(hash) = blocks_to_remove.pop() {
+            if let Some((removed, children)) = self.remove_by_hash(hash) {
+                trace!(target: "engine::tree", num_hash=?removed.recovered_block().num_hash(), "Removed block above the new tip");
+                blocks_to_remove.extend(children);
+            }
+        }
+
+        self.persisted_trie_updates.retain(|_, (block_num, _)| *block_num <= new_tip_num);
+    }
+
     /// Removes all blocks that are below the finalized block, as well as removing non-canonical
     /// sidechains that fork from below the finalized block.
     pub(crate) fn prune_finalized_sidechains(&mut self, finalized_num_hash: BlockNumHash) {
@@ -606,6 +636,10 @@ where
     most_recent_cache: Option<SavedCache>,
     /// Thread pool used for the state root task and prewarming
     thread_pool: Arc<rayon::ThreadPool>,
+    /// Pending `SetHead` requests that are answered once the removal of the persisted blocks above
+    /// the new head has finished.
+    pending_set_head:
+        Vec<(BlockNumHash, oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>)>,
 }
 
 impl<N, P: Debug, E: Debug, T: EngineTypes + Debug, V: Debug, C: Debug> std::fmt::Debug
@@ -703,6 +737,7 @@ where
             engine_kind,
             most_recent_cache: None,
             thread_pool,
+            pending_set_head: Vec::new(),
         }
     }
 
@@ -1358,6 +1393,15 @@ where
             }
         }
 
+        if !self.persistence_state.in_progress() &&
+            self.persistence_state.remove_above_state.is_empty()
+        {
+            // all scheduled removals have been processed, so the rewound blocks are gone from disk
+            for (head, tx) in self.pending_set_head.drain(..) {
+                let _ = tx.send(Ok(head));
+            }
+        }
+
         Ok(())
     }
 
@@ -1455,6 +1499,25 @@ where
                                 self.canonical_in_memory_state
                                     .on_transition_configuration_exchanged();
                             }
+                            BeaconEngineMessage::SetHead { number, tx } => {
+                                match self.on_set_head(number) {
+                                    // wait for the removal of the persisted blocks before replying
+                                    Ok(head)
+                                        if self.persistence_state.in_progress() ||
+                                            !self
+                                                .persistence_state
+                                                .remove_above_state
+                                                .is_empty() =>
+                                    {
+                                        self.pending_set_head.push((head, tx));
+                                    }
+                                    output => {
+                                        if let Err(err) = tx.send(output) {
+                                            error!(target: "engine::tree", "Failed to send event: {err:?}");
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -1468,6 +1531,122 @@ where
         Ok(())
     }
 
+    /// Rewinds the canonical chain to the block with the given number.
+    ///
+    /// All canonical blocks above the new head are dropped from the tree and the in-memory state,
+    /// and persisted blocks above it are scheduled for removal from disk. The removal unwinds the
+    /// entire block and execution data of these blocks, like `reth stage unwind` does.
+    ///
+    /// The safe and finalized blocks are clamped to the new head if they were rewound, and a
+    /// [`CanonStateNotification::Reorg`] is emitted with the rewound blocks as the old chain and
+    /// the new head as the new chain, since a notification can't commit an empty chain.
+    fn on_set_head(&mut self, number: BlockNumber) -> Result<BlockNumHash, BeaconSetHeadError> {
+        if !self.backfill_sync_state.is_idle() {
+            return Err(BeaconSetHeadError::Syncing)
+        }
+
+        let head = *self.state.tree_state.canonical_head();
+        if number > head.number {
+            return Err(BeaconSetHeadError::AboveHead { target: number, head: head.number })
+        }
+        if number == head.number {
+            return Ok(head)
+        }
+
+        // the new head is either still in memory or already persisted
+        let new_head = match self.canonical_in_memory_state.state_by_number(number) {
+            Some(state) => state.block_ref().recovered_block().clone_sealed_header(),
+            None => self
+                .provider
+                .sealed_header(number)
+                .map_err(BeaconSetHeadError::internal)?
+                .ok_or_else(|| {
+                    BeaconSetHeadError::internal(ProviderError::HeaderNotFound(number.into()))
+                })?,
+        };
+        let new_head_num_hash = new_head.num_hash();
+
+        // collect the rewound blocks before they are dropped, the persisted ones are still on disk
+        // because their removal is only scheduled below
+        let mut old_blocks = Vec::with_capacity((head.number - number) as usize);
+        let mut hash = head.hash;
+        while hash != new_head_num_hash.hash {
+            let block = self
+                .canonical_block_by_hash(hash)
+                .map_err(BeaconSetHeadError::internal)?
+                .ok_or_else(|| {
+                BeaconSetHeadError::internal(ProviderError::HeaderNotFound(hash.into()))
+            })?;
+            hash = block.recovered_block().parent_hash();
+            old_blocks.push(block);
+        }
+        let new_head_block = self
+            .canonical_block_by_hash(new_head_num_hash.hash)
+            .map_err(BeaconSetHeadError::internal)?
+            .ok_or_else(|| {
+                BeaconSetHeadError::internal(ProviderError::HeaderNotFound(number.into()))
+            })?;
+
+        info!(target: "engine::tree", old_head=?head, new_head=?new_head_num_hash, "Rewinding canonical chain");
+
+        // the persistence task skips the removal if the blocks above the new head were not
+        // persisted by the time it is processed
+        self.persistence_state.schedule_removal(number);
+
+        self.state.tree_state.remove_canonical_above(number);
+        self.state.tree_state.set_canonical_head(new_head_num_hash);
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+
+        self.canonical_in_memory_state.remove_blocks_above(number);
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+        self.metrics.tree.canonical_chain_height.set(number as f64);
+
+        // clamp the safe and finalized blocks and the tracked forkchoice state to the new head
+        if self.canonical_in_memory_state.get_safe_num_hash().is_some_and(|b| b.number > number) {
+            self.canonical_in_memory_state.set_safe(new_head.clone());
+        }
+        if self
+            .canonical_in_memory_state
+            .get_finalized_num_hash()
+            .is_some_and(|b| b.number > number)
+        {
+            self.canonical_in_memory_state.set_finalized(new_head);
+        }
+        if let Some(state) = self.state.forkchoice_state_tracker.last_valid_state() {
+            let clamp = |hash: B256| {
+                if old_blocks.iter().any(|block| block.recovered_block().hash() == hash) {
+                    new_head_num_hash.hash
+                } else {
+                    hash
+                }
+            };
+            let state = ForkchoiceState {
+                head_block_hash: new_head_num_hash.hash,
+                safe_block_hash: clamp(state.safe_block_hash),
+                finalized_block_hash: clamp(state.finalized_block_hash),
+            };
+            self.state.forkchoice_state_tracker.set_latest(state, ForkchoiceStatus::Valid);
+        }
+
+        // notify listeners about the rewound blocks
+        self.update_reorg_metrics(old_blocks.len());
+        let old = old_blocks.iter().rev().fold(Chain::default(), |mut chain, block| {
+            chain.append_block(block.recovered_block().clone(), block.execution_outcome().clone());
+            chain
+        });
+        let new = Chain::from_block(
+            new_head_block.recovered_block().clone(),
+            new_head_block.execution_outcome().clone(),
+            None,
+        );
+        self.canonical_in_memory_state.notify_canon_state(CanonStateNotification::Reorg {
+            old: Arc::new(old),
+            new: Arc::new(new),
+        });
+
+        Ok(new_head_num_hash)
+    }
+
     /// Invoked if the backfill sync has finished to target.
     ///
     /// At this point we consider the block synced to the backfill target.
@@ -2846,6 +3025,8 @@ where
 
         // keep track of the invalid header
         self.state.invalid_headers.insert(block.block_with_parent());
+        // retain the full block so it can be inspected via `debug_getBadBlocks`
+        self.canonical_in_memory_state.insert_bad_block(block.clone());
         self.emit_event(EngineApiEvent::BeaconConsensus(BeaconConsensusEngineEvent::InvalidBlock(
             Box::new(block),
         )));
@@ -3974,6 +4155,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_tree_state_remove_canonical_above() {
+        let start_num_hash = BlockNumHash::default();
+        let mut tree_state = TreeState::new(start_num_hash);
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(1..6).collect();
+
+        for block in &blocks {
+            tree_state.insert_executed(block.clone());
+        }
+
+        // fork off block 3, this must be removed together with its parent
+        let fork_block = test_block_builder
+            .get_executed_block_with_number(4, blocks[2].recovered_block().hash());
+        tree_state.insert_executed(fork_block.clone());
+
+        tree_state.set_canonical_head(blocks.last().unwrap().recovered_block().num_hash());
+
+        tree_state.remove_canonical_above(2);
+
+        assert!(tree_state.blocks_by_hash.contains_key(&blocks[0].recovered_block().hash()));
+        assert!(tree_state.blocks_by_hash.contains_key(&blocks[1].recovered_block().hash()));
+        for block in blocks.iter().skip(2).chain(std::iter::once(&fork_block)) {
+            assert!(!tree_state.blocks_by_hash.contains_key(&block.recovered_block().hash()));
+        }
+        assert_eq!(tree_state.blocks_by_number.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(!tree_state.parent_to_child.contains_key(&blocks[1].recovered_block().hash()));
+    }
+
     #[tokio::test]
     async fn test_tree_state_remove_before_finalized() {
         let start_num_hash = BlockNumHash::default();
@@ -4200,6 +4410,85 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_set_head_rewinds_canonical_chain() {
+        reth_tracing::init_test_tracing();
+        let chain_spec = MAINNET.clone();
+
+        let mut test_block_builder = TestBlockBuilder::eth();
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..6).collect();
+        let mut test_harness = TestHarness::new(chain_spec).with_blocks(blocks.clone());
+
+        // blocks up to 4 are on disk, safe and finalized are above the new head
+        test_harness.tree.persistence_state.last_persisted_block =
+            blocks[4].recovered_block().num_hash();
+        let safe = blocks[4].recovered_block().clone_sealed_header();
+        let finalized = blocks[3].recovered_block().clone_sealed_header();
+        test_harness.tree.canonical_in_memory_state.set_safe(safe.clone());
+        test_harness.tree.canonical_in_memory_state.set_finalized(finalized.clone());
+        test_harness.tree.state.forkchoice_state_tracker.set_latest(
+            ForkchoiceState {
+                head_block_hash: blocks[5].recovered_block().hash(),
+                safe_block_hash: safe.hash(),
+                finalized_block_hash: finalized.hash(),
+            },
+            ForkchoiceStatus::Valid,
+        );
+        let mut notifications = test_harness.tree.canonical_in_memory_state.subscribe_canon_state();
+
+        let (tx, mut rx) = oneshot::channel();
+        test_harness
+            .tree
+            .on_engine_message(FromEngine::Request(
+                BeaconEngineMessage::SetHead { number: 2, tx }.into(),
+            ))
+            .unwrap();
+
+        let new_head = blocks[2].recovered_block().num_hash();
+        assert_eq!(*test_harness.tree.state.tree_state.canonical_head(), new_head);
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+            new_head
+        );
+        assert_eq!(test_harness.tree.canonical_in_memory_state.get_safe_num_hash(), Some(new_head));
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_finalized_num_hash(),
+            Some(new_head)
+        );
+        assert_eq!(
+            test_harness.tree.state.forkchoice_state_tracker.last_valid_state(),
+            Some(ForkchoiceState {
+                head_block_hash: new_head.hash,
+                safe_block_hash: new_head.hash,
+                finalized_block_hash: new_head.hash,
+            })
+        );
+
+        let notification = notifications.try_recv().unwrap();
+        let CanonStateNotification::Reorg { old, new } = notification else {
+            panic!("expected a reorg notification")
+        };
+        assert_eq!(
+            old.blocks_iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            blocks[3..].iter().map(|block| block.recovered_block().hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(new.tip().num_hash(), new_head);
+
+        // the reply waits for the persisted blocks to be removed
+        test_harness.tree.advance_persistence().unwrap();
+        let PersistenceAction::RemoveBlocksAbove(number, sender) =
+            test_harness.action_rx.recv().unwrap()
+        else {
+            panic!("received wrong action")
+        };
+        assert_eq!(number, 2);
+        assert!(rx.try_recv().is_err());
+
+        sender.send(Some(new_head)).unwrap();
+        test_harness.tree.advance_persistence().unwrap();
+        assert_eq!(rx.await.unwrap().unwrap(), new_head);
+    }
+
     #[tokio::test]
     async fn test_tree_state_on_new_head_deep_fork() {
         reth_tracing::init_test_tracing();
@@ -0,0 +1,84 @@
+//! Accumulator root of an era1 epoch.
+
+use crate::MAX_BLOCKS_PER_ERA1;
+use alloy_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Depth of the merkle tree of the header records of an epoch.
+const ACCUMULATOR_DEPTH: u32 = MAX_BLOCKS_PER_ERA1.trailing_zeros();
+
+/// Hash and total difficulty of a block, the leaves of the accumulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ hash tree root of the record.
+    fn tree_root(&self) -> B256 {
+        hash_pair(self.block_hash, self.total_difficulty.to_le_bytes::<32>().into())
+    }
+}
+
+/// Returns the accumulator root of the given header records.
+///
+/// This is the SSZ hash tree root of the records as a `List[HeaderRecord, 8192]`, which era1 files
+/// commit to.
+pub fn accumulator_root(records: &[HeaderRecord]) -> B256 {
+    debug_assert!(records.len() <= MAX_BLOCKS_PER_ERA1, "too many header records");
+
+    let mut layer = records.iter().map(HeaderRecord::tree_root).collect::<Vec<_>>();
+    // root of an empty subtree of the current layer
+    let mut zero = B256::ZERO;
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+        zero = hash_pair(zero, zero);
+    }
+    let root = layer.first().copied().unwrap_or(zero);
+
+    // mix in the length of the list
+    let mut length = B256::ZERO;
+    length[..8].copy_from_slice(&(records.len() as u64).to_le_bytes());
+    hash_pair(root, length)
+}
+
+/// Returns the sha256 hash of the concatenation of both nodes.
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn accumulator_root_of_records() {
+        let records = [0x11, 0x22, 0x33]
+            .into_iter()
+            .zip(1u64..)
+            .map(|(byte, td)| HeaderRecord {
+                block_hash: B256::repeat_byte(byte),
+                total_difficulty: U256::from(td),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            accumulator_root(&records),
+            b256!("d85acc91610b6b103a70fe4a3892e8f7151be898858fe7cb6195230d27b10f3f")
+        );
+        assert_eq!(
+            accumulator_root(&[]),
+            b256!("4a8c3a07c8d23adc5bac61157555c3c784d53d9bc110c1370809bd23cd93777d")
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! The [e2store] format era1 files are built on.
+//!
+//! An e2store file is a sequence of entries. Every entry starts with an 8 byte header holding the
+//! little endian entry type (2 bytes), data length (4 bytes) and 2 reserved zero bytes, followed by
+//! the entry data.
+//!
+//! [e2store]: https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+
+use crate::EraError;
+use std::io::{self, Read, Write};
+
+/// Size of the header of an entry.
+pub const HEADER_SIZE: u64 = 8;
+
+/// Type of the version entry every e2store file starts with.
+pub const VERSION: u16 = 0x3265;
+
+/// A single e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The type of the entry.
+    pub entry_type: u16,
+    /// The data of the entry.
+    pub data: Vec<u8>,
+}
+
+impl Entry {
+    /// Creates a new entry.
+    pub const fn new(entry_type: u16, data: Vec<u8>) -> Self {
+        Self { entry_type, data }
+    }
+
+    /// Returns the size of the encoded entry, including its header.
+    pub fn encoded_len(&self) -> u64 {
+        HEADER_SIZE + self.data.len() as u64
+    }
+}
+
+/// Reads entries from an e2store file.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+    /// Offset of the next entry from the start of the file.
+    position: u64,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a new reader positioned at the start of the file.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, position: 0 }
+    }
+
+    /// Returns the offset of the next entry from the start of the file.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next entry, or returns `None` at the end of the file.
+    pub fn read_entry(&mut self) -> Result<Option<Entry>, EraError> {
+        let mut header = [0u8; HEADER_SIZE as usize];
+        if !self.read_header(&mut header)? {
+            return Ok(None)
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let length = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if header[6] != 0 || header[7] != 0 {
+            return Err(EraError::InvalidEntry { entry_type, reason: "reserved bytes are not zero" })
+        }
+
+        // The length is read from the file, so the buffer only grows with the data that is
+        // actually there instead of being allocated upfront.
+        let mut data = Vec::new();
+        (&mut self.reader).take(length as u64).read_to_end(&mut data)?;
+        if data.len() != length as usize {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+
+        let entry = Entry::new(entry_type, data);
+        self.position += entry.encoded_len();
+        Ok(Some(entry))
+    }
+
+    /// Fills the header buffer, returns `false` if the file ends before the header.
+    fn read_header(&mut self, buf: &mut [u8]) -> io::Result<bool> {
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) if read == 0 => return Ok(false),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Writes entries to an e2store file.
+#[derive(Debug)]
+pub struct E2StoreWriter<W> {
+    writer: W,
+    /// Offset of the next entry from the start of the file.
+    position: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Creates a new writer at the start of the file.
+    pub const fn new(writer: W) -> Self {
+        Self { writer, position: 0 }
+    }
+
+    /// Returns the offset of the next entry from the start of the file.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Appends the entry to the file.
+    pub fn write_entry(&mut self, entry: &Entry) -> Result<(), EraError> {
+        let length = u32::try_from(entry.data.len()).map_err(|_| EraError::InvalidEntry {
+            entry_type: entry.entry_type,
+            reason: "data exceeds the maximum entry length",
+        })?;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        header[..2].copy_from_slice(&entry.entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&length.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(&entry.data)?;
+
+        self.position += entry.encoded_len();
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_roundtrip() {
+        let entries = vec![
+            Entry::new(VERSION, Vec::new()),
+            Entry::new(0x03, vec![1, 2, 3]),
+            Entry::new(0x3266, vec![0xff; 1024]),
+        ];
+
+        let mut writer = E2StoreWriter::new(Vec::new());
+        for entry in &entries {
+            writer.write_entry(entry).unwrap();
+        }
+        assert_eq!(writer.position(), 3 * HEADER_SIZE + 3 + 1024);
+        let buf = writer.into_inner();
+        assert_eq!(&buf[..8], &[0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let mut reader = E2StoreReader::new(buf.as_slice());
+        for entry in &entries {
+            assert_eq!(reader.read_entry().unwrap().as_ref(), Some(entry));
+        }
+        assert_eq!(reader.read_entry().unwrap(), None);
+        assert_eq!(reader.position(), buf.len() as u64);
+    }
+
+    #[test]
+    fn rejects_malformed_entries() {
+        // truncated header
+        let mut reader = E2StoreReader::new([0x03, 0x00, 0x01].as_slice());
+        assert!(matches!(reader.read_entry(), Err(EraError::Io(_))));
+
+        // truncated data
+        let mut reader = E2StoreReader::new([0x03, 0x00, 0x02, 0, 0, 0, 0, 0, 1].as_slice());
+        assert!(matches!(reader.read_entry(), Err(EraError::Io(_))));
+
+        // maximum length with truncated data
+        let mut reader =
+            E2StoreReader::new([0x03, 0x00, 0xff, 0xff, 0xff, 0xff, 0, 0, 1].as_slice());
+        assert!(matches!(reader.read_entry(), Err(EraError::Io(_))));
+
+        // non-zero reserved bytes
+        let mut reader = E2StoreReader::new([0x03, 0x00, 0, 0, 0, 0, 0, 1].as_slice());
+        assert!(matches!(
+            reader.read_entry(),
+            Err(EraError::InvalidEntry { entry_type: 0x03, .. })
+        ));
+    }
+}
@@ -0,0 +1,449 @@
+//! era1 files: the block tuples of consecutive blocks, followed by the accumulator root and the
+//! block index of the file.
+
+use crate::{
+    accumulator_root,
+    e2s::{E2StoreReader, E2StoreWriter, Entry, VERSION},
+    EraError, HeaderRecord,
+};
+use alloy_primitives::{hex, keccak256, B256, U256};
+use alloy_rlp::{Decodable, Encodable};
+use snap::{read::FrameDecoder, write::FrameEncoder};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Write},
+    ops::Range,
+    path::Path,
+};
+
+/// Maximum number of blocks of an era1 file, the length of an epoch.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// Entry type of the snappy compressed RLP encoded header.
+const COMPRESSED_HEADER: u16 = 0x03;
+/// Entry type of the snappy compressed RLP encoded body.
+const COMPRESSED_BODY: u16 = 0x04;
+/// Entry type of the snappy compressed RLP encoded receipts.
+const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Entry type of the little endian total difficulty.
+const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Entry type of the accumulator root.
+const ACCUMULATOR: u16 = 0x07;
+/// Entry type of the block index.
+const BLOCK_INDEX: u16 = 0x3266;
+
+/// Returns the canonical name of an era1 file: `<network>-<epoch>-<short accumulator root>.era1`.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    format!("{network}-{epoch:05}-{}.era1", hex::encode(&accumulator_root[..4]))
+}
+
+/// The snappy compressed header, body and receipts of a block, and its total difficulty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockTuple {
+    header: Vec<u8>,
+    body: Vec<u8>,
+    receipts: Vec<u8>,
+    /// Total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl BlockTuple {
+    /// Encodes and compresses the given block.
+    ///
+    /// The receipts are expected to be the list of the network encoded receipts, including their
+    /// blooms.
+    pub fn new<H, B, R>(
+        header: &H,
+        body: &B,
+        receipts: &R,
+        total_difficulty: U256,
+    ) -> Result<Self, EraError>
+    where
+        H: Encodable,
+        B: Encodable,
+        R: Encodable + ?Sized,
+    {
+        Ok(Self {
+            header: compress(&alloy_rlp::encode(header))?,
+            body: compress(&alloy_rlp::encode(body))?,
+            receipts: compress(&alloy_rlp::encode(receipts))?,
+            total_difficulty,
+        })
+    }
+
+    /// Returns the RLP encoded header.
+    pub fn header_rlp(&self) -> Result<Vec<u8>, EraError> {
+        decompress(&self.header)
+    }
+
+    /// Returns the hash of the header.
+    pub fn header_hash(&self) -> Result<B256, EraError> {
+        Ok(keccak256(self.header_rlp()?))
+    }
+
+    /// Decodes the header.
+    pub fn decode_header<H: Decodable>(&self) -> Result<H, EraError> {
+        decode(&self.header)
+    }
+
+    /// Decodes the body.
+    pub fn decode_body<B: Decodable>(&self) -> Result<B, EraError> {
+        decode(&self.body)
+    }
+
+    /// Decodes the receipts.
+    pub fn decode_receipts<R: Decodable>(&self) -> Result<R, EraError> {
+        decode(&self.receipts)
+    }
+}
+
+/// Index of the block tuples of an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockIndex {
+    /// Number of the first block of the file.
+    pub starting_number: u64,
+    /// Offsets of the block tuples, relative to the start of the block index entry.
+    pub offsets: Vec<i64>,
+}
+
+impl BlockIndex {
+    /// Returns the range of block numbers in the file.
+    pub fn blocks(&self) -> Range<u64> {
+        self.starting_number..self.starting_number + self.offsets.len() as u64
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(16 + self.offsets.len() * 8);
+        data.extend_from_slice(&self.starting_number.to_le_bytes());
+        for offset in &self.offsets {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        data
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, EraError> {
+        let invalid = |reason| EraError::InvalidEntry { entry_type: BLOCK_INDEX, reason };
+        if data.len() < 16 || data.len() % 8 != 0 {
+            return Err(invalid("invalid length"))
+        }
+
+        let mut words = data
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("chunks have a length of 8")));
+        let starting_number = words.next().expect("length is checked");
+        let count = words.next_back().expect("length is checked");
+        let offsets = words.map(|offset| offset as i64).collect::<Vec<_>>();
+        if offsets.len() as u64 != count {
+            return Err(invalid("count doesn't match the number of offsets"))
+        }
+
+        Ok(Self { starting_number, offsets })
+    }
+}
+
+/// Writes the blocks of an epoch to an era1 file.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: E2StoreWriter<W>,
+    /// Number of the first block of the file.
+    starting_number: u64,
+    /// Offsets of the written block tuples from the start of the file.
+    positions: Vec<u64>,
+    /// Header records of the written blocks.
+    records: Vec<HeaderRecord>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer for the blocks starting at the given block number, and writes the
+    /// version entry.
+    pub fn new(writer: W, starting_number: u64) -> Result<Self, EraError> {
+        let mut writer = E2StoreWriter::new(writer);
+        writer.write_entry(&Entry::new(VERSION, Vec::new()))?;
+        Ok(Self { writer, starting_number, positions: Vec::new(), records: Vec::new() })
+    }
+
+    /// Returns the number of written blocks.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no block was written yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends the next block.
+    pub fn append(&mut self, block: &BlockTuple) -> Result<(), EraError> {
+        if self.records.len() >= MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::TooManyBlocks)
+        }
+
+        let block_hash = block.header_hash()?;
+        self.positions.push(self.writer.position());
+        self.writer.write_entry(&Entry::new(COMPRESSED_HEADER, block.header.clone()))?;
+        self.writer.write_entry(&Entry::new(COMPRESSED_BODY, block.body.clone()))?;
+        self.writer.write_entry(&Entry::new(COMPRESSED_RECEIPTS, block.receipts.clone()))?;
+        self.writer.write_entry(&Entry::new(
+            TOTAL_DIFFICULTY,
+            block.total_difficulty.to_le_bytes::<32>().to_vec(),
+        ))?;
+
+        self.records.push(HeaderRecord { block_hash, total_difficulty: block.total_difficulty });
+        Ok(())
+    }
+
+    /// Writes the accumulator and block index, and returns the accumulator root.
+    pub fn finish(mut self) -> Result<B256, EraError> {
+        let root = accumulator_root(&self.records);
+        self.writer.write_entry(&Entry::new(ACCUMULATOR, root.to_vec()))?;
+
+        let index_position = self.writer.position();
+        let index = BlockIndex {
+            starting_number: self.starting_number,
+            offsets: self
+                .positions
+                .iter()
+                .map(|position| *position as i64 - index_position as i64)
+                .collect(),
+        };
+        self.writer.write_entry(&Entry::new(BLOCK_INDEX, index.encode()))?;
+        self.writer.flush()?;
+
+        Ok(root)
+    }
+}
+
+/// Reads the blocks of an era1 file.
+///
+/// The block tuples are yielded in order. The accumulator root and block index at the end of the
+/// file are available once all blocks are read.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    reader: E2StoreReader<R>,
+    /// Number of read block tuples.
+    blocks: usize,
+    /// The accumulator root, once all blocks are read.
+    accumulator: Option<B256>,
+    /// The block index, once all blocks are read.
+    block_index: Option<BlockIndex>,
+}
+
+impl Era1Reader<BufReader<File>> {
+    /// Opens the era1 file at the given path.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EraError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader and reads the version entry.
+    pub fn new(reader: R) -> Result<Self, EraError> {
+        let mut reader = E2StoreReader::new(reader);
+        let version = reader.read_entry()?.ok_or(EraError::Incomplete("version"))?;
+        if version.entry_type != VERSION {
+            return Err(EraError::UnexpectedEntry { expected: VERSION, got: version.entry_type })
+        }
+        if !version.data.is_empty() {
+            return Err(EraError::InvalidEntry { entry_type: VERSION, reason: "data is not empty" })
+        }
+
+        Ok(Self { reader, blocks: 0, accumulator: None, block_index: None })
+    }
+
+    /// Returns the accumulator root, if all blocks were read.
+    pub const fn accumulator(&self) -> Option<B256> {
+        self.accumulator
+    }
+
+    /// Returns the block index, if all blocks were read.
+    pub const fn block_index(&self) -> Option<&BlockIndex> {
+        self.block_index.as_ref()
+    }
+
+    /// Reads the next block tuple, or returns `None` after the last block.
+    ///
+    /// Entries of unknown types are skipped.
+    pub fn next_block(&mut self) -> Result<Option<BlockTuple>, EraError> {
+        if self.block_index.is_some() {
+            return Ok(None)
+        }
+
+        loop {
+            let entry = self.reader.read_entry()?.ok_or(EraError::Incomplete("accumulator"))?;
+            match entry.entry_type {
+                COMPRESSED_HEADER => {
+                    let body = self.read_entry_of_type(COMPRESSED_BODY)?;
+                    let receipts = self.read_entry_of_type(COMPRESSED_RECEIPTS)?;
+                    let total_difficulty = self.read_entry_of_type(TOTAL_DIFFICULTY)?;
+                    let total_difficulty: [u8; 32] = total_difficulty
+                        .as_slice()
+                        .try_into()
+                        .map_err(|_| EraError::InvalidEntry {
+                            entry_type: TOTAL_DIFFICULTY,
+                            reason: "expected 32 bytes",
+                        })?;
+
+                    self.blocks += 1;
+                    return Ok(Some(BlockTuple {
+                        header: entry.data,
+                        body,
+                        receipts,
+                        total_difficulty: U256::from_le_bytes(total_difficulty),
+                    }))
+                }
+                ACCUMULATOR => {
+                    if entry.data.len() != 32 {
+                        return Err(EraError::InvalidEntry {
+                            entry_type: ACCUMULATOR,
+                            reason: "expected 32 bytes",
+                        })
+                    }
+                    self.accumulator = Some(B256::from_slice(&entry.data));
+
+                    let index = self.read_entry_of_type(BLOCK_INDEX)?;
+                    let index = BlockIndex::decode(&index)?;
+                    if index.offsets.len() != self.blocks {
+                        return Err(EraError::InvalidEntry {
+                            entry_type: BLOCK_INDEX,
+                            reason: "number of blocks doesn't match the file",
+                        })
+                    }
+                    self.block_index = Some(index);
+                    return Ok(None)
+                }
+                COMPRESSED_BODY | COMPRESSED_RECEIPTS | TOTAL_DIFFICULTY | BLOCK_INDEX => {
+                    return Err(EraError::UnexpectedEntry {
+                        expected: COMPRESSED_HEADER,
+                        got: entry.entry_type,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the next entry and returns its data, if it has the expected type.
+    fn read_entry_of_type(&mut self, expected: u16) -> Result<Vec<u8>, EraError> {
+        let entry = self.reader.read_entry()?.ok_or(EraError::Incomplete("block tuple"))?;
+        if entry.entry_type != expected {
+            return Err(EraError::UnexpectedEntry { expected, got: entry.entry_type })
+        }
+        Ok(entry.data)
+    }
+}
+
+impl<R: Read> Iterator for Era1Reader<R> {
+    type Item = Result<BlockTuple, EraError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_block().transpose()
+    }
+}
+
+/// Compresses the data with the snappy framing format.
+fn compress(data: &[u8]) -> Result<Vec<u8>, EraError> {
+    let mut compressed = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut compressed);
+    encoder.write_all(data)?;
+    encoder.flush()?;
+    drop(encoder);
+    Ok(compressed)
+}
+
+/// Decompresses data compressed with the snappy framing format.
+fn decompress(data: &[u8]) -> Result<Vec<u8>, EraError> {
+    let mut decompressed = Vec::new();
+    FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Decompresses and decodes the RLP encoded value.
+fn decode<T: Decodable>(data: &[u8]) -> Result<T, EraError> {
+    let rlp = decompress(data)?;
+    let buf = &mut rlp.as_slice();
+    let value = T::decode(buf)?;
+    if !buf.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength.into())
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::e2s::HEADER_SIZE;
+    use alloy_primitives::Bytes;
+    use std::io::Cursor;
+
+    fn block(number: u64) -> (Bytes, Vec<Bytes>, Vec<Bytes>, U256) {
+        let header = Bytes::from(number.to_be_bytes().to_vec());
+        let body = vec![Bytes::from(vec![number as u8; number as usize])];
+        let receipts = vec![Bytes::from(vec![0xaa; 3]); number as usize];
+        (header, body, receipts, U256::from(number * 10))
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let mut buf = Vec::new();
+        let mut writer = Era1Writer::new(&mut buf, 100).unwrap();
+        let mut records = Vec::new();
+        for number in 100..103 {
+            let (header, body, receipts, td) = block(number);
+            writer.append(&BlockTuple::new(&header, &body, &receipts, td).unwrap()).unwrap();
+            records.push(HeaderRecord {
+                block_hash: keccak256(alloy_rlp::encode(&header)),
+                total_difficulty: td,
+            });
+        }
+        assert_eq!(writer.len(), 3);
+        let root = writer.finish().unwrap();
+        assert_eq!(root, accumulator_root(&records));
+
+        let mut reader = Era1Reader::new(Cursor::new(&buf)).unwrap();
+        assert_eq!(reader.accumulator(), None);
+        for number in 100..103 {
+            let (header, body, receipts, td) = block(number);
+            let tuple = reader.next().unwrap().unwrap();
+            assert_eq!(tuple.decode_header::<Bytes>().unwrap(), header);
+            assert_eq!(tuple.decode_body::<Vec<Bytes>>().unwrap(), body);
+            assert_eq!(tuple.decode_receipts::<Vec<Bytes>>().unwrap(), receipts);
+            assert_eq!(tuple.total_difficulty, td);
+        }
+        assert!(reader.next().is_none());
+        assert_eq!(reader.accumulator(), Some(root));
+
+        let index = reader.block_index().unwrap();
+        assert_eq!(index.blocks(), 100..103);
+        // the block index holds the starting number, three offsets and the count
+        let index_position = buf.len() as u64 - (HEADER_SIZE + 5 * 8);
+        // the first block tuple follows the version entry
+        assert_eq!(index.offsets[0], HEADER_SIZE as i64 - index_position as i64);
+    }
+
+    #[test]
+    fn era1_rejects_incomplete_files() {
+        let mut buf = Vec::new();
+        let mut writer = Era1Writer::new(&mut buf, 0).unwrap();
+        let (header, body, receipts, td) = block(0);
+        writer.append(&BlockTuple::new(&header, &body, &receipts, td).unwrap()).unwrap();
+        drop(writer);
+
+        let mut reader = Era1Reader::new(buf.as_slice()).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(reader.next(), Some(Err(EraError::Incomplete("accumulator")))));
+
+        assert!(matches!(
+            Era1Reader::new([0x03, 0, 0, 0, 0, 0, 0, 0].as_slice()),
+            Err(EraError::UnexpectedEntry { expected: VERSION, got: 0x03 })
+        ));
+    }
+
+    #[test]
+    fn era1_file_names() {
+        assert_eq!(
+            era1_file_name("mainnet", 1, B256::repeat_byte(0xab)),
+            "mainnet-00001-abababab.era1"
+        );
+    }
+}
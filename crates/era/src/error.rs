@@ -0,0 +1,35 @@
+use crate::MAX_BLOCKS_PER_ERA1;
+use std::io;
+
+/// Errors that can occur when reading or writing era1 files.
+#[derive(Debug, thiserror::Error)]
+pub enum EraError {
+    /// Error while reading or writing the file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Error while decoding the RLP of an entry.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// An entry of a different type was expected at this position.
+    #[error("unexpected e2store entry type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The expected entry type.
+        expected: u16,
+        /// The entry type found in the file.
+        got: u16,
+    },
+    /// The entry is malformed.
+    #[error("invalid e2store entry of type {entry_type:#06x}: {reason}")]
+    InvalidEntry {
+        /// The type of the malformed entry.
+        entry_type: u16,
+        /// Why the entry is malformed.
+        reason: &'static str,
+    },
+    /// The file ended before the given entry.
+    #[error("era1 file is missing the {0} entry")]
+    Incomplete(&'static str),
+    /// An era1 file holds at most [`MAX_BLOCKS_PER_ERA1`] blocks.
+    #[error("era1 file can't hold more than {MAX_BLOCKS_PER_ERA1} blocks")]
+    TooManyBlocks,
+}
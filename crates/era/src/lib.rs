@@ -0,0 +1,29 @@
+//! Reader and writer for [era1] history archives.
+//!
+//! An era1 file stores the headers, bodies, receipts and total difficulties of up to
+//! [`MAX_BLOCKS_PER_ERA1`] consecutive pre-merge blocks in the [e2store] format, followed by the
+//! epoch's accumulator root and an index of the blocks in the file.
+//!
+//! [era1]: https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md
+//! [e2store]: https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{accumulator_root, HeaderRecord};
+
+pub mod e2s;
+
+mod era1;
+pub use era1::{
+    era1_file_name, BlockIndex, BlockTuple, Era1Reader, Era1Writer, MAX_BLOCKS_PER_ERA1,
+};
+
+mod error;
+pub use error::EraError;
@@ -30,8 +30,8 @@ use reth_rpc_eth_types::{error::FromEvmError, EthApiError};
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolTransaction, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::EvmTransactionSimulator, EthTransactionPool,
+    PoolTransaction, TransactionPool, TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::primitives::TxEnv;
@@ -277,16 +277,34 @@ where
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
             .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
+        if let Some(simulation_config) = ctx.config().txpool.simulation_config() {
+            let simulator = EvmTransactionSimulator::new(
+                ctx.provider().clone(),
+                EthEvmConfig::new(ctx.chain_spec()),
+                simulation_config,
+            );
+            validator.validator = validator.validator.with_simulator(simulator);
+            info!(target: "reth::cli", "Transaction simulation enabled");
+        }
+
+        let lifetimes = pool_config.lifetimes;
         let transaction_pool =
             reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
         info!(target: "reth::cli", "Transaction pool initialized");
+
+        if let Some(path) = &ctx.config().txpool.record_trace {
+            transaction_pool
+                .set_recorder(Some(reth_transaction_pool::record::PoolRecorder::create(path)?));
+            info!(target: "reth::cli", ?path, "Recording transaction pool trace");
+        }
+
         let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
@@ -308,6 +326,26 @@ where
                 },
             );
 
+            if ctx.config().txpool.snapshot {
+                let snapshot_config =
+                    reth_transaction_pool::maintain::TransactionPoolSnapshotConfig::new(
+                        data_dir.txpool_snapshot(),
+                    )
+                    .with_interval(ctx.config().txpool.snapshot_interval)
+                    .with_restore_batch_size(ctx.config().txpool.snapshot_restore_batch_size);
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool snapshot task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::snapshot_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            snapshot_config,
+                        )
+                    },
+                );
+            }
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -316,7 +354,10 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig {
+                        lifetimes,
+                        ..Default::default()
+                    },
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
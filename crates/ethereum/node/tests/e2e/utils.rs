@@ -41,7 +41,10 @@ where
     Provider: FullProvider<NodeTypesWithDBAdapter<EthereumNode, TmpDB>>,
 {
     let provider = ProviderBuilder::new().on_http(node.rpc_url());
-    let signers = Wallet::new(1).with_chain_id(provider.get_chain_id().await?).gen();
+    let mut signers = Wallet::new(2).with_chain_id(provider.get_chain_id().await?).gen();
+    // EIP-7702 authorizations are signed by an account that doesn't send transactions, since the
+    // pool limits the in-flight transactions of delegated accounts
+    let authority = signers.pop().unwrap();
 
     // simple contract which writes to storage on any call
     let dummy_bytecode = bytes!("6080604052348015600f57600080fd5b50602880601d6000396000f3fe4360a09081523360c0526040608081905260e08152902080805500fea164736f6c6343000810000a");
@@ -88,16 +91,15 @@ where
             }
 
             if tx_type == TxType::Eip7702 {
-                let signer = signers.choose(rng).unwrap();
                 let auth = Authorization {
                     chain_id: U256::from(provider.get_chain_id().await?),
                     address: *call_destinations.choose(rng).unwrap(),
                     nonce: provider
-                        .get_transaction_count(signer.address())
+                        .get_transaction_count(authority.address())
                         .block_id(BlockId::Number(BlockNumberOrTag::Pending))
                         .await?,
                 };
-                let sig = signer.sign_hash_sync(&auth.signature_hash())?;
+                let sig = authority.sign_hash_sync(&auth.signature_hash())?;
                 tx = tx.with_authorization_list(vec![auth.into_signed(sig)])
             }
 
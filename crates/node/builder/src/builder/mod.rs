@@ -35,7 +35,10 @@ use reth_provider::{
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    admission::{AdmissionPolicyConfig, MinTipByType},
+    PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -638,8 +641,28 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     }
 
     /// Returns the transaction pool config of the node.
+    ///
+    /// Admission policies of the node's cli arguments are merged with the ones in the
+    /// `reth.toml`.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let mut config = self.config().txpool.pool_config();
+        let admission = &self.reth_config().txpool.admission;
+        config.admission = config.admission.merge(AdmissionPolicyConfig {
+            sender_rate_limit: admission.sender_rate_limit,
+            origin_rate_limit: admission.origin_rate_limit,
+            rate_limit_interval: admission.rate_limit_interval,
+            deny_from: admission.deny_from.clone(),
+            deny_to: admission.deny_to.clone(),
+            no_contract_creation: admission.no_contract_creation,
+            min_tip: admission.min_tip,
+            min_tip_by_type: admission
+                .min_tip_by_type
+                .iter()
+                .map(|min_tip| MinTipByType { tx_type: min_tip.tx_type, min_tip: min_tip.min_tip })
+                .collect(),
+            max_calldata_size: admission.max_calldata_size,
+        });
+        config
     }
 
     /// Loads `EnvKzgSettings::Default`.
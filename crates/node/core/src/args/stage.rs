@@ -50,4 +50,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional log index stage within the pipeline.
+    ///
+    /// Indexes the addresses and topics of logs.
+    LogIndex,
 }
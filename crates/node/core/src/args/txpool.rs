@@ -6,16 +6,21 @@ use alloy_primitives::Address;
 use clap::Args;
 use reth_cli_util::parse_duration_from_secs_or_ms;
 use reth_transaction_pool::{
+    admission::{AdmissionPolicyConfig, MinTipByType},
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
-    maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
+    maintain::{DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE, MAX_QUEUED_TRANSACTION_LIFETIME},
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
-    validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    validate::{
+        SimulationConfig, DEFAULT_MAX_TX_INPUT_BYTES, DEFAULT_SIMULATION_CACHE_SIZE,
+        DEFAULT_SIMULATION_GAS_LIMIT, DEFAULT_SIMULATION_TIMEOUT,
+    },
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, TransactionLifetimes,
+    DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+    MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -53,6 +58,11 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max number of in-flight transactions of an EIP-7702 delegated account or an account with a
+    /// pending authorization
+    #[arg(long = "txpool.max-inflight-delegated-slots", default_value_t = TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS)]
+    pub max_inflight_delegated_slots: usize,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
@@ -106,6 +116,126 @@ pub struct TxPoolArgs {
     /// Maximum amount of time non-executable transaction are queued.
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
+
+    /// Maximum amount of time external transactions are kept in the pending sub-pool.
+    #[arg(long = "txpool.pending-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_pending_lifetime: Option<Duration>,
+
+    /// Maximum amount of time external transactions are kept in the basefee sub-pool.
+    ///
+    /// Defaults to `--txpool.lifetime`.
+    #[arg(long = "txpool.basefee-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_basefee_lifetime: Option<Duration>,
+
+    /// Maximum amount of time external transactions are kept in the blob sub-pool.
+    #[arg(long = "txpool.blob-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_blob_lifetime: Option<Duration>,
+
+    /// Maximum amount of time local transactions are kept in the pool, regardless of their
+    /// sub-pool.
+    #[arg(long = "txpool.local-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_local_lifetime: Option<Duration>,
+
+    /// Maximum amount of time external transactions are kept in the pool, regardless of their
+    /// sub-pool.
+    #[arg(long = "txpool.external-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_external_lifetime: Option<Duration>,
+
+    /// Maximum amount of time private transactions are kept in the pool, regardless of their
+    /// sub-pool.
+    #[arg(long = "txpool.private-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_private_lifetime: Option<Duration>,
+
+    /// Flag to snapshot all pending, basefee and queued transactions on shutdown and restore them
+    /// on startup.
+    #[arg(long = "txpool.snapshot")]
+    pub snapshot: bool,
+
+    /// Interval at which the transaction pool snapshot is written in addition to shutdown.
+    #[arg(long = "txpool.snapshot-interval", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION", requires = "snapshot")]
+    pub snapshot_interval: Option<Duration>,
+
+    /// Number of snapshotted transactions that are revalidated and inserted at once on startup.
+    #[arg(long = "txpool.snapshot-restore-batch-size", default_value_t = DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE)]
+    pub snapshot_restore_batch_size: usize,
+
+    /// Maximum number of transactions admitted per sender within the rate limit interval.
+    #[arg(long = "txpool.admission.sender-rate-limit", value_name = "COUNT")]
+    pub admission_sender_rate_limit: Option<u32>,
+
+    /// Maximum number of transactions admitted per origin (local, external or private) within
+    /// the rate limit interval.
+    #[arg(long = "txpool.admission.origin-rate-limit", value_name = "COUNT")]
+    pub admission_origin_rate_limit: Option<u32>,
+
+    /// Interval of the admission rate limits, one second if not set.
+    #[arg(long = "txpool.admission.rate-limit-interval", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub admission_rate_limit_interval: Option<Duration>,
+
+    /// Rejects transactions sent from the given addresses.
+    #[arg(long = "txpool.admission.deny-from", value_name = "ADDRESS")]
+    pub admission_deny_from: Vec<Address>,
+
+    /// Rejects transactions sent to the given addresses.
+    #[arg(long = "txpool.admission.deny-to", value_name = "ADDRESS")]
+    pub admission_deny_to: Vec<Address>,
+
+    /// Rejects transactions that create contracts.
+    #[arg(long = "txpool.admission.no-contract-creation")]
+    pub admission_no_contract_creation: bool,
+
+    /// Minimum priority fee (gas price for legacy transactions) in wei of admitted transactions.
+    #[arg(long = "txpool.admission.min-tip", value_name = "WEI")]
+    pub admission_min_tip: Option<u64>,
+
+    /// Minimum priority fee in wei of admitted transactions of a type, e.g. `2=1000000000`.
+    ///
+    /// Takes precedence over `--txpool.admission.min-tip` for the given type.
+    #[arg(long = "txpool.admission.min-tip-by-type", value_name = "TYPE=WEI", value_parser = parse_min_tip_by_type)]
+    pub admission_min_tip_by_type: Vec<MinTipByType>,
+
+    /// Maximum calldata size in bytes of admitted transactions.
+    #[arg(long = "txpool.admission.max-calldata-size", value_name = "BYTES")]
+    pub admission_max_calldata_size: Option<usize>,
+
+    /// Flag to simulate transactions against the latest state after validation and reject
+    /// transactions that revert.
+    #[arg(long = "txpool.simulation")]
+    pub simulation: bool,
+
+    /// Maximum amount of gas a single transaction simulation can use.
+    #[arg(long = "txpool.simulation.gas-limit", default_value_t = DEFAULT_SIMULATION_GAS_LIMIT, requires = "simulation")]
+    pub simulation_gas_limit: u64,
+
+    /// Maximum time a single transaction simulation can take.
+    #[arg(long = "txpool.simulation.timeout", value_parser = parse_duration_from_secs_or_ms, default_value = "50ms", value_name = "DURATION", requires = "simulation")]
+    pub simulation_timeout: Duration,
+
+    /// Number of transaction simulation results to cache.
+    #[arg(long = "txpool.simulation.cache-size", default_value_t = DEFAULT_SIMULATION_CACHE_SIZE, requires = "simulation")]
+    pub simulation_cache_size: u32,
+
+    /// Also simulate transactions that were not submitted through private endpoints, e.g. the
+    /// ones received from the network.
+    #[arg(long = "txpool.simulation.public", requires = "simulation")]
+    pub simulation_public: bool,
+
+    /// Records all added transactions, canonical state updates and best transactions requests
+    /// of the transaction pool to the given file, for replaying them in benchmarks.
+    #[arg(long = "txpool.record-trace", value_name = "FILE")]
+    pub record_trace: Option<PathBuf>,
+}
+
+impl TxPoolArgs {
+    /// Returns the transaction simulation configuration if simulation is enabled.
+    pub fn simulation_config(&self) -> Option<SimulationConfig> {
+        self.simulation.then_some(SimulationConfig {
+            gas_limit: self.simulation_gas_limit,
+            timeout: self.simulation_timeout,
+            cache_size: self.simulation_cache_size,
+            private_only: !self.simulation_public,
+        })
+    }
 }
 
 impl Default for TxPoolArgs {
@@ -120,6 +250,7 @@ impl Default for TxPoolArgs {
             blobpool_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             blobpool_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_inflight_delegated_slots: TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS,
             price_bump: DEFAULT_PRICE_BUMP,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             enforced_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -134,6 +265,30 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_pending_lifetime: None,
+            max_basefee_lifetime: None,
+            max_blob_lifetime: None,
+            max_local_lifetime: None,
+            max_external_lifetime: None,
+            max_private_lifetime: None,
+            snapshot: false,
+            snapshot_interval: None,
+            snapshot_restore_batch_size: DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE,
+            admission_sender_rate_limit: None,
+            admission_origin_rate_limit: None,
+            admission_rate_limit_interval: None,
+            admission_deny_from: Default::default(),
+            admission_deny_to: Default::default(),
+            admission_no_contract_creation: false,
+            admission_min_tip: None,
+            admission_min_tip_by_type: Default::default(),
+            admission_max_calldata_size: None,
+            simulation: false,
+            simulation_gas_limit: DEFAULT_SIMULATION_GAS_LIMIT,
+            simulation_timeout: DEFAULT_SIMULATION_TIMEOUT,
+            simulation_cache_size: DEFAULT_SIMULATION_CACHE_SIZE,
+            simulation_public: false,
+            record_trace: None,
         }
     }
 }
@@ -164,6 +319,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.blobpool_max_size.saturating_mul(1024 * 1024),
             },
             max_account_slots: self.max_account_slots,
+            max_inflight_delegated_slots: self.max_inflight_delegated_slots,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
@@ -173,11 +329,43 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
-            max_queued_lifetime: self.max_queued_lifetime,
+            lifetimes: TransactionLifetimes {
+                pending: self.max_pending_lifetime,
+                basefee: Some(self.max_basefee_lifetime.unwrap_or(self.max_queued_lifetime)),
+                queued: Some(self.max_queued_lifetime),
+                blob: self.max_blob_lifetime,
+                local: self.max_local_lifetime,
+                external: self.max_external_lifetime,
+                private: self.max_private_lifetime,
+            },
+            admission: AdmissionPolicyConfig {
+                sender_rate_limit: self.admission_sender_rate_limit,
+                origin_rate_limit: self.admission_origin_rate_limit,
+                rate_limit_interval: self.admission_rate_limit_interval,
+                deny_from: self.admission_deny_from.clone(),
+                deny_to: self.admission_deny_to.clone(),
+                no_contract_creation: self.admission_no_contract_creation,
+                min_tip: self.admission_min_tip,
+                min_tip_by_type: self.admission_min_tip_by_type.clone(),
+                max_calldata_size: self.admission_max_calldata_size,
+            },
         }
     }
 }
 
+/// Parses a minimum tip for a transaction type in the form of `TYPE=WEI`.
+fn parse_min_tip_by_type(s: &str) -> Result<MinTipByType, String> {
+    let (tx_type, min_tip) =
+        s.split_once('=').ok_or_else(|| format!("expected TYPE=WEI, got {s}"))?;
+    let tx_type = tx_type
+        .trim()
+        .parse()
+        .map_err(|err| format!("invalid transaction type {tx_type}: {err}"))?;
+    let min_tip =
+        min_tip.trim().parse().map_err(|err| format!("invalid minimum tip {min_tip}: {err}"))?;
+    Ok(MinTipByType { tx_type, min_tip })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +408,113 @@ mod tests {
         assert_eq!(args.max_queued_lifetime, Duration::from_secs(3 * 60 * 60)); // Default is 3h
     }
 
+    #[test]
+    fn txpool_parse_lifetimes() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.lifetime",
+            "600",
+            "--txpool.pending-lifetime",
+            "3600",
+            "--txpool.private-lifetime",
+            "30000ms",
+        ])
+        .args;
+        let lifetimes = args.pool_config().lifetimes;
+        assert_eq!(lifetimes.pending, Some(Duration::from_secs(3600)));
+        assert_eq!(lifetimes.basefee, Some(Duration::from_secs(600)));
+        assert_eq!(lifetimes.queued, Some(Duration::from_secs(600)));
+        assert_eq!(lifetimes.blob, None);
+        assert_eq!(lifetimes.local, None);
+        assert_eq!(lifetimes.private, Some(Duration::from_secs(30)));
+
+        // Test with the default values
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.pool_config().lifetimes, TransactionLifetimes::default());
+    }
+
+    #[test]
+    fn txpool_parse_snapshot() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.snapshot",
+            "--txpool.snapshot-interval",
+            "60",
+        ])
+        .args;
+        assert!(args.snapshot);
+        assert_eq!(args.snapshot_interval, Some(Duration::from_secs(60)));
+
+        // the interval requires the snapshot to be enabled
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.snapshot-interval",
+            "60"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_admission() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.admission.deny-to",
+            "0x0000000000000000000000000000000000000000",
+            "--txpool.admission.no-contract-creation",
+            "--txpool.admission.min-tip-by-type",
+            "2=1000",
+            "--txpool.admission.sender-rate-limit",
+            "10",
+        ])
+        .args;
+        let admission = args.pool_config().admission;
+        assert_eq!(admission.deny_to, vec![Address::ZERO]);
+        assert!(admission.no_contract_creation);
+        assert_eq!(admission.min_tip_by_type, vec![MinTipByType { tx_type: 2, min_tip: 1000 }]);
+        assert_eq!(admission.sender_rate_limit, Some(10));
+        assert_eq!(admission.rate_limit_interval, None);
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.admission.min-tip-by-type",
+            "2"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_simulation() {
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.simulation_config(), None);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.simulation",
+            "--txpool.simulation.timeout",
+            "20ms",
+        ])
+        .args;
+        let config = args.simulation_config().unwrap();
+        assert_eq!(config.timeout, Duration::from_millis(20));
+        assert_eq!(config.gas_limit, DEFAULT_SIMULATION_GAS_LIMIT);
+        assert!(config.private_only);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.simulation",
+            "--txpool.simulation.public",
+        ])
+        .args;
+        assert!(!args.simulation_config().unwrap().private_only);
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.simulation.gas-limit",
+            "1000000"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn txpool_parse_max_tx_lifetime_invalid() {
         let result =
@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool snapshot file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.bin`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -16,6 +16,7 @@ pub mod constants;
 mod dev;
 mod op;
 mod op_sepolia;
+mod superchain;
 
 use alloc::{boxed::Box, vec, vec::Vec};
 use alloy_chains::Chain;
@@ -37,6 +38,11 @@ use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, ForkCondition, Hardf
 use reth_network_peers::NodeRecord;
 use reth_optimism_forks::{OpHardfork, OpHardforks};
 use reth_primitives_traits::sync::LazyLock;
+pub use superchain::{
+    SuperchainBlockRef, SuperchainBundle, SuperchainConfig, SuperchainGenesisMismatch,
+    SuperchainGenesisSource, SuperchainHardforks, SuperchainOptimismConfig,
+    SuperchainRollupGenesis,
+};
 
 /// Chain spec builder for a OP stack chain.
 #[derive(Debug, Default, From)]
@@ -482,7 +488,11 @@ mod tests {
                 ),
                 (
                     Head { number: 0, timestamp: 1736445601, ..Default::default() },
-                    ForkId { hash: ForkHash([0x3a, 0x2a, 0xf1, 0x83]), next: 0 },
+                    ForkId { hash: ForkHash([0x3a, 0x2a, 0xf1, 0x83]), next: 1746806401 },
+                ),
+                (
+                    Head { number: 0, timestamp: 1746806401, ..Default::default() },
+                    ForkId { hash: ForkHash([0x86, 0x72, 0x8b, 0x4e]), next: 0 },
                 ),
             ],
         );
@@ -531,7 +541,11 @@ mod tests {
                 ),
                 (
                     Head { number: 0, timestamp: 1732633200, ..Default::default() },
-                    ForkId { hash: ForkHash([0x4a, 0x1c, 0x79, 0x2e]), next: 0 },
+                    ForkId { hash: ForkHash([0x4a, 0x1c, 0x79, 0x2e]), next: 1744905600 },
+                ),
+                (
+                    Head { number: 0, timestamp: 1744905600, ..Default::default() },
+                    ForkId { hash: ForkHash([0x6c, 0x62, 0x5e, 0xe1]), next: 0 },
                 ),
             ],
         );
@@ -602,7 +616,11 @@ mod tests {
                 ),
                 (
                     Head { number: 0, timestamp: 1732633200, ..Default::default() },
-                    ForkId { hash: ForkHash([0x8b, 0x5e, 0x76, 0x29]), next: 0 },
+                    ForkId { hash: ForkHash([0x8b, 0x5e, 0x76, 0x29]), next: 1744905600 },
+                ),
+                (
+                    Head { number: 0, timestamp: 1744905600, ..Default::default() },
+                    ForkId { hash: ForkHash([0x06, 0x0a, 0x4d, 0x1d]), next: 0 },
                 ),
             ],
         );
@@ -653,7 +671,7 @@ mod tests {
     #[test]
     fn latest_base_mainnet_fork_id() {
         assert_eq!(
-            ForkId { hash: ForkHash([0x3a, 0x2a, 0xf1, 0x83]), next: 0 },
+            ForkId { hash: ForkHash([0x86, 0x72, 0x8b, 0x4e]), next: 0 },
             BASE_MAINNET.latest_fork_id()
         )
     }
@@ -662,7 +680,7 @@ mod tests {
     fn latest_base_mainnet_fork_id_with_builder() {
         let base_mainnet = OpChainSpecBuilder::base_mainnet().build();
         assert_eq!(
-            ForkId { hash: ForkHash([0x3a, 0x2a, 0xf1, 0x83]), next: 0 },
+            ForkId { hash: ForkHash([0x86, 0x72, 0x8b, 0x4e]), next: 0 },
             base_mainnet.latest_fork_id()
         )
     }
@@ -685,6 +703,7 @@ mod tests {
         "fjordTime": 50,
         "graniteTime": 51,
         "holoceneTime": 52,
+        "isthmusTime": 53,
         "optimism": {
           "eip1559Elasticity": 60,
           "eip1559Denominator": 70
@@ -708,6 +727,8 @@ mod tests {
         assert_eq!(actual_granite_timestamp, Some(serde_json::Value::from(51)).as_ref());
         let actual_holocene_timestamp = genesis.config.extra_fields.get("holoceneTime");
         assert_eq!(actual_holocene_timestamp, Some(serde_json::Value::from(52)).as_ref());
+        let actual_isthmus_timestamp = genesis.config.extra_fields.get("isthmusTime");
+        assert_eq!(actual_isthmus_timestamp, Some(serde_json::Value::from(53)).as_ref());
 
         let optimism_object = genesis.config.extra_fields.get("optimism").unwrap();
         assert_eq!(
@@ -732,6 +753,7 @@ mod tests {
         assert!(!chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, 0));
         assert!(!chain_spec.is_fork_active_at_timestamp(OpHardfork::Granite, 0));
         assert!(!chain_spec.is_fork_active_at_timestamp(OpHardfork::Holocene, 0));
+        assert!(!chain_spec.is_fork_active_at_timestamp(OpHardfork::Isthmus, 0));
 
         assert!(chain_spec.is_fork_active_at_block(OpHardfork::Bedrock, 10));
         assert!(chain_spec.is_fork_active_at_timestamp(OpHardfork::Regolith, 20));
@@ -740,6 +762,8 @@ mod tests {
         assert!(chain_spec.is_fork_active_at_timestamp(OpHardfork::Fjord, 50));
         assert!(chain_spec.is_fork_active_at_timestamp(OpHardfork::Granite, 51));
         assert!(chain_spec.is_fork_active_at_timestamp(OpHardfork::Holocene, 52));
+        assert!(!chain_spec.is_fork_active_at_timestamp(OpHardfork::Isthmus, 52));
+        assert!(chain_spec.is_fork_active_at_timestamp(OpHardfork::Isthmus, 53));
     }
 
     #[test]
@@ -920,6 +944,7 @@ mod tests {
                 merge_netsplit_block: Some(0),
                 shanghai_time: Some(0),
                 cancun_time: Some(0),
+                prague_time: Some(0),
                 terminal_total_difficulty: Some(U256::ZERO),
                 extra_fields: [
                     (String::from("bedrockBlock"), 0.into()),
@@ -929,6 +954,7 @@ mod tests {
                     (String::from("fjordTime"), 0.into()),
                     (String::from("graniteTime"), 0.into()),
                     (String::from("holoceneTime"), 0.into()),
+                    (String::from("isthmusTime"), 0.into()),
                 ]
                 .into_iter()
                 .collect(),
@@ -964,7 +990,8 @@ mod tests {
             OpHardfork::Fjord.boxed(),
             OpHardfork::Granite.boxed(),
             OpHardfork::Holocene.boxed(),
-            // OpHardfork::Isthmus.boxed(),
+            EthereumHardfork::Prague.boxed(),
+            OpHardfork::Isthmus.boxed(),
         ];
 
         for (expected, actual) in expected_hardforks.iter().zip(hardforks.iter()) {
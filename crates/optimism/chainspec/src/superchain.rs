@@ -0,0 +1,348 @@
+//! Chain specifications of OP Stack chains described in the format of the
+//! [superchain registry](https://github.com/ethereum-optimism/superchain-registry).
+
+use alloc::{boxed::Box, string::String};
+use alloy_genesis::Genesis;
+use alloy_primitives::B256;
+use serde::Deserialize;
+
+use crate::OpChainSpec;
+
+/// Config of an OP Stack chain, as found in the chain configs of the superchain registry.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SuperchainConfig {
+    /// Name of the chain.
+    pub name: String,
+    /// Chain id of the chain.
+    pub chain_id: u64,
+    /// L2 block time in seconds.
+    #[serde(default)]
+    pub block_time: Option<u64>,
+    /// Activation timestamps of the hardforks.
+    #[serde(default)]
+    pub hardforks: SuperchainHardforks,
+    /// EIP-1559 parameters of the chain.
+    #[serde(default)]
+    pub optimism: Option<SuperchainOptimismConfig>,
+    /// Rollup genesis of the chain.
+    pub genesis: SuperchainRollupGenesis,
+}
+
+impl SuperchainConfig {
+    /// Applies the chain id, genesis block number, hardfork timestamps and base fee params of the
+    /// config to the given L2 genesis.
+    ///
+    /// Hardforks that are not scheduled in the config keep their activation of the genesis.
+    pub fn apply_to_genesis(&self, genesis: &mut Genesis) {
+        let hardforks = &self.hardforks;
+
+        genesis.config.chain_id = self.chain_id;
+        genesis.number = Some(self.genesis.l2.number);
+        // the L1 hardforks are activated together with the OP Stack hardforks that include them
+        if let Some(time) = hardforks.canyon_time {
+            genesis.config.shanghai_time = Some(time);
+        }
+        if let Some(time) = hardforks.ecotone_time {
+            genesis.config.cancun_time = Some(time);
+        }
+        if let Some(time) = hardforks.isthmus_time {
+            genesis.config.prague_time = Some(time);
+        }
+
+        let fields = &mut genesis.config.extra_fields;
+        // the rollup starts post Bedrock, Regolith is active from genesis unless scheduled
+        fields.insert("bedrockBlock".into(), self.genesis.l2.number.into());
+        fields.insert("regolithTime".into(), hardforks.regolith_time.unwrap_or_default().into());
+        for (name, time) in [
+            ("canyonTime", hardforks.canyon_time),
+            ("ecotoneTime", hardforks.ecotone_time),
+            ("fjordTime", hardforks.fjord_time),
+            ("graniteTime", hardforks.granite_time),
+            ("holoceneTime", hardforks.holocene_time),
+            ("isthmusTime", hardforks.isthmus_time),
+        ] {
+            if let Some(time) = time {
+                fields.insert(name.into(), time.into());
+            }
+        }
+
+        if let Some(optimism) = &self.optimism {
+            let mut base_fee_info = serde_json::Map::new();
+            base_fee_info.insert("eip1559Elasticity".into(), optimism.eip1559_elasticity.into());
+            base_fee_info.insert("eip1559Denominator".into(), optimism.eip1559_denominator.into());
+            if let Some(denominator) = optimism.eip1559_denominator_canyon {
+                base_fee_info.insert("eip1559DenominatorCanyon".into(), denominator.into());
+            }
+            fields.insert("optimism".into(), base_fee_info.into());
+        }
+    }
+}
+
+/// Activation timestamps of the OP Stack hardforks of a [`SuperchainConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct SuperchainHardforks {
+    /// Regolith activation timestamp, active from genesis if unset.
+    #[serde(default)]
+    pub regolith_time: Option<u64>,
+    /// Canyon activation timestamp.
+    #[serde(default)]
+    pub canyon_time: Option<u64>,
+    /// Ecotone activation timestamp.
+    #[serde(default)]
+    pub ecotone_time: Option<u64>,
+    /// Fjord activation timestamp.
+    #[serde(default)]
+    pub fjord_time: Option<u64>,
+    /// Granite activation timestamp.
+    #[serde(default)]
+    pub granite_time: Option<u64>,
+    /// Holocene activation timestamp.
+    #[serde(default)]
+    pub holocene_time: Option<u64>,
+    /// Isthmus activation timestamp.
+    #[serde(default)]
+    pub isthmus_time: Option<u64>,
+}
+
+/// EIP-1559 parameters of a [`SuperchainConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SuperchainOptimismConfig {
+    /// EIP-1559 elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// EIP-1559 base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// EIP-1559 base fee max change denominator since Canyon.
+    #[serde(default)]
+    pub eip1559_denominator_canyon: Option<u64>,
+}
+
+/// Rollup genesis of a [`SuperchainConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SuperchainRollupGenesis {
+    /// Timestamp of the L2 genesis block.
+    pub l2_time: u64,
+    /// L1 block the rollup starts at.
+    pub l1: SuperchainBlockRef,
+    /// L2 genesis block.
+    pub l2: SuperchainBlockRef,
+}
+
+/// Reference to a block by hash and number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SuperchainBlockRef {
+    /// Block hash.
+    pub hash: B256,
+    /// Block number.
+    pub number: u64,
+}
+
+/// A [`SuperchainConfig`] bundled with the L2 genesis of the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SuperchainBundle {
+    /// Config of the chain.
+    #[serde(flatten)]
+    pub config: SuperchainConfig,
+    /// L2 genesis of the chain.
+    pub l2_genesis: SuperchainGenesisSource,
+}
+
+/// Where the L2 genesis of a [`SuperchainBundle`] is found.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum SuperchainGenesisSource {
+    /// Path to a genesis json file, relative to the bundle.
+    Path(String),
+    /// The genesis itself.
+    Genesis(Box<Genesis>),
+}
+
+/// Error of [`OpChainSpec::from_superchain`] if the L2 genesis doesn't match the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("genesis block hash {got} doesn't match the L2 genesis hash {expected} of the config")]
+pub struct SuperchainGenesisMismatch {
+    /// Hash of the genesis block built from the L2 genesis.
+    pub got: B256,
+    /// Hash of the L2 genesis block in the config.
+    pub expected: B256,
+}
+
+impl OpChainSpec {
+    /// Builds the chain spec of a chain described by a [`SuperchainConfig`], with the given L2
+    /// genesis.
+    ///
+    /// Returns an error if the hash of the resulting genesis block isn't the L2 genesis hash of
+    /// the config.
+    pub fn from_superchain(
+        config: &SuperchainConfig,
+        mut genesis: Genesis,
+    ) -> Result<Self, SuperchainGenesisMismatch> {
+        config.apply_to_genesis(&mut genesis);
+        let mut spec = Self::from_genesis(genesis);
+
+        // The L2 genesis of a chain that started before bedrock isn't block zero.
+        let mut header = spec.genesis_header().clone();
+        header.number = config.genesis.l2.number;
+        spec.inner.genesis_header = header.into();
+
+        let got = spec.genesis_hash();
+        let expected = config.genesis.l2.hash;
+        if got != expected {
+            return Err(SuperchainGenesisMismatch { got, expected })
+        }
+
+        Ok(spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+    use reth_chainspec::{BaseFeeParams, BaseFeeParamsKind, EthChainSpec, EthereumHardforks};
+    use reth_ethereum_forks::{EthereumHardfork, Hardfork};
+    use reth_optimism_forks::{OpHardfork, OpHardforks};
+
+    const CONFIG: &str = r#"
+    {
+      "name": "Test Chain",
+      "chain_id": 1337,
+      "block_time": 2,
+      "hardforks": {
+        "canyon_time": 10,
+        "ecotone_time": 20,
+        "fjord_time": 30,
+        "granite_time": 40,
+        "holocene_time": 50,
+        "isthmus_time": 60
+      },
+      "optimism": {
+        "eip1559_elasticity": 6,
+        "eip1559_denominator": 50,
+        "eip1559_denominator_canyon": 250
+      },
+      "genesis": {
+        "l2_time": 0,
+        "l1": {
+          "hash": "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108",
+          "number": 17422590
+        },
+        "l2": {
+          "hash": "0xcc786e99d63562f18e6da9791b92bf4400736ae03852113175df692d3398fd2a",
+          "number": 10
+        }
+      },
+      "l2_genesis": {
+        "config": {
+          "chainId": 1,
+          "homesteadBlock": 0,
+          "eip150Block": 0,
+          "eip155Block": 0,
+          "eip158Block": 0,
+          "byzantiumBlock": 0,
+          "constantinopleBlock": 0,
+          "petersburgBlock": 0,
+          "istanbulBlock": 0,
+          "muirGlacierBlock": 0,
+          "berlinBlock": 0,
+          "londonBlock": 0,
+          "arrowGlacierBlock": 0,
+          "grayGlacierBlock": 0,
+          "mergeNetsplitBlock": 0,
+          "terminalTotalDifficulty": 0,
+          "holoceneTime": 1000
+        },
+        "gasLimit": "0x1c9c380",
+        "difficulty": "0x0",
+        "alloc": {}
+      }
+    }
+    "#;
+
+    #[test]
+    fn superchain_bundle() {
+        let bundle: SuperchainBundle = serde_json::from_str(CONFIG).unwrap();
+        assert_eq!(bundle.config.name, "Test Chain");
+
+        let SuperchainGenesisSource::Genesis(genesis) = bundle.l2_genesis else {
+            panic!("expected inline genesis")
+        };
+        let spec = OpChainSpec::from_superchain(&bundle.config, *genesis).unwrap();
+
+        assert_eq!(spec.chain().id(), 1337);
+        assert_eq!(
+            spec.genesis_hash(),
+            b256!("cc786e99d63562f18e6da9791b92bf4400736ae03852113175df692d3398fd2a")
+        );
+
+        assert_eq!(spec.genesis_header().number, 10);
+        assert!(!spec.is_bedrock_active_at_block(9));
+        assert!(spec.is_bedrock_active_at_block(10));
+        assert!(spec.is_regolith_active_at_timestamp(0));
+        assert!(!spec.is_canyon_active_at_timestamp(9));
+        assert!(spec.is_canyon_active_at_timestamp(10));
+        assert!(spec.is_shanghai_active_at_timestamp(10));
+        assert!(spec.is_cancun_active_at_timestamp(20));
+        assert!(spec.is_fjord_active_at_timestamp(30));
+        assert!(spec.is_granite_active_at_timestamp(40));
+        // the registry overrides the genesis
+        assert!(spec.is_holocene_active_at_timestamp(50));
+        assert!(!spec.is_isthmus_active_at_timestamp(59));
+        assert!(spec.is_isthmus_active_at_timestamp(60));
+        assert!(spec.is_prague_active_at_timestamp(60));
+
+        assert_eq!(
+            spec.base_fee_params,
+            BaseFeeParamsKind::Variable(
+                vec![
+                    (EthereumHardfork::London.boxed(), BaseFeeParams::new(50, 6)),
+                    (OpHardfork::Canyon.boxed(), BaseFeeParams::new(250, 6)),
+                ]
+                .into()
+            )
+        );
+    }
+
+    #[test]
+    fn superchain_genesis_mismatch() {
+        let mut bundle: SuperchainBundle = serde_json::from_str(CONFIG).unwrap();
+        bundle.config.genesis.l2.hash = B256::ZERO;
+
+        let SuperchainGenesisSource::Genesis(genesis) = bundle.l2_genesis else {
+            panic!("expected inline genesis")
+        };
+        let err = OpChainSpec::from_superchain(&bundle.config, *genesis).unwrap_err();
+        assert_eq!(err.expected, B256::ZERO);
+        assert_eq!(
+            err.got,
+            b256!("cc786e99d63562f18e6da9791b92bf4400736ae03852113175df692d3398fd2a")
+        );
+    }
+
+    #[test]
+    fn superchain_bundle_genesis_path() {
+        let bundle: SuperchainBundle = serde_json::from_str(
+            r#"
+        {
+          "name": "Test Chain",
+          "chain_id": 1337,
+          "genesis": {
+            "l2_time": 0,
+            "l1": {
+              "hash": "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108",
+              "number": 17422590
+            },
+            "l2": {
+              "hash": "0xf712aa9241cc24369b143cf6dce85f0902a9731e70d66818a3a5845b296c73dd",
+              "number": 0
+            }
+          },
+          "l2_genesis": "genesis.json"
+        }
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(bundle.config.hardforks, SuperchainHardforks::default());
+        assert_eq!(bundle.l2_genesis, SuperchainGenesisSource::Path("genesis.json".into()));
+    }
+}
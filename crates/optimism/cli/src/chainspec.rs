@@ -1,8 +1,18 @@
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use reth_optimism_chainspec::{
-    OpChainSpec, BASE_MAINNET, BASE_SEPOLIA, OP_DEV, OP_MAINNET, OP_SEPOLIA,
+    OpChainSpec, SuperchainBundle, SuperchainGenesisSource, BASE_MAINNET, BASE_SEPOLIA, OP_DEV,
+    OP_MAINNET, OP_SEPOLIA,
 };
-use std::sync::Arc;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Environment variable holding the path to a directory of superchain registry chain bundles.
+///
+/// Chains in this directory can be selected by name, e.g. `mainnet/zora` for
+/// `<dir>/mainnet/zora.toml`.
+pub const SUPERCHAIN_REGISTRY_ENV: &str = "RETH_SUPERCHAIN_REGISTRY";
 
 /// Optimism chain specification parser.
 #[derive(Debug, Clone, Default)]
@@ -25,12 +35,17 @@ impl ChainSpecParser for OpChainSpecParser {
     fn parse(s: &str) -> eyre::Result<Arc<Self::ChainSpec>> {
         chain_value_parser(s)
     }
+
+    fn help_message() -> String {
+        format!("The chain this node is running.\nPossible values are either a built-in chain, the path to a chain specification file, the path to a superchain registry bundle or the name of a chain in the registry directory set by {SUPERCHAIN_REGISTRY_ENV}.\n\nBuilt-in chains:\n    {}", Self::SUPPORTED_CHAINS.join(", "))
+    }
 }
 
 /// Clap value parser for [`OpChainSpec`]s.
 ///
-/// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// The value parser matches either a known chain, the path to a superchain registry bundle, the
+/// name of a chain in the [`SUPERCHAIN_REGISTRY_ENV`] directory, the path to a json file, or a
+/// json formatted string in-memory. The json needs to be a Genesis struct.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<OpChainSpec>, eyre::Error> {
     Ok(match s {
         "dev" => OP_DEV.clone(),
@@ -38,13 +53,113 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<OpChainSpec>, eyre::Error
         "optimism_sepolia" | "optimism-sepolia" => OP_SEPOLIA.clone(),
         "base" => BASE_MAINNET.clone(),
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
-        _ => Arc::new(parse_genesis(s)?.into()),
+        _ => {
+            let registry = std::env::var_os(SUPERCHAIN_REGISTRY_ENV).map(PathBuf::from);
+            match parse_superchain_bundle(s, registry.as_deref())? {
+                Some(spec) => Arc::new(spec),
+                None => Arc::new(parse_genesis(s)?.into()),
+            }
+        }
     })
 }
 
+/// Parses a superchain registry bundle, see [`SuperchainBundle`].
+///
+/// `s` is either the path to a toml or json bundle, or the name of a chain in the `registry`
+/// directory. The L2 genesis of a bundle may be a path relative to the bundle.
+///
+/// Returns `None` if `s` doesn't refer to a bundle, e.g. if it is a plain genesis file.
+pub fn parse_superchain_bundle(
+    s: &str,
+    registry: Option<&Path>,
+) -> eyre::Result<Option<OpChainSpec>> {
+    let path = PathBuf::from(shellexpand::full(s)?.into_owned());
+    let path = if path.is_file() {
+        path
+    } else if let Some(path) = registry.and_then(|registry| {
+        ["toml", "json"]
+            .into_iter()
+            .map(|extension| registry.join(format!("{s}.{extension}")))
+            .find(|path| path.is_file())
+    }) {
+        path
+    } else {
+        return Ok(None)
+    };
+
+    let raw = reth_fs_util::read_to_string(&path)?;
+    let bundle: SuperchainBundle = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&raw)?
+    } else {
+        let value: serde_json::Value = serde_json::from_str(&raw)?;
+        if value.get("l2_genesis").is_none() {
+            return Ok(None)
+        }
+        serde_json::from_value(value)?
+    };
+
+    let genesis = match bundle.l2_genesis {
+        SuperchainGenesisSource::Genesis(genesis) => *genesis,
+        SuperchainGenesisSource::Path(genesis_path) => {
+            let genesis_path = match path.parent() {
+                Some(dir) => dir.join(genesis_path),
+                None => PathBuf::from(genesis_path),
+            };
+            serde_json::from_str(&reth_fs_util::read_to_string(genesis_path)?)?
+        }
+    };
+
+    Ok(Some(OpChainSpec::from_superchain(&bundle.config, genesis)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_chainspec::{EthChainSpec, EthereumHardforks};
+
+    const BUNDLE: &str = r#"
+name = "Test Chain"
+chain_id = 1337
+block_time = 2
+l2_genesis = "genesis/test.json"
+
+[hardforks]
+canyon_time = 10
+ecotone_time = 20
+fjord_time = 30
+granite_time = 40
+holocene_time = 50
+isthmus_time = 60
+
+[optimism]
+eip1559_elasticity = 6
+eip1559_denominator = 50
+eip1559_denominator_canyon = 250
+
+[genesis]
+l2_time = 0
+
+[genesis.l1]
+hash = "0x438335a20d98863a4c0c97999eb2481921ccd28553eac6f913af7c12aec04108"
+number = 17422590
+
+[genesis.l2]
+hash = "0x1a33392c3b77b01d6046d9bcb42638cf69cced478254e6b80107860b6f8fe1d9"
+number = 0
+"#;
+
+    const GENESIS: &str = r#"
+{
+  "config": {
+    "chainId": 1,
+    "londonBlock": 0,
+    "terminalTotalDifficulty": 0
+  },
+  "gasLimit": "0x1c9c380",
+  "difficulty": "0x0",
+  "alloc": {}
+}
+"#;
 
     #[test]
     fn parse_known_chain_spec() {
@@ -52,4 +167,36 @@ mod tests {
             assert!(<OpChainSpecParser as ChainSpecParser>::parse(chain).is_ok());
         }
     }
+
+    #[test]
+    fn parse_superchain_registry_chain() {
+        let registry = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(registry.path().join("mainnet/genesis")).unwrap();
+        std::fs::write(registry.path().join("mainnet/test.toml"), BUNDLE).unwrap();
+        std::fs::write(registry.path().join("mainnet/genesis/test.json"), GENESIS).unwrap();
+
+        // by name
+        let spec = parse_superchain_bundle("mainnet/test", Some(registry.path())).unwrap().unwrap();
+        assert_eq!(spec.chain().id(), 1337);
+        assert!(spec.is_prague_active_at_timestamp(60));
+
+        // by path
+        let path = registry.path().join("mainnet/test.toml");
+        let by_path = parse_superchain_bundle(path.to_str().unwrap(), None).unwrap().unwrap();
+        assert_eq!(spec, by_path);
+
+        assert!(parse_superchain_bundle("mainnet/unknown", Some(registry.path()))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn parse_genesis_is_not_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("genesis.json");
+        std::fs::write(&path, GENESIS).unwrap();
+
+        assert!(parse_superchain_bundle(path.to_str().unwrap(), None).unwrap().is_none());
+        assert_eq!(chain_value_parser(path.to_str().unwrap()).unwrap().chain().id(), 1);
+    }
 }
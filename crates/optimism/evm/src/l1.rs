@@ -271,7 +271,9 @@ impl RethL1BlockInfo for L1BlockInfo {
             return Ok(U256::ZERO);
         }
 
-        let spec_id = if chain_spec.is_fjord_active_at_timestamp(timestamp) {
+        let spec_id = if chain_spec.is_isthmus_active_at_timestamp(timestamp) {
+            SpecId::ISTHMUS
+        } else if chain_spec.is_fjord_active_at_timestamp(timestamp) {
             SpecId::FJORD
         } else if chain_spec.is_ecotone_active_at_timestamp(timestamp) {
             SpecId::ECOTONE
@@ -294,7 +296,9 @@ impl RethL1BlockInfo for L1BlockInfo {
         block_number: u64,
         input: &[u8],
     ) -> Result<U256, BlockExecutionError> {
-        let spec_id = if chain_spec.is_fjord_active_at_timestamp(timestamp) {
+        let spec_id = if chain_spec.is_isthmus_active_at_timestamp(timestamp) {
+            SpecId::ISTHMUS
+        } else if chain_spec.is_fjord_active_at_timestamp(timestamp) {
             SpecId::FJORD
         } else if chain_spec.is_regolith_active_at_timestamp(timestamp) {
             SpecId::REGOLITH
@@ -353,7 +357,7 @@ mod tests {
     use super::*;
     use alloy_consensus::{Block, BlockBody};
     use alloy_eips::eip2718::Decodable2718;
-    use reth_optimism_chainspec::OP_MAINNET;
+    use reth_optimism_chainspec::{BASE_MAINNET, OP_MAINNET};
     use reth_optimism_forks::OpHardforks;
     use reth_optimism_primitives::OpTransactionSigned;
 
@@ -471,4 +475,55 @@ mod tests {
         assert_eq!(l1_block_info.operator_fee_scalar, operator_fee_scalar);
         assert_eq!(l1_block_info.operator_fee_constant, operator_fee_constant);
     }
+
+    #[test]
+    fn sanity_l1_block_isthmus() {
+        // rig
+
+        // first block of base mainnet with Isthmus activated
+        const TIMESTAMP: u64 = 1746806401;
+        assert!(BASE_MAINNET.is_isthmus_active_at_timestamp(TIMESTAMP));
+        assert!(!BASE_MAINNET.is_isthmus_active_at_timestamp(TIMESTAMP - 1));
+
+        // L1 block info from a devnet with Isthmus activated
+        const DATA: &[u8] = &hex!("098999be00000558000c5fc500000000000000030000000067a9f765000000000000002900000000000000000000000000000000000000000000000000000000006a6d09000000000000000000000000000000000000000000000000000000000000000172fcc8e8886636bdbe96ba0e4baab67ea7e7811633f52b52e8cf7a5123213b6f000000000000000000000000d3f2c5afb2d76f5579f326b0cd7da5f5a4126c3500004e2000000000000001f4");
+
+        let tx = OpTransactionSigned::new_unhashed(
+            op_alloy_consensus::OpTypedTransaction::Deposit(op_alloy_consensus::TxDeposit {
+                from: address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"),
+                to: address!("4200000000000000000000000000000000000015").into(),
+                gas_limit: 1_000_000,
+                input: Bytes::from_static(DATA),
+                ..Default::default()
+            }),
+            alloy_primitives::PrimitiveSignature::test_signature(),
+        );
+        let block: Block<OpTransactionSigned> = Block {
+            body: BlockBody { transactions: vec![tx], ..Default::default() },
+            ..Default::default()
+        };
+
+        // test
+
+        let mut l1_block_info: L1BlockInfo = extract_l1_info(&block.body).unwrap();
+
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(6974729));
+        assert_eq!(l1_block_info.operator_fee_scalar, Some(U256::from(20000)));
+        assert_eq!(l1_block_info.operator_fee_constant, Some(U256::from(500)));
+
+        let input = [0x42u8; 100];
+        let data_gas = l1_block_info.l1_data_gas(&*BASE_MAINNET, TIMESTAMP, 0, &input).unwrap();
+        assert_eq!(data_gas, l1_block_info.data_gas(&input, SpecId::ISTHMUS));
+
+        let fee =
+            l1_block_info.l1_tx_data_fee(&*BASE_MAINNET, TIMESTAMP, 0, &input, false).unwrap();
+        assert!(!fee.is_zero());
+        assert_eq!(fee, l1_block_info.calculate_tx_l1_cost(&input, SpecId::ISTHMUS));
+
+        // deposits don't pay an l1 fee
+        assert_eq!(
+            l1_block_info.l1_tx_data_fee(&*BASE_MAINNET, TIMESTAMP, 0, &input, true).unwrap(),
+            U256::ZERO
+        );
+    }
 }
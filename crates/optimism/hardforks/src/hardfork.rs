@@ -152,6 +152,7 @@ impl OpHardfork {
                 EthereumHardfork::Paris |
                 EthereumHardfork::Shanghai => Some(1699981200),
                 EthereumHardfork::Cancun => Some(1708534800),
+                EthereumHardfork::Prague => Some(1744905600),
                 _ => None,
             },
             |fork| match fork {
@@ -161,7 +162,7 @@ impl OpHardfork {
                 Self::Fjord => Some(1716998400),
                 Self::Granite => Some(1723478400),
                 Self::Holocene => Some(1732633200),
-                Self::Isthmus => todo!(),
+                Self::Isthmus => Some(1744905600),
             },
         )
     }
@@ -188,6 +189,7 @@ impl OpHardfork {
                 EthereumHardfork::Paris |
                 EthereumHardfork::Shanghai => Some(1704992401),
                 EthereumHardfork::Cancun => Some(1710374401),
+                EthereumHardfork::Prague => Some(1746806401),
                 _ => None,
             },
             |fork| match fork {
@@ -197,7 +199,7 @@ impl OpHardfork {
                 Self::Fjord => Some(1720627201),
                 Self::Granite => Some(1726070401),
                 Self::Holocene => Some(1736445601),
-                Self::Isthmus => todo!(),
+                Self::Isthmus => Some(1746806401),
             },
         )
     }
@@ -235,6 +237,8 @@ impl OpHardfork {
             (Self::Fjord.boxed(), ForkCondition::Timestamp(1720627201)),
             (Self::Granite.boxed(), ForkCondition::Timestamp(1726070401)),
             (Self::Holocene.boxed(), ForkCondition::Timestamp(1736445601)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Timestamp(1746806401)),
+            (Self::Isthmus.boxed(), ForkCondition::Timestamp(1746806401)),
         ])
     }
 
@@ -271,6 +275,8 @@ impl OpHardfork {
             (Self::Fjord.boxed(), ForkCondition::Timestamp(1716998400)),
             (Self::Granite.boxed(), ForkCondition::Timestamp(1723478400)),
             (Self::Holocene.boxed(), ForkCondition::Timestamp(1732633200)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Timestamp(1744905600)),
+            (Self::Isthmus.boxed(), ForkCondition::Timestamp(1744905600)),
         ])
     }
 
@@ -307,6 +313,8 @@ impl OpHardfork {
             (Self::Fjord.boxed(), ForkCondition::Timestamp(1716998400)),
             (Self::Granite.boxed(), ForkCondition::Timestamp(1723478400)),
             (Self::Holocene.boxed(), ForkCondition::Timestamp(1732633200)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Timestamp(1744905600)),
+            (Self::Isthmus.boxed(), ForkCondition::Timestamp(1744905600)),
         ])
     }
 
@@ -343,6 +351,8 @@ impl OpHardfork {
             (Self::Fjord.boxed(), ForkCondition::Timestamp(1720627201)),
             (Self::Granite.boxed(), ForkCondition::Timestamp(1726070401)),
             (Self::Holocene.boxed(), ForkCondition::Timestamp(1736445601)),
+            (EthereumHardfork::Prague.boxed(), ForkCondition::Timestamp(1746806401)),
+            (Self::Isthmus.boxed(), ForkCondition::Timestamp(1746806401)),
         ])
     }
 }
@@ -376,6 +386,53 @@ mod tests {
         assert_eq!(OpHardfork::base_mainnet_activation_block(OpHardfork::Canyon), Some(9101527));
     }
 
+    #[test]
+    fn base_activation_timestamps() {
+        let forks = [
+            OpHardfork::Bedrock,
+            OpHardfork::Regolith,
+            OpHardfork::Canyon,
+            OpHardfork::Ecotone,
+            OpHardfork::Fjord,
+            OpHardfork::Granite,
+            OpHardfork::Holocene,
+            OpHardfork::Isthmus,
+        ];
+        for fork in forks {
+            assert!(fork.activation_timestamp(fork, Chain::base_mainnet()).is_some());
+            assert!(fork.activation_timestamp(fork, Chain::base_sepolia()).is_some());
+        }
+
+        assert_eq!(
+            OpHardfork::base_mainnet_activation_timestamp(OpHardfork::Isthmus),
+            Some(1746806401)
+        );
+        assert_eq!(
+            OpHardfork::base_sepolia_activation_timestamp(OpHardfork::Isthmus),
+            Some(1744905600)
+        );
+        assert_eq!(
+            OpHardfork::base_mainnet_activation_timestamp(EthereumHardfork::Prague),
+            OpHardfork::base_mainnet_activation_timestamp(OpHardfork::Isthmus),
+        );
+    }
+
+    #[test]
+    fn isthmus_in_chain_hardforks() {
+        for (hardforks, timestamp) in [
+            (OpHardfork::op_mainnet(), 1746806401),
+            (OpHardfork::base_mainnet(), 1746806401),
+            (OpHardfork::op_sepolia(), 1744905600),
+            (OpHardfork::base_sepolia(), 1744905600),
+        ] {
+            assert_eq!(hardforks.fork(OpHardfork::Isthmus), ForkCondition::Timestamp(timestamp));
+            assert_eq!(
+                hardforks.fork(EthereumHardfork::Prague),
+                ForkCondition::Timestamp(timestamp)
+            );
+        }
+    }
+
     #[test]
     fn check_op_hardfork_from_str() {
         let hardfork_str =
@@ -39,15 +39,17 @@ use reth_optimism_rpc::{
     witness::{DebugExecutionWitnessApiServer, OpDebugWitnessApi},
     OpEthApi, OpEthApiError, SequencerClient,
 };
-use reth_optimism_txpool::conditional::MaybeConditionalTransaction;
+use reth_optimism_txpool::{conditional::MaybeConditionalTransaction, OpPoolOrdering};
 use reth_provider::{providers::ProviderFactoryBuilder, CanonStateSubscriptions, EthStorage};
 use reth_rpc_eth_api::ext::L2EthApiExtServer;
 use reth_rpc_eth_types::error::FromEvmError;
 use reth_rpc_server_types::RethRpcModule;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, EthPoolTransaction, PoolTransaction,
-    TransactionPool, TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore,
+    validate::{EvmTransactionSimulator, TransactionSimulator},
+    CoinbaseTipOrdering, EthPoolTransaction, PoolTransaction, TransactionPool,
+    TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
 use revm::primitives::TxEnv;
@@ -429,33 +431,50 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
+///
+/// By default, transactions are ordered by their coinbase tip, see
+/// [`OpPoolBuilder::with_ordering`] for ordering them by the revenue including the L1 data fee.
 #[derive(Debug, Clone)]
-pub struct OpPoolBuilder<T = crate::txpool::OpPooledTransaction> {
+pub struct OpPoolBuilder<T = crate::txpool::OpPooledTransaction, O = CoinbaseTipOrdering<T>> {
     /// Enforced overrides that are applied to the pool config.
     pub pool_config_overrides: PoolBuilderConfigOverrides,
-    /// Marker for the pooled transaction type.
-    _pd: core::marker::PhantomData<T>,
+    /// Marker for the pooled transaction type and the ordering of the pool.
+    _pd: core::marker::PhantomData<(T, O)>,
 }
 
-impl<T> Default for OpPoolBuilder<T> {
+impl<T, O> Default for OpPoolBuilder<T, O> {
     fn default() -> Self {
         Self { pool_config_overrides: Default::default(), _pd: Default::default() }
     }
 }
 
-impl<Node, T> PoolBuilder<Node> for OpPoolBuilder<T>
+impl<T, O> OpPoolBuilder<T, O> {
+    /// Configures the [`TransactionOrdering`](reth_transaction_pool::TransactionOrdering) of the
+    /// pool.
+    ///
+    /// For example, [`OpTransactionOrdering`](crate::txpool::OpTransactionOrdering) orders
+    /// transactions by the revenue they generate per gas, including their L1 data fee.
+    pub fn with_ordering<Ordering>(self) -> OpPoolBuilder<T, Ordering> {
+        OpPoolBuilder { pool_config_overrides: self.pool_config_overrides, _pd: Default::default() }
+    }
+}
+
+impl<Node, T, O> PoolBuilder<Node> for OpPoolBuilder<T, O>
 where
     Node: FullNodeTypes<Types: NodeTypes<ChainSpec: OpHardforks>>,
-    T: EthPoolTransaction<Consensus = TxTy<Node::Types>>,
+    T: EthPoolTransaction<Consensus = TxTy<Node::Types>> + MaybeConditionalTransaction,
+    O: OpPoolOrdering<<Node::Types as NodeTypes>::ChainSpec, Transaction = T>,
+    EvmTransactionSimulator<Node::Provider, OpEvmConfig<<Node::Types as NodeTypes>::ChainSpec>>:
+        TransactionSimulator<T>,
 {
-    type Pool = OpTransactionPool<Node::Provider, DiskFileBlobStore, T>;
+    type Pool = OpTransactionPool<Node::Provider, DiskFileBlobStore, T, O>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let Self { pool_config_overrides, .. } = self;
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
 
-        let validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
+        let mut validator = TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone())
             .no_eip4844()
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -464,21 +483,39 @@ where
                     .additional_validation_tasks
                     .unwrap_or_else(|| ctx.config().txpool.additional_validation_tasks),
             )
-            .build_with_tasks(ctx.task_executor().clone(), blob_store.clone())
-            .map(|validator| {
-                OpTransactionValidator::new(validator)
-                    // In --dev mode we can't require gas fees because we're unable to decode
-                    // the L1 block info
-                    .require_l1_data_gas_fee(!ctx.config().dev.dev)
-            });
+            .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
-        let transaction_pool = reth_transaction_pool::Pool::new(
-            validator,
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config_overrides.apply(ctx.pool_config()),
-        );
+        if let Some(simulation_config) = ctx.config().txpool.simulation_config() {
+            let simulator = EvmTransactionSimulator::new(
+                ctx.provider().clone(),
+                OpEvmConfig::new(ctx.chain_spec()),
+                simulation_config,
+            );
+            validator.validator = validator.validator.with_simulator(simulator);
+            info!(target: "reth::cli", "Transaction simulation enabled");
+        }
+
+        let validator = validator.map(|validator| {
+            OpTransactionValidator::new(validator)
+                // In --dev mode we can't require gas fees because we're unable to decode
+                // the L1 block info
+                .require_l1_data_gas_fee(!ctx.config().dev.dev)
+        });
+
+        let ordering =
+            O::from_l1_block_info(ctx.chain_spec(), validator.validator.block_info().clone());
+        let pool_config = pool_config_overrides.apply(ctx.pool_config());
+        let lifetimes = pool_config.lifetimes;
+        let transaction_pool =
+            reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config);
         info!(target: "reth::cli", "Transaction pool initialized");
+
+        if let Some(path) = &ctx.config().txpool.record_trace {
+            transaction_pool
+                .set_recorder(Some(reth_transaction_pool::record::PoolRecorder::create(path)?));
+            info!(target: "reth::cli", ?path, "Recording transaction pool trace");
+        }
+
         let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
@@ -500,6 +537,26 @@ where
                 },
             );
 
+            if ctx.config().txpool.snapshot {
+                let snapshot_config =
+                    reth_transaction_pool::maintain::TransactionPoolSnapshotConfig::new(
+                        data_dir.txpool_snapshot(),
+                    )
+                    .with_interval(ctx.config().txpool.snapshot_interval)
+                    .with_restore_batch_size(ctx.config().txpool.snapshot_restore_batch_size);
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool snapshot task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::snapshot_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            snapshot_config,
+                        )
+                    },
+                );
+            }
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -508,10 +565,25 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig {
+                        lifetimes,
+                        ..Default::default()
+                    },
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
+
+            // spawn the maintenance task for transactions with conditionals
+            ctx.task_executor().spawn_critical(
+                "txpool conditional maintenance task",
+                reth_optimism_txpool::maintain::maintain_transaction_pool_conditional_future(
+                    ctx.provider().clone(),
+                    transaction_pool.clone(),
+                    ctx.provider().canonical_state_stream(),
+                    ctx.task_executor().clone(),
+                ),
+            );
+            debug!(target: "reth::cli", "Spawned txpool conditional maintenance task");
         }
 
         Ok(transaction_pool)
@@ -585,8 +657,10 @@ impl<Txs> OpPayloadBuilder<Txs> {
                 Primitives = OpPrimitives,
             >,
         >,
-        Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
-            + Unpin
+        Pool: TransactionPool<
+                Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+                                 + MaybeConditionalTransaction,
+            > + Unpin
             + 'static,
         Evm: ConfigureEvmFor<PrimitivesTy<Node::Types>>,
         Txs: OpPayloadTransactions<Pool::Transaction>,
@@ -613,8 +687,10 @@ where
             Primitives = OpPrimitives,
         >,
     >,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
-        + Unpin
+    Pool: TransactionPool<
+            Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>
+                             + MaybeConditionalTransaction,
+        > + Unpin
         + 'static,
     Txs: OpPayloadTransactions<Pool::Transaction>,
 {
@@ -31,6 +31,9 @@ use reth_optimism_forks::OpHardforks;
 use reth_optimism_primitives::{
     transaction::signed::OpTransaction, ADDRESS_L2_TO_L1_MESSAGE_PASSER,
 };
+use reth_optimism_txpool::conditional::{
+    matches_known_accounts_in_block, MaybeConditionalTransaction,
+};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
 use reth_payload_util::{BestPayloadTransactions, NoopPayloadTransactions, PayloadTransactions};
@@ -150,7 +153,9 @@ impl<Pool, Client, EvmConfig, N: NodePrimitives, Txs>
 
 impl<Pool, Client, EvmConfig, N, T> OpPayloadBuilder<Pool, Client, EvmConfig, N, T>
 where
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+    >,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + OpHardforks>,
     N: OpPayloadPrimitives,
     EvmConfig: ConfigureEvmFor<N>,
@@ -169,7 +174,9 @@ where
         best: impl FnOnce(BestTransactionsAttributes) -> Txs + Send + Sync + 'a,
     ) -> Result<BuildOutcome<OpBuiltPayload<N>>, PayloadBuilderError>
     where
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        >,
     {
         let evm_env = self
             .evm_env(&args.config.attributes, &args.config.parent_header)
@@ -261,7 +268,9 @@ impl<Pool, Client, EvmConfig, N, Txs> PayloadBuilder
 where
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + OpHardforks> + Clone,
     N: OpPayloadPrimitives,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+    Pool: TransactionPool<
+        Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+    >,
     EvmConfig: ConfigureEvmFor<N>,
     Txs: OpPayloadTransactions<Pool::Transaction>,
 {
@@ -340,7 +349,9 @@ impl<Txs> OpBuilder<'_, Txs> {
     ) -> Result<BuildOutcomeKind<ExecutedPayload<N>>, PayloadBuilderError>
     where
         N: OpPayloadPrimitives,
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        >,
         EvmConfig: ConfigureEvmFor<N>,
         ChainSpec: EthChainSpec + OpHardforks,
         DB: Database<Error = ProviderError> + AsRef<P>,
@@ -406,7 +417,9 @@ impl<Txs> OpBuilder<'_, Txs> {
         EvmConfig: ConfigureEvmFor<N>,
         ChainSpec: EthChainSpec + OpHardforks,
         N: OpPayloadPrimitives,
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        >,
         DB: Database<Error = ProviderError> + AsRef<P>,
         P: StateRootProvider + HashedPostStateProvider + StorageRootProvider,
     {
@@ -532,7 +545,9 @@ impl<Txs> OpBuilder<'_, Txs> {
         EvmConfig: ConfigureEvmFor<N>,
         ChainSpec: EthChainSpec + OpHardforks,
         N: OpPayloadPrimitives,
-        Txs: PayloadTransactions<Transaction: PoolTransaction<Consensus = N::SignedTx>>,
+        Txs: PayloadTransactions<
+            Transaction: PoolTransaction<Consensus = N::SignedTx> + MaybeConditionalTransaction,
+        >,
         DB: Database<Error = ProviderError> + AsRef<P>,
         P: StateProofProvider + StorageRootProvider,
     {
@@ -945,26 +960,46 @@ where
     /// Executes the given best transactions and updates the execution info.
     ///
     /// Returns `Ok(Some(())` if the job was cancelled.
-    pub fn execute_best_transactions<DB>(
+    pub fn execute_best_transactions<DB, P>(
         &self,
         info: &mut ExecutionInfo<N>,
         db: &mut State<DB>,
         mut best_txs: impl PayloadTransactions<
-            Transaction: PoolTransaction<Consensus = EvmConfig::Transaction>,
+            Transaction: PoolTransaction<Consensus = EvmConfig::Transaction>
+                             + MaybeConditionalTransaction,
         >,
     ) -> Result<Option<()>, PayloadBuilderError>
     where
-        DB: Database<Error = ProviderError>,
+        DB: Database<Error = ProviderError> + AsRef<P>,
+        P: StorageRootProvider,
     {
         let block_gas_limit = self.block_gas_limit();
         let block_da_limit = self.da_config.max_da_block_size();
         let tx_da_limit = self.da_config.max_da_tx_size();
         let base_fee = self.base_fee();
+        let block_number = self.block_number();
+        let timestamp = self.attributes().timestamp();
 
         let mut evm = self.evm_config.evm_with_env(&mut *db, self.evm_env.clone());
 
         while let Some(tx) = best_txs.next(()) {
+            // The conditional of the transaction doesn't allow inclusion in this block, its known
+            // accounts are checked against the state of the block built so far. The pool
+            // maintenance removes it once it can't be included anymore.
+            let conditional_matches = tx.matches_block_attributes(block_number, timestamp) &&
+                match tx.conditional() {
+                    Some(conditional) => {
+                        matches_known_accounts_in_block(conditional, evm.db_mut())?
+                    }
+                    None => true,
+                };
             let tx = tx.into_consensus();
+
+            if !conditional_matches {
+                best_txs.mark_invalid(tx.signer(), tx.nonce());
+                continue
+            }
+
             if info.is_tx_over_limits(tx.tx(), block_gas_limit, tx_da_limit, block_da_limit) {
                 // we can't fit this transaction into the block, so we need to mark it as
                 // invalid which also removes all dependent transaction from
@@ -106,6 +106,9 @@ pub enum TxConditionalErr {
     /// Invalid conditional parameters
     #[error("invalid conditional parameters")]
     InvalidCondition,
+    /// Known accounts of the conditional don't match the latest state
+    #[error("known accounts don't match the latest state")]
+    KnownAccountsMismatch,
 }
 
 impl From<TxConditionalErr> for jsonrpsee_types::error::ErrorObject<'static> {
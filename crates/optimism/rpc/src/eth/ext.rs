@@ -6,7 +6,9 @@ use alloy_eips::BlockNumberOrTag;
 use alloy_primitives::{Bytes, B256};
 use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use jsonrpsee_core::RpcResult;
-use reth_optimism_txpool::conditional::MaybeConditionalTransaction;
+use reth_optimism_txpool::conditional::{
+    matches_block_attributes, matches_known_accounts, MaybeConditionalTransaction,
+};
 use reth_provider::{BlockReaderIdExt, StateProviderFactory};
 use reth_rpc_eth_api::L2EthApiExtServer;
 use reth_rpc_eth_types::utils::recover_raw_transaction;
@@ -114,13 +116,24 @@ where
             .ok_or_else(header_not_found)?;
 
         // check condition against header
-        if !condition.has_exceeded_block_number(header.header().number()) ||
-            !condition.has_exceeded_timestamp(header.header().timestamp())
-        {
+        if !matches_block_attributes(
+            &condition,
+            header.header().number(),
+            header.header().timestamp(),
+        ) {
             return Err(TxConditionalErr::InvalidCondition.into());
         }
 
-        // TODO: check condition against state
+        // check condition against state
+        let state = self
+            .provider()
+            .state_by_block_hash(header.hash())
+            .map_err(|err| OpEthApiError::Eth(err.into()))?;
+        if !matches_known_accounts(&condition, &*state)
+            .map_err(|err| OpEthApiError::Eth(err.into()))?
+        {
+            return Err(TxConditionalErr::KnownAccountsMismatch.into());
+        }
 
         if let Some(sequencer) = self.sequencer_client() {
             // If we have a sequencer client, forward the transaction
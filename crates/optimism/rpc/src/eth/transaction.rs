@@ -13,10 +13,11 @@ use reth_provider::{
 };
 use reth_rpc_eth_api::{
     helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
-    FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt, TransactionCompat,
+    EthApiTypes, FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt, TransactionCompat,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use std::time::Instant;
 
 use crate::{eth::OpNodeCore, OpEthApi, OpEthApiError, SequencerClient};
 
@@ -33,8 +34,33 @@ where
     ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
+        self.submit_raw_transaction(tx, None).await
+    }
+
+    async fn send_raw_transaction_with_deadline(
+        &self,
+        tx: Bytes,
+        deadline: Instant,
+    ) -> Result<B256, Self::Error> {
+        self.submit_raw_transaction(tx, Some(deadline)).await
+    }
+}
+
+impl<N> OpEthApi<N>
+where
+    Self: EthTransactions,
+    N: OpNodeCore,
+{
+    /// Decodes and recovers the transaction, forwards it to the sequencer and submits it to the
+    /// pool with an optional deadline after which it is evicted.
+    async fn submit_raw_transaction(
+        &self,
+        tx: Bytes,
+        deadline: Option<Instant>,
+    ) -> Result<B256, <Self as EthApiTypes>::Error> {
         let recovered = recover_raw_transaction(&tx)?;
-        let pool_transaction = <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered);
+        let pool_transaction =
+            <<Self as RpcNodeCore>::Pool as TransactionPool>::Transaction::from_pooled(recovered);
 
         // On optimism, transactions are forwarded directly to the sequencer to be included in
         // blocks that it builds.
@@ -46,11 +72,19 @@ where
         }
 
         // submit the transaction to the pool with a `Local` origin
-        let hash = self
-            .pool()
-            .add_transaction(TransactionOrigin::Local, pool_transaction)
-            .await
-            .map_err(Self::Error::from_eth_err)?;
+        let hash = match deadline {
+            Some(deadline) => {
+                self.pool()
+                    .add_transaction_with_deadline(
+                        TransactionOrigin::Local,
+                        pool_transaction,
+                        deadline,
+                    )
+                    .await
+            }
+            None => self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await,
+        }
+        .map_err(<Self as EthApiTypes>::Error::from_eth_err)?;
 
         Ok(hash)
     }
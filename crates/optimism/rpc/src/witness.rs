@@ -9,6 +9,7 @@ use reth_evm::ConfigureEvmFor;
 use reth_node_api::NodePrimitives;
 use reth_optimism_chainspec::OpChainSpec;
 use reth_optimism_payload_builder::{OpPayloadBuilder, OpPayloadPrimitives};
+use reth_optimism_txpool::conditional::MaybeConditionalTransaction;
 use reth_primitives::SealedHeader;
 use reth_provider::{
     BlockReaderIdExt, NodePrimitivesProvider, ProviderError, ProviderResult, StateProviderFactory,
@@ -60,7 +61,7 @@ where
     Pool: TransactionPool<
             Transaction: PoolTransaction<
                 Consensus = <Provider::Primitives as NodePrimitives>::SignedTx,
-            >,
+            > + MaybeConditionalTransaction,
         > + 'static,
     Provider: BlockReaderIdExt<Header = reth_primitives::Header>
         + NodePrimitivesProvider<Primitives: OpPayloadPrimitives>
@@ -1,12 +1,38 @@
 //! Additional support for pooled transactions with [`TransactionConditional`]
 
-use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+use reth_storage_api::{
+    errors::provider::{ProviderError, ProviderResult},
+    StateProvider, StorageRootProvider,
+};
+use reth_trie_common::HashedStorage;
+use revm::{db::State, Database};
 
 /// Helper trait that allows attaching a [`TransactionConditional`].
 pub trait MaybeConditionalTransaction {
     /// Attach a [`TransactionConditional`].
     fn set_conditional(&mut self, conditional: TransactionConditional);
 
+    /// Get attached [`TransactionConditional`] if any.
+    fn conditional(&self) -> Option<&TransactionConditional>;
+
+    /// Returns `true` if the attached [`TransactionConditional`] can't be satisfied anymore by a
+    /// block with the given number and timestamp, or any block after it.
+    fn has_exceeded_block_attributes(&self, number: BlockNumber, timestamp: u64) -> bool {
+        self.conditional().is_some_and(|conditional| {
+            conditional.has_exceeded_block_number(number) ||
+                conditional.has_exceeded_timestamp(timestamp)
+        })
+    }
+
+    /// Returns `true` if the attached [`TransactionConditional`], if any, allows inclusion in a
+    /// block with the given number and timestamp.
+    fn matches_block_attributes(&self, number: BlockNumber, timestamp: u64) -> bool {
+        self.conditional()
+            .is_none_or(|conditional| matches_block_attributes(conditional, number, timestamp))
+    }
+
     /// Helper that sets the conditional and returns the instance again
     fn with_conditional(mut self, conditional: TransactionConditional) -> Self
     where
@@ -16,3 +42,192 @@ pub trait MaybeConditionalTransaction {
         self
     }
 }
+
+/// Returns `true` if the block number and timestamp bounds of the conditional allow inclusion in
+/// a block with the given number and timestamp.
+pub fn matches_block_attributes(
+    conditional: &TransactionConditional,
+    number: BlockNumber,
+    timestamp: u64,
+) -> bool {
+    conditional.block_number_min.is_none_or(|min| number >= min) &&
+        conditional.timestamp_min.is_none_or(|min| timestamp >= min) &&
+        conditional.block_number_max.is_none_or(|max| number <= max) &&
+        conditional.timestamp_max.is_none_or(|max| timestamp <= max)
+}
+
+/// Returns `true` if the known accounts of the conditional match the given state.
+///
+/// Accounts with an expected storage root are compared against the storage root computed from
+/// the state, accounts with expected slots against the values of the slots.
+pub fn matches_known_accounts<S: StateProvider + ?Sized>(
+    conditional: &TransactionConditional,
+    state: &S,
+) -> ProviderResult<bool> {
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::RootHash(expected) => {
+                if state.storage_root(*address, Default::default())? != *expected {
+                    return Ok(false)
+                }
+            }
+            AccountStorage::Slots(slots) => {
+                for (slot, expected) in slots {
+                    let value = state.storage(*address, B256::from(*slot))?.unwrap_or_default();
+                    if B256::from(value) != *expected {
+                        return Ok(false)
+                    }
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Returns `true` if the known accounts of the conditional match the state of a block that is
+/// being built.
+///
+/// Slots are read from the state, which includes the changes of the transactions executed so far.
+/// Storage roots are computed from the storage of the parent block, with the storage changed by
+/// the block applied on top.
+pub fn matches_known_accounts_in_block<DB, P>(
+    conditional: &TransactionConditional,
+    state: &mut State<DB>,
+) -> ProviderResult<bool>
+where
+    DB: Database<Error = ProviderError> + AsRef<P>,
+    P: StorageRootProvider,
+{
+    for (address, storage) in &conditional.known_accounts {
+        // storage reads of the state require the account to be loaded into the cache
+        state.load_cache_account(*address)?;
+        match storage {
+            AccountStorage::RootHash(expected) => {
+                let storage = state
+                    .cache
+                    .accounts
+                    .get(address)
+                    .map(|account| {
+                        HashedStorage::from_plain_storage(
+                            account.status,
+                            account.account.iter().flat_map(|account| &account.storage),
+                        )
+                    })
+                    .unwrap_or_default();
+                if state.database.as_ref().storage_root(*address, storage)? != *expected {
+                    return Ok(false)
+                }
+            }
+            AccountStorage::Slots(slots) => {
+                for (slot, expected) in slots {
+                    if B256::from(state.storage(*address, *slot)?) != *expected {
+                        return Ok(false)
+                    }
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_revm::database::StateProviderDatabase;
+    use reth_storage_api::StateProviderFactory;
+
+    #[test]
+    fn block_attributes() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_min: Some(100),
+            timestamp_max: Some(200),
+            ..Default::default()
+        };
+
+        assert!(!matches_block_attributes(&conditional, 9, 150));
+        assert!(!matches_block_attributes(&conditional, 15, 99));
+        assert!(matches_block_attributes(&conditional, 10, 100));
+        assert!(matches_block_attributes(&conditional, 20, 200));
+        assert!(!matches_block_attributes(&conditional, 21, 200));
+        assert!(!matches_block_attributes(&conditional, 20, 201));
+        assert!(matches_block_attributes(&TransactionConditional::default(), 0, 0));
+    }
+
+    #[test]
+    fn known_accounts() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(2))]),
+        );
+        let state = provider.latest().unwrap();
+
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(
+                std::iter::once((U256::from(1), B256::from(U256::from(2)))).collect(),
+            ),
+        );
+        assert!(matches_known_accounts(&conditional, &*state).unwrap());
+
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(std::iter::once((U256::from(1), B256::ZERO)).collect()),
+        );
+        assert!(!matches_known_accounts(&conditional, &*state).unwrap());
+
+        // slots of unknown accounts are empty
+        conditional.known_accounts.clear();
+        conditional.known_accounts.insert(
+            Address::random(),
+            AccountStorage::Slots(std::iter::once((U256::from(1), B256::ZERO)).collect()),
+        );
+        assert!(matches_known_accounts(&conditional, &*state).unwrap());
+    }
+
+    #[test]
+    fn known_accounts_in_block() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(2))]),
+        );
+        let mut state = State::builder()
+            .with_database(StateProviderDatabase::new(provider.latest().unwrap()))
+            .build();
+
+        let mut conditional = TransactionConditional::default();
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(
+                std::iter::once((U256::from(1), B256::from(U256::from(2)))).collect(),
+            ),
+        );
+        assert!(matches_known_accounts_in_block(&conditional, &mut state).unwrap());
+
+        // a transaction of the block changed the slot
+        state.insert_account_with_storage(
+            address,
+            Default::default(),
+            std::iter::once((U256::from(1), U256::from(3))).collect(),
+        );
+        assert!(!matches_known_accounts_in_block(&conditional, &mut state).unwrap());
+
+        conditional.known_accounts.insert(
+            address,
+            AccountStorage::Slots(
+                std::iter::once((U256::from(1), B256::from(U256::from(3)))).collect(),
+            ),
+        );
+        assert!(matches_known_accounts_in_block(&conditional, &mut state).unwrap());
+    }
+}
@@ -14,14 +14,14 @@ mod validator;
 pub use validator::{OpL1BlockInfo, OpTransactionValidator};
 
 pub mod conditional;
+pub mod maintain;
+mod ordering;
+pub use ordering::{OpPoolOrdering, OpTransactionOrdering};
 mod transaction;
-pub use transaction::OpPooledTransaction;
+pub use transaction::{OpPooledTransaction, OpPooledTx};
 
 use reth_transaction_pool::{CoinbaseTipOrdering, Pool, TransactionValidationTaskExecutor};
 
 /// Type alias for default optimism transaction pool
-pub type OpTransactionPool<Client, S, T = OpPooledTransaction> = Pool<
-    TransactionValidationTaskExecutor<OpTransactionValidator<Client, T>>,
-    CoinbaseTipOrdering<T>,
-    S,
->;
+pub type OpTransactionPool<Client, S, T = OpPooledTransaction, O = CoinbaseTipOrdering<T>> =
+    Pool<TransactionValidationTaskExecutor<OpTransactionValidator<Client, T>>, O, S>;
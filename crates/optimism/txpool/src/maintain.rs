@@ -0,0 +1,157 @@
+//! Support for maintaining the state of the transaction pool with respect to the
+//! [`TransactionConditional`](alloy_rpc_types_eth::erc4337::TransactionConditional)s attached to
+//! pooled transactions.
+
+use crate::conditional::{matches_known_accounts, MaybeConditionalTransaction};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{TxHash, B256};
+use futures_util::{future::BoxFuture, FutureExt, Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives_traits::NodePrimitives;
+use reth_storage_api::StateProviderFactory;
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{PoolTransaction, TransactionPool, ValidPoolTransaction};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use tracing::{debug, trace};
+
+/// Transaction pool maintenance metrics for conditional transactions.
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+struct MaintainPoolConditionalMetrics {
+    /// Number of conditional transactions removed from the pool because their block number or
+    /// timestamp bounds were exceeded.
+    removed_tx_conditional_expired: Counter,
+    /// Number of conditional transactions removed from the pool because the state of their known
+    /// accounts changed.
+    removed_tx_conditional_known_accounts: Counter,
+}
+
+/// Returns a spawnable future for maintaining the conditional transactions of the pool.
+pub fn maintain_transaction_pool_conditional_future<N, Client, Pool, St, Tasks>(
+    client: Client,
+    pool: Pool,
+    events: St,
+    task_spawner: Tasks,
+) -> BoxFuture<'static, ()>
+where
+    N: NodePrimitives,
+    Client: StateProviderFactory + Clone + 'static,
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    async move {
+        maintain_transaction_pool_conditional(client, pool, events, task_spawner).await;
+    }
+    .boxed()
+}
+
+/// Maintains the conditional transactions of the pool.
+///
+/// On every new canonical tip, the conditionals of all pooled transactions are checked against
+/// the tip. Transactions that can no longer be included because the tip exceeded their block
+/// number or timestamp bounds, or because the state of one of their known accounts changed, are
+/// removed from the pool.
+///
+/// The known accounts are read from the state of the tip on a blocking task.
+pub async fn maintain_transaction_pool_conditional<N, Client, Pool, St, Tasks>(
+    client: Client,
+    pool: Pool,
+    mut events: St,
+    task_spawner: Tasks,
+) where
+    N: NodePrimitives,
+    Client: StateProviderFactory + Clone + 'static,
+    Pool: TransactionPool<Transaction: MaybeConditionalTransaction> + 'static,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    let metrics = MaintainPoolConditionalMetrics::default();
+
+    while let Some(event) = events.next().await {
+        let tip = event.tip();
+
+        let conditional_txs = pool
+            .pooled_transactions()
+            .into_iter()
+            .filter(|tx| tx.transaction.conditional().is_some())
+            .collect::<Vec<_>>();
+        if conditional_txs.is_empty() {
+            continue
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let client = client.clone();
+        let (hash, number, timestamp) = (tip.hash(), tip.number(), tip.timestamp());
+        task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(check_conditional_transactions(
+                &client,
+                hash,
+                number,
+                timestamp,
+                conditional_txs,
+            ));
+        }));
+        let Ok(Some((mut expired, mut known_accounts_changed))) = rx.await else { continue };
+
+        if expired.is_empty() && known_accounts_changed.is_empty() {
+            continue
+        }
+
+        trace!(target: "txpool", expired=expired.len(), known_accounts_changed=known_accounts_changed.len(), "Removing conditional transactions");
+        metrics.removed_tx_conditional_expired.increment(expired.len() as u64);
+        metrics
+            .removed_tx_conditional_known_accounts
+            .increment(known_accounts_changed.len() as u64);
+
+        expired.append(&mut known_accounts_changed);
+        pool.remove_transactions(expired);
+    }
+}
+
+/// Checks the conditionals of the transactions against the block, and returns the hashes of the
+/// transactions that exceeded their block number or timestamp bounds, and of those with changed
+/// known accounts.
+///
+/// Returns `None` if the state of the block isn't available.
+fn check_conditional_transactions<Client, T>(
+    client: &Client,
+    hash: B256,
+    number: u64,
+    timestamp: u64,
+    conditional_txs: Vec<Arc<ValidPoolTransaction<T>>>,
+) -> Option<(Vec<TxHash>, Vec<TxHash>)>
+where
+    Client: StateProviderFactory,
+    T: MaybeConditionalTransaction + PoolTransaction,
+{
+    let state = match client.state_by_block_hash(hash) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, block=%hash, "Failed to get state for checking conditional transactions");
+            return None
+        }
+    };
+
+    let mut expired = Vec::new();
+    let mut known_accounts_changed = Vec::new();
+    for tx in conditional_txs {
+        if tx.transaction.has_exceeded_block_attributes(number, timestamp) {
+            expired.push(*tx.hash());
+            continue
+        }
+
+        let Some(conditional) = tx.transaction.conditional() else { continue };
+        match matches_known_accounts(conditional, &*state) {
+            Ok(true) => {}
+            Ok(false) => known_accounts_changed.push(*tx.hash()),
+            Err(err) => {
+                debug!(target: "txpool", %err, tx=%tx.hash(), "Failed to check known accounts of conditional transaction");
+            }
+        }
+    }
+
+    Some((expired, known_accounts_changed))
+}
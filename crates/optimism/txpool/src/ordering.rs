@@ -0,0 +1,237 @@
+//! Transaction orderings for the OP transaction pool.
+
+use crate::{OpL1BlockInfo, OpPooledTx};
+use alloy_primitives::U256;
+use reth_optimism_evm::RethL1BlockInfo;
+use reth_optimism_forks::OpHardforks;
+use reth_transaction_pool::{CoinbaseTipOrdering, PoolTransaction, Priority, TransactionOrdering};
+use std::{
+    marker::PhantomData,
+    sync::{atomic::Ordering, Arc},
+};
+
+/// A [`TransactionOrdering`] of the OP transaction pool that can be built from the
+/// [`OpL1BlockInfo`] tracked by the [`OpTransactionValidator`](crate::OpTransactionValidator).
+pub trait OpPoolOrdering<ChainSpec>: TransactionOrdering {
+    /// Creates the ordering for the given chain spec and L1 block info.
+    fn from_l1_block_info(chain_spec: Arc<ChainSpec>, block_info: Arc<OpL1BlockInfo>) -> Self;
+}
+
+impl<T, ChainSpec> OpPoolOrdering<ChainSpec> for CoinbaseTipOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    fn from_l1_block_info(_chain_spec: Arc<ChainSpec>, _block_info: Arc<OpL1BlockInfo>) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders OP transactions by the revenue they are expected to generate per unit of gas.
+///
+/// In addition to the coinbase tip, this accounts for the L1 data fee a transaction pays, which is
+/// derived from the current [`OpL1BlockInfo`]. The priority of a transaction is its effective tip
+/// per gas plus its L1 data fee divided by its gas limit. Since Fjord, the L1 data fee is computed
+/// from the estimated compressed size of the transaction, see
+/// [`OpPooledTx::estimated_compressed_size`].
+///
+/// Note: the pool computes the priority of a transaction when it enters the pending subpool and
+/// only recomputes it when the base fee increases. The L1 data fee component therefore reflects the
+/// [`OpL1BlockInfo`] at that time and is not updated when only the L1 block info changes.
+#[derive(Debug)]
+pub struct OpTransactionOrdering<T, ChainSpec> {
+    /// The chain spec, used to determine the active hardfork.
+    chain_spec: Arc<ChainSpec>,
+    /// The L1 block info of the current block.
+    block_info: Arc<OpL1BlockInfo>,
+    _pd: PhantomData<T>,
+}
+
+impl<T, ChainSpec> OpTransactionOrdering<T, ChainSpec> {
+    /// Creates a new ordering for the given chain spec and L1 block info.
+    pub const fn new(chain_spec: Arc<ChainSpec>, block_info: Arc<OpL1BlockInfo>) -> Self {
+        Self { chain_spec, block_info, _pd: PhantomData }
+    }
+}
+
+impl<T, ChainSpec> OpTransactionOrdering<T, ChainSpec>
+where
+    T: OpPooledTx,
+    ChainSpec: OpHardforks,
+{
+    /// Returns the L1 data fee the transaction pays in the current block.
+    fn l1_data_fee(&self, transaction: &T) -> U256 {
+        let timestamp = self.block_info.timestamp.load(Ordering::Relaxed);
+        let l1_block_info = self.block_info.l1_block_info.read();
+
+        // <https://specs.optimism.io/protocol/exec-engine.html#l1-cost-fees-l1-fee-vault>
+        let l1_fee_scaled = l1_block_info
+            .l1_base_fee
+            .saturating_mul(U256::from(16))
+            .saturating_mul(l1_block_info.l1_base_fee_scalar)
+            .saturating_add(
+                l1_block_info
+                    .l1_blob_base_fee
+                    .unwrap_or_default()
+                    .saturating_mul(l1_block_info.l1_blob_base_fee_scalar.unwrap_or_default()),
+            );
+
+        if self.chain_spec.is_fjord_active_at_timestamp(timestamp) {
+            return U256::from(transaction.estimated_compressed_size()).saturating_mul(l1_fee_scaled) /
+                U256::from(1_000_000_000_000u64)
+        }
+
+        let number = self.block_info.number.load(Ordering::Relaxed);
+        let data_gas = l1_block_info
+            .l1_data_gas(&*self.chain_spec, timestamp, number, transaction.encoded_2718())
+            .unwrap_or_default();
+        match l1_block_info.l1_fee_overhead {
+            // the overhead is only set for the bedrock cost function, which is also used for the
+            // first ecotone block
+            Some(overhead) => {
+                data_gas
+                    .saturating_add(overhead)
+                    .saturating_mul(l1_block_info.l1_base_fee)
+                    .saturating_mul(l1_block_info.l1_base_fee_scalar) /
+                    U256::from(1_000_000)
+            }
+            None => data_gas.saturating_mul(l1_fee_scaled) / U256::from(16_000_000),
+        }
+    }
+}
+
+impl<T, ChainSpec> TransactionOrdering for OpTransactionOrdering<T, ChainSpec>
+where
+    T: OpPooledTx + 'static,
+    ChainSpec: OpHardforks + Send + Sync + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let Some(tip) = transaction.effective_tip_per_gas(base_fee) else { return Priority::None };
+        let l1_data_fee_per_gas =
+            self.l1_data_fee(transaction) / U256::from(transaction.gas_limit().max(1));
+        Priority::Value(U256::from(tip).saturating_add(l1_data_fee_per_gas))
+    }
+}
+
+impl<T, ChainSpec> OpPoolOrdering<ChainSpec> for OpTransactionOrdering<T, ChainSpec>
+where
+    T: OpPooledTx + 'static,
+    ChainSpec: OpHardforks + Send + Sync + 'static,
+{
+    fn from_l1_block_info(chain_spec: Arc<ChainSpec>, block_info: Arc<OpL1BlockInfo>) -> Self {
+        Self::new(chain_spec, block_info)
+    }
+}
+
+impl<T, ChainSpec> Clone for OpTransactionOrdering<T, ChainSpec> {
+    fn clone(&self) -> Self {
+        Self::new(self.chain_spec.clone(), self.block_info.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OpPooledTransaction;
+    use alloy_consensus::transaction::Recovered;
+    use alloy_eips::Encodable2718;
+    use alloy_primitives::{keccak256, Address, Bytes, PrimitiveSignature as Signature, TxKind};
+    use op_alloy_consensus::OpTypedTransaction;
+    use reth_optimism_chainspec::{OpChainSpec, OP_MAINNET};
+    use reth_optimism_primitives::OpTransactionSigned;
+
+    fn transaction(input: Bytes) -> OpPooledTransaction {
+        let tx = OpTypedTransaction::Eip1559(alloy_consensus::TxEip1559 {
+            chain_id: 10,
+            gas_limit: 100_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: TxKind::Call(Address::ZERO),
+            input,
+            ..Default::default()
+        });
+        let signed = OpTransactionSigned::new_unhashed(tx, Signature::test_signature());
+        let len = signed.encode_2718_len();
+        OpPooledTransaction::new(Recovered::new_unchecked(signed, Address::ZERO), len)
+    }
+
+    fn l1_fee_ordering(
+        l1_base_fee: u64,
+    ) -> OpTransactionOrdering<OpPooledTransaction, OpChainSpec> {
+        let block_info = OpL1BlockInfo::default();
+        // post Fjord
+        block_info.timestamp.store(1_720_627_201, Ordering::Relaxed);
+        {
+            let mut l1_block_info = block_info.l1_block_info.write();
+            l1_block_info.l1_base_fee = U256::from(l1_base_fee);
+            l1_block_info.l1_base_fee_scalar = U256::from(5227);
+            l1_block_info.l1_blob_base_fee = Some(U256::from(l1_base_fee));
+            l1_block_info.l1_blob_base_fee_scalar = Some(U256::from(1014213));
+        }
+        OpTransactionOrdering::new(OP_MAINNET.clone(), Arc::new(block_info))
+    }
+
+    #[test]
+    fn l1_data_fee_aware_priority() {
+        // incompressible calldata
+        let input = (0..64u8).flat_map(|i| keccak256([i]).0).collect::<Vec<_>>().into();
+        let small = transaction(Bytes::new());
+        let large = transaction(input);
+
+        // without L1 fees the priority is the coinbase tip
+        let ordering = l1_fee_ordering(0);
+        let tip_ordering = CoinbaseTipOrdering::<OpPooledTransaction>::default();
+        assert_eq!(ordering.priority(&large, 0), tip_ordering.priority(&large, 0));
+
+        // with L1 fees the transaction paying more L1 data fee per gas ranks higher
+        let ordering = l1_fee_ordering(1_000_000_000);
+        let Priority::Value(small) = ordering.priority(&small, 0) else { panic!("no priority") };
+        let Priority::Value(large) = ordering.priority(&large, 0) else { panic!("no priority") };
+        assert!(small > U256::from(1_000_000_000u64));
+        assert!(large > small);
+
+        // transactions that can't pay the base fee have no priority
+        assert_eq!(ordering.priority(&transaction(Bytes::new()), u64::MAX), Priority::None);
+    }
+
+    #[test]
+    fn l1_data_fee_pre_fjord() {
+        let input: Bytes = (0..64u8).flat_map(|i| keccak256([i]).0).collect::<Vec<_>>().into();
+        let tx = transaction(input);
+
+        // pre ecotone with the bedrock cost function, and post ecotone
+        for (timestamp, l1_fee_overhead) in
+            [(1_710_374_400, Some(U256::from(188))), (1_710_374_401, None)]
+        {
+            let block_info = OpL1BlockInfo::default();
+            block_info.timestamp.store(timestamp, Ordering::Relaxed);
+            block_info.number.store(120_000_000, Ordering::Relaxed);
+            {
+                let mut l1_block_info = block_info.l1_block_info.write();
+                l1_block_info.l1_base_fee = U256::from(1_000_000_000);
+                l1_block_info.l1_fee_overhead = l1_fee_overhead;
+                l1_block_info.l1_base_fee_scalar = U256::from(5227);
+                l1_block_info.l1_blob_base_fee = Some(U256::from(1_000_000_000));
+                l1_block_info.l1_blob_base_fee_scalar = Some(U256::from(1014213));
+            }
+            let expected = block_info
+                .l1_block_info
+                .read()
+                .clone()
+                .l1_tx_data_fee(&*OP_MAINNET, timestamp, 120_000_000, tx.encoded_2718(), false)
+                .unwrap();
+            let ordering = OpTransactionOrdering::<OpPooledTransaction, OpChainSpec>::new(
+                OP_MAINNET.clone(),
+                Arc::new(block_info),
+            );
+            assert!(!expected.is_zero());
+            assert_eq!(ordering.l1_data_fee(&tx), expected);
+        }
+    }
+}
@@ -19,6 +19,7 @@ use std::sync::{Arc, OnceLock};
 /// This type wraps the actual transaction and caches values that are frequently used by the pool.
 /// For payload building this lazily tracks values that are required during payload building:
 ///  - Estimated compressed size of this transaction
+///  - The EIP-2718 encoded transaction
 #[derive(Debug, Clone, derive_more::Deref)]
 pub struct OpPooledTransaction<
     Cons = OpTransactionSigned,
@@ -28,6 +29,8 @@ pub struct OpPooledTransaction<
     inner: EthPooledTransaction<Cons>,
     /// The estimated size of this transaction, lazily computed.
     estimated_tx_compressed_size: OnceLock<u64>,
+    /// The EIP-2718 encoded transaction, lazily computed.
+    encoded_2718: OnceLock<Bytes>,
     /// The pooled transaction type.
     _pd: core::marker::PhantomData<Pooled>,
 
@@ -41,6 +44,7 @@ impl<Cons: SignedTransaction, Pooled> OpPooledTransaction<Cons, Pooled> {
         Self {
             inner: EthPooledTransaction::new(transaction, encoded_length),
             estimated_tx_compressed_size: Default::default(),
+            encoded_2718: Default::default(),
             conditional: None,
             _pd: core::marker::PhantomData,
         }
@@ -50,9 +54,14 @@ impl<Cons: SignedTransaction, Pooled> OpPooledTransaction<Cons, Pooled> {
     /// This value is computed based on the following formula:
     /// `max(minTransactionSize, intercept + fastlzCoef*fastlzSize)`
     pub fn estimated_compressed_size(&self) -> u64 {
-        *self.estimated_tx_compressed_size.get_or_init(|| {
-            op_alloy_flz::tx_estimated_size_fjord(&self.inner.transaction().encoded_2718())
-        })
+        *self
+            .estimated_tx_compressed_size
+            .get_or_init(|| op_alloy_flz::tx_estimated_size_fjord(self.encoded_2718()))
+    }
+
+    /// Returns the EIP-2718 encoded transaction, the encoding is computed once and cached.
+    pub fn encoded_2718(&self) -> &Bytes {
+        self.encoded_2718.get_or_init(|| self.inner.transaction().encoded_2718().into())
     }
 
     /// Conditional setter.
@@ -71,6 +80,36 @@ impl<Cons, Pooled> MaybeConditionalTransaction for OpPooledTransaction<Cons, Poo
     fn set_conditional(&mut self, conditional: TransactionConditional) {
         self.conditional = Some(Box::new(conditional))
     }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+}
+
+/// Helper trait for pooled OP transactions that provides the data required to estimate the L1
+/// data fee of a transaction.
+pub trait OpPooledTx: PoolTransaction + MaybeConditionalTransaction {
+    /// Returns the EIP-2718 encoded transaction.
+    fn encoded_2718(&self) -> &Bytes;
+
+    /// Returns the estimated compressed size of the transaction in bytes scaled by 1e6.
+    ///
+    /// See also [`OpPooledTransaction::estimated_compressed_size`].
+    fn estimated_compressed_size(&self) -> u64;
+}
+
+impl<Cons, Pooled> OpPooledTx for OpPooledTransaction<Cons, Pooled>
+where
+    Cons: SignedTransaction + From<Pooled>,
+    Pooled: SignedTransaction + TryFrom<Cons, Error: core::error::Error>,
+{
+    fn encoded_2718(&self) -> &Bytes {
+        Self::encoded_2718(self)
+    }
+
+    fn estimated_compressed_size(&self) -> u64 {
+        Self::estimated_compressed_size(self)
+    }
 }
 
 impl<Cons, Pooled> PoolTransaction for OpPooledTransaction<Cons, Pooled>
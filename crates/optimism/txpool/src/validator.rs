@@ -22,11 +22,11 @@ use std::sync::{
 #[derive(Debug, Default)]
 pub struct OpL1BlockInfo {
     /// The current L1 block info.
-    l1_block_info: RwLock<L1BlockInfo>,
+    pub(crate) l1_block_info: RwLock<L1BlockInfo>,
     /// Current block timestamp.
-    timestamp: AtomicU64,
+    pub(crate) timestamp: AtomicU64,
     /// Current block number.
-    number: AtomicU64,
+    pub(crate) number: AtomicU64,
 }
 
 /// Validator for Optimism transactions.
@@ -56,6 +56,11 @@ impl<Client, Tx> OpTransactionValidator<Client, Tx> {
         self.inner.client()
     }
 
+    /// Returns the [`OpL1BlockInfo`] tracked by this validator.
+    pub const fn block_info(&self) -> &Arc<OpL1BlockInfo> {
+        &self.block_info
+    }
+
     /// Returns the current block timestamp.
     fn block_timestamp(&self) -> u64 {
         self.block_info.timestamp.load(Ordering::Relaxed)
@@ -158,6 +163,8 @@ where
             state_nonce,
             transaction: valid_tx,
             propagate,
+            bytecode_hash,
+            authorities,
         } = outcome
         {
             let mut l1_block_info = self.block_info.l1_block_info.read().clone();
@@ -196,6 +203,8 @@ where
                 state_nonce,
                 transaction: valid_tx,
                 propagate,
+                bytecode_hash,
+                authorities,
             }
         }
 
@@ -0,0 +1,173 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_db::{tables, transaction::DbTxMut};
+use reth_provider::{providers::StaticFileProvider, DBProvider, StaticFileProviderFactory};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::trace;
+
+#[derive(Debug)]
+pub struct AccountChangeSets<N> {
+    static_file_provider: StaticFileProvider<N>,
+}
+
+impl<N> AccountChangeSets<N> {
+    pub const fn new(static_file_provider: StaticFileProvider<N>) -> Self {
+        Self { static_file_provider }
+    }
+}
+
+impl<Provider> Segment<Provider> for AccountChangeSets<Provider::Primitives>
+where
+    Provider: StaticFileProviderFactory + DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AccountChangeSets
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        self.static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .map(PruneMode::before_inclusive)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::StaticFile
+    }
+
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No account changesets to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+
+        let mut last_pruned_block = None;
+        let (pruned, done) =
+            provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
+                range,
+                &mut limiter,
+                |_| false,
+                |(block_number, _)| last_pruned_block = Some(block_number),
+            )?;
+        trace!(target: "pruner", %pruned, %done, "Pruned account changesets");
+
+        let last_pruned_block = last_pruned_block
+            // If there's more account changesets to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its account changesets on the next run.
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{PruneInput, Segment, SegmentOutput};
+    use alloy_primitives::{BlockNumber, B256};
+    use assert_matches::assert_matches;
+    use reth_db::{
+        models::{AccountBeforeTx, StaticFileAccountChangeSet},
+        tables,
+    };
+    use reth_provider::{
+        providers::StaticFileWriter, DatabaseProviderFactory, StaticFileProviderFactory,
+    };
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_static_file_types::StaticFileSegment;
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn prune_only_copied_blocks() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=99,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+
+        // Copy the changesets of the first 50 blocks to static files.
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for (block_number, changeset) in changesets.iter().enumerate().take(50) {
+            let changes = changeset
+                .iter()
+                .map(|(address, account, _)| AccountBeforeTx {
+                    address: *address,
+                    info: Some(*account),
+                })
+                .collect();
+            writer
+                .append_account_changeset(
+                    &StaticFileAccountChangeSet { changes },
+                    block_number as BlockNumber,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let copied = changesets.iter().take(50).flatten().count();
+        let kept = changesets.iter().skip(50).flatten().count();
+
+        // Only the blocks that are in static files can be pruned from the database.
+        let segment: &dyn Segment<_> =
+            &super::AccountChangeSets::new(db.factory.static_file_provider());
+        assert_eq!(segment.mode(), Some(PruneMode::before_inclusive(49)));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 49, limiter: Default::default() };
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+
+        assert_matches!(
+            result,
+            SegmentOutput { progress: PruneProgress::Finished, pruned, checkpoint: Some(checkpoint) }
+                if pruned == copied &&
+                    checkpoint.block_number == Some(49)
+        );
+        // Changesets that weren't copied to static files stay in the database.
+        let rows = db.table::<tables::AccountChangeSets>().unwrap();
+        assert_eq!(rows.len(), kept);
+        assert!(rows.iter().all(|(block_number, _)| *block_number >= 50));
+    }
+}
@@ -6,10 +6,11 @@ use crate::{
 use itertools::Itertools;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::ShardedKey;
-use reth_provider::DBProvider;
+use reth_provider::{ChangeSetReader, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -32,7 +33,7 @@ impl AccountHistory {
 
 impl<Provider> Segment<Provider> for AccountHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: StaticFileProviderFactory + DBProvider<Tx: DbTxMut>,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::AccountHistory
@@ -69,7 +70,6 @@ where
             ))
         }
 
-        let mut last_changeset_pruned_block = None;
         // Deleted account changeset keys (account addresses) with the highest block number deleted
         // for that key.
         //
@@ -79,22 +79,59 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_accounts = FxHashMap::default();
-        let (pruned_changesets, done) =
-            provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
-                range,
-                &mut limiter,
-                |_| false,
-                |(block_number, account)| {
+
+        // Changesets that were already moved to static files can't be deleted from there, but
+        // they still hold the keys of the history indices that have to be pruned. This happens
+        // when the account history pruning is enabled after the changesets were copied.
+        let static_file_range = provider
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .map(|block_number| *range.start()..=block_number.min(range_end))
+            .filter(|range| !range.is_empty());
+        let mut last_static_file_block = None;
+        let mut done = true;
+        if let Some(static_file_range) = static_file_range.clone() {
+            let static_file_provider = provider.static_file_provider();
+            for block_number in static_file_range {
+                if limiter.is_limit_reached() {
+                    done = false;
+                    break
+                }
+
+                let changeset = static_file_provider.account_block_changeset(block_number)?;
+                limiter.increment_deleted_entries_count_by(changeset.len());
+                for account in changeset {
                     highest_deleted_accounts.insert(account.address, block_number);
-                    last_changeset_pruned_block = Some(block_number);
-                },
-            )?;
+                }
+                last_static_file_block = Some(block_number);
+            }
+        }
+        trace!(target: "pruner", ?last_static_file_block, %done, "Read account history (static file changesets)");
+
+        let mut last_changeset_pruned_block = None;
+        let mut pruned_changesets = 0;
+        let database_range_start =
+            static_file_range.map_or(*range.start(), |range| *range.end() + 1);
+        if done && database_range_start <= range_end {
+            (pruned_changesets, done) =
+                provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
+                    database_range_start..=range_end,
+                    &mut limiter,
+                    |_| false,
+                    |(block_number, account)| {
+                        highest_deleted_accounts.insert(account.address, block_number);
+                        last_changeset_pruned_block = Some(block_number);
+                    },
+                )?;
+        }
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
 
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more account changesets to prune, set the checkpoint block number to
             // previous, so we could finish pruning its account changesets on the next run.
             .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            // Static file changesets are read a whole block at a time.
+            .or_else(|| last_static_file_block.filter(|_| !done))
             .unwrap_or(range_end);
 
         // Sort highest deleted block numbers by account address and turn them into sharded keys.
@@ -133,12 +170,21 @@ mod tests {
     };
     use alloy_primitives::{BlockNumber, B256};
     use assert_matches::assert_matches;
-    use reth_db::{tables, BlockNumberList};
-    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_db::{
+        models::{AccountBeforeTx, StaticFileAccountChangeSet},
+        tables,
+        transaction::DbTxMut,
+        BlockNumberList,
+    };
+    use reth_provider::{
+        providers::StaticFileWriter, DatabaseProviderFactory, PruneCheckpointReader,
+        StaticFileProviderFactory,
+    };
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_static_file_types::StaticFileSegment;
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
@@ -304,4 +350,86 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 998));
         test_prune(1400, 3, (PruneProgress::Finished, 804));
     }
+
+    #[test]
+    fn prune_static_file_changesets() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=99,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        // Move the changesets of the first 50 blocks to static files, as the static file
+        // producer and the static file changesets segment would do.
+        let static_file_provider = db.factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for (block_number, changeset) in changesets.iter().enumerate().take(50) {
+            let changes = changeset
+                .iter()
+                .map(|(address, account, _)| AccountBeforeTx {
+                    address: *address,
+                    info: Some(*account),
+                })
+                .collect();
+            writer
+                .append_account_changeset(
+                    &StaticFileAccountChangeSet { changes },
+                    block_number as BlockNumber,
+                )
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        db.commit(|tx| {
+            for block_number in 0..50 {
+                tx.delete::<tables::AccountChangeSets>(block_number, None)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 79;
+        let input = PruneInput { previous_checkpoint: None, to_block, limiter: Default::default() };
+        let segment = AccountHistory::new(PruneMode::Before(to_block + 1));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+
+        assert_matches!(
+            result,
+            SegmentOutput {
+                progress: PruneProgress::Finished,
+                checkpoint: Some(checkpoint),
+                ..
+            } if checkpoint.block_number == Some(to_block)
+        );
+
+        // Only the database changesets above the pruned range are left.
+        assert_eq!(
+            db.table::<tables::AccountChangeSets>().unwrap().len(),
+            changesets.iter().skip(to_block as usize + 1).flatten().count()
+        );
+        // Indices of the blocks read from both static files and the database are pruned.
+        let shards = db.table::<tables::AccountsHistory>().unwrap();
+        assert!(!shards.is_empty());
+        assert!(shards.iter().all(|(_, blocks)| blocks.iter().all(|block| block > to_block)));
+    }
 }
@@ -0,0 +1,199 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use alloy_consensus::TxReceipt;
+use itertools::Itertools;
+use reth_db::{tables, transaction::DbTxMut};
+use reth_db_api::{models::ShardedKey, transaction::DbTx};
+use reth_provider::{DBProvider, ReceiptProvider};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashMap;
+use tracing::{instrument, trace};
+
+/// Number of log index tables to prune in one step.
+///
+/// Log Index consists of two tables: [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+/// We want to prune them to the same block number.
+const LOG_INDEX_TABLES_TO_PRUNE: usize = 2;
+
+/// Prunes the log index according to the receipts prune mode.
+///
+/// The pruned keys are read from the receipts of the pruned blocks, so the segment has to run
+/// before the receipts are pruned.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for LogIndex
+where
+    Provider: DBProvider<Tx: DbTxMut> + ReceiptProvider,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        // The log index is optional, don't read the receipts if it has never been built.
+        if provider.tx_ref().entries::<tables::LogAddressIndex>()? == 0 &&
+            provider.tx_ref().entries::<tables::LogTopicIndex>()? == 0
+        {
+            trace!(target: "pruner", "Log index is empty");
+            return Ok(SegmentOutput {
+                progress: input.limiter.progress(true),
+                pruned: 0,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(range_end),
+                    tx_number: None,
+                }),
+            })
+        }
+
+        let mut limiter = if let Some(limit) = input.limiter.deleted_entries_limit() {
+            input.limiter.set_deleted_entries_limit(limit / LOG_INDEX_TABLES_TO_PRUNE)
+        } else {
+            input.limiter
+        };
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                limiter.interrupt_reason(),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        // Addresses and topics of the logs in the pruned blocks with the highest block number
+        // they were seen in. Every walked log counts as a deleted entry for the limiter.
+        let mut highest_addresses = FxHashMap::default();
+        let mut highest_topics = FxHashMap::default();
+        let mut last_pruned_block = None;
+        let mut done = true;
+        for block_number in range {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            let receipts = provider.receipts_by_block(block_number.into())?.unwrap_or_default();
+            let mut walked_logs = 0;
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                highest_addresses.insert(log.address, block_number);
+                for topic in log.topics() {
+                    highest_topics.insert(*topic, block_number);
+                }
+                walked_logs += 1;
+            }
+            limiter.increment_deleted_entries_count_by(walked_logs);
+            last_pruned_block = Some(block_number);
+        }
+        let last_pruned_block = last_pruned_block.unwrap_or(range_end);
+
+        // Sort highest block numbers by key and turn them into sharded keys.
+        // We did not use `BTreeMap` from the beginning, because it's inefficient for hashes.
+        let address_outcomes = prune_history_indices::<Provider, tables::LogAddressIndex, _>(
+            provider,
+            highest_addresses
+                .into_iter()
+                .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+                .map(|(address, block_number)| ShardedKey::new(address, block_number)),
+            |a, b| a.key == b.key,
+        )?;
+        let topic_outcomes = prune_history_indices::<Provider, tables::LogTopicIndex, _>(
+            provider,
+            highest_topics
+                .into_iter()
+                .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+                .map(|(topic, block_number)| ShardedKey::new(topic, block_number)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?address_outcomes, ?topic_outcomes, %done, "Pruned log index");
+
+        let progress = limiter.progress(done);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: address_outcomes.deleted + topic_outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, PruneLimiter, Segment};
+    use reth_db::{tables, transaction::DbTxMut, BlockNumberList};
+    use reth_db_api::models::ShardedKey;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_prune_types::{PruneMode, PruneProgress};
+    use reth_stages::test_utils::{TestStageDB, TEST_LOG_ADDRESS, TEST_LOG_TOPIC};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        db.insert_blocks_with_logs(5).expect("insert blocks");
+
+        db.commit(|tx| {
+            tx.put::<tables::LogAddressIndex>(
+                ShardedKey::last(TEST_LOG_ADDRESS),
+                BlockNumberList::new(0..=5).unwrap(),
+            )?;
+            tx.put::<tables::LogTopicIndex>(
+                ShardedKey::last(TEST_LOG_TOPIC),
+                BlockNumberList::new(0..=5).unwrap(),
+            )?;
+            Ok(())
+        })
+        .expect("insert log index");
+
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 3,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(2000),
+        };
+        let segment = LogIndex::new(PruneMode::Before(4));
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert_eq!(result.checkpoint.and_then(|checkpoint| checkpoint.block_number), Some(3));
+        // The shards are only updated, not deleted
+        assert_eq!(result.pruned, 0);
+
+        let address_index = db.table::<tables::LogAddressIndex>().unwrap();
+        assert_eq!(address_index.len(), 1);
+        assert_eq!(address_index[0].1.iter().collect::<Vec<_>>(), vec![4, 5]);
+        let topic_index = db.table::<tables::LogTopicIndex>().unwrap();
+        assert_eq!(topic_index.len(), 1);
+        assert_eq!(topic_index[0].1.iter().collect::<Vec<_>>(), vec![4, 5]);
+    }
+}
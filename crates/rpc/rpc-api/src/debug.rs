@@ -2,11 +2,126 @@ use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{transaction::TransactionRequest, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A block that was rejected as invalid, as returned by `debug_getBadBlocks`.
+///
+/// See also <https://github.com/ethereum/go-ethereum/blob/v1.14.13/eth/api_debug.go#L101-L106>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock {
+    /// Hash of the block.
+    pub hash: B256,
+    /// The block object, including full transaction objects.
+    pub block: serde_json::Value,
+    /// The RLP encoded block.
+    pub rlp: Bytes,
+}
+
+/// A page of storage slots, as returned by `debug_storageRangeAt`.
+///
+/// See also <https://github.com/ethereum/go-ethereum/blob/v1.14.13/eth/api_debug.go#L208-L217>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of the page, keyed by hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed slot of the first slot after this page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot of a [`StorageRangeResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The preimage of the hashed slot, if known.
+    pub key: Option<B256>,
+    /// Value of the slot.
+    pub value: B256,
+}
+
+/// A page of accounts, as returned by `debug_accountRange`.
+///
+/// See also <https://github.com/ethereum/go-ethereum/blob/v1.14.13/core/state/dump.go#L58-L64>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// State root of the dumped state, hex encoded without `0x` prefix.
+    pub root: String,
+    /// Accounts of the page, keyed by checksummed address, or by `pre(<hashed address>)` if the
+    /// address is not known.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// Hashed address of the first account after this page, if any.
+    ///
+    /// This is base64 encoded, like geth encodes raw bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64_bytes")]
+    pub next: Option<Bytes>,
+}
+
+/// An account of a [`StateDump`].
+///
+/// See also <https://github.com/ethereum/go-ethereum/blob/v1.14.13/core/state/dump.go#L45-L56>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Balance of the account in decimal.
+    pub balance: String,
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Storage root of the account.
+    pub root: B256,
+    /// Hash of the account's code.
+    pub code_hash: B256,
+    /// Code of the account, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Storage of the account keyed by hashed slot, with values hex encoded without `0x` prefix
+    /// and leading zeros, unless omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, String>>,
+    /// Address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Hashed address of the account.
+    #[serde(rename = "key")]
+    pub address_hash: B256,
+}
+
+/// (De)serializes optional bytes as a base64 string.
+mod base64_bytes {
+    use alloy_primitives::Bytes;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_str(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|encoded| STANDARD.decode(encoded).map(Into::into).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Subscription kinds of the `debug_subscribe` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Streams the traces of a range of blocks, block by block.
+    TraceChain,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -36,17 +151,38 @@ pub trait DebugApi {
 
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
+    ///
+    /// The number of blocks that can be traced with a single call is limited, larger ranges
+    /// should use the `traceChain` subscription instead.
     #[method(name = "traceChain")]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>>;
 
+    /// Creates a `debug` subscription for the given kind.
+    ///
+    /// The `traceChain` subscription traces all blocks between two blocks (excluding start) and
+    /// emits the result of each block as soon as it has been traced, in order.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = BlockTraceResult
+    )]
+    async fn debug_subscribe(
+        &self,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
     ///
@@ -174,7 +310,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StateDump>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -249,17 +385,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -272,7 +409,7 @@ pub trait DebugApi {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -369,7 +506,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
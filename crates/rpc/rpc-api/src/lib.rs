@@ -31,6 +31,10 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{
+    BadBlock, DebugSubscriptionKind, DumpAccount, StateDump, StorageRangeEntry, StorageRangeResult,
+};
+
 /// re-export of all server traits
 pub use servers::*;
 
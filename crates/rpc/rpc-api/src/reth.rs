@@ -1,5 +1,5 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use std::collections::HashMap;
 
@@ -13,4 +13,13 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Sends a signed transaction like `eth_sendRawTransaction`, but evicts it from the pool if
+    /// it was not included before the given deadline, a unix timestamp in seconds.
+    #[method(name = "sendRawTransactionWithDeadline")]
+    async fn reth_send_raw_transaction_with_deadline(
+        &self,
+        bytes: Bytes,
+        deadline: u64,
+    ) -> RpcResult<B256>;
 }
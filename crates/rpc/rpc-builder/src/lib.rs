@@ -22,7 +22,10 @@
 //! use reth_evm_ethereum::EthEvmConfig;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_primitives::{Header, PooledTransaction, TransactionSigned};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, BadBlockProvider, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     HashedStateRangeReader, HistoryReader, StorageChangeSetReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -46,6 +49,10 @@
 //!             Header = reth_primitives::Header,
 //!         > + AccountReader
 //!         + ChangeSetReader
+//!         + BadBlockProvider<Block = reth_primitives::Block>
+//!         + HistoryReader
+//!         + HashedStateRangeReader
+//!         + StorageChangeSetReader
 //!         + CanonStateSubscriptions<Primitives = reth_primitives::EthPrimitives>,
 //!     Pool: TransactionPool<
 //!             Transaction: PoolTransaction<
@@ -92,7 +99,10 @@
 //! use reth_evm_ethereum::EthEvmConfig;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_primitives::{Header, PooledTransaction, TransactionSigned};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, BadBlockProvider, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider,
+//!     HashedStateRangeReader, HistoryReader, StorageChangeSetReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
 //! use reth_rpc_builder::{
@@ -121,6 +131,10 @@
 //!             Header = reth_primitives::Header,
 //!         > + AccountReader
 //!         + ChangeSetReader
+//!         + BadBlockProvider<Block = reth_primitives::Block>
+//!         + HistoryReader
+//!         + HashedStateRangeReader
+//!         + StorageChangeSetReader
 //!         + CanonStateSubscriptions<Primitives = reth_primitives::EthPrimitives>,
 //!     Pool: TransactionPool<
 //!             Transaction: PoolTransaction<
@@ -194,12 +208,14 @@ use jsonrpsee::{
 };
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::{ConsensusError, FullConsensus};
+use reth_engine_primitives::ChainHeadSetter;
 use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives::NodePrimitives;
 use reth_provider::{
-    AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    FullRpcProvider, ProviderBlock, ProviderHeader, ProviderReceipt, StateProviderFactory,
+    AccountReader, BadBlockProvider, BlockReader, CanonStateSubscriptions, ChainSpecProvider,
+    ChangeSetReader, FullRpcProvider, HashedStateRangeReader, HistoryReader, ProviderBlock,
+    ProviderHeader, ProviderReceipt, StateProviderFactory, StorageChangeSetReader,
 };
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthBundle, MinerApi, NetApi, OtterscanApi, RPCApi, RethApi,
@@ -208,7 +224,8 @@ use reth_rpc::{
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    EthApiServer, EthApiTypes, FullEthApiServer, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt,
+    RpcTransaction,
 };
 use reth_rpc_eth_types::{EthConfig, EthStateCache, EthSubscriptionIdProvider};
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
@@ -287,6 +304,11 @@ where
             Header = <BlockExecutor::Primitives as NodePrimitives>::BlockHeader,
         > + CanonStateSubscriptions<Primitives = BlockExecutor::Primitives>,
     >,
+    EthApi::Provider: BadBlockProvider<Block = <BlockExecutor::Primitives as NodePrimitives>::Block>
+        + HistoryReader
+        + HashedStateRangeReader
+        + ChangeSetReader
+        + StorageChangeSetReader,
     BlockExecutor: BlockExecutorProvider,
 {
     let module_config = module_config.into();
@@ -326,6 +348,8 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, EvmConfig, BlockExec
     block_executor: BlockExecutor,
     /// The consensus implementation.
     consensus: Consensus,
+    /// Handle to rewind the canonical chain, used by `debug_setHead`.
+    head_setter: Option<Arc<dyn ChainHeadSetter>>,
 }
 
 // === impl RpcBuilder ===
@@ -344,7 +368,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
         block_executor: BlockExecutor,
         consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, evm_config, block_executor, consensus }
+        Self {
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter: None,
+        }
     }
 
     /// Configure the provider instance.
@@ -355,7 +388,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         P: BlockReader + StateProviderFactory + 'static,
     {
-        let Self { pool, network, executor, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -364,6 +406,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -375,7 +418,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            provider,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -384,6 +436,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -403,7 +456,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
         BlockExecutor,
         Consensus,
     > {
-        let Self { provider, executor, network, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            provider,
+            executor,
+            network,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             executor,
@@ -412,6 +474,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             block_executor,
             pool: NoopTransactionPool::default(),
             consensus,
+            head_setter,
         }
     }
 
@@ -423,7 +486,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -432,6 +504,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -444,7 +517,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
         self,
     ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, EvmConfig, BlockExecutor, Consensus>
     {
-        let Self { provider, pool, executor, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            provider,
+            pool,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -453,6 +535,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -464,7 +547,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            pool,
+            network,
+            provider,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -473,6 +565,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -491,7 +584,16 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
         BlockExecutor,
         Consensus,
     > {
-        let Self { pool, network, provider, evm_config, block_executor, consensus, .. } = self;
+        let Self {
+            pool,
+            network,
+            provider,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+            ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -500,6 +602,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -511,7 +614,9 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, block_executor, consensus, .. } = self;
+        let Self {
+            provider, pool, executor, network, block_executor, consensus, head_setter, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -520,6 +625,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -531,7 +637,8 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
     where
         BE: BlockExecutorProvider,
     {
-        let Self { provider, network, pool, executor, evm_config, consensus, .. } = self;
+        let Self { provider, network, pool, executor, evm_config, consensus, head_setter, .. } =
+            self;
         RpcModuleBuilder {
             provider,
             network,
@@ -540,6 +647,7 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
 
@@ -548,7 +656,9 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
         self,
         consensus: C,
     ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, C> {
-        let Self { provider, network, pool, executor, evm_config, block_executor, .. } = self;
+        let Self {
+            provider, network, pool, executor, evm_config, block_executor, head_setter, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -557,8 +667,17 @@ impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
             evm_config,
             block_executor,
             consensus,
+            head_setter,
         }
     }
+
+    /// Configure the handle that is used to rewind the canonical chain in `debug_setHead`.
+    ///
+    /// Without it `debug_setHead` is not supported.
+    pub fn with_head_setter(mut self, head_setter: Arc<dyn ChainHeadSetter>) -> Self {
+        self.head_setter = Some(head_setter);
+        self
+    }
 }
 
 impl<Provider, Pool, Network, Tasks, EvmConfig, BlockExecutor, Consensus>
@@ -606,9 +725,22 @@ where
                 Header = <BlockExecutor::Primitives as NodePrimitives>::BlockHeader,
             > + CanonStateSubscriptions<Primitives = BlockExecutor::Primitives>,
         >,
+        EthApi::Provider: BadBlockProvider<Block = <BlockExecutor::Primitives as NodePrimitives>::Block>
+            + HistoryReader
+            + HashedStateRangeReader
+            + ChangeSetReader
+            + StorageChangeSetReader,
     {
-        let Self { provider, pool, network, executor, evm_config, block_executor, consensus } =
-            self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+        } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
@@ -623,6 +755,7 @@ where
             eth,
             block_executor,
         );
+        registry.head_setter = head_setter;
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -677,9 +810,17 @@ where
     where
         EthApi: EthApiTypes + 'static,
     {
-        let Self { provider, pool, network, executor, evm_config, block_executor, consensus } =
-            self;
-        RpcRegistryInner::new(
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+        } = self;
+        let mut registry = RpcRegistryInner::new(
             provider,
             pool,
             network,
@@ -689,7 +830,9 @@ where
             evm_config,
             eth,
             block_executor,
-        )
+        );
+        registry.head_setter = head_setter;
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -707,12 +850,25 @@ where
                 Header = <BlockExecutor::Primitives as NodePrimitives>::BlockHeader,
             > + CanonStateSubscriptions<Primitives = BlockExecutor::Primitives>,
         >,
+        EthApi::Provider: BadBlockProvider<Block = <BlockExecutor::Primitives as NodePrimitives>::Block>
+            + HistoryReader
+            + HashedStateRangeReader
+            + ChangeSetReader
+            + StorageChangeSetReader,
         Pool: TransactionPool<Transaction = <EthApi::Pool as TransactionPool>::Transaction>,
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, evm_config, block_executor, consensus } =
-            self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            evm_config,
+            block_executor,
+            consensus,
+            head_setter,
+        } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -728,6 +884,7 @@ where
                 eth,
                 block_executor,
             );
+            registry.head_setter = head_setter;
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -846,6 +1003,8 @@ pub struct RpcRegistryInner<
     blocking_pool_guard: BlockingTaskGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// Handle to rewind the canonical chain, used by `debug_setHead`.
+    head_setter: Option<Arc<dyn ChainHeadSetter>>,
 }
 
 // === impl RpcRegistryInner ===
@@ -904,6 +1063,7 @@ where
             modules: Default::default(),
             blocking_pool_guard,
             block_executor,
+            head_setter: None,
         }
     }
 }
@@ -1034,6 +1194,7 @@ where
     pub fn register_ots(&mut self) -> &mut Self
     where
         EthApi: TraceExt + EthTransactions,
+        EthApi::Provider: HistoryReader,
     {
         let otterscan_api = self.otterscan_api();
         self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
@@ -1047,7 +1208,11 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_debug(&mut self) -> &mut Self
     where
-        EthApi: EthApiSpec + EthTransactions + TraceExt,
+        EthApi: EthApiSpec + EthTransactions + TraceExt + FullEthApiTypes,
+        EthApi::Provider: BadBlockProvider<Block = ProviderBlock<EthApi::Provider>>
+            + HashedStateRangeReader
+            + ChangeSetReader
+            + StorageChangeSetReader,
         BlockExecutor::Primitives: NodePrimitives<Block = ProviderBlock<EthApi::Provider>>,
     {
         let debug_api = self.debug_api();
@@ -1092,7 +1257,10 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        EthApi: EthTransactions + 'static,
+    {
         let rethapi = self.reth_api();
         self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
         self
@@ -1157,6 +1325,7 @@ where
             self.eth_api().clone(),
             self.blocking_pool_guard.clone(),
             self.block_executor.clone(),
+            self.head_setter.clone(),
         )
     }
 
@@ -1174,8 +1343,12 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn reth_api(&self) -> RethApi<Provider, EthApi> {
+        RethApi::new(self.provider.clone(), self.eth_api().clone(), Box::new(self.executor.clone()))
     }
 }
 
@@ -1196,6 +1369,11 @@ where
             Header = <BlockExecutor::Primitives as NodePrimitives>::BlockHeader,
         > + CanonStateSubscriptions<Primitives = BlockExecutor::Primitives>,
     >,
+    EthApi::Provider: BadBlockProvider<Block = <BlockExecutor::Primitives as NodePrimitives>::Block>
+        + HistoryReader
+        + HashedStateRangeReader
+        + ChangeSetReader
+        + StorageChangeSetReader,
     BlockExecutor: BlockExecutorProvider,
     Consensus: FullConsensus<BlockExecutor::Primitives, Error = ConsensusError> + Clone + 'static,
 {
@@ -1284,6 +1462,7 @@ where
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                             self.block_executor.clone(),
+                            self.head_setter.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -1328,11 +1507,13 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            eth_api.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
                         // TODO: can we get rid of this here?
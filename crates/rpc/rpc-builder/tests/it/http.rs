@@ -365,7 +365,33 @@ where
     DebugApiClient::raw_block(client, block_id).await.unwrap_err();
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    assert!(DebugApiClient::bad_blocks(client).await.unwrap().is_empty());
+    DebugApiClient::debug_storage_range_at(
+        client,
+        B256::default(),
+        0,
+        Address::ZERO,
+        B256::ZERO,
+        1,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::debug_intermediate_roots(client, B256::default(), None).await.unwrap_err();
+    DebugApiClient::debug_get_modified_accounts_by_number(client, 1, None).await.unwrap_err();
+    DebugApiClient::debug_get_modified_accounts_by_number(client, 2, Some(1)).await.unwrap_err();
+    DebugApiClient::debug_account_range(
+        client,
+        BlockNumberOrTag::Latest,
+        Bytes::default(),
+        1,
+        false,
+        false,
+        false,
+    )
+    .await
+    .unwrap_err();
+    // no engine is configured
+    DebugApiClient::debug_set_head(client, 0).await.unwrap_err();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -466,28 +492,28 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    let before = OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(before.txs.is_empty() && before.receipts.is_empty());
+    assert!(!before.first_page && before.last_page);
+
+    let after = OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(after.txs.is_empty() && after.receipts.is_empty());
+    assert!(after.first_page && !after.last_page);
+
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -3,13 +3,14 @@
 use super::{Call, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction};
 use crate::FromEvmError;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::B256;
+use alloy_primitives::{map::HashMap, Address, B256, U256};
 use alloy_rpc_types_eth::{BlockId, TransactionInfo};
 use futures::Future;
 use reth_chainspec::ChainSpecProvider;
 use reth_errors::ProviderError;
 use reth_evm::{
-    env::EvmEnv, system_calls::SystemCaller, ConfigureEvm, ConfigureEvmEnv, Database, Evm,
+    env::EvmEnv, state_change::post_block_balance_increments, system_calls::SystemCaller,
+    ConfigureEvm, ConfigureEvmEnv, Database, Evm,
 };
 use reth_primitives::RecoveredBlock;
 use reth_primitives_traits::{BlockBody, SignedTransaction};
@@ -21,7 +22,7 @@ use reth_rpc_eth_types::{
 };
 use revm::{db::CacheDB, DatabaseCommit, GetInspector, Inspector};
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
-use revm_primitives::{EvmState, ExecutionResult, ResultAndState};
+use revm_primitives::{Account, AccountStatus, EvmState, ExecutionResult, ResultAndState};
 use std::{fmt::Display, sync::Arc};
 
 /// Executes CPU heavy tasks.
@@ -469,4 +470,50 @@ pub trait Trace:
 
         Ok(())
     }
+
+    /// Applies chain-specific state transitions required after executing all transactions of a
+    /// block, like block rewards and withdrawals.
+    ///
+    /// Note: This is only needed when the state of the block is carried forward to trace the next
+    /// block, because tracing a block never observes these transitions.
+    fn apply_post_execution_changes<DB: Send + Database<Error: Display> + DatabaseCommit>(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Self::Provider>>,
+        db: &mut DB,
+        _evm_env: &EvmEnv<<Self::Evm as ConfigureEvmEnv>::Spec>,
+    ) -> Result<(), Self::Error> {
+        let balance_increments =
+            post_block_balance_increments(&self.provider().chain_spec(), block);
+        increment_balances(db, balance_increments)?;
+
+        Ok(())
+    }
+}
+
+/// Increments the balances of the given accounts and commits the changes to the database.
+pub fn increment_balances<DB>(
+    db: &mut DB,
+    balance_increments: HashMap<Address, u128>,
+) -> Result<(), EthApiError>
+where
+    DB: Database<Error: Display> + DatabaseCommit,
+{
+    let mut state = EvmState::default();
+    for (address, increment) in balance_increments {
+        if increment == 0 {
+            continue
+        }
+        let mut info = db
+            .basic(address)
+            .map_err(|err| EthApiError::EvmCustom(err.to_string()))?
+            .unwrap_or_default();
+        info.balance = info.balance.saturating_add(U256::from(increment));
+        state.insert(
+            address,
+            Account { info, storage: Default::default(), status: AccountStatus::Touched },
+        );
+    }
+    db.commit(state);
+
+    Ok(())
 }
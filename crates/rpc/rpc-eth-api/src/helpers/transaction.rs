@@ -23,7 +23,7 @@ use reth_provider::{
 use reth_rpc_eth_types::{utils::binary_search, EthApiError, SignError, TransactionSource};
 use reth_rpc_types_compat::transaction::TransactionCompat;
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 /// Transaction related functions for the [`EthApiServer`](crate::EthApiServer) trait in
 /// the `eth_` namespace.
@@ -63,6 +63,18 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         tx: Bytes,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
 
+    /// Decodes and recovers the transaction and submits it to the pool, like
+    /// [`EthTransactions::send_raw_transaction`].
+    ///
+    /// The transaction is evicted if it is still in the pool once the deadline has passed.
+    ///
+    /// Returns the hash of the transaction.
+    fn send_raw_transaction_with_deadline(
+        &self,
+        tx: Bytes,
+        deadline: Instant,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
+
     /// Returns the transaction by hash.
     ///
     /// Checks the pool and state.
@@ -1,13 +1,18 @@
 //! `eth_` RPC API for pubsub subscription.
 
 use alloy_json_rpc::RpcObject;
-use alloy_rpc_types_eth::pubsub::{Params, SubscriptionKind};
+use alloy_rpc_types_eth::pubsub::SubscriptionKind;
 use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::PubSubParams;
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi<T: RpcObject> {
     /// Create an ethereum subscription for the given params
+    ///
+    /// `newPendingTransactions` subscriptions accept a
+    /// [`PendingTransactionFilter`](reth_rpc_eth_types::PendingTransactionFilter) that is applied
+    /// on the server.
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -16,6 +21,6 @@ pub trait EthPubSubApi<T: RpcObject> {
     async fn subscribe(
         &self,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<PubSubParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
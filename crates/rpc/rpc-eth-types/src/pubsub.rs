@@ -0,0 +1,182 @@
+//! Helper types for `eth_subscribe` params.
+
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, Selector, U256, U8};
+use alloy_rpc_types_eth::Filter;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Params of an `eth_subscribe` call.
+///
+/// Extends the standard [`Params`](alloy_rpc_types_eth::pubsub::Params) with a
+/// [`PendingTransactionFilter`] for `newPendingTransactions` subscriptions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PubSubParams {
+    /// Log filter for `logs` subscriptions.
+    Logs(Box<Filter>),
+    /// Whether full transactions are requested for `newPendingTransactions` subscriptions.
+    Bool(bool),
+    /// Server side filter for `newPendingTransactions` subscriptions.
+    PendingTransactions(Box<PendingTransactionFilter>),
+}
+
+impl<'de> Deserialize<'de> for PubSubParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // The pending transaction filter rejects unknown fields, so it doesn't match log filters.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawParams {
+            Bool(bool),
+            PendingTransactions(Box<PendingTransactionFilter>),
+            Logs(Box<Filter>),
+        }
+
+        Ok(
+            match RawParams::deserialize(deserializer).map_err(|_| {
+                serde::de::Error::custom(
+                    "expected a bool, a log filter or a pending transaction filter",
+                )
+            })? {
+                RawParams::Bool(full) => Self::Bool(full),
+                RawParams::PendingTransactions(filter) => Self::PendingTransactions(filter),
+                RawParams::Logs(filter) => Self::Logs(filter),
+            },
+        )
+    }
+}
+
+/// Server side filter for `newPendingTransactions` subscriptions.
+///
+/// A transaction matches if it matches all given criteria. Criteria that are lists match if any
+/// of their entries matches, empty lists match all transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionFilter {
+    /// Senders of the transaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from: Vec<Address>,
+    /// Recipients of the transaction. Contract creations never match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<Address>,
+    /// 4-byte method selectors the calldata of the transaction starts with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selector: Vec<Selector>,
+    /// Minimum value transferred by the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_value: Option<U256>,
+    /// EIP-2718 types of the transaction.
+    #[serde(default, rename = "type", skip_serializing_if = "Vec::is_empty")]
+    pub tx_type: Vec<U8>,
+    /// Whether full transactions are sent instead of hashes.
+    #[serde(default)]
+    pub full_transactions: bool,
+}
+
+impl PendingTransactionFilter {
+    /// Returns `true` if the filter has no criteria and matches all transactions.
+    pub fn is_empty(&self) -> bool {
+        self.from.is_empty() &&
+            self.to.is_empty() &&
+            self.selector.is_empty() &&
+            self.min_value.is_none() &&
+            self.tx_type.is_empty()
+    }
+
+    /// Returns `true` if the transaction sent by the given sender matches the filter.
+    pub fn matches<T: Transaction>(&self, sender: Address, transaction: &T) -> bool {
+        if !self.from.is_empty() && !self.from.contains(&sender) {
+            return false
+        }
+
+        if !self.to.is_empty() && !transaction.to().is_some_and(|to| self.to.contains(&to)) {
+            return false
+        }
+
+        if !self.selector.is_empty() &&
+            !transaction
+                .input()
+                .get(..4)
+                .is_some_and(|selector| self.selector.iter().any(|s| s.as_slice() == selector))
+        {
+            return false
+        }
+
+        if self.min_value.is_some_and(|min_value| transaction.value() < min_value) {
+            return false
+        }
+
+        self.tx_type.is_empty() || self.tx_type.contains(&U8::from(transaction.ty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxEip1559;
+    use alloy_primitives::{address, bytes, TxKind};
+
+    #[test]
+    fn deserialize_params() {
+        let params: PubSubParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, PubSubParams::Bool(true));
+
+        let params: PubSubParams =
+            serde_json::from_str(r#"{"address":"0x0000000000000000000000000000000000000001"}"#)
+                .unwrap();
+        assert!(matches!(params, PubSubParams::Logs(_)));
+
+        let params: PubSubParams = serde_json::from_str(
+            r#"{"from":["0x0000000000000000000000000000000000000001"],"selector":["0xa9059cbb"],"minValue":"0x10","type":["0x2"],"fullTransactions":true}"#,
+        )
+        .unwrap();
+        let PubSubParams::PendingTransactions(filter) = params else {
+            panic!("expected pending transaction filter, got {params:?}")
+        };
+        assert_eq!(filter.from, vec![address!("0000000000000000000000000000000000000001")]);
+        assert_eq!(filter.selector, vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])]);
+        assert_eq!(filter.min_value, Some(U256::from(16)));
+        assert_eq!(filter.tx_type, vec![U8::from(2)]);
+        assert!(filter.full_transactions);
+
+        assert!(serde_json::from_str::<PubSubParams>(r#""latest""#).is_err());
+    }
+
+    #[test]
+    fn matches_pending_transactions() {
+        let sender = address!("0000000000000000000000000000000000000001");
+        let recipient = address!("0000000000000000000000000000000000000002");
+        let tx = TxEip1559 {
+            to: TxKind::Call(recipient),
+            value: U256::from(100),
+            input: bytes!("a9059cbb0000"),
+            ..Default::default()
+        };
+
+        assert!(PendingTransactionFilter::default().matches(sender, &tx));
+
+        let filter = PendingTransactionFilter {
+            from: vec![sender],
+            to: vec![recipient],
+            selector: vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])],
+            min_value: Some(U256::from(100)),
+            tx_type: vec![U8::from(2)],
+            ..Default::default()
+        };
+        assert!(filter.matches(sender, &tx));
+        assert!(!filter.matches(recipient, &tx));
+
+        let filter = PendingTransactionFilter { min_value: Some(U256::from(101)), ..filter };
+        assert!(!filter.matches(sender, &tx));
+
+        let filter =
+            PendingTransactionFilter { selector: vec![Selector::ZERO], ..Default::default() };
+        assert!(!filter.matches(sender, &tx));
+
+        // contract creations don't match recipients
+        let creation = TxEip1559 { to: TxKind::Create, ..tx };
+        let filter = PendingTransactionFilter { to: vec![recipient], ..Default::default() };
+        assert!(!filter.matches(sender, &creation));
+    }
+}
@@ -1,12 +1,16 @@
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{
+    hex, keccak256,
+    map::{B256Map, B256Set},
+    Address, Bytes, B256, U256,
+};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, BlockTransactionsKind,
+    Bundle, StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -14,37 +18,86 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{Future, Stream, StreamExt, TryStreamExt};
+use jsonrpsee::{
+    core::RpcResult, server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink,
+};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::{BeaconSetHeadError, ChainHeadSetter};
+use reth_errors::RethError;
 use reth_evm::{
     env::EvmEnv,
     execute::{BlockExecutorProvider, Executor},
-    ConfigureEvmEnv, TransactionEnv,
+    ConfigureEvm, ConfigureEvmEnv, Evm, TransactionEnv,
 };
 use reth_primitives::{NodePrimitives, ReceiptWithBloom, RecoveredBlock};
 use reth_primitives_traits::{Block as _, BlockBody, SignedTransaction};
 use reth_provider::{
-    BlockIdReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, ProviderBlock,
-    ReceiptProviderIdExt, StateProofProvider, TransactionVariant,
+    BadBlockProvider, BlockIdReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, HashedStateRangeReader, HeaderProvider, ProviderBlock, ProviderError,
+    ReceiptProviderIdExt, StateProofProvider, StateProviderBox, StateProviderFactory,
+    StorageChangeSetReader, TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    BadBlock, DebugApiServer, DebugSubscriptionKind, DumpAccount, StateDump, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    EthApiTypes, FromEthApiError, FromEvmError, FullEthApiTypes, RpcNodeCore,
+};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::{
+    constants::DEFAULT_MAX_BLOCKS_PER_FILTER, result::internal_rpc_err, ToRpcResult,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner};
+use reth_trie::TrieInput;
+use reth_trie_common::{HashedPostState, HashedStorage, MultiProofTargets, Nibbles, TrieNode};
+use reth_trie_sparse::{
+    blinded::{pad_path_to_key, BlindedProvider, BlindedProviderFactory, RevealedNode},
+    errors::{SparseTrieError, SparseTrieErrorKind},
+    SparseStateTrie, SparseTrie,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
-use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
-    db::{CacheDB, State},
-    primitives::db::DatabaseCommit,
+    db::{AccountState, CacheDB, State},
+    primitives::db::{Database, DatabaseCommit, DatabaseRef},
 };
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+/// Maximum number of accounts returned by a single `debug_accountRange` call.
+///
+/// This matches geth's `AccountRangeMaxResults`.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// Maximum number of traced blocks that `debug_traceChain` buffers until they are consumed.
+const TRACE_CHAIN_BUFFER: usize = 4;
+
+/// Number of blocks after which `debug_traceChain` reads the state from the database again instead
+/// of carrying it forward. This bounds the lifetime of the database transaction it reads from.
+const TRACE_CHAIN_STATE_BLOCKS: u64 = 128;
+
+/// Maximum number of accounts and storage slots that `debug_traceChain` keeps in the state it
+/// carries forward. If a block leaves more entries, the state is read from the database again.
+const TRACE_CHAIN_MAX_CACHED_ENTRIES: usize = 250_000;
+
+/// The state that `debug_traceChain` carries forward from block to block.
+type TraceChainDb = CacheDB<StateProviderDatabase<StateProviderBox>>;
+
+/// Maximum number of blocks that can be traced by a single `debug_traceChain` call.
+///
+/// Larger ranges must use the `traceChain` subscription, which emits the traces block by block.
+const TRACE_CHAIN_MAX_BLOCKS: u64 = 128;
 
 /// `debug` API implementation.
 ///
@@ -57,12 +110,20 @@ pub struct DebugApi<Eth, BlockExecutor> {
 
 impl<Eth, BlockExecutor> DebugApi<Eth, BlockExecutor> {
     /// Create a new instance of the [`DebugApi`]
+    ///
+    /// `debug_setHead` is only supported if a `head_setter` is configured.
     pub fn new(
         eth: Eth,
         blocking_task_guard: BlockingTaskGuard,
         block_executor: BlockExecutor,
+        head_setter: Option<Arc<dyn ChainHeadSetter>>,
     ) -> Self {
-        let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, block_executor });
+        let inner = Arc::new(DebugApiInner {
+            eth_api: eth,
+            blocking_task_guard,
+            block_executor,
+            head_setter,
+        });
         Self { inner }
     }
 
@@ -103,46 +164,63 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
-                let mut results = Vec::with_capacity(block.body().transactions().len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
-
-                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
-
-                let mut transactions = block.transactions_with_sender().enumerate().peekable();
-                let mut inspector = None;
-                while let Some((index, (signer, tx))) = transactions.next() {
-                    let tx_hash = *tx.tx_hash();
-
-                    let tx_env = this.eth_api().evm_config().tx_env(tx, *signer);
-
-                    let (result, state_changes) = this.trace_transaction(
-                        &opts,
-                        evm_env.clone(),
-                        tx_env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash: Some(block.hash()),
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                        &mut inspector,
-                    )?;
-
-                    inspector = inspector.map(|insp| insp.fused());
-
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
-                    }
-                }
-
-                Ok(results)
+                this.trace_block_transactions(&block, &evm_env, &opts, &mut db)
             })
             .await
     }
 
+    /// Traces all transactions of the block on top of the given database, after applying the
+    /// pre-execution system calls.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn trace_block_transactions<DB>(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Eth::Provider>>,
+        evm_env: &EvmEnv<<Eth::Evm as ConfigureEvmEnv>::Spec>,
+        opts: &GethDebugTracingOptions,
+        db: &mut DB,
+    ) -> Result<Vec<TraceResult>, Eth::Error>
+    where
+        DB: Database<Error = ProviderError>
+            + DatabaseRef<Error = ProviderError>
+            + DatabaseCommit
+            + Send,
+    {
+        let mut results = Vec::with_capacity(block.body().transactions().len());
+
+        self.eth_api().apply_pre_execution_changes(block, db, evm_env)?;
+
+        let mut inspector = None;
+        for (index, (signer, tx)) in block.transactions_with_sender().enumerate() {
+            let tx_hash = *tx.tx_hash();
+
+            let tx_env = self.eth_api().evm_config().tx_env(tx, *signer);
+
+            let (result, state_changes) = self.trace_transaction(
+                opts,
+                evm_env.clone(),
+                tx_env,
+                db,
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &mut inspector,
+            )?;
+
+            inspector = inspector.map(|insp| insp.fused());
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+            // need to apply the state changes of this transaction before executing the next
+            // transaction
+            db.commit(state_changes)
+        }
+
+        Ok(results)
+    }
+
     /// Replays the given block and returns the trace of each transaction.
     ///
     /// This expects a rlp encoded block
@@ -206,6 +284,148 @@ where
         self.trace_block(block, evm_env, opts).await
     }
 
+    /// Replays all blocks in the range `(start_exclusive, end_inclusive]` and returns the traces
+    /// of each block, in order.
+    ///
+    /// See also [`Self::debug_trace_chain_stream`].
+    pub async fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<BlockTraceResult>, Eth::Error> {
+        let (start, end) = self.trace_chain_range(start_exclusive, end_inclusive)?;
+        if end - start > TRACE_CHAIN_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {TRACE_CHAIN_MAX_BLOCKS} blocks, use the \
+                 traceChain subscription instead"
+            ))
+            .into())
+        }
+
+        self.debug_trace_chain_stream(start_exclusive, end_inclusive, opts)?.try_collect().await
+    }
+
+    /// Returns a stream that replays all blocks in the range `(start_exclusive, end_inclusive]`
+    /// and yields the traces of each block, in order.
+    ///
+    /// The blocks are traced one after another on a spawned task. The state is read from the
+    /// database for the parent of the first block and then carried forward: every block is traced
+    /// on top of the state left by the previous one, and its post-execution changes are applied
+    /// afterwards. The state is read from the database again every [`TRACE_CHAIN_STATE_BLOCKS`]
+    /// blocks, or once it caches more than [`TRACE_CHAIN_MAX_CACHED_ENTRIES`] entries. At most
+    /// [`TRACE_CHAIN_BUFFER`] traced blocks are buffered until the stream is polled, so a slow
+    /// consumer pauses the tracing.
+    ///
+    /// If tracing a block fails, the error is the last item of the stream.
+    pub fn debug_trace_chain_stream(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> Result<impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static, Eth::Error>
+    {
+        let (start, end) = self.trace_chain_range(start_exclusive, end_inclusive)?;
+        let (sender, receiver) = mpsc::channel(TRACE_CHAIN_BUFFER);
+        let this = self.clone();
+        self.eth_api().io_task_spawner().spawn(Box::pin(async move {
+            let mut db = None;
+            for number in start + 1..=end {
+                let (result, state) = match this.trace_chain_block(number, db.take(), &opts).await {
+                    Ok(result) => result,
+                    Err(err) => {
+                        let _ = sender.send(Err(err)).await;
+                        return
+                    }
+                };
+                if number % TRACE_CHAIN_STATE_BLOCKS != 0 &&
+                    cached_entries(&state) <= TRACE_CHAIN_MAX_CACHED_ENTRIES
+                {
+                    db = Some(state);
+                }
+                if sender.send(Ok(result)).await.is_err() {
+                    // the stream was dropped
+                    return
+                }
+            }
+        }));
+
+        Ok(ReceiverStream::new(receiver))
+    }
+
+    /// Traces the block with the given number on top of the given state and applies the
+    /// post-execution changes of the block to it, so it can be used for the next block.
+    ///
+    /// If no state is given, the state of the parent block is read from the database.
+    fn trace_chain_block(
+        &self,
+        number: u64,
+        db: Option<TraceChainDb>,
+        opts: &GethDebugTracingOptions,
+    ) -> impl Future<Output = Result<(BlockTraceResult, TraceChainDb), Eth::Error>> + Send + 'static
+    {
+        let this = self.clone();
+        let opts = opts.clone();
+        async move {
+            let _permit = this.acquire_trace_permit().await;
+            let block_id = BlockId::from(number);
+            let block = this
+                .eth_api()
+                .block_with_senders(block_id)
+                .await?
+                .ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let evm_env = this.eth_api().evm_config().evm_env(block.header());
+
+            // spawned on the tracing pool directly, the future of `spawn_tracing` borrows the
+            // api, which the compiler can't prove `Send` for inside this `'static` future
+            let debug_api = this.clone();
+            this.eth_api()
+                .tracing_task_pool()
+                .spawn(move || {
+                    let eth_api = debug_api.eth_api();
+                    let mut db = match db {
+                        Some(db) => db,
+                        None => CacheDB::new(StateProviderDatabase::new(
+                            eth_api.state_at_block_id(block.parent_hash().into())?,
+                        )),
+                    };
+
+                    let traces =
+                        debug_api.trace_block_transactions(&block, &evm_env, &opts, &mut db)?;
+                    eth_api.apply_post_execution_changes(&block, &mut db, &evm_env)?;
+
+                    Ok((
+                        BlockTraceResult { block: U256::from(number), hash: block.hash(), traces },
+                        db,
+                    ))
+                })
+                .await
+                .map_err(|_| EthApiError::InternalBlockingTaskError)?
+        }
+    }
+
+    /// Resolves the given block tags into a `(start_exclusive, end_inclusive)` block number
+    /// range.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<(u64, u64), Eth::Error> {
+        let convert = |tag: BlockNumberOrTag| -> Result<u64, Eth::Error> {
+            self.provider()
+                .convert_block_number(tag)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(tag.into()).into())
+        };
+        let start = convert(start_exclusive)?;
+        let end = convert(end_inclusive)?;
+        if start >= end {
+            return Err(EthApiError::InvalidBlockRange.into())
+        }
+
+        Ok((start, end))
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -660,6 +880,421 @@ where
             .await
     }
 
+    /// Returns the blocks that were rejected by the engine, most recent first.
+    ///
+    /// Each entry contains the block hash, the block object with full transactions and the RLP
+    /// encoded block, matching the response of geth's `debug_getBadBlocks`.
+    pub fn debug_bad_blocks(&self) -> Result<Vec<BadBlock>, Eth::Error>
+    where
+        Eth: FullEthApiTypes,
+        Eth::Provider: BadBlockProvider<Block = ProviderBlock<Eth::Provider>>,
+    {
+        let mut bad_blocks = Vec::new();
+        for sealed_block in self.provider().bad_blocks() {
+            let hash = sealed_block.hash();
+            let rlp = alloy_rlp::encode(&*sealed_block).into();
+
+            // a block can be rejected because of an invalid signature, in which case only the
+            // hash and the raw block can be returned
+            let block = match (*sealed_block).clone().try_recover_unchecked() {
+                Ok(block) => {
+                    let block = from_block(
+                        block,
+                        BlockTransactionsKind::Full,
+                        self.eth_api().tx_resp_builder(),
+                    )?;
+                    serde_json::to_value(block).map_err(|err| {
+                        Eth::Error::from_eth_err(EthApiError::Internal(RethError::msg(err)))
+                    })?
+                }
+                Err(_) => serde_json::Value::Null,
+            };
+
+            bad_blocks.push(BadBlock { hash, block, rlp });
+        }
+
+        Ok(bad_blocks)
+    }
+
+    /// Re-executes the given block and returns the state root after each of its transactions.
+    ///
+    /// The block is looked up in the canonical chain first and in the bad blocks otherwise, so
+    /// this can be used to find the transaction at which a rejected block diverged. The changes
+    /// of each transaction are applied to a sparse trie of the parent state, so every root is
+    /// computed incrementally from the changed trie paths only.
+    pub async fn debug_intermediate_roots(&self, block_hash: B256) -> Result<Vec<B256>, Eth::Error>
+    where
+        Eth::Provider: BadBlockProvider<Block = ProviderBlock<Eth::Provider>>,
+    {
+        let block = match self.eth_api().block_with_senders(block_hash.into()).await? {
+            Some(block) => block,
+            None => {
+                let bad_block = self
+                    .provider()
+                    .bad_blocks()
+                    .into_iter()
+                    .find(|block| block.hash() == block_hash)
+                    .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+                let block = (*bad_block)
+                    .clone()
+                    .try_recover()
+                    .map_err(|_| EthApiError::InvalidTransactionSignature)?;
+                Arc::new(block)
+            }
+        };
+        if block.number() == 0 {
+            return Err(EthApiError::InvalidParams("genesis is not traceable".to_string()).into())
+        }
+
+        let evm_env = self.eth_api().evm_config().evm_env(block.header());
+        let state_clear =
+            self.provider().chain_spec().is_spurious_dragon_active_at_block(block.number());
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state))
+                    .with_bundle_update()
+                    .build();
+                db.set_state_clear_flag(state_clear);
+
+                // the trie paths touched by the block are revealed from proofs against the parent
+                // state, so every root only rehashes the paths changed since the previous one
+                let blinded_provider_factory = StateProofBlindedProviderFactory { state: &state };
+                let mut trie = SparseStateTrie::new(blinded_provider_factory);
+                let mut revealed = MultiProofTargets::default();
+                let mut wiped = B256Set::default();
+                let to_eth_err =
+                    |err: RethError| Eth::Error::from_eth_err(EthApiError::Internal(err));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                update_sparse_trie(
+                    blinded_provider_factory,
+                    &mut trie,
+                    &mut revealed,
+                    &mut wiped,
+                    take_hashed_transitions(&mut db),
+                )
+                .map_err(to_eth_err)?;
+
+                let mut roots = Vec::with_capacity(block.body().transactions().len());
+                let mut evm = this.eth_api().evm_config().evm_with_env(&mut db, evm_env);
+                for (signer, tx) in block.transactions_with_sender() {
+                    let tx_env = this.eth_api().evm_config().tx_env(tx, *signer);
+                    evm.transact_commit(tx_env).map_err(Eth::Error::from_evm_err)?;
+
+                    update_sparse_trie(
+                        blinded_provider_factory,
+                        &mut trie,
+                        &mut revealed,
+                        &mut wiped,
+                        take_hashed_transitions(evm.db_mut()),
+                    )
+                    .map_err(to_eth_err)?;
+                    roots.push(trie.root().map_err(|err| to_eth_err(RethError::msg(err)))?);
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns the accounts that were modified after block `start_number` up to and including
+    /// block `end_number`, sorted by address.
+    ///
+    /// Without `end_number`, the accounts modified in block `start_number` are returned. Like
+    /// `eth_getLogs`, at most [`DEFAULT_MAX_BLOCKS_PER_FILTER`] blocks can be queried at once.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error>
+    where
+        Eth::Provider: ChangeSetReader + StorageChangeSetReader,
+    {
+        let (start, end) = match end_number {
+            Some(end_number) => (start_number, end_number),
+            None => {
+                let parent_number = start_number.checked_sub(1).ok_or_else(|| {
+                    EthApiError::InvalidParams(format!("block {start_number} has no parent"))
+                })?;
+                (parent_number, start_number)
+            }
+        };
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({end})"
+            ))
+            .into())
+        }
+        if end - start > DEFAULT_MAX_BLOCKS_PER_FILTER {
+            return Err(EthApiError::InvalidParams(format!(
+                "query exceeds max block range {DEFAULT_MAX_BLOCKS_PER_FILTER}"
+            ))
+            .into())
+        }
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                if provider.header_by_number(end).map_err(Eth::Error::from_eth_err)?.is_none() {
+                    return Err(EthApiError::HeaderNotFound(end.into()).into())
+                }
+
+                // an account is modified if its info or any of its storage slots changed, both
+                // are recorded in the changesets of the block
+                let mut accounts = BTreeSet::new();
+                for block_number in start + 1..=end {
+                    let account_changes = provider
+                        .account_block_changeset(block_number)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(account_changes.into_iter().map(|change| change.address));
+
+                    let storage_changes = provider
+                        .storage_changeset(block_number)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(storage_changes.into_iter().map(|(key, _)| key.address()));
+                }
+
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns the accounts that were modified after block `start_hash` up to and including
+    /// block `end_hash`, sorted by address.
+    ///
+    /// See [`Self::debug_get_modified_accounts_by_number`] for more info.
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error>
+    where
+        Eth::Provider: ChangeSetReader + StorageChangeSetReader,
+    {
+        let block_number = |hash: B256| -> Result<u64, Eth::Error> {
+            self.provider()
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(hash.into()).into())
+        };
+        let start_number = block_number(start_hash)?;
+        let end_number = end_hash.map(block_number).transpose()?;
+
+        self.debug_get_modified_accounts_by_number(start_number, end_number).await
+    }
+
+    /// Returns the storage of `address` after the first `tx_idx` transactions of the given block,
+    /// starting at the hashed slot `key_start`.
+    ///
+    /// The state of the parent block is read from the hashed state tables and the transactions
+    /// preceding `tx_idx` are replayed on top of it. The preimages of the returned slots are
+    /// included where they are known.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error>
+    where
+        Eth::Provider: HashedStateRangeReader,
+    {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+
+        // like geth, the first index of an empty block refers to the state of its parent
+        let target_tx_hash = match block.body().transactions().get(tx_idx) {
+            Some(tx) => Some(*tx.tx_hash()),
+            None if tx_idx == 0 && block.body().transactions().is_empty() => None,
+            None => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "transaction index {tx_idx} out of range for block {block_hash}"
+                ))
+                .into())
+            }
+        };
+        let (evm_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let limit = usize::try_from(max_result).unwrap_or(usize::MAX);
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                if let Some(target_tx_hash) = target_tx_hash {
+                    this.eth_api().apply_pre_execution_changes(&block, &mut db, &evm_env)?;
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_with_sender(),
+                        target_tx_hash,
+                    )?;
+                }
+
+                // the storage changes of the replayed transactions, keyed by hashed slot
+                let mut changed_slots = B256Map::default();
+                let mut overlay = HashedStorage::default();
+                if let Some(account) = db.accounts.get(&address) {
+                    overlay.wiped = matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    );
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        changed_slots.insert(hashed_slot, slot);
+                        overlay.storage.insert(hashed_slot, *value);
+                    }
+                }
+
+                let parent_number = block.number().saturating_sub(1);
+                let range = this
+                    .provider()
+                    .hashed_storage_range(parent_number, address, overlay, key_start, limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let hashed_slots = range
+                    .slots
+                    .iter()
+                    .map(|(hashed_slot, _)| *hashed_slot)
+                    .filter(|hashed_slot| !changed_slots.contains_key(hashed_slot))
+                    .collect::<Vec<_>>();
+                let mut preimages = this
+                    .provider()
+                    .storage_preimages(address, &hashed_slots)
+                    .map_err(Eth::Error::from_eth_err)?;
+                preimages.extend(changed_slots);
+
+                let storage = range
+                    .slots
+                    .into_iter()
+                    .map(|(hashed_slot, value)| {
+                        let entry = StorageRangeEntry {
+                            key: preimages.get(&hashed_slot).copied(),
+                            value: value.into(),
+                        };
+                        (hashed_slot, entry)
+                    })
+                    .collect();
+
+                Ok(StorageRangeResult { storage, next_key: range.next_key })
+            })
+            .await
+    }
+
+    /// Enumerates the accounts of the state after the given block, starting at the hashed address
+    /// `start`.
+    ///
+    /// At most [`ACCOUNT_RANGE_MAX_RESULTS`] accounts are returned per page. Recovering addresses
+    /// requires walking the plain state, so it is only done for the accounts of the page and only
+    /// if `incompletes` is not set. Without `incompletes`, accounts whose address cannot be
+    /// recovered are skipped, so a page can hold fewer accounts than requested. With it, every
+    /// account is keyed by its hashed address.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<StateDump, Eth::Error>
+    where
+        Eth::Provider: HashedStateRangeReader,
+    {
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key too long: {} bytes",
+                start.len()
+            ))
+            .into())
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let limit = match usize::try_from(max_results) {
+            Ok(max_results) if max_results > 0 && max_results <= ACCOUNT_RANGE_MAX_RESULTS => {
+                max_results
+            }
+            _ => ACCOUNT_RANGE_MAX_RESULTS,
+        };
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let number = provider
+                    .convert_block_number(block_number)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                let header = provider
+                    .header_by_number(number)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                let state = provider.latest().map_err(Eth::Error::from_eth_err)?;
+
+                let range = provider
+                    .hashed_account_range(number, start_key, limit, !nostorage, !incompletes)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut dump = StateDump {
+                    root: hex::encode(header.state_root()),
+                    accounts: BTreeMap::new(),
+                    next: range.next_key.map(Into::into),
+                };
+
+                for entry in range.accounts {
+                    if entry.address.is_none() && !incompletes {
+                        continue
+                    }
+
+                    let code_hash = entry.account.bytecode_hash.unwrap_or(KECCAK_EMPTY);
+                    let code = match entry.account.bytecode_hash {
+                        Some(code_hash) if !nocode => state
+                            .bytecode_by_hash(&code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                            .filter(|code| !code.is_empty()),
+                        _ => None,
+                    };
+                    let storage = entry.storage.map(|storage| {
+                        storage
+                            .into_iter()
+                            .map(|(hashed_slot, value)| {
+                                (hashed_slot, hex::encode(value.to_be_bytes_trimmed_vec()))
+                            })
+                            .collect()
+                    });
+
+                    let key = match entry.address {
+                        Some(address) => address.to_checksum(None),
+                        None => format!("pre({})", entry.hashed_address),
+                    };
+                    dump.accounts.insert(
+                        key,
+                        DumpAccount {
+                            balance: entry.account.balance.to_string(),
+                            nonce: entry.account.nonce,
+                            root: entry.storage_root,
+                            code_hash,
+                            code,
+                            storage,
+                            address: entry.address,
+                            address_hash: entry.hashed_address,
+                        },
+                    );
+                }
+
+                Ok(dump)
+            })
+            .await
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// It optionally takes fused inspector ([`TracingInspector::fused`]) to avoid re-creating the
@@ -674,15 +1309,18 @@ where
     /// Note: this does not apply any state overrides if they're configured in the `opts`.
     ///
     /// Caution: this is blocking and should be performed on a blocking task.
-    fn trace_transaction(
+    fn trace_transaction<DB>(
         &self,
         opts: &GethDebugTracingOptions,
         evm_env: EvmEnv<<Eth::Evm as ConfigureEvmEnv>::Spec>,
         tx_env: <Eth::Evm as ConfigureEvmEnv>::TxEnv,
-        db: &mut StateCacheDb<'_>,
+        db: &mut DB,
         transaction_context: Option<TransactionContext>,
         fused_inspector: &mut Option<TracingInspector>,
-    ) -> Result<(GethTrace, revm_primitives::EvmState), Eth::Error> {
+    ) -> Result<(GethTrace, revm_primitives::EvmState), Eth::Error>
+    where
+        DB: Database<Error = ProviderError> + DatabaseRef<Error = ProviderError>,
+    {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
 
         let tx_info = TransactionInfo {
@@ -837,7 +1475,11 @@ where
 #[async_trait]
 impl<Eth, BlockExecutor> DebugApiServer for DebugApi<Eth, BlockExecutor>
 where
-    Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth: FullEthApiTypes + EthTransactions + TraceExt + 'static,
+    Eth::Provider: BadBlockProvider<Block = ProviderBlock<Eth::Provider>>
+        + HashedStateRangeReader
+        + ChangeSetReader
+        + StorageChangeSetReader,
     BlockExecutor:
         BlockExecutorProvider<Primitives: NodePrimitives<Block = ProviderBlock<Eth::Provider>>>,
 {
@@ -910,17 +1552,76 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>> {
+        Self::debug_bad_blocks(self).map_err(Into::into)
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Self::debug_trace_chain(self, start_exclusive, end_inclusive, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Handler for `debug_subscribe`
+    async fn debug_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let stream = match kind {
+            DebugSubscriptionKind::TraceChain => {
+                match self.debug_trace_chain_stream(
+                    start_exclusive,
+                    end_inclusive,
+                    opts.unwrap_or_default(),
+                ) {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        pending.reject(err).await;
+                        return Ok(())
+                    }
+                }
+            }
+        };
+
+        let sink = pending.accept().await?;
+        self.eth_api().io_task_spawner().spawn(Box::pin(async move {
+            let mut stream = std::pin::pin!(stream);
+            loop {
+                tokio::select! {
+                    _ = sink.closed() => break,
+                    item = stream.next() => {
+                        let result = match item {
+                            Some(Ok(result)) => result,
+                            Some(Err(err)) => {
+                                let err: ErrorObject<'static> = err.into();
+                                debug!(target: "rpc::debug", %err, "traceChain subscription failed");
+                                if let Ok(msg) = SubscriptionMessage::from_json(&err) {
+                                    let _ = sink.send(msg).await;
+                                }
+                                break
+                            }
+                            None => break,
+                        };
+                        let Ok(msg) = SubscriptionMessage::from_json(&result) else { break };
+                        if sink.send(msg).await.is_err() {
+                            break
+                        }
+                    }
+                }
+            }
+        }));
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1016,16 +1717,27 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<StateDump> {
+        Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1084,32 +1796,40 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_number(self, start_number, end_number)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1140,8 +1860,18 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: u64) -> RpcResult<()> {
+        let Some(head_setter) = &self.inner.head_setter else {
+            return Err(internal_rpc_err("debug_setHead is not supported by this node"))
+        };
+        match head_setter.set_head(number).await {
+            Ok(_) => Ok(()),
+            Err(err @ BeaconSetHeadError::AboveHead { .. }) => {
+                Err(EthApiError::InvalidParams(err.to_string()).into())
+            }
+            Err(err) => Err(internal_rpc_err(err.to_string())),
+        }
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1188,15 +1918,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -1228,6 +1969,167 @@ where
     }
 }
 
+/// Takes the state transitions that were recorded by the given [`State`] since the last call and
+/// returns them as a [`HashedPostState`].
+fn take_hashed_transitions<DB>(db: &mut State<DB>) -> HashedPostState {
+    let mut hashed_state = HashedPostState::default();
+    let Some(transition_state) = db.transition_state.as_mut() else { return hashed_state };
+
+    for (address, transition) in std::mem::take(&mut transition_state.transitions) {
+        let hashed_address = keccak256(address);
+        hashed_state.accounts.insert(hashed_address, transition.info.map(Into::into));
+
+        let storage = HashedStorage::from_iter(
+            transition.storage_was_destroyed,
+            transition
+                .storage
+                .into_iter()
+                .map(|(slot, value)| (keccak256(B256::from(slot)), value.present_value)),
+        );
+        if !storage.is_empty() {
+            hashed_state.storages.insert(hashed_address, storage);
+        }
+    }
+
+    hashed_state
+}
+
+/// Applies the given changes to the sparse trie, revealing the trie paths they touch from proofs
+/// against the state of `factory` first.
+///
+/// `revealed` holds the targets that were revealed so far and `wiped` the accounts whose storage
+/// was wiped, which must not be revealed from the state anymore.
+fn update_sparse_trie<'a>(
+    factory: StateProofBlindedProviderFactory<'a>,
+    trie: &mut SparseStateTrie<StateProofBlindedProviderFactory<'a>>,
+    revealed: &mut MultiProofTargets,
+    wiped: &mut B256Set,
+    hashed_state: HashedPostState,
+) -> Result<(), RethError> {
+    let mut targets = MultiProofTargets::default();
+    for hashed_address in hashed_state.accounts.keys() {
+        if !revealed.contains_key(hashed_address) {
+            targets.insert(*hashed_address, B256Set::default());
+        }
+    }
+    for (hashed_address, storage) in &hashed_state.storages {
+        if storage.wiped {
+            wiped.insert(*hashed_address);
+        }
+        if wiped.contains(hashed_address) {
+            continue
+        }
+        let revealed_slots = revealed.get(hashed_address);
+        let slots = storage
+            .storage
+            .keys()
+            .filter(|slot| !revealed_slots.is_some_and(|slots| slots.contains(*slot)))
+            .copied()
+            .collect::<B256Set>();
+        if !slots.is_empty() {
+            targets.entry(*hashed_address).or_default().extend(slots);
+        }
+    }
+    if !targets.is_empty() {
+        let multiproof = factory.state.multiproof(TrieInput::default(), targets.clone())?;
+        trie.reveal_multiproof(multiproof).map_err(RethError::msg)?;
+        for (hashed_address, slots) in targets {
+            revealed.entry(hashed_address).or_default().extend(slots);
+        }
+    }
+
+    for (hashed_address, storage) in hashed_state.storages {
+        if storage.wiped {
+            if trie.storage_trie_ref(&hashed_address).is_some() {
+                trie.wipe_storage(hashed_address).map_err(RethError::msg)?;
+            } else {
+                let mut storage_trie = SparseTrie::default();
+                storage_trie
+                    .reveal_root_with_provider(
+                        factory.storage_node_provider(hashed_address),
+                        TrieNode::EmptyRoot,
+                        None,
+                        None,
+                        false,
+                    )
+                    .map_err(RethError::msg)?;
+                trie.insert_storage_trie(hashed_address, storage_trie);
+            }
+        }
+        for (slot, value) in storage.storage {
+            let slot = Nibbles::unpack(slot);
+            if value.is_zero() {
+                trie.remove_storage_leaf(hashed_address, &slot).map_err(RethError::msg)?;
+            } else {
+                trie.update_storage_leaf(
+                    hashed_address,
+                    slot,
+                    alloy_rlp::encode_fixed_size(&value).to_vec(),
+                )
+                .map_err(RethError::msg)?;
+            }
+        }
+    }
+    for (hashed_address, account) in hashed_state.accounts {
+        trie.update_account(hashed_address, account.unwrap_or_default()).map_err(RethError::msg)?;
+    }
+
+    Ok(())
+}
+
+/// Retrieves the blinded nodes of a sparse trie from proofs against a state.
+#[derive(Clone, Copy)]
+struct StateProofBlindedProviderFactory<'a> {
+    state: &'a dyn StateProofProvider,
+}
+
+impl<'a> BlindedProviderFactory for StateProofBlindedProviderFactory<'a> {
+    type AccountNodeProvider = StateProofBlindedProvider<'a>;
+    type StorageNodeProvider = StateProofBlindedProvider<'a>;
+
+    fn account_node_provider(&self) -> Self::AccountNodeProvider {
+        StateProofBlindedProvider { state: self.state, account: None }
+    }
+
+    fn storage_node_provider(&self, account: B256) -> Self::StorageNodeProvider {
+        StateProofBlindedProvider { state: self.state, account: Some(account) }
+    }
+}
+
+/// Retrieves the blinded nodes of the account trie, or of the storage trie of `account`, from
+/// proofs against a state.
+struct StateProofBlindedProvider<'a> {
+    state: &'a dyn StateProofProvider,
+    account: Option<B256>,
+}
+
+impl BlindedProvider for StateProofBlindedProvider<'_> {
+    fn blinded_node(&mut self, path: &Nibbles) -> Result<Option<RevealedNode>, SparseTrieError> {
+        let key = pad_path_to_key(path);
+        let targets = match self.account {
+            Some(account) => MultiProofTargets::from_iter([(account, B256Set::from_iter([key]))]),
+            None => MultiProofTargets::from_iter([(key, B256Set::default())]),
+        };
+        let mut multiproof = self
+            .state
+            .multiproof(TrieInput::default(), targets)
+            .map_err(|err| SparseTrieErrorKind::Other(Box::new(err)))?;
+        let node = match self.account {
+            Some(account) => multiproof
+                .storages
+                .remove(&account)
+                .and_then(|storage| storage.subtree.into_inner().remove(path)),
+            None => multiproof.account_subtree.into_inner().remove(path),
+        };
+        Ok(node.map(|node| RevealedNode { node, tree_mask: None, hash_mask: None }))
+    }
+}
+
+/// Returns the number of accounts and storage slots cached in the given database.
+fn cached_entries<ExtDB>(db: &CacheDB<ExtDB>) -> usize {
+    db.accounts.values().map(|account| 1 + account.storage.len()).sum()
+}
+
 impl<Eth, BlockExecutor> std::fmt::Debug for DebugApi<Eth, BlockExecutor> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1247,4 +2149,413 @@ struct DebugApiInner<Eth, BlockExecutor> {
     blocking_task_guard: BlockingTaskGuard,
     /// block executor for debug & trace apis
     block_executor: BlockExecutor,
+    /// handle to rewind the canonical chain for `debug_setHead`
+    head_setter: Option<Arc<dyn ChainHeadSetter>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_consensus::{Header, TxEip1559};
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+    use alloy_genesis::{Genesis, GenesisAccount};
+    use alloy_primitives::TxKind;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_common::init::init_genesis;
+    use reth_evm::execute::BasicBlockExecutorProvider;
+    use reth_evm_ethereum::{
+        execute::{EthExecutionStrategyFactory, EthExecutorProvider},
+        EthEvmConfig,
+    };
+    use reth_primitives::{Block, BlockBody, Transaction};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{
+            create_test_provider_factory_with_chain_spec, ExtendedAccount, MockEthProvider,
+            MockNodeTypesWithDB,
+        },
+        BlockHashReader, BlockWriter, ExecutionOutcome, StateRootProvider,
+    };
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, generate_keys, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use reth_trie_common::KeccakKeyHasher;
+
+    /// Recipient of the transfers of the test chains.
+    const RECIPIENT: Address = Address::new([0x42; 20]);
+
+    /// Contract with two storage slots in the genesis state of [`debug_api_with_state`].
+    const CONTRACT: Address = Address::new([0x43; 20]);
+
+    /// Returns a debug API over a database with `blocks` executed blocks of `transfers` transfers
+    /// each, and the sender of the transfers.
+    ///
+    /// The genesis state holds the funded sender and [`CONTRACT`].
+    #[allow(clippy::type_complexity)]
+    fn debug_api_with_state(
+        blocks: u64,
+        transfers: u64,
+    ) -> (
+        DebugApi<
+            EthApi<BlockchainProvider<MockNodeTypesWithDB>, TestPool, (), EthEvmConfig>,
+            BasicBlockExecutorProvider<EthExecutionStrategyFactory>,
+        >,
+        Address,
+    ) {
+        let key_pair = generate_keys(&mut generators::rng(), 1)[0];
+        let sign_transfer = |nonce| {
+            sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Eip1559(TxEip1559 {
+                    chain_id: 1,
+                    nonce,
+                    gas_limit: 21_000,
+                    max_fee_per_gas: 10,
+                    max_priority_fee_per_gas: 1,
+                    to: TxKind::Call(RECIPIENT),
+                    value: U256::from(1),
+                    ..Default::default()
+                }),
+            )
+        };
+        let sender = sign_transfer(0).recover_signer().unwrap();
+        let genesis = Genesis::default().extend_accounts([
+            (sender, GenesisAccount::default().with_balance(U256::from(u64::MAX))),
+            (
+                CONTRACT,
+                GenesisAccount::default()
+                    .with_code(Some(Bytes::from_static(&[0x00])))
+                    .with_storage(Some(BTreeMap::from([
+                        (B256::with_last_byte(1), B256::with_last_byte(1)),
+                        (B256::with_last_byte(2), B256::with_last_byte(2)),
+                    ]))),
+            ),
+        ]);
+        let chain_spec =
+            Arc::new(ChainSpecBuilder::mainnet().genesis(genesis).paris_activated().build());
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&factory).unwrap();
+
+        let executor_provider = EthExecutorProvider::ethereum(chain_spec.clone());
+        let mut parent = chain_spec.genesis_header().clone();
+        for number in 1..=blocks {
+            let body = BlockBody {
+                transactions: ((number - 1) * transfers..number * transfers)
+                    .map(sign_transfer)
+                    .collect(),
+                ..Default::default()
+            };
+            let mut header = Header {
+                number,
+                parent_hash: parent.hash_slow(),
+                timestamp: number * 12,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+                base_fee_per_gas: Some(7),
+                ..Default::default()
+            };
+            let block =
+                RecoveredBlock::try_recover(Block::new(header.clone(), body.clone())).unwrap();
+            let output = executor_provider
+                .executor(StateProviderDatabase::new(factory.latest().unwrap()))
+                .execute(&block)
+                .unwrap();
+
+            let hashed_state =
+                HashedPostState::from_bundle_state::<KeccakKeyHasher>(&output.state.state);
+            let (state_root, trie_updates) =
+                factory.latest().unwrap().state_root_with_updates(hashed_state.clone()).unwrap();
+            header.state_root = state_root;
+            header.gas_used = output.gas_used;
+
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .append_blocks_with_state(
+                    vec![RecoveredBlock::try_recover(Block::new(header.clone(), body)).unwrap()],
+                    &ExecutionOutcome::new(
+                        output.state,
+                        vec![output.receipts],
+                        number,
+                        vec![output.requests],
+                    ),
+                    hashed_state.into_sorted(),
+                    trie_updates,
+                )
+                .unwrap();
+            provider_rw.commit().unwrap();
+            parent = header;
+        }
+
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(chain_spec.clone()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        let debug_api = DebugApi::new(
+            eth_api,
+            BlockingTaskGuard::new(2),
+            EthExecutorProvider::ethereum(chain_spec),
+            None,
+        );
+        (debug_api, sender)
+    }
+
+    /// Returns a debug API over a chain of `blocks` blocks on top of an empty genesis block, where
+    /// every block contains a transfer by the same sender.
+    fn debug_api_with_transfers(
+        blocks: u64,
+    ) -> DebugApi<
+        EthApi<MockEthProvider, TestPool, (), EthEvmConfig>,
+        BasicBlockExecutorProvider<EthExecutionStrategyFactory>,
+    > {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().london_activated().build());
+        let provider = MockEthProvider::default().with_chain_spec((*chain_spec).clone());
+
+        let key_pair = generate_keys(&mut generators::rng(), 1)[0];
+        let mut parent = Header {
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+        provider.add_header(parent.hash_slow(), parent.clone());
+        provider.add_block(parent.hash_slow(), Block::new(parent.clone(), Default::default()));
+        for number in 1..=blocks {
+            let transaction = sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Eip1559(TxEip1559 {
+                    chain_id: 1,
+                    nonce: number - 1,
+                    gas_limit: 21_000,
+                    max_fee_per_gas: 10,
+                    max_priority_fee_per_gas: 1,
+                    to: TxKind::Call(Address::with_last_byte(0x42)),
+                    value: U256::from(1),
+                    ..Default::default()
+                }),
+            );
+            if number == 1 {
+                let sender = transaction.recover_signer().unwrap();
+                provider.add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+            }
+
+            let header = Header {
+                number,
+                parent_hash: parent.hash_slow(),
+                timestamp: number * 12,
+                ..parent.clone()
+            };
+            let body = BlockBody { transactions: vec![transaction], ..Default::default() };
+            provider.add_header(header.hash_slow(), header.clone());
+            provider.add_block(header.hash_slow(), Block::new(header.clone(), body));
+            parent = header;
+        }
+
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(chain_spec.clone()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        DebugApi::new(
+            eth_api,
+            BlockingTaskGuard::new(2),
+            EthExecutorProvider::ethereum(chain_spec),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn trace_chain_carries_state_forward() {
+        let debug_api = debug_api_with_transfers(3);
+
+        // The mock provider always returns the genesis state, so the transactions of the second
+        // and third block only have a valid nonce on top of the state carried forward.
+        let results = debug_api
+            .debug_trace_chain(
+                BlockNumberOrTag::Number(0),
+                BlockNumberOrTag::Number(3),
+                GethDebugTracingOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        for (number, result) in (1u64..).zip(&results) {
+            assert_eq!(result.block, U256::from(number));
+            assert_eq!(result.traces.len(), 1);
+            assert!(matches!(result.traces[0], TraceResult::Success { .. }));
+        }
+    }
+
+    #[tokio::test]
+    async fn trace_chain_stream_ends_with_error() {
+        let debug_api = debug_api_with_transfers(2);
+
+        let items = debug_api
+            .debug_trace_chain_stream(
+                BlockNumberOrTag::Number(0),
+                BlockNumberOrTag::Number(3),
+                GethDebugTracingOptions::default(),
+            )
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(items.len(), 3);
+        assert!(items[..2].iter().all(Result::is_ok));
+        assert!(items[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn account_range_pages_historical_state() {
+        let (debug_api, sender) = debug_api_with_state(2, 1);
+
+        // The sender, the contract, the recipient and the fee recipient, two per page.
+        let mut accounts = BTreeMap::new();
+        let mut start = Bytes::new();
+        loop {
+            let dump = debug_api
+                .debug_account_range(BlockNumberOrTag::Number(1), start, 2, true, true, false)
+                .await
+                .unwrap();
+            assert!(dump.accounts.len() <= 2);
+            accounts.extend(dump.accounts);
+            match dump.next {
+                Some(next) => start = next,
+                None => break,
+            }
+        }
+        assert_eq!(accounts.len(), 4);
+        assert!(accounts.values().all(|account| account.address.is_some()));
+
+        // The state of the first block is reverted from the state of the second one.
+        assert_eq!(accounts[&sender.to_checksum(None)].nonce, 1);
+        assert_eq!(accounts[&RECIPIENT.to_checksum(None)].balance, "1");
+
+        let dump = debug_api
+            .debug_account_range(BlockNumberOrTag::Number(0), Bytes::new(), 0, false, false, false)
+            .await
+            .unwrap();
+        assert_eq!(dump.accounts.len(), 2);
+        let contract = &dump.accounts[&CONTRACT.to_checksum(None)];
+        assert_eq!(contract.code, Some(Bytes::from_static(&[0x00])));
+        assert_eq!(
+            contract.storage,
+            Some(BTreeMap::from([
+                (keccak256(B256::with_last_byte(1)), "01".to_string()),
+                (keccak256(B256::with_last_byte(2)), "02".to_string()),
+            ]))
+        );
+        assert_eq!(
+            dump.root,
+            hex::encode(debug_api.provider().chain_spec().genesis_header().state_root)
+        );
+
+        // Without preimages, the accounts are keyed by their hashed address.
+        let dump = debug_api
+            .debug_account_range(BlockNumberOrTag::Latest, Bytes::new(), 0, true, true, true)
+            .await
+            .unwrap();
+        assert_eq!(dump.accounts.len(), 4);
+        for (key, account) in dump.accounts {
+            assert_eq!(account.address, None);
+            assert_eq!(key, format!("pre({})", account.address_hash));
+        }
+    }
+
+    #[tokio::test]
+    async fn storage_range_at_returns_preimages() {
+        let (debug_api, _) = debug_api_with_state(1, 1);
+        let block_hash = debug_api.provider().block_hash(1).unwrap().unwrap();
+
+        let mut slots = [B256::with_last_byte(1), B256::with_last_byte(2)];
+        slots.sort_by_key(|slot| keccak256(slot));
+
+        let range =
+            debug_api.debug_storage_range_at(block_hash, 0, CONTRACT, B256::ZERO, 1).await.unwrap();
+        assert_eq!(
+            range.storage,
+            BTreeMap::from([(
+                keccak256(slots[0]),
+                StorageRangeEntry { key: Some(slots[0]), value: slots[0] }
+            )])
+        );
+        assert_eq!(range.next_key, Some(keccak256(slots[1])));
+
+        let range = debug_api
+            .debug_storage_range_at(block_hash, 0, CONTRACT, keccak256(slots[1]), 1)
+            .await
+            .unwrap();
+        assert_eq!(range.storage.len(), 1);
+        assert_eq!(range.next_key, None);
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_from_change_sets() {
+        let (debug_api, sender) = debug_api_with_state(3, 1);
+        let transfer_accounts = BTreeSet::from([sender, RECIPIENT, Address::ZERO]);
+
+        let accounts = debug_api.debug_get_modified_accounts_by_number(2, None).await.unwrap();
+        assert_eq!(accounts, transfer_accounts.iter().copied().collect::<Vec<_>>());
+
+        let accounts = debug_api.debug_get_modified_accounts_by_number(0, Some(3)).await.unwrap();
+        assert_eq!(accounts, transfer_accounts.iter().copied().collect::<Vec<_>>());
+
+        let block_hash = |number| debug_api.provider().block_hash(number).unwrap().unwrap();
+        let accounts = debug_api
+            .debug_get_modified_accounts_by_hash(block_hash(1), Some(block_hash(3)))
+            .await
+            .unwrap();
+        assert_eq!(accounts, transfer_accounts.into_iter().collect::<Vec<_>>());
+        assert!(debug_api
+            .debug_get_modified_accounts_by_hash(block_hash(3), Some(block_hash(2)))
+            .await
+            .is_err());
+
+        assert!(debug_api.debug_get_modified_accounts_by_number(0, Some(4)).await.is_err());
+        assert!(debug_api
+            .debug_get_modified_accounts_by_number(0, Some(DEFAULT_MAX_BLOCKS_PER_FILTER + 1))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_end_at_state_root() {
+        let (debug_api, _) = debug_api_with_state(2, 3);
+
+        for number in 1..=2 {
+            let header = debug_api.provider().sealed_header(number).unwrap().unwrap();
+            let roots = debug_api.debug_intermediate_roots(header.hash()).await.unwrap();
+
+            assert_eq!(roots.len(), 3);
+            assert_eq!(roots.iter().collect::<BTreeSet<_>>().len(), 3);
+            assert_eq!(roots.last(), Some(&header.state_root));
+        }
+    }
 }
@@ -11,8 +11,8 @@ use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_primitives::RecoveredBlock;
 use reth_provider::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderError, ProviderReceipt,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, HistoryReader,
+    ProviderBlock, ProviderError, ProviderReceipt,
 };
 use reth_rpc_eth_api::{
     EthApiTypes, EthFilterApiServer, FullEthApiTypes, RpcNodeCoreExt, RpcTransaction,
@@ -26,7 +26,7 @@ use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     iter::StepBy,
     ops::RangeInclusive,
@@ -155,7 +155,7 @@ where
 
 impl<Eth> EthFilter<Eth>
 where
-    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader> + RpcNodeCoreExt,
+    Eth: FullEthApiTypes<Provider: BlockReader + BlockIdReader + HistoryReader> + RpcNodeCoreExt,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -262,7 +262,7 @@ where
 #[async_trait]
 impl<Eth> EthFilterApiServer<RpcTransaction<Eth::NetworkTypes>> for EthFilter<Eth>
 where
-    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader> + 'static,
+    Eth: FullEthApiTypes + RpcNodeCoreExt<Provider: BlockIdReader + HistoryReader> + 'static,
 {
     /// Handler for `eth_newFilter`
     async fn new_filter(&self, filter: Filter) -> RpcResult<FilterId> {
@@ -381,7 +381,8 @@ struct EthFilterInner<Eth: EthApiTypes> {
 
 impl<Eth> EthFilterInner<Eth>
 where
-    Eth: RpcNodeCoreExt<Provider: BlockIdReader, Pool: TransactionPool> + EthApiTypes,
+    Eth: RpcNodeCoreExt<Provider: BlockIdReader + HistoryReader, Pool: TransactionPool>
+        + EthApiTypes,
 {
     /// Access the underlying provider.
     fn provider(&self) -> &Eth::Provider {
@@ -499,15 +500,136 @@ where
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
+        // the part of the range covered by the log index, if it has been built
+        let indexed_range = self.provider().log_index_range()?.and_then(|index_range| {
+            let start = (*index_range.start()).max(from_block);
+            let end = (*index_range.end()).min(to_block);
+            (start <= end).then_some(start..=end)
+        });
+        let indexed_blocks = match indexed_range {
+            Some(range) => {
+                self.indexed_candidate_blocks(filter, range.clone())?.map(|blocks| (range, blocks))
+            }
+            None => None,
+        };
+
+        let Some((indexed_range, blocks)) = indexed_blocks else {
+            self.append_logs_matching_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=to_block,
+                from_block,
+                to_block,
+                chain_info,
+            )
+            .await?;
+            return Ok(all_logs)
+        };
+
+        // scan the blocks before the indexed range
+        if from_block < *indexed_range.start() {
+            self.append_logs_matching_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=*indexed_range.start() - 1,
+                from_block,
+                to_block,
+                chain_info,
+            )
+            .await?;
+        }
+
+        // only check the blocks the log index has entries for
+        for number in blocks {
+            let header = self
+                .provider()
+                .sealed_header(number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+            self.append_block_logs(
+                &mut all_logs,
+                &filter_params,
+                BlockNumHash::new(number, header.hash()),
+                header.timestamp(),
+                from_block,
+                to_block,
+                chain_info,
+            )
+            .await?;
+        }
+
+        // scan the blocks after the indexed range
+        if *indexed_range.end() < to_block {
+            self.append_logs_matching_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                *indexed_range.end() + 1..=to_block,
+                from_block,
+                to_block,
+                chain_info,
+            )
+            .await?;
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Returns the blocks in the range that have logs with any of the filter's addresses and
+    /// topics according to the log index.
+    ///
+    /// Returns `None` if the filter matches logs of any address and with any topics.
+    fn indexed_candidate_blocks(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Option<BTreeSet<u64>>, EthFilterError> {
+        let mut candidates = if filter.address.is_empty() {
+            None
+        } else {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider().log_address_blocks(*address, range.clone())?);
+            }
+            Some(blocks)
+        };
+
+        // every topic position has to match one of its topics
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider().log_topic_blocks(*topic, range.clone())?);
+            }
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        Ok(candidates)
+    }
+
+    /// Appends the logs matching the filter of the blocks in the range whose bloom filter matches
+    /// the filter.
+    #[allow(clippy::too_many_arguments)]
+    async fn append_logs_matching_bloom(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        filter_params: &FilteredParams,
+        range: RangeInclusive<u64>,
+        from_block: u64,
+        to_block: u64,
+        chain_info: ChainInfo,
+    ) -> Result<(), EthFilterError> {
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider().headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -525,38 +647,65 @@ where
                             .ok_or_else(|| ProviderError::HeaderNotFound(header.number().into()))?,
                     };
 
-                    let num_hash = BlockNumHash::new(header.number(), block_hash);
-                    if let Some((receipts, maybe_block)) =
-                        self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
-                    {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            maybe_block
-                                .map(ProviderOrBlock::Block)
-                                .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
-                            &filter_params,
-                            num_hash,
-                            &receipts,
-                            false,
-                            header.timestamp(),
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults {
-                                max_logs: self.max_logs_per_response,
-                                from_block,
-                                to_block: num_hash.number.saturating_sub(1),
-                            });
-                        }
-                    }
+                    self.append_block_logs(
+                        all_logs,
+                        filter_params,
+                        BlockNumHash::new(header.number(), block_hash),
+                        header.timestamp(),
+                        from_block,
+                        to_block,
+                        chain_info,
+                    )
+                    .await?;
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends the logs of the block matching the filter.
+    ///
+    /// Returns an error if the amount of matches exceeds the configured limit.
+    #[allow(clippy::too_many_arguments)]
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        num_hash: BlockNumHash,
+        timestamp: u64,
+        from_block: u64,
+        to_block: u64,
+        chain_info: ChainInfo,
+    ) -> Result<(), EthFilterError> {
+        if let Some((receipts, maybe_block)) =
+            self.receipts_and_maybe_block(&num_hash, chain_info.best_number).await?
+        {
+            append_matching_block_logs(
+                all_logs,
+                maybe_block
+                    .map(ProviderOrBlock::Block)
+                    .unwrap_or_else(|| ProviderOrBlock::Provider(self.provider())),
+                filter_params,
+                num_hash,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+
+            // size check but only if range is multiple blocks, so we always return all
+            // logs of a single block
+            let is_multi_block_range = from_block != to_block;
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(EthFilterError::QueryExceedsMaxResults {
+                    max_logs: self.max_logs_per_response,
+                    from_block,
+                    to_block: num_hash.number.saturating_sub(1),
+                });
+            }
+        }
+
+        Ok(())
     }
 
     /// Retrieves receipts and block from cache if near the tip (4 blocks), otherwise only receipts.
@@ -802,8 +951,172 @@ impl From<ProviderError> for EthFilterError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EthApi;
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
+    use alloy_primitives::{address, b256, logs_bloom, Address, Bytes, B256};
     use rand::Rng;
-    use reth_testing_utils::generators;
+    use reth_chain_state::{ExecutedBlockWithTrieUpdates, NewCanonicalChain};
+    use reth_chainspec::MAINNET;
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Block, Receipt, SealedBlock};
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, CanonChainTracker, DatabaseProviderFactory, ExecutionOutcome, HistoryWriter,
+        ProviderFactory, PruneCheckpointWriter, StageCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+    use reth_rpc_eth_types::{FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle};
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::{pool::BlockingTaskPool, TokioTaskExecutor};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use revm::db::BundleState;
+
+    /// Address of the logs matching [`filter`].
+    const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+
+    /// Address of the logs not matching [`filter`].
+    const OTHER_ADDRESS: Address = address!("0000000000000000000000000000000000000002");
+
+    /// Topic of all logs.
+    const TOPIC: B256 = b256!("0000000000000000000000000000000000000000000000000000000000000003");
+
+    type TestEthFilter =
+        EthFilter<EthApi<BlockchainProvider<MockNodeTypesWithDB>, TestPool, (), EthEvmConfig>>;
+
+    fn eth_filter(provider: BlockchainProvider<MockNodeTypesWithDB>) -> TestEthFilter {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(MAINNET.clone()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        EthFilter::new(eth_api, EthFilterConfig::default(), Box::new(TokioTaskExecutor::default()))
+    }
+
+    /// Returns the filter for the logs of [`ADDRESS`] with [`TOPIC`] in the range.
+    fn filter(range: RangeInclusive<u64>) -> Filter {
+        Filter::new()
+            .from_block(*range.start())
+            .to_block(*range.end())
+            .address(ADDRESS)
+            .event_signature(TOPIC)
+    }
+
+    /// Returns the numbers of the blocks of the logs matching the filter.
+    async fn log_blocks(eth_filter: &TestEthFilter, range: RangeInclusive<u64>) -> Vec<u64> {
+        eth_filter
+            .logs(filter(range))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|log| log.block_number.unwrap())
+            .collect()
+    }
+
+    /// Returns blocks with a single transaction on top of the parent, and their receipts.
+    ///
+    /// The log of the receipt is emitted by [`ADDRESS`] if the block number is `matching`, and by
+    /// [`OTHER_ADDRESS`] otherwise.
+    fn blocks_with_logs(
+        range: RangeInclusive<u64>,
+        mut parent: B256,
+        matching: impl Fn(u64) -> bool,
+    ) -> Vec<(RecoveredBlock<Block>, Receipt)> {
+        let mut rng = generators::rng();
+        random_block_range(
+            &mut rng,
+            range,
+            BlockRangeParams { tx_count: 1..2, ..Default::default() },
+        )
+        .into_iter()
+        .map(|block| {
+            let address = if matching(block.number) { ADDRESS } else { OTHER_ADDRESS };
+            let receipt = Receipt {
+                success: true,
+                logs: vec![alloy_primitives::Log::new_unchecked(
+                    address,
+                    vec![TOPIC],
+                    Bytes::default(),
+                )],
+                ..Default::default()
+            };
+
+            let (mut header, body) = block.split_header_body();
+            header.parent_hash = parent;
+            header.logs_bloom = logs_bloom(&receipt.logs);
+            let block = SealedBlock::<Block>::seal_parts(header, body);
+            parent = block.hash();
+
+            (block.try_recover().unwrap(), receipt)
+        })
+        .collect()
+    }
+
+    /// Returns a provider factory with the blocks after the genesis block.
+    fn factory_with_blocks(
+        blocks: &[(RecoveredBlock<Block>, Receipt)],
+    ) -> ProviderFactory<MockNodeTypesWithDB> {
+        let factory = create_test_provider_factory();
+        init_genesis(&factory).unwrap();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .append_blocks_with_state(
+                blocks.iter().map(|(block, _)| block.clone()).collect(),
+                &ExecutionOutcome::new(
+                    BundleState::default(),
+                    blocks.iter().map(|(_, receipt)| vec![receipt.clone()]).collect(),
+                    1,
+                    Vec::new(),
+                ),
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        factory
+    }
+
+    /// Builds the log index for the range of the blocks, as if the blocks before it were pruned.
+    fn index_logs(
+        factory: &ProviderFactory<MockNodeTypesWithDB>,
+        blocks: &[(RecoveredBlock<Block>, Receipt)],
+        range: RangeInclusive<u64>,
+    ) {
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let pruned = *range.start() - 1;
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::LogIndex,
+                PruneCheckpoint {
+                    block_number: Some(pruned),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(*range.start()),
+                },
+            )
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::LogIndex, StageCheckpoint::new(pruned)).unwrap();
+        for (block, receipt) in blocks.iter().filter(|(block, _)| range.contains(&block.number)) {
+            provider_rw.update_log_index(block.number, &receipt.logs).unwrap();
+        }
+        provider_rw.commit().unwrap();
+    }
 
     #[test]
     fn test_block_range_iter() {
@@ -826,4 +1139,84 @@ mod tests {
 
         assert_eq!(end, *range.end());
     }
+
+    #[tokio::test]
+    async fn logs_from_log_index() {
+        let genesis_hash = MAINNET.genesis_hash();
+        let blocks = blocks_with_logs(1..=10, genesis_hash, |number| number % 2 == 0);
+        let factory = factory_with_blocks(&blocks);
+        let eth_filter = eth_filter(BlockchainProvider::new(factory.clone()).unwrap());
+
+        // ranges before, inside, after and around the indexed blocks 4 to 7
+        let ranges = [1..=3, 2..=5, 4..=7, 5..=6, 6..=9, 8..=10, 1..=10];
+        let mut bloom_logs = Vec::new();
+        for range in ranges.clone() {
+            let logs = eth_filter.logs(filter(range.clone())).await.unwrap();
+            assert_eq!(
+                logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>(),
+                range.filter(|number| number % 2 == 0).collect::<Vec<_>>()
+            );
+            bloom_logs.push(logs);
+        }
+
+        index_logs(&factory, &blocks, 4..=7);
+        for (range, bloom_logs) in ranges.into_iter().zip(bloom_logs) {
+            assert_eq!(eth_filter.logs(filter(range)).await.unwrap(), bloom_logs);
+        }
+
+        // without the index entries of blocks 6 and 7, only the blocks outside of the index are
+        // scanned for the log of block 6
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .unwind_log_index(
+                blocks[5..7].iter().flat_map(|(block, receipt)| {
+                    receipt.logs.iter().map(|log| (block.number, log))
+                }),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(log_blocks(&eth_filter, 1..=10).await, vec![2, 4, 8, 10]);
+    }
+
+    #[tokio::test]
+    async fn logs_of_in_memory_blocks_above_log_index() {
+        let genesis_hash = MAINNET.genesis_hash();
+        let blocks = blocks_with_logs(1..=8, genesis_hash, |number| number % 2 == 0);
+        let factory = factory_with_blocks(&blocks);
+        index_logs(&factory, &blocks, 4..=8);
+
+        // the in-memory chain replaces the indexed blocks 7 and 8, and all of its blocks match
+        let in_memory_blocks = blocks_with_logs(7..=9, blocks[5].0.hash(), |_| true);
+        let provider = BlockchainProvider::new(factory).unwrap();
+        provider.canonical_in_memory_state().update_chain(NewCanonicalChain::Commit {
+            new: in_memory_blocks
+                .iter()
+                .map(|(block, receipt)| {
+                    ExecutedBlockWithTrieUpdates::new(
+                        Arc::new(block.clone()),
+                        Arc::new(ExecutionOutcome {
+                            receipts: vec![vec![receipt.clone()]],
+                            first_block: block.number,
+                            ..Default::default()
+                        }),
+                        Default::default(),
+                        Default::default(),
+                    )
+                })
+                .collect(),
+        });
+        provider.set_canonical_head(in_memory_blocks[2].0.clone_sealed_header());
+        assert_eq!(provider.log_index_range().unwrap(), Some(4..=6));
+
+        let eth_filter = eth_filter(provider);
+        let logs = eth_filter.logs(filter(1..=9)).await.unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>(),
+            vec![2, 4, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            logs.iter().map(|log| log.block_hash.unwrap()).skip(3).collect::<Vec<_>>(),
+            in_memory_blocks.iter().map(|(block, _)| block.hash()).collect::<Vec<_>>()
+        );
+    }
 }
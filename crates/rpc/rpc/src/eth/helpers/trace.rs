@@ -1,11 +1,23 @@
 //! Contains RPC handler implementations specific to tracing.
 
-use reth_evm::ConfigureEvm;
-use reth_provider::{BlockReader, ProviderHeader, ProviderTx};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::U256;
+use reth_chainspec::{ChainSpecProvider, EthereumHardfork, EthereumHardforks};
+use reth_evm::{
+    env::EvmEnv, state_change::post_block_balance_increments, system_calls::SystemCaller,
+    ConfigureEvm, ConfigureEvmEnv, Database,
+};
+use reth_evm_ethereum::dao_fork::{DAO_HARDFORK_ACCOUNTS, DAO_HARDFORK_BENEFICIARY};
+use reth_primitives::RecoveredBlock;
+use reth_provider::{BlockReader, ProviderBlock, ProviderHeader, ProviderTx};
 use reth_rpc_eth_api::{
-    helpers::{LoadState, Trace},
-    FromEvmError,
+    helpers::{trace::increment_balances, LoadState, Trace},
+    FromEvmError, RpcNodeCore,
 };
+use reth_rpc_eth_types::EthApiError;
+use revm::DatabaseCommit;
+use revm_primitives::{Account, AccountStatus, EvmState};
+use std::fmt::Display;
 
 use crate::EthApi;
 
@@ -21,4 +33,55 @@ where
     >,
     Provider: BlockReader,
 {
+    fn apply_post_execution_changes<DB: Send + Database<Error: Display> + DatabaseCommit>(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Self::Provider>>,
+        db: &mut DB,
+        evm_env: &EvmEnv<<Self::Evm as ConfigureEvmEnv>::Spec>,
+    ) -> Result<(), Self::Error> {
+        let chain_spec = self.provider().chain_spec();
+
+        if chain_spec.is_prague_active_at_timestamp(block.timestamp()) {
+            let mut system_caller =
+                SystemCaller::new(self.evm_config().clone(), chain_spec.clone());
+            system_caller.post_block_withdrawal_requests_contract_call(db, evm_env).map_err(
+                |_| EthApiError::EvmCustom("failed to apply 7002 system call".to_string()),
+            )?;
+            system_caller.post_block_consolidation_requests_contract_call(db, evm_env).map_err(
+                |_| EthApiError::EvmCustom("failed to apply 7251 system call".to_string()),
+            )?;
+        }
+
+        let mut balance_increments = post_block_balance_increments(&chain_spec, block);
+
+        // Irregular state change at Ethereum DAO hardfork
+        if chain_spec
+            .ethereum_fork_activation(EthereumHardfork::Dao)
+            .transitions_at_block(block.number())
+        {
+            let mut drained = EvmState::default();
+            let mut drained_balance = U256::ZERO;
+            for address in DAO_HARDFORK_ACCOUNTS {
+                let Some(mut info) =
+                    db.basic(address).map_err(|err| EthApiError::EvmCustom(err.to_string()))?
+                else {
+                    continue
+                };
+                drained_balance += std::mem::take(&mut info.balance);
+                drained.insert(
+                    address,
+                    Account { info, storage: Default::default(), status: AccountStatus::Touched },
+                );
+            }
+            db.commit(drained);
+
+            // return balance to DAO beneficiary.
+            *balance_increments.entry(DAO_HARDFORK_BENEFICIARY).or_default() +=
+                drained_balance.to::<u128>();
+        }
+
+        increment_balances(db, balance_increments)?;
+
+        Ok(())
+    }
 }
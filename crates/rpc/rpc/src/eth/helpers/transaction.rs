@@ -5,10 +5,11 @@ use alloy_primitives::{Bytes, B256};
 use reth_provider::{BlockReader, BlockReaderIdExt, ProviderTx, TransactionsProvider};
 use reth_rpc_eth_api::{
     helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
-    FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt,
+    EthApiTypes, FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt,
 };
 use reth_rpc_eth_types::utils::recover_raw_transaction;
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use std::time::Instant;
 
 impl<Provider, Pool, Network, EvmConfig> EthTransactions
     for EthApi<Provider, Pool, Network, EvmConfig>
@@ -25,19 +26,52 @@ where
     ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
+        self.submit_raw_transaction(tx, None).await
+    }
+
+    async fn send_raw_transaction_with_deadline(
+        &self,
+        tx: Bytes,
+        deadline: Instant,
+    ) -> Result<B256, Self::Error> {
+        self.submit_raw_transaction(tx, Some(deadline)).await
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Self: EthTransactions,
+    Provider: BlockReader,
+{
+    /// Decodes and recovers the transaction and submits it to the pool with an optional deadline
+    /// after which it is evicted.
+    async fn submit_raw_transaction(
+        &self,
+        tx: Bytes,
+        deadline: Option<Instant>,
+    ) -> Result<B256, <Self as EthApiTypes>::Error> {
         let recovered = recover_raw_transaction(&tx)?;
 
         // broadcast raw transaction to subscribers if there is any.
         self.broadcast_raw_transaction(tx);
 
-        let pool_transaction = <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered);
+        let pool_transaction =
+            <<Self as RpcNodeCore>::Pool as TransactionPool>::Transaction::from_pooled(recovered);
 
         // submit the transaction to the pool with a `Local` origin
-        let hash = self
-            .pool()
-            .add_transaction(TransactionOrigin::Local, pool_transaction)
-            .await
-            .map_err(Self::Error::from_eth_err)?;
+        let hash = match deadline {
+            Some(deadline) => {
+                self.pool()
+                    .add_transaction_with_deadline(
+                        TransactionOrigin::Local,
+                        pool_transaction,
+                        deadline,
+                    )
+                    .await
+            }
+            None => self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await,
+        }
+        .map_err(<Self as EthApiTypes>::Error::from_eth_err)?;
 
         Ok(hash)
     }
@@ -70,24 +104,24 @@ mod tests {
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, TestPool},
+        TransactionPool,
+    };
 
-    #[tokio::test]
-    async fn send_raw_transaction() {
+    fn build_test_eth_api(
+        pool: TestPool,
+    ) -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
         let noop_provider = NoopProvider::default();
-        let noop_network_provider = NoopNetwork::default();
-
-        let pool = testing_pool();
-
         let evm_config = EthEvmConfig::new(noop_provider.chain_spec());
         let cache = EthStateCache::spawn(noop_provider.clone(), Default::default());
         let fee_history_cache = FeeHistoryCache::new(FeeHistoryCacheConfig::default());
-        let eth_api = EthApi::new(
+        EthApi::new(
             noop_provider.clone(),
-            pool.clone(),
-            noop_network_provider,
+            pool,
+            NoopNetwork::default(),
             cache.clone(),
-            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            GasPriceOracle::new(noop_provider, Default::default(), cache),
             ETHEREUM_BLOCK_GAS_LIMIT_30M,
             DEFAULT_MAX_SIMULATE_BLOCKS,
             DEFAULT_ETH_PROOF_WINDOW,
@@ -95,7 +129,13 @@ mod tests {
             fee_history_cache,
             evm_config,
             DEFAULT_PROOF_PERMITS,
-        );
+        )
+    }
+
+    #[tokio::test]
+    async fn send_raw_transaction() {
+        let pool = testing_pool();
+        let eth_api = build_test_eth_api(pool.clone());
 
         // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
         let tx_1 = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
@@ -122,4 +162,21 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_raw_transaction_with_deadline() {
+        let pool = testing_pool();
+        let eth_api = build_test_eth_api(pool.clone());
+        let mut raw_txs = eth_api.subscribe_to_raw_transactions();
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+        let deadline = Instant::now() + std::time::Duration::from_secs(60);
+
+        let hash = eth_api.send_raw_transaction_with_deadline(tx.clone(), deadline).await.unwrap();
+
+        // the transaction goes through the same plumbing as `eth_sendRawTransaction`
+        assert_eq!(raw_txs.try_recv().unwrap(), tx);
+        assert_eq!(pool.get(&hash).unwrap().deadline, Some(deadline));
+    }
 }
@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::{
-    pubsub::{Params, PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
+    pubsub::{PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
     FilteredParams, Header, Log,
 };
 use futures::StreamExt;
@@ -17,7 +17,7 @@ use reth_provider::{BlockNumReader, CanonStateSubscriptions};
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcNodeCore, RpcTransaction, TransactionCompat,
 };
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{logs_utils, PendingTransactionFilter, PubSubParams};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{NewTransactionEvent, PoolConsensusTx, TransactionPool};
@@ -71,7 +71,7 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<PubSubParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
@@ -88,7 +88,7 @@ async fn handle_accepted<Eth>(
     pubsub: Arc<EthPubSubInner<Eth>>,
     accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
-    params: Option<Params>,
+    params: Option<PubSubParams>,
 ) -> Result<(), ErrorObject<'static>>
 where
     Eth: RpcNodeCore<
@@ -104,42 +104,44 @@ where
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
+                Some(PubSubParams::Logs(filter)) => FilteredParams::new(Some(*filter)),
+                // an empty object is parsed as an empty pending transaction filter
+                Some(PubSubParams::PendingTransactions(filter)) if filter.is_empty() => {
+                    FilteredParams::default()
+                }
+                Some(PubSubParams::Bool(_) | PubSubParams::PendingTransactions(_)) => {
                     return Err(invalid_params_rpc_err("Invalid params for logs"))
                 }
-                _ => FilteredParams::default(),
+                None => FilteredParams::default(),
             };
             pipe_from_stream(accepted_sink, pubsub.log_stream(filter)).await
         }
         SubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
                 match params {
-                    Params::Bool(true) => {
+                    PubSubParams::Bool(true) => {
                         // full transaction objects requested
                         let stream = pubsub.full_pending_transaction_stream().filter_map(|tx| {
-                            let tx_value = match pubsub
-                                .eth_api
-                                .tx_resp_builder()
-                                .fill_pending(tx.transaction.to_consensus())
-                            {
-                                Ok(tx) => Some(tx),
-                                Err(err) => {
-                                    error!(target = "rpc",
-                                        %err,
-                                        "Failed to fill transaction with block context"
-                                    );
-                                    None
-                                }
-                            };
-                            std::future::ready(tx_value)
+                            std::future::ready(pubsub.fill_pending_transaction(tx))
                         });
                         return pipe_from_stream(accepted_sink, stream).await
                     }
-                    Params::Bool(false) | Params::None => {
+                    PubSubParams::Bool(false) => {
                         // only hashes requested
                     }
-                    Params::Logs(_) => {
+                    PubSubParams::PendingTransactions(filter) => {
+                        let full_transactions = filter.full_transactions;
+                        let stream = pubsub.filtered_pending_transaction_stream(*filter);
+                        if full_transactions {
+                            let stream = stream.filter_map(|tx| {
+                                std::future::ready(pubsub.fill_pending_transaction(tx))
+                            });
+                            return pipe_from_stream(accepted_sink, stream).await
+                        }
+                        let stream = stream.map(|tx| *tx.transaction.hash());
+                        return pipe_from_stream(accepted_sink, stream).await
+                    }
+                    PubSubParams::Logs(_) => {
                         return Err(invalid_params_rpc_err(
                             "Invalid params for newPendingTransactions",
                         ))
@@ -290,6 +292,43 @@ where
     ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
         self.eth_api.pool().new_pending_pool_transactions_listener()
     }
+
+    /// Returns a stream that yields all transactions emitted by the txpool that match the filter.
+    fn filtered_pending_transaction_stream(
+        &self,
+        filter: PendingTransactionFilter,
+    ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
+        self.full_pending_transaction_stream().filter(move |event| {
+            std::future::ready(
+                filter.matches(event.transaction.sender(), &event.transaction.transaction),
+            )
+        })
+    }
+}
+
+impl<Eth> EthPubSubInner<Eth>
+where
+    Eth: RpcNodeCore<Pool: TransactionPool>
+        + EthApiTypes<TransactionCompat: TransactionCompat<PoolConsensusTx<Eth::Pool>>>,
+{
+    /// Converts a transaction emitted by the txpool into its RPC representation.
+    fn fill_pending_transaction(
+        &self,
+        event: NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>,
+    ) -> Option<
+        <Eth::TransactionCompat as TransactionCompat<PoolConsensusTx<Eth::Pool>>>::Transaction,
+    > {
+        match self.eth_api.tx_resp_builder().fill_pending(event.transaction.to_consensus()) {
+            Ok(tx) => Some(tx),
+            Err(err) => {
+                error!(target = "rpc",
+                    %err,
+                    "Failed to fill transaction with block context"
+                );
+                None
+            }
+        }
+    }
 }
 
 impl<N: NodePrimitives, Eth> EthPubSubInner<Eth>
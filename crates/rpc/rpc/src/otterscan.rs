@@ -12,10 +12,12 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_provider::{BlockNumReader, HistoryReader};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction, TransactionCompat,
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    TransactionCompat,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
@@ -24,9 +26,14 @@ use revm_inspectors::{
     transfer::{TransferInspector, TransferKind},
 };
 use revm_primitives::ExecutionResult;
+use std::ops::RangeInclusive;
 
 const API_LEVEL: u64 = 8;
 
+/// Maximum number of transactions of a `ots_searchTransactionsBefore` and
+/// `ots_searchTransactionsAfter` page.
+const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -62,6 +69,173 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + TraceExt
+        + 'static,
+    Eth::Provider: HistoryReader,
+{
+    /// Returns the numbers of at most `limit` blocks in the given range that changed the state of
+    /// `address`, starting at the end of the range if `newest_first` is set.
+    async fn address_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<u64>,
+        limit: usize,
+        newest_first: bool,
+    ) -> RpcResult<Vec<u64>> {
+        self.eth
+            .spawn_blocking_io(move |this| {
+                this.provider()
+                    .account_history_blocks(address, range, limit, newest_first)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Returns the transactions of the given block whose call tree touches `address`, together
+    /// with their receipts, in block order.
+    async fn block_transactions_touching(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<Vec<(RpcTransaction<Eth::NetworkTypes>, OtsTransactionReceipt)>> {
+        let block_id = block_number.into();
+
+        // An address can be part of a transaction without being its sender or recipient, so the
+        // whole call tree of every transaction is checked.
+        let touched = self
+            .eth
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |_tx_info, inspector, _, _, _| {
+                    Ok(inspector.into_traces().into_nodes().into_iter().any(
+                        |CallTraceNode { trace, .. }| {
+                            trace.caller == address || trace.address == address
+                        },
+                    ))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+
+        if !touched.contains(&true) {
+            return Ok(Vec::new())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        let timestamp = Some(block.header.timestamp());
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        if transactions.len() != receipts.len() || transactions.len() != touched.len() {
+            return Err(internal_rpc_err(
+                "the number of transactions does not match the number of receipts",
+            ))
+        }
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(touched)
+            .filter(|(_, touched)| *touched)
+            .map(|((tx, receipt), _)| {
+                let receipt = ots_transaction_receipt(&receipt, tx.ty(), timestamp);
+                (tx, receipt)
+            })
+            .collect())
+    }
+
+    /// Collects the transactions touching `address` from the blocks in `range` that changed its
+    /// state until at least `page_size` transactions were found.
+    ///
+    /// The blocks are visited from the end of the range if `newest_first` is set, and looked up
+    /// in batches, so only the part of the history that is needed for the page is read.
+    ///
+    /// Like Erigon, all matching transactions of the last visited block are included, so a page
+    /// may contain more than `page_size` transactions. Transactions of a block are returned in
+    /// reverse order if `newest_first` is set. The page size is capped at
+    /// [`MAX_SEARCH_PAGE_SIZE`], as every visited block is traced.
+    ///
+    /// Returns the transactions, their receipts and whether any candidate blocks are left.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<u64>,
+        page_size: usize,
+        newest_first: bool,
+    ) -> RpcResult<(Vec<RpcTransaction<Eth::NetworkTypes>>, Vec<OtsTransactionReceipt>, bool)> {
+        let page_size = page_size.min(MAX_SEARCH_PAGE_SIZE);
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+
+        // A block may not contain a matching transaction, so one more block than strictly needed
+        // is looked up per batch.
+        let batch_size = page_size.saturating_add(1);
+        let mut remaining = Some(range);
+
+        while txs.len() < page_size {
+            let Some(range) = remaining.take() else { return Ok((txs, receipts, false)) };
+            let blocks = self
+                .address_history_blocks(address, range.clone(), batch_size, newest_first)
+                .await?;
+            let exhausted = blocks.len() < batch_size;
+
+            let mut blocks = blocks.into_iter().peekable();
+            while let Some(block_number) = blocks.next_if(|_| txs.len() < page_size) {
+                let mut matches = self.block_transactions_touching(address, block_number).await?;
+                if newest_first {
+                    matches.reverse();
+                }
+                for (tx, receipt) in matches {
+                    txs.push(tx);
+                    receipts.push(receipt);
+                }
+
+                // Only the blocks after the visited one in the requested order are left.
+                remaining = if newest_first {
+                    block_number.checked_sub(1).map(|end| *range.start()..=end)
+                } else {
+                    block_number.checked_add(1).map(|start| start..=*range.end())
+                };
+            }
+
+            if blocks.peek().is_some() {
+                return Ok((txs, receipts, true))
+            }
+            if exhausted {
+                return Ok((txs, receipts, false))
+            }
+        }
+
+        let has_more = match remaining {
+            Some(range) => {
+                !self.address_history_blocks(address, range, 1, newest_first).await?.is_empty()
+            }
+            None => false,
+        };
+
+        Ok((txs, receipts, has_more))
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -74,6 +248,7 @@ where
         > + EthTransactions
         + TraceExt
         + 'static,
+    Eth::Provider: HistoryReader,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
     async fn get_header_by_number(
@@ -255,32 +430,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_transaction_receipt(&receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -292,21 +442,47 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // A block number of 0 requests the most recent transactions, which is the first page.
+        let first_page = block_number == 0;
+        let end = if first_page {
+            self.eth.provider().best_block_number().map_err(EthApiError::from)?
+        } else {
+            block_number - 1
+        };
+
+        let (txs, receipts, has_more) =
+            self.search_transactions(address, 0..=end, page_size, true).await?;
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: !has_more })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // A block number of 0 requests the oldest transactions, which is the last page.
+        let last_page = block_number == 0;
+        let start = if last_page { 0 } else { block_number.saturating_add(1) };
+        let end = self.eth.provider().best_block_number().map_err(EthApiError::from)?;
+
+        let (mut txs, mut receipts, has_more) = if start > end {
+            (Vec::new(), Vec::new(), false)
+        } else {
+            self.search_transactions(address, start..=end, page_size, false).await?
+        };
+
+        // Pages are always ordered from the newest to the oldest transaction.
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page: !has_more, last_page })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -387,3 +563,35 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into the trimmed down receipt returned by the otterscan API.
+fn ots_transaction_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
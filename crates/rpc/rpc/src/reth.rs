@@ -1,12 +1,18 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_errors::RethResult;
 use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_eth_api::{helpers::EthTransactions, FromEthApiError};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
@@ -14,28 +20,58 @@ use tokio::sync::oneshot;
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Eth> {
+    inner: Arc<RethApiInner<Provider, Eth>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Eth> RethApi<Provider, Eth> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// The `eth_` API raw transactions are submitted with.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(provider: Provider, eth_api: Eth, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        let inner = Arc::new(RethApiInner { provider, eth_api, task_spawner });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Eth> RethApi<Provider, Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Submits the raw transaction like `eth_sendRawTransaction` and evicts it if it is still in
+    /// the pool once the deadline, a unix timestamp in seconds, has passed.
+    pub async fn send_raw_transaction_with_deadline(
+        &self,
+        tx: Bytes,
+        deadline: u64,
+    ) -> Result<B256, Eth::Error> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let Some(remaining) = deadline.checked_sub(now).filter(|remaining| *remaining > 0) else {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                "deadline must be in the future".to_string(),
+            )))
+        };
+
+        self.eth_api()
+            .send_raw_transaction_with_deadline(tx, Instant::now() + Duration::from_secs(remaining))
+            .await
+    }
+}
+
+impl<Provider, Eth> RethApi<Provider, Eth>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Eth: Send + Sync + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -86,9 +122,10 @@ where
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Eth> RethApiServer for RethApi<Provider, Eth>
 where
     Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Eth: EthTransactions + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -97,23 +134,34 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_sendRawTransactionWithDeadline`
+    async fn reth_send_raw_transaction_with_deadline(
+        &self,
+        bytes: Bytes,
+        deadline: u64,
+    ) -> RpcResult<B256> {
+        Self::send_raw_transaction_with_deadline(self, bytes, deadline).await.map_err(Into::into)
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Eth> std::fmt::Debug for RethApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Eth> Clone for RethApi<Provider, Eth> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Eth> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The `eth_` API raw transactions are submitted with.
+    eth_api: Eth,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
@@ -248,16 +248,21 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     ///   [`StageId::Execution`]
     /// - [`StaticFileSegment::Transactions`](reth_static_file_types::StaticFileSegment::Transactions)
     ///   -> [`StageId::Bodies`]
+    /// - [`StaticFileSegment::AccountChangeSets`](reth_static_file_types::StaticFileSegment::AccountChangeSets)
+    ///   and [`StaticFileSegment::StorageChangeSets`](reth_static_file_types::StaticFileSegment::StorageChangeSets)
+    ///   -> the lowest checkpoint of [`StageId::Execution`] and the stages reading the changesets,
+    ///   minus [`MINIMUM_PRUNING_DISTANCE`](reth_prune::MINIMUM_PRUNING_DISTANCE)
     ///
     /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
     /// lock is occupied.
     pub fn move_to_static_files(&self) -> RethResult<()> {
         // Copies data from database to static files
-        let lowest_static_file_height =
-            self.static_file_producer.lock().copy_to_static_files()?.min_block_num();
+        let highest_static_file_height =
+            self.static_file_producer.lock().copy_to_static_files()?.max_block_num();
 
-        // Deletes data which has been copied to static files.
-        if let Some(prune_tip) = lowest_static_file_height {
+        // Deletes data which has been copied to static files. Every segment is only pruned up to
+        // its own highest static file block.
+        if let Some(prune_tip) = highest_static_file_height {
             // Run the pruner so we don't potentially end up with higher height in the database vs
             // static files during a pipeline unwind
             let mut pruner = PrunerBuilder::new(Default::default())
@@ -287,6 +292,13 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
 
         let mut provider_rw = self.provider_factory.database_provider_rw()?;
 
+        // Stages unwind the changesets from the database, so the ones that were moved to static
+        // files have to be restored first.
+        if provider_rw.restore_change_sets_above(to)? {
+            UnifiedStorageWriter::commit_unwind(provider_rw)?;
+            provider_rw = self.provider_factory.database_provider_rw()?;
+        }
+
         for stage in unwind_pipeline {
             let stage_id = stage.id();
             let span = info_span!("Unwinding", stage = %stage_id);
@@ -596,11 +608,18 @@ mod tests {
 
     use super::*;
     use crate::{test_utils::TestStage, UnwindOutput};
+    use alloy_primitives::Address;
     use assert_matches::assert_matches;
     use reth_consensus::ConsensusError;
+    use reth_db_api::models::{AccountBeforeTx, StaticFileAccountChangeSet};
     use reth_errors::ProviderError;
-    use reth_provider::test_utils::{create_test_provider_factory, MockNodeTypesWithDB};
+    use reth_provider::{
+        providers::StaticFileWriter,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        ChangeSetReader, StaticFileProviderFactory,
+    };
     use reth_prune::PruneModes;
+    use reth_static_file_types::StaticFileSegment;
     use reth_testing_utils::generators::{self, random_block_with_parent};
     use tokio_stream::StreamExt;
 
@@ -858,6 +877,60 @@ mod tests {
         assert_eq!(post_unwind_commit_counter_c.load(Ordering::Relaxed), 1);
     }
 
+    /// Unwinds a pipeline with changesets in static files, which have to be restored to the
+    /// database before the stages unwind them.
+    #[test]
+    fn unwind_pipeline_restores_change_sets() {
+        let provider_factory = create_test_provider_factory();
+
+        let static_file_provider = provider_factory.static_file_provider();
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        for block_number in 0..=10 {
+            let changes = vec![AccountBeforeTx {
+                address: Address::with_last_byte(block_number as u8),
+                info: None,
+            }];
+            writer
+                .append_account_changeset(&StaticFileAccountChangeSet { changes }, block_number)
+                .unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Other("A"), StageCheckpoint::new(10)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut pipeline = Pipeline::<MockNodeTypesWithDB>::builder()
+            .add_stage(
+                TestStage::new(StageId::Other("A"))
+                    .add_unwind(Ok(UnwindOutput { checkpoint: StageCheckpoint::new(5) })),
+            )
+            .build(
+                provider_factory.clone(),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+            );
+        pipeline.unwind(5, None).expect("Could not unwind pipeline");
+
+        // Changesets above the unwind target are moved back to the database
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(5)
+        );
+        let provider = provider_factory.provider().unwrap();
+        for block_number in 6..=10 {
+            assert_eq!(
+                provider.account_block_changeset(block_number).unwrap(),
+                vec![AccountBeforeTx {
+                    address: Address::with_last_byte(block_number as u8),
+                    info: None
+                }]
+            );
+        }
+    }
+
     /// Unwinds a pipeline with intermediate progress.
     #[tokio::test]
     async fn unwind_pipeline_with_intermediate_progress() {
@@ -39,8 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
-        PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexStorageHistoryStage, LogIndexStage, MerkleStage,
+        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
+        TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -78,6 +79,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`LogIndexStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -433,6 +435,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    LogIndexStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -451,5 +454,13 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            // The log index is optional, it's only added if it's enabled in the config.
+            .add_stage_opt(self.stages_config.log_index.enabled.then(|| {
+                LogIndexStage::new(
+                    self.stages_config.log_index,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.receipts,
+                )
+            }))
     }
 }
@@ -180,7 +180,8 @@ where
     ///
     /// This function compares the highest receipt number recorded in the database with that in the
     /// static file to detect any discrepancies due to unexpected shutdowns or database rollbacks.
-    /// **If the height in the static file is higher**, it rolls back (unwinds) the static file.
+    /// **If the height in the static file is higher**, the receipts are kept and reused, since they
+    /// belong to blocks that already have bodies.
     /// **Conversely, if the height in the database is lower**, it triggers a rollback in the
     /// database (by returning [`StageError`]) until the heights in both the database and static
     /// file match.
@@ -214,16 +215,27 @@ where
         // Check if we had any unexpected shutdown after committing to static files, but
         // NOT committing to database.
         match next_static_file_receipt_num.cmp(&next_receipt_num) {
+            // Receipts ahead of the checkpoint belong to blocks with bodies, they're imported from
+            // era1 archives or written by an execution that wasn't committed to the database. They
+            // are kept, and not written again.
+            //
             // It can be equal when it's a chain of empty blocks, but we still need to update the
             // last block in the range.
             Ordering::Greater | Ordering::Equal => {
-                let mut static_file_producer =
-                    static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
-                static_file_producer
-                    .prune_receipts(next_static_file_receipt_num - next_receipt_num, checkpoint)?;
-                // Since this is a database <-> static file inconsistency, we commit the change
-                // straight away.
-                static_file_producer.commit()?;
+                if static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::Receipts)
+                    .is_none_or(|block| block < checkpoint)
+                {
+                    let mut static_file_producer =
+                        static_file_provider.latest_writer(StaticFileSegment::Receipts)?;
+                    static_file_producer.prune_receipts(
+                        next_static_file_receipt_num - next_receipt_num,
+                        checkpoint,
+                    )?;
+                    // Since this is a database <-> static file inconsistency, we commit the change
+                    // straight away.
+                    static_file_producer.commit()?;
+                }
             }
             Ordering::Less => {
                 // If we are already in the process of unwind, this might be fine because we will
@@ -0,0 +1,332 @@
+use super::{load_history_indices, DEFAULT_CACHE_THRESHOLD};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_config::config::{EtlConfig, LogIndexConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    models::ShardedKey,
+    table::{Decode, Key},
+    transaction::DbTxMut,
+};
+use reth_etl::Collector;
+use reth_provider::{
+    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter, ReceiptProvider,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+use tracing::info;
+
+/// Stage indexing the addresses and topics of the logs in the receipts generated by the
+/// [`ExecutionStage`][crate::stages::ExecutionStage], so `eth_getLogs` doesn't have to scan the
+/// bloom filters of every block in the queried range. The index is sharded like the history
+/// indices, take a look at [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The stage is optional, it's only added to the pipeline if it's enabled in the
+/// [`LogIndexConfig`].
+#[derive(Debug)]
+pub struct LogIndexStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl LogIndexStage {
+    /// Create new instance of [`LogIndexStage`].
+    pub const fn new(
+        config: LogIndexConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for LogIndexStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for LogIndexStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter
+        + ReceiptProvider,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::LogIndex
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have leftovers of a previously built index. We clear the tables
+        // since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        info!(target: "sync::stages::log_index::exec", ?first_sync, "Collecting indices");
+        let (address_collector, topic_collector) =
+            collect_log_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::log_index::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        for block_number in range {
+            let receipts = provider.receipts_by_block(block_number.into())?.unwrap_or_default();
+            provider.unwind_log_index(
+                receipts.iter().flat_map(|receipt| receipt.logs()).map(|log| (block_number, log)),
+            )?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the log address and topic indices of the receipts in the block range into two
+/// [`Collector`]s.
+///
+/// Works like [`collect_history_indices`](super::collect_history_indices), except that the
+/// indices are read from the receipts of every block instead of a changeset table.
+#[expect(clippy::type_complexity)]
+fn collect_log_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+>
+where
+    Provider: ReceiptProvider,
+{
+    // The ETL buffer is shared between both collectors.
+    let mut address_collector = Collector::new(etl_config.file_size / 2, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size / 2, etl_config.dir.clone());
+    let mut address_cache: HashMap<Address, Vec<u64>> = HashMap::default();
+    let mut topic_cache: HashMap<B256, Vec<u64>> = HashMap::default();
+
+    // observability
+    let total_blocks = range.end() - range.start() + 1;
+    let interval = (total_blocks / 1000).max(1);
+
+    let mut flush_counter = 0;
+    for (idx, block_number) in range.enumerate() {
+        for receipt in provider.receipts_by_block(block_number.into())?.unwrap_or_default() {
+            for log in receipt.logs() {
+                push_block(&mut address_cache, log.address, block_number);
+                for topic in log.topics() {
+                    push_block(&mut topic_cache, *topic, block_number);
+                }
+            }
+        }
+
+        let idx = idx as u64;
+        if idx > 0 && idx % interval == 0 && total_blocks > 1000 {
+            info!(target: "sync::stages::log_index", progress = %format!("{:.4}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+
+        // Make sure we only flush the caches every DEFAULT_CACHE_THRESHOLD blocks.
+        flush_counter += 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush(&mut address_cache, &mut address_collector)?;
+            flush(&mut topic_cache, &mut topic_collector)?;
+            flush_counter = 0;
+        }
+    }
+    flush(&mut address_cache, &mut address_collector)?;
+    flush(&mut topic_cache, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+/// Adds the block number to the indices of the key, unless it's already the last one.
+fn push_block<K: Eq + Hash>(cache: &mut HashMap<K, Vec<u64>>, key: K, block_number: BlockNumber) {
+    let indices = cache.entry(key).or_default();
+    if indices.last() != Some(&block_number) {
+        indices.push(block_number);
+    }
+}
+
+/// Moves the cached indices into the collector, keyed by their highest block number.
+fn flush<K>(
+    cache: &mut HashMap<K, Vec<u64>>,
+    collector: &mut Collector<ShardedKey<K>, BlockNumberList>,
+) -> Result<(), StageError>
+where
+    K: Copy + Eq + Hash,
+    ShardedKey<K>: Key,
+{
+    for (key, indices) in cache.drain() {
+        let last = *indices.last().expect("qed");
+        collector.insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(indices))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestStageDB, TEST_LOG_ADDRESS, TEST_LOG_TOPIC};
+    use reth_provider::DatabaseProviderFactory;
+    use std::collections::BTreeMap;
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = LogIndexStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = LogIndexStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    fn address_index(db: &TestStageDB) -> BTreeMap<u64, Vec<u64>> {
+        db.table::<tables::LogAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.key, TEST_LOG_ADDRESS);
+                (key.highest_block_number, list.iter().collect())
+            })
+            .collect()
+    }
+
+    fn topic_index(db: &TestStageDB) -> BTreeMap<u64, Vec<u64>> {
+        db.table::<tables::LogTopicIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.key, TEST_LOG_TOPIC);
+                (key.highest_block_number, list.iter().collect())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn index_from_genesis() {
+        let db = TestStageDB::default();
+        db.insert_blocks_with_logs(5).unwrap();
+
+        run(&db, 3, None);
+        assert_eq!(address_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2, 3])]));
+        assert_eq!(topic_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2, 3])]));
+
+        // continue from the checkpoint, appending to the last shard
+        run(&db, 5, Some(3));
+        assert_eq!(address_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2, 3, 4, 5])]));
+        assert_eq!(topic_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2, 3, 4, 5])]));
+
+        unwind(&db, 5, 2);
+        assert_eq!(address_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2])]));
+        assert_eq!(topic_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2])]));
+    }
+
+    #[tokio::test]
+    async fn first_sync_clears_leftovers() {
+        let db = TestStageDB::default();
+        db.insert_blocks_with_logs(3).unwrap();
+        db.commit(|tx| {
+            tx.put::<tables::LogAddressIndex>(
+                ShardedKey::last(TEST_LOG_ADDRESS),
+                BlockNumberList::new([7, 8]).unwrap(),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        run(&db, 3, None);
+        assert_eq!(address_index(&db), BTreeMap::from([(u64::MAX, vec![0, 1, 2, 3])]));
+    }
+}
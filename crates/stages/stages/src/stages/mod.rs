@@ -14,6 +14,8 @@ mod headers;
 mod index_account_history;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index addresses and topics of logs
+mod log_index;
 /// Stage for computing state root.
 mod merkle;
 mod prune;
@@ -32,6 +34,7 @@ pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
 pub use index_storage_history::*;
+pub use log_index::*;
 pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
@@ -54,6 +57,7 @@ mod tests {
     };
     use reth_db_api::{
         cursor::{DbCursorRO, DbCursorRW},
+        models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet},
         table::Table,
         transaction::{DbTx, DbTxMut},
     };
@@ -75,7 +79,7 @@ mod tests {
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_receipt, BlockRangeParams,
     };
-    use std::{io::Write, sync::Arc};
+    use std::{io::Write, ops::RangeInclusive, sync::Arc};
 
     #[tokio::test]
     #[ignore]
@@ -432,8 +436,8 @@ mod tests {
             db.factory.block_body_indices(block).unwrap().map(|b| b.last_tx_num())
         );
 
-        let block = 86;
-        save_checkpoint_and_check(&db, StageId::Execution, block, None);
+        // Receipts above the execution checkpoint are only deleted above the bodies checkpoint.
+        save_checkpoint_and_check(&db, StageId::Execution, 86, None);
         assert_eq!(
             db.factory
                 .static_file_provider()
@@ -511,4 +515,53 @@ mod tests {
         // Fill the gap, and ensure no unwind is necessary.
         update_db_and_check::<tables::Receipts>(&db, current + 1, None);
     }
+
+    /// Appends empty changesets for the given blocks to both changeset segments.
+    fn append_empty_change_sets(db: &TestStageDB, blocks: RangeInclusive<BlockNumber>) {
+        let static_file_provider = db.factory.static_file_provider();
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            let mut writer = static_file_provider.latest_writer(segment).unwrap();
+            for block_number in blocks.clone() {
+                if segment == StaticFileSegment::AccountChangeSets {
+                    writer
+                        .append_account_changeset(
+                            &StaticFileAccountChangeSet::default(),
+                            block_number,
+                        )
+                        .unwrap();
+                } else {
+                    writer
+                        .append_storage_changeset(
+                            &StaticFileStorageChangeSet::default(),
+                            block_number,
+                        )
+                        .unwrap();
+                }
+            }
+            writer.commit().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_consistency_change_sets() {
+        let db = seed_data(90).unwrap();
+        let highest_change_sets = |db: &TestStageDB| {
+            [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets].map(
+                |segment| db.factory.static_file_provider().get_highest_static_file_block(segment),
+            )
+        };
+
+        // Changesets are only copied to static files some time after the execution, so a
+        // checkpoint ahead of them doesn't require an unwind.
+        append_empty_change_sets(&db, 0..=50);
+        save_checkpoint_and_check(&db, StageId::Execution, 89, None);
+        assert_eq!(highest_change_sets(&db), [Some(50); 2]);
+
+        // Changesets above the execution checkpoint are left behind by an unwind which failed to
+        // commit to static files, so they're removed.
+        append_empty_change_sets(&db, 51..=89);
+        save_checkpoint_and_check(&db, StageId::Execution, 80, None);
+        assert_eq!(highest_change_sets(&db), [Some(80); 2]);
+    }
 }
@@ -1,3 +1,4 @@
+use alloy_primitives::{address, b256, Address, B256};
 use reth_stages_api::StageId;
 
 #[cfg(test)]
@@ -20,3 +21,10 @@ pub use set::TestStages;
 
 /// The test stage id
 pub const TEST_STAGE_ID: StageId = StageId::Other("TestStage");
+
+/// The address of the logs inserted by [`TestStageDB::insert_blocks_with_logs`]
+pub const TEST_LOG_ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+
+/// The topic of the logs inserted by [`TestStageDB::insert_blocks_with_logs`]
+pub const TEST_LOG_TOPIC: B256 =
+    b256!("0000000000000000000000000000000000000000000000000000000000000002");
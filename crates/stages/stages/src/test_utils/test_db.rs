@@ -1,4 +1,5 @@
-use alloy_primitives::{keccak256, Address, BlockNumber, TxHash, TxNumber, B256, U256};
+use super::{TEST_LOG_ADDRESS, TEST_LOG_TOPIC};
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, Log, TxHash, TxNumber, B256, U256};
 use reth_chainspec::MAINNET;
 use reth_db::{
     tables,
@@ -23,7 +24,9 @@ use reth_provider::{
     HistoryWriter, ProviderError, ProviderFactory, StaticFileProviderFactory,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_testing_utils::generators::ChangeSet;
+use reth_testing_utils::generators::{
+    self, random_block_range, random_receipt, BlockRangeParams, ChangeSet,
+};
 use std::{collections::BTreeMap, fmt::Debug, path::Path};
 use tempfile::TempDir;
 
@@ -299,6 +302,26 @@ impl TestStageDB {
         Ok(())
     }
 
+    /// Inserts random blocks up to `tip` with a single transaction each, whose receipt has a log of
+    /// [`TEST_LOG_ADDRESS`] with the [`TEST_LOG_TOPIC`].
+    pub fn insert_blocks_with_logs(&self, tip: BlockNumber) -> ProviderResult<()> {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=tip,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..2, ..Default::default() },
+        );
+        self.insert_blocks(blocks.iter(), StorageKind::Static)?;
+
+        let receipts = blocks.iter().enumerate().map(|(tx_num, block)| {
+            let mut receipt = random_receipt(&mut rng, &block.body().transactions[0], Some(0));
+            receipt.logs =
+                vec![Log::new_unchecked(TEST_LOG_ADDRESS, vec![TEST_LOG_TOPIC], Bytes::default())];
+            (block.number, vec![(tx_num as u64, receipt)])
+        });
+        self.insert_receipts_by_block(receipts.collect::<Vec<_>>(), StorageKind::Static)
+    }
+
     pub fn insert_tx_hash_numbers<I>(&self, tx_hash_numbers: I) -> ProviderResult<()>
     where
         I: IntoIterator<Item = (TxHash, TxNumber)>,
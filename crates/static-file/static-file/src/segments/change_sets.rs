@@ -0,0 +1,92 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{
+        BlockNumberAddress, StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+    },
+    transaction::DbTx,
+};
+use reth_provider::{providers::StaticFileWriter, DBProvider, StaticFileProviderFactory};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::AccountChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct AccountChangeSets;
+
+impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for AccountChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::AccountChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::AccountChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        let mut changesets_walker = changesets_cursor.walk_range(block_range.clone())?.peekable();
+
+        // Every block gets a row, blocks without changes have no entries in the database.
+        for block in block_range {
+            let mut changeset = StaticFileAccountChangeSet::default();
+            while let Some(entry) = changesets_walker.next_if(|entry| {
+                entry.as_ref().map_or(true, |(block_number, _)| *block_number == block)
+            }) {
+                let (_, account_before) = entry?;
+                changeset.changes.push(account_before);
+            }
+
+            static_file_writer.append_account_changeset(&changeset, block)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Static File segment responsible for [`StaticFileSegment::StorageChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct StorageChangeSets;
+
+impl<Provider: StaticFileProviderFactory + DBProvider> Segment<Provider> for StorageChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::StorageChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let static_file_provider = provider.static_file_provider();
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::StorageChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+        let mut changesets_walker = changesets_cursor
+            .walk_range(BlockNumberAddress::range(block_range.clone()))?
+            .peekable();
+
+        // Every block gets a row, blocks without changes have no entries in the database.
+        for block in block_range {
+            let mut changeset = StaticFileStorageChangeSet::default();
+            while let Some(entry) = changesets_walker.next_if(|entry| {
+                entry.as_ref().map_or(true, |(key, _)| key.block_number() == block)
+            }) {
+                let (key, entry) = entry?;
+                changeset.changes.push(StorageBeforeTx { address: key.address(), entry });
+            }
+
+            static_file_writer.append_storage_changeset(&changeset, block)?;
+        }
+
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ use reth_provider::{
     providers::StaticFileWriter, BlockReader, ChainStateBlockReader, DBProvider,
     DatabaseProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneModes, MINIMUM_PRUNING_DISTANCE};
 use reth_stages_types::StageId;
 use reth_static_file_types::{HighestStaticFiles, StaticFileTargets};
 use reth_storage_errors::provider::ProviderResult;
@@ -140,6 +140,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_change_sets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_change_sets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -175,6 +181,10 @@ where
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
+    /// Changesets are read by several stages after the execution, so they're only copied up to
+    /// the lowest checkpoint of these stages. They're additionally kept in the database for the
+    /// last [`MINIMUM_PRUNING_DISTANCE`] blocks, where reorgs unwind them.
+    ///
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
@@ -182,12 +192,27 @@ where
             .into_iter()
             .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
             .collect::<Result<Vec<_>, _>>()?;
+        let change_sets_checkpoint = [
+            StageId::Execution,
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::IndexAccountHistory,
+            StageId::IndexStorageHistory,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Option<Vec<_>>, _>>()?
+        .and_then(|checkpoints| checkpoints.into_iter().min())
+        .and_then(|block_number| block_number.checked_sub(MINIMUM_PRUNING_DISTANCE));
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
             block_meta: stages_checkpoints[2],
+            account_change_sets: change_sets_checkpoint,
+            storage_change_sets: change_sets_checkpoint,
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -230,6 +255,30 @@ where
             block_meta: finalized_block_numbers.block_meta.and_then(|finalized_block_number| {
                 self.get_static_file_target(highest_static_files.block_meta, finalized_block_number)
             }),
+            // StaticFile changesets only if the history isn't pruned according to the user
+            // configuration, so the history pruning can delete them from the database. If the
+            // pruning is enabled later, it reads the changesets that were already copied from
+            // static files.
+            account_change_sets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_change_sets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -261,16 +310,22 @@ mod tests {
     };
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db_api::{
+        database::Database,
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::DbTx,
+    };
+    use reth_primitives_traits::StorageEntry;
     use reth_provider::{
-        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
-        ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ChangeSetReader,
+        ProviderError, ProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
     };
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::generators::{
-        self, random_block_range, random_receipt, BlockRangeParams,
+        self, random_block_range, random_changeset_range, random_eoa_accounts, random_receipt,
+        BlockRangeParams,
     };
     use std::{sync::mpsc::channel, time::Duration};
     use tempfile::TempDir;
@@ -327,6 +382,8 @@ mod tests {
                 receipts: Some(1),
                 transactions: Some(1),
                 block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -335,7 +392,9 @@ mod tests {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
                 transactions: Some(0..=1),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -345,7 +404,9 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
 
@@ -355,6 +416,8 @@ mod tests {
                 receipts: Some(3),
                 transactions: Some(3),
                 block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -363,7 +426,9 @@ mod tests {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
                 transactions: Some(2..=3),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
@@ -373,7 +438,9 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
 
@@ -383,6 +450,8 @@ mod tests {
                 receipts: Some(4),
                 transactions: Some(4),
                 block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -391,7 +460,9 @@ mod tests {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
                 transactions: Some(4..=4),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
         assert_matches!(
@@ -404,9 +475,108 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
-                block_meta: None
+                block_meta: None,
+                account_change_sets: None,
+                storage_change_sets: None
+            }
+        );
+    }
+
+    #[test]
+    fn change_sets() {
+        let mut rng = generators::rng();
+        let db = TestStageDB::default();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=3,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        let accounts = random_eoa_accounts(&mut rng, 3);
+        let (mut changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            1..3,
+            1..3,
+        );
+        // A block changes each storage slot of an account at most once
+        for (_, _, entries) in changesets.iter_mut().flatten() {
+            entries.sort_by_key(|entry| entry.key);
+            entries.dedup_by_key(|entry| entry.key);
+        }
+        // Block without any state changes still gets a row in static files
+        changesets[1].clear();
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+
+        let static_file_producer =
+            StaticFileProducerInner::new(db.factory.clone(), PruneModes::default());
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_change_sets: Some(3),
+                storage_change_sets: Some(3),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                block_meta: None,
+                account_change_sets: Some(0..=3),
+                storage_change_sets: Some(0..=3),
             }
         );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = db.factory.static_file_provider();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            Some(3)
+        );
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(3)
+        );
+
+        for (block_number, changeset) in changesets.iter().enumerate() {
+            let block_number = block_number as u64;
+
+            let mut expected_accounts = changeset
+                .iter()
+                .map(|(address, account, _)| AccountBeforeTx {
+                    address: *address,
+                    info: Some(*account),
+                })
+                .collect::<Vec<_>>();
+            expected_accounts.sort_by_key(|account| account.address);
+            assert_eq!(
+                static_file_provider.account_block_changeset(block_number).unwrap(),
+                expected_accounts
+            );
+
+            let mut expected_storage = changeset
+                .iter()
+                .flat_map(|(address, _, entries)| {
+                    entries.iter().map(|entry| ((block_number, *address).into(), *entry))
+                })
+                .collect::<Vec<(BlockNumberAddress, StorageEntry)>>();
+            expected_storage.sort_by_key(|(key, entry)| (*key, entry.key));
+            assert_eq!(
+                static_file_provider.storage_changeset(block_number).unwrap(),
+                expected_storage
+            );
+        }
+
+        // Blocks past the static files are not silently treated as unchanged
+        assert_matches!(
+            static_file_provider.account_block_changeset(4),
+            Err(ProviderError::MissingStaticFileBlock(StaticFileSegment::AccountChangeSets, 4))
+        );
     }
 
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
@@ -434,6 +604,8 @@ mod tests {
                         receipts: Some(1),
                         transactions: Some(1),
                         block_meta: None,
+                        account_change_sets: None,
+                        storage_change_sets: None,
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -36,6 +36,12 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub block_meta: Option<BlockNumber>,
+    /// Highest static file block of account changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub account_change_sets: Option<BlockNumber>,
+    /// Highest static file block of storage changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub storage_change_sets: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -46,6 +52,8 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
             StaticFileSegment::BlockMeta => self.block_meta,
+            StaticFileSegment::AccountChangeSets => self.account_change_sets,
+            StaticFileSegment::StorageChangeSets => self.storage_change_sets,
         }
     }
 
@@ -56,12 +64,22 @@ impl HighestStaticFiles {
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
             StaticFileSegment::BlockMeta => &mut self.block_meta,
+            StaticFileSegment::AccountChangeSets => &mut self.account_change_sets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_change_sets,
         }
     }
 
     /// Returns an iterator over all static file segments
     fn iter(&self) -> impl Iterator<Item = Option<BlockNumber>> {
-        [self.headers, self.transactions, self.receipts, self.block_meta].into_iter()
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.block_meta,
+            self.account_change_sets,
+            self.storage_change_sets,
+        ]
+        .into_iter()
     }
 
     /// Returns the minimum block of all segments.
@@ -86,6 +104,10 @@ pub struct StaticFileTargets {
     pub transactions: Option<RangeInclusive<BlockNumber>>,
     /// Targeted range of block meta.
     pub block_meta: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of account changesets.
+    pub account_change_sets: Option<RangeInclusive<BlockNumber>>,
+    /// Targeted range of storage changesets.
+    pub storage_change_sets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
@@ -94,7 +116,9 @@ impl StaticFileTargets {
         self.headers.is_some() ||
             self.receipts.is_some() ||
             self.transactions.is_some() ||
-            self.block_meta.is_some()
+            self.block_meta.is_some() ||
+            self.account_change_sets.is_some() ||
+            self.storage_change_sets.is_some()
     }
 
     /// Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -105,6 +129,8 @@ impl StaticFileTargets {
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
             (self.block_meta.as_ref(), static_files.block_meta),
+            (self.account_change_sets.as_ref(), static_files.account_change_sets),
+            (self.storage_change_sets.as_ref(), static_files.storage_change_sets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -139,6 +165,8 @@ mod tests {
             receipts: Some(200),
             transactions: None,
             block_meta: None,
+            account_change_sets: None,
+            storage_change_sets: None,
         };
 
         // Test for headers segment
@@ -179,6 +207,8 @@ mod tests {
             receipts: Some(100),
             transactions: None,
             block_meta: None,
+            account_change_sets: None,
+            storage_change_sets: None,
         };
 
         // Minimum value among the available segments
@@ -196,6 +226,8 @@ mod tests {
             receipts: Some(100),
             transactions: Some(500),
             block_meta: Some(500),
+            account_change_sets: None,
+            storage_change_sets: None,
         };
 
         // Maximum value among the available segments
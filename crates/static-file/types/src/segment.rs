@@ -37,6 +37,12 @@ pub enum StaticFileSegment {
     /// Static File segment responsible for the `BlockBodyIndices`, `BlockOmmers`,
     /// `BlockWithdrawals` tables.
     BlockMeta,
+    #[strum(serialize = "accountchangesets")]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storagechangesets")]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -47,6 +53,8 @@ impl StaticFileSegment {
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
             Self::BlockMeta => "blockmeta",
+            Self::AccountChangeSets => "accountchangesets",
+            Self::StorageChangeSets => "storagechangesets",
         }
     }
 
@@ -54,7 +62,15 @@ impl StaticFileSegment {
     pub fn iter() -> impl Iterator<Item = Self> {
         // The order of segments is significant and must be maintained to ensure correctness. For
         // example, Transactions require BlockBodyIndices from Blockmeta to be sound.
-        [Self::Headers, Self::BlockMeta, Self::Transactions, Self::Receipts].into_iter()
+        [
+            Self::Headers,
+            Self::BlockMeta,
+            Self::Transactions,
+            Self::Receipts,
+            Self::AccountChangeSets,
+            Self::StorageChangeSets,
+        ]
+        .into_iter()
     }
 
     /// Returns the default configuration of the segment.
@@ -66,7 +82,10 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers | Self::BlockMeta => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
         }
     }
 
@@ -139,6 +158,12 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_change_sets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if a segment row is linked to a transaction.
     pub const fn is_tx_based(&self) -> bool {
         matches!(self, Self::Receipts | Self::Transactions)
@@ -146,7 +171,10 @@ impl StaticFileSegment {
 
     /// Returns `true` if a segment row is linked to a block.
     pub const fn is_block_based(&self) -> bool {
-        matches!(self, Self::Headers | Self::BlockMeta)
+        matches!(
+            self,
+            Self::Headers | Self::BlockMeta | Self::AccountChangeSets | Self::StorageChangeSets
+        )
     }
 }
 
@@ -373,6 +401,18 @@ mod tests {
         let test_vectors = [
             (StaticFileSegment::Headers, 2..=30, "static_file_headers_2_30", None),
             (StaticFileSegment::Receipts, 30..=300, "static_file_receipts_30_300", None),
+            (
+                StaticFileSegment::AccountChangeSets,
+                0..=499_999,
+                "static_file_accountchangesets_0_499999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                500_000..=999_999,
+                "static_file_storagechangesets_500000_999999",
+                None,
+            ),
             (
                 StaticFileSegment::Transactions,
                 1_123_233..=11_223_233,
@@ -15,7 +15,9 @@ use core::ops::Bound;
 use std::{collections::BTreeMap, ops::RangeBounds};
 
 /// Mock database used for testing with inner `BTreeMap` structure
-// TODO
+///
+/// The mock doesn't store anything, use the `MemDatabase` of `reth-db` for a functional in-memory
+/// database.
 #[derive(Clone, Debug, Default)]
 pub struct DatabaseMock {
     /// Main data. TODO (Make it table aware)
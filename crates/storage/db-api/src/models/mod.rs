@@ -25,8 +25,8 @@ pub use accounts::*;
 pub use blocks::*;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
-    blocks::StaticFileBlockWithdrawals, AccountBeforeTx, ClientVersion, StoredBlockBodyIndices,
-    StoredBlockWithdrawals,
+    blocks::StaticFileBlockWithdrawals, AccountBeforeTx, ClientVersion, StaticFileAccountChangeSet,
+    StaticFileStorageChangeSet, StorageBeforeTx, StoredBlockBodyIndices, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
 
@@ -226,6 +226,8 @@ impl_compression_for_compact!(
     StoredBlockOmmers<H>,
     StoredBlockWithdrawals,
     StaticFileBlockWithdrawals,
+    StaticFileAccountChangeSet,
+    StaticFileStorageChangeSet,
     Bytecode,
     AccountBeforeTx,
     TransactionSigned,
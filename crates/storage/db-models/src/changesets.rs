@@ -0,0 +1,81 @@
+use crate::AccountBeforeTx;
+use alloy_primitives::{bytes::Buf, Address};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives_traits::StorageEntry;
+use serde::{Deserialize, Serialize};
+
+/// Storage slot as it is saved in the storage changesets static files.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StorageBeforeTx {
+    /// Address of the account the slot belongs to.
+    pub address: Address,
+    /// Storage slot and its value before the transaction.
+    pub entry: StorageEntry,
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        let (entry, buf) = StorageEntry::from_compact(buf, len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// All account changes of a block, as stored in a row of the account changesets static files.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StaticFileAccountChangeSet {
+    /// The accounts changed in the block, sorted by address, with their state before the block.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl Compact for StaticFileAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.changes.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = Vec::from_compact(buf, len);
+        (Self { changes }, buf)
+    }
+}
+
+/// All storage changes of a block, as stored in a row of the storage changesets static files.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StaticFileStorageChangeSet {
+    /// The storage slots changed in the block, sorted by address and slot, with their values
+    /// before the block.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl Compact for StaticFileStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.changes.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let (changes, buf) = Vec::from_compact(buf, len);
+        (Self { changes }, buf)
+    }
+}
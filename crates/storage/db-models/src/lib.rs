@@ -8,6 +8,10 @@ pub use accounts::AccountBeforeTx;
 pub mod blocks;
 pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockWithdrawals};
 
+/// Changesets
+pub mod changesets;
+pub use changesets::{StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx};
+
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
@@ -0,0 +1,469 @@
+//! Cursor of the in-memory database.
+
+use super::{
+    read_table, remove_value, upsert_value, write_table, MemError, TableData, TablesData,
+    TransactionKind, RO, RW,
+};
+use crate::{
+    tables::utils::{decode_one, decode_value, decoder},
+    DatabaseError,
+};
+use parking_lot::RwLock;
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, DupSort, Encode, Table},
+};
+use reth_storage_errors::db::{DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, Bound},
+    marker::PhantomData,
+    ops::RangeBounds,
+    sync::Arc,
+};
+
+/// Read only Cursor.
+pub type CursorRO<T> = Cursor<RO, T>;
+/// Read write cursor.
+pub type CursorRW<T> = Cursor<RW, T>;
+
+/// An encoded `(key, value)` pair.
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// Position of a cursor.
+#[derive(Debug)]
+enum Position {
+    /// The cursor was not positioned yet.
+    Unset,
+    /// The cursor is at the entry.
+    At(Vec<u8>, Vec<u8>),
+    /// The entry the cursor was at was deleted. The cursor is at the entry following it, which is
+    /// returned by both [`DbCursorRO::current`] and [`DbCursorRO::next`].
+    Deleted(Vec<u8>, Vec<u8>),
+    /// A seek found no entry, the cursor is past the last entry.
+    End,
+}
+
+/// Cursor to access the entries of a table of the in-memory database.
+///
+/// The cursor moves over the encoded entries of the table, and follows the semantics of the MDBX
+/// cursor.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// Tables of the transaction the cursor belongs to.
+    tables: Arc<RwLock<TablesData>>,
+    /// Current position of the cursor.
+    position: Position,
+    /// Phantom data to enforce encoding/decoding.
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) const fn new(tables: Arc<RwLock<TablesData>>) -> Self {
+        Self { tables, position: Position::Unset, _dbi: PhantomData }
+    }
+
+    /// Calls the closure with the contents of the table and the position of the cursor.
+    fn read<R>(&self, f: impl FnOnce(&TableData, &Position) -> R) -> R {
+        read_table::<T, _>(&self.tables, |table| f(table, &self.position))
+    }
+
+    /// Moves the cursor to the entry and decodes it. If there is no entry, the cursor stays where
+    /// it is.
+    fn move_to(&mut self, entry: Option<Entry>) -> PairResult<T> {
+        let Some((key, value)) = entry else { return Ok(None) };
+        let row = decoder::<T>((Cow::Borrowed(key.as_slice()), Cow::Borrowed(value.as_slice())));
+        self.position = Position::At(key, value);
+        row.map(Some)
+    }
+
+    /// Moves the cursor to the first entry with a key greater than or equal to the given key. If
+    /// there is no such entry, the cursor moves past the last entry.
+    fn set_range(&mut self, key: &[u8]) -> Option<Entry> {
+        let entry = self.read(|table, _| {
+            table.range::<[u8], _>((Bound::Included(key), Bound::Unbounded)).next().map(first)
+        });
+        match &entry {
+            Some((key, value)) => self.position = Position::At(key.clone(), value.clone()),
+            None => self.position = Position::End,
+        }
+        entry
+    }
+
+    /// Moves the cursor to the first value of the key that is greater than or equal to the given
+    /// value. If there is no such value, the cursor moves past the last entry.
+    fn get_both_range(&mut self, key: &[u8], value: &[u8]) -> Option<Entry> {
+        let entry = self.read(|table, _| {
+            table.get_key_value(key).and_then(|(key, values)| {
+                values
+                    .range::<[u8], _>((Bound::Included(value), Bound::Unbounded))
+                    .next()
+                    .map(|value| (key.clone(), value.clone()))
+            })
+        });
+        match &entry {
+            Some((key, value)) => self.position = Position::At(key.clone(), value.clone()),
+            None => self.position = Position::End,
+        }
+        entry
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        let entry = self.read(|table, _| table.iter().next().map(first));
+        self.move_to(entry)
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let key = key.encode();
+        // Like in MDBX, the cursor is left at the next key if the key doesn't exist.
+        match self.set_range(key.as_ref()) {
+            Some(entry) if entry.0 == key.as_ref() => self.move_to(Some(entry)),
+            _ => Ok(None),
+        }
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let entry = self.set_range(key.encode().as_ref());
+        self.move_to(entry)
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let entry = self.read(|table, position| match position {
+            Position::Unset => table.iter().next().map(first),
+            Position::At(key, value) => entry_after(table, key, value, false),
+            Position::Deleted(key, value) => entry_after(table, key, value, true),
+            Position::End => None,
+        });
+        self.move_to(entry)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let entry = self.read(|table, position| match position {
+            Position::Unset | Position::End => table.iter().next_back().map(last),
+            Position::At(key, value) | Position::Deleted(key, value) => {
+                entry_before(table, key, value)
+            }
+        });
+        self.move_to(entry)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let entry = self.read(|table, _| table.iter().next_back().map(last));
+        self.move_to(entry)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.read(|table, position| match position {
+            Position::Unset | Position::End => None,
+            Position::At(key, value) | Position::Deleted(key, value) => {
+                entry_after(table, key, value, true)
+            }
+        })
+        .map(|(key, value)| decoder::<T>((Cow::Owned(key), Cow::Owned(value))))
+        .transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek(start_key).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> Cursor<K, T> {
+    /// Moves the cursor to the next value of the current key.
+    fn next_dup_entry(&mut self) -> Option<Entry> {
+        let entry = self.read(|table, position| match position {
+            Position::Unset => table.iter().next().map(first),
+            Position::At(key, value) => value_after(table, key, value, false),
+            Position::Deleted(key, value) => value_after(table, key, value, true),
+            Position::End => None,
+        });
+        if let Some((key, value)) = &entry {
+            self.position = Position::At(key.clone(), value.clone());
+        }
+        entry
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        self.next_dup_entry()
+            .map(|(key, value)| decoder::<T>((Cow::Owned(key), Cow::Owned(value))))
+            .transpose()
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let entry = self.read(|table, position| match position {
+            Position::Unset => table.iter().next().map(first),
+            Position::At(key, _) | Position::Deleted(key, _) => table
+                .range::<[u8], _>((Bound::Excluded(key.as_slice()), Bound::Unbounded))
+                .next()
+                .map(first),
+            Position::End => None,
+        });
+        self.move_to(entry)
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        self.next_dup_entry()
+            .map(|(key, value)| decode_value::<T>((Cow::Owned(key), Cow::Owned(value))))
+            .transpose()
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        self.get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+            .map(|(_, value)| decode_one::<T>(Cow::Owned(value)))
+            .transpose()
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => self
+                .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+                .map(|(key, value)| decoder::<T>((Cow::Owned(key), Cow::Owned(value)))),
+            (Some(key), None) => {
+                let key = key.encode();
+                match self.set_range(key.as_ref()) {
+                    Some((found, value)) if found == key.as_ref() => {
+                        Some(decoder::<T>((Cow::Owned(found), Cow::Owned(value))))
+                    }
+                    _ => {
+                        self.position = Position::End;
+                        None
+                    }
+                }
+            }
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    self.get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
+                        .map(|(key, value)| decoder::<T>((Cow::Owned(key), Cow::Owned(value))))
+                } else {
+                    Some(Err(DatabaseError::Read(MemError::NotFound.into())))
+                }
+            }
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Returns the entry [`DbCursorRW::delete_current`] deletes, which is the one
+    /// [`DbCursorRO::current`] returns.
+    fn current_entry(&self) -> Result<Entry, DatabaseError> {
+        self.read(|table, position| match position {
+            Position::Unset | Position::End => None,
+            Position::At(key, value) | Position::Deleted(key, value) => {
+                entry_after(table, key, value, true)
+            }
+        })
+        .ok_or_else(|| DatabaseError::Delete(MemError::NotFound.into()))
+    }
+
+    /// Inserts the entry unless the closure returns an error, and moves the cursor to it.
+    ///
+    /// The closure can move the cursor to the entry that caused the error, which is where MDBX
+    /// leaves its cursor.
+    fn put(
+        &mut self,
+        key: T::Key,
+        value: &T::Value,
+        operation: DatabaseWriteOperation,
+        check: impl FnOnce(&TableData, &[u8], &[u8]) -> Result<(), (MemError, Option<Entry>)>,
+    ) -> Result<(), DatabaseError> {
+        let key: Vec<u8> = key.encode().into();
+        let value = compress::<T>(value);
+
+        let result = write_table::<T, _>(&self.tables, |table| {
+            check(table, key.as_slice(), value.as_slice())?;
+            upsert_value::<T>(table, key.clone(), value.clone());
+            Ok(())
+        });
+        match result {
+            Ok(()) => {
+                self.position = Position::At(key, value);
+                Ok(())
+            }
+            Err((info, entry)) => {
+                if let Some((key, value)) = entry {
+                    self.position = Position::At(key, value);
+                }
+                Err(DatabaseWriteError { info: info.into(), operation, table_name: T::NAME, key }
+                    .into())
+            }
+        }
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same.
+    fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put(key, value, DatabaseWriteOperation::CursorUpsert, |_, _, _| Ok(()))
+    }
+
+    fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put(key, value, DatabaseWriteOperation::CursorInsert, |table, key, _| {
+            match table.get_key_value(key) {
+                Some(existing) => Err((MemError::KeyExist, Some(first(existing)))),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put(key, value, DatabaseWriteOperation::CursorAppend, |table, key, _| {
+            match table.last_key_value() {
+                // Values of `DUPSORT` tables can be appended to the last key.
+                Some(last_entry)
+                    if key < last_entry.0.as_slice() ||
+                        (key == last_entry.0.as_slice() && !T::DUPSORT) =>
+                {
+                    Err((MemError::KeyMismatch, Some(last(last_entry))))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let (key, value) = self.current_entry()?;
+        write_table::<T, _>(&self.tables, |table| remove_value(table, &key, &value));
+        self.position = Position::Deleted(key, value);
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let (key, value) = self.current_entry()?;
+        write_table::<T, _>(&self.tables, |table| table.remove(&key));
+        self.position = Position::Deleted(key, value);
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.put(key, &value, DatabaseWriteOperation::CursorAppendDup, |table, key, value| {
+            match table.get_key_value(key) {
+                Some(entry) if value < last(entry).1.as_slice() => {
+                    Err((MemError::KeyMismatch, Some(last(entry))))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+/// Returns the first entry of a key of the table.
+fn first((key, values): (&Vec<u8>, &BTreeSet<Vec<u8>>)) -> Entry {
+    (key.clone(), values.first().expect("keys have at least one value").clone())
+}
+
+/// Returns the last entry of a key of the table.
+fn last((key, values): (&Vec<u8>, &BTreeSet<Vec<u8>>)) -> Entry {
+    (key.clone(), values.last().expect("keys have at least one value").clone())
+}
+
+/// Returns the first value of the key after the given value, or the value itself if it exists and
+/// `inclusive` is set.
+fn value_after(table: &TableData, key: &[u8], value: &[u8], inclusive: bool) -> Option<Entry> {
+    let start = if inclusive { Bound::Included(value) } else { Bound::Excluded(value) };
+    table.get_key_value(key).and_then(|(key, values)| {
+        values
+            .range::<[u8], _>((start, Bound::Unbounded))
+            .next()
+            .map(|value| (key.clone(), value.clone()))
+    })
+}
+
+/// Returns the first entry after the given entry, or the entry itself if it exists and
+/// `inclusive` is set.
+fn entry_after(table: &TableData, key: &[u8], value: &[u8], inclusive: bool) -> Option<Entry> {
+    value_after(table, key, value, inclusive).or_else(|| {
+        table.range::<[u8], _>((Bound::Excluded(key), Bound::Unbounded)).next().map(first)
+    })
+}
+
+/// Returns the last entry before the given entry.
+fn entry_before(table: &TableData, key: &[u8], value: &[u8]) -> Option<Entry> {
+    table
+        .get_key_value(key)
+        .and_then(|(key, values)| {
+            values
+                .range::<[u8], _>((Bound::Unbounded, Bound::Excluded(value)))
+                .next_back()
+                .map(|value| (key.clone(), value.clone()))
+        })
+        .or_else(|| {
+            table.range::<[u8], _>((Bound::Unbounded, Bound::Excluded(key))).next_back().map(last)
+        })
+}
+
+/// Compresses the value, without going through a buffer if the value can't be compressed.
+fn compress<T: Table>(value: &T::Value) -> Vec<u8> {
+    if let Some(value) = value.uncompressable_ref() {
+        return value.to_vec()
+    }
+    let mut buf = Vec::new();
+    value.compress_to_buf(&mut buf);
+    buf
+}
@@ -0,0 +1,248 @@
+//! In-memory implementation of the database abstraction layer.
+//!
+//! Tables are kept in ordered maps of the encoded keys and values, so that cursors follow the same
+//! order and have the same semantics as in MDBX, including `DUPSORT` tables.
+//!
+//! Transactions work on a snapshot of the tables taken when they are opened. Only one read-write
+//! transaction can be open at a time, its changes are published to the database when it's
+//! committed, and discarded when it's aborted or dropped.
+
+use crate::DatabaseError;
+use parking_lot::{Condvar, Mutex, RwLock};
+use reth_db_api::{database::Database, database_metrics::DatabaseMetrics, table::Table};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    sync::Arc,
+};
+use tx::Tx;
+
+pub mod cursor;
+pub mod tx;
+
+/// Contents of a table, the sorted values of every key.
+///
+/// Keys of tables without `DUPSORT` hold a single value.
+pub(crate) type TableData = BTreeMap<Vec<u8>, BTreeSet<Vec<u8>>>;
+
+/// Contents of all tables, by table name.
+///
+/// Tables are copied on the first write of a transaction, so that the snapshots held by other
+/// transactions are not affected.
+pub(crate) type TablesData = BTreeMap<&'static str, Arc<TableData>>;
+
+/// Marker of read-only transactions and cursors.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker of read-write transactions and cursors.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+/// A marker trait for the transaction kind, either [`RO`] or [`RW`].
+pub trait TransactionKind: private::Sealed + Send + Sync + Debug + 'static {}
+
+impl TransactionKind for RO {}
+impl TransactionKind for RW {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RO {}
+    impl Sealed for super::RW {}
+}
+
+/// Errors of the in-memory database.
+///
+/// The messages are the same as the ones of the equivalent MDBX errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum MemError {
+    /// Key/data pair already exists.
+    #[error("key/data pair already exists")]
+    KeyExist,
+    /// No matching key/data pair found.
+    #[error("no matching key/data pair found")]
+    NotFound,
+    /// The given key value is mismatched to the current cursor position.
+    #[error("the given key value is mismatched to the current cursor position")]
+    KeyMismatch,
+}
+
+impl From<MemError> for i32 {
+    fn from(error: MemError) -> Self {
+        // Codes of the equivalent MDBX errors.
+        match error {
+            MemError::KeyExist => -30799,
+            MemError::NotFound => -30798,
+            MemError::KeyMismatch => -30418,
+        }
+    }
+}
+
+/// In-memory database.
+///
+/// Cloning the database is cheap, the clones share the same tables.
+#[derive(Debug, Clone, Default)]
+pub struct MemDatabase {
+    inner: Arc<MemDatabaseInner>,
+}
+
+/// State shared by the clones of [`MemDatabase`] and its read-write transactions.
+#[derive(Debug, Default)]
+pub(crate) struct MemDatabaseInner {
+    /// Committed tables.
+    tables: RwLock<TablesData>,
+    /// Whether a read-write transaction is open.
+    writer: Mutex<bool>,
+    /// Notified when the read-write transaction is closed.
+    writer_released: Condvar,
+}
+
+impl MemDatabaseInner {
+    /// Waits until no other read-write transaction is open and takes the writer lock.
+    fn acquire_writer(&self) {
+        let mut writer = self.writer.lock();
+        while *writer {
+            self.writer_released.wait(&mut writer);
+        }
+        *writer = true;
+    }
+
+    /// Releases the writer lock taken by [`Self::acquire_writer`].
+    pub(crate) fn release_writer(&self) {
+        *self.writer.lock() = false;
+        self.writer_released.notify_one();
+    }
+
+    /// Replaces the committed tables.
+    pub(crate) fn commit(&self, tables: TablesData) {
+        *self.tables.write() = tables;
+    }
+}
+
+impl MemDatabase {
+    /// Creates a new, empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Database for MemDatabase {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        Ok(Tx::new(self.inner.tables.read().clone(), None))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        self.inner.acquire_writer();
+        Ok(Tx::new(self.inner.tables.read().clone(), Some(self.inner.clone())))
+    }
+}
+
+impl DatabaseMetrics for MemDatabase {}
+
+/// Contents of the tables that were never written.
+static EMPTY_TABLE: TableData = BTreeMap::new();
+
+/// Calls the closure with the contents of the table.
+pub(crate) fn read_table<T: Table, R>(
+    tables: &RwLock<TablesData>,
+    f: impl FnOnce(&TableData) -> R,
+) -> R {
+    let tables = tables.read();
+    f(tables.get(T::NAME).map_or(&EMPTY_TABLE, |table| table.as_ref()))
+}
+
+/// Calls the closure with the mutable contents of the table, copying them first if they are
+/// shared with other transactions.
+pub(crate) fn write_table<T: Table, R>(
+    tables: &RwLock<TablesData>,
+    f: impl FnOnce(&mut TableData) -> R,
+) -> R {
+    let mut tables = tables.write();
+    f(Arc::make_mut(tables.entry(T::NAME).or_default()))
+}
+
+/// Inserts the value of the key. The value replaces the current value of the key, unless the table
+/// is a `DUPSORT` table.
+pub(crate) fn upsert_value<T: Table>(table: &mut TableData, key: Vec<u8>, value: Vec<u8>) {
+    let values = table.entry(key).or_default();
+    if !T::DUPSORT {
+        values.clear();
+    }
+    values.insert(value);
+}
+
+/// Removes the value of the key, and the key if it was its last value.
+pub(crate) fn remove_value(table: &mut TableData, key: &[u8], value: &[u8]) -> bool {
+    let Some(values) = table.get_mut(key) else { return false };
+    let removed = values.remove(value);
+    if values.is_empty() {
+        table.remove(key);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{implementation::tests::database_tests, tables::CanonicalHeaders};
+    use alloy_primitives::B256;
+    use reth_db_api::transaction::{DbTx, DbTxMut};
+
+    const ERROR_PUT: &str = "Not able to insert value into table.";
+    const ERROR_DEL: &str = "Not able to delete from table.";
+    const ERROR_COMMIT: &str = "Not able to commit transaction.";
+    const ERROR_INIT_TX: &str = "Failed to create a transaction.";
+
+    #[test]
+    fn db_tx_isolation() {
+        let db = MemDatabase::new();
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<CanonicalHeaders>(0, B256::ZERO).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+
+        let old_tx = db.tx().expect(ERROR_INIT_TX);
+
+        // Changes of a read-write transaction are only visible to itself until it's committed
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<CanonicalHeaders>(1, B256::ZERO).expect(ERROR_PUT);
+        tx.delete::<CanonicalHeaders>(0, None).expect(ERROR_DEL);
+        assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(1));
+        assert_eq!(db.tx().expect(ERROR_INIT_TX).entries::<CanonicalHeaders>(), Ok(1));
+        assert_eq!(db.tx().expect(ERROR_INIT_TX).get::<CanonicalHeaders>(1), Ok(None));
+        tx.commit().expect(ERROR_COMMIT);
+
+        let new_tx = db.tx().expect(ERROR_INIT_TX);
+        assert_eq!(new_tx.get::<CanonicalHeaders>(0), Ok(None));
+        assert_eq!(new_tx.get::<CanonicalHeaders>(1), Ok(Some(B256::ZERO)));
+
+        // Read transactions keep the tables as they were when the transaction was opened
+        assert_eq!(old_tx.get::<CanonicalHeaders>(0), Ok(Some(B256::ZERO)));
+        assert_eq!(old_tx.get::<CanonicalHeaders>(1), Ok(None));
+    }
+
+    #[test]
+    fn db_tx_abort() {
+        let db = MemDatabase::new();
+
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<CanonicalHeaders>(0, B256::ZERO).expect(ERROR_PUT);
+        tx.abort();
+
+        // Dropping the transaction aborts it as well
+        let tx = db.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<CanonicalHeaders>(1, B256::ZERO).expect(ERROR_PUT);
+        drop(tx);
+
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(0));
+    }
+
+    database_tests!(MemDatabase::new());
+}
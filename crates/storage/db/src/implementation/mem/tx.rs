@@ -0,0 +1,137 @@
+//! Transaction of the in-memory database.
+
+use super::{
+    cursor::Cursor, read_table, remove_value, upsert_value, write_table, MemDatabaseInner,
+    TablesData, TransactionKind, RW,
+};
+use crate::{tables::utils::decode_one, DatabaseError};
+use parking_lot::RwLock;
+use reth_db_api::{
+    table::{Compress, DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use std::{borrow::Cow, collections::BTreeSet, marker::PhantomData, sync::Arc};
+
+/// Transaction of the in-memory database.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    /// Tables as seen by the transaction. They are shared with the cursors of the transaction,
+    /// so that the cursors see its writes.
+    tables: Arc<RwLock<TablesData>>,
+    /// Database to publish the tables to on commit. Only set for read-write transactions, which
+    /// hold the writer lock of the database until they are closed.
+    db: Option<Arc<MemDatabaseInner>>,
+    _kind: PhantomData<K>,
+}
+
+impl<K: TransactionKind> Tx<K> {
+    /// Creates a transaction on a snapshot of the tables.
+    pub(crate) fn new(tables: TablesData, db: Option<Arc<MemDatabaseInner>>) -> Self {
+        Self { tables: Arc::new(RwLock::new(tables)), db, _kind: PhantomData }
+    }
+
+    /// Create db Cursor
+    pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(self.tables.clone()))
+    }
+}
+
+impl<K: TransactionKind> Drop for Tx<K> {
+    fn drop(&mut self) {
+        if let Some(db) = self.db.take() {
+            db.release_writer();
+        }
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        self.get_by_encoded_key::<T>(&key.encode())
+    }
+
+    fn get_by_encoded_key<T: Table>(
+        &self,
+        key: &<T::Key as Encode>::Encoded,
+    ) -> Result<Option<T::Value>, DatabaseError> {
+        read_table::<T, _>(&self.tables, |table| {
+            table
+                .get(key.as_ref())
+                .and_then(BTreeSet::first)
+                .map(|value| decode_one::<T>(Cow::Borrowed(value)))
+                .transpose()
+        })
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        if let Some(db) = &self.db {
+            db.commit(std::mem::take(&mut *self.tables.write()));
+        }
+        Ok(false)
+    }
+
+    fn abort(self) {}
+
+    // Iterate over read only values in database.
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Iterate over read only values in database.
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Returns number of entries in the table, counting every value of `DUPSORT` tables.
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(read_table::<T, _>(&self.tables, |table| table.values().map(BTreeSet::len).sum()))
+    }
+
+    /// Transactions of the in-memory database have no time limit.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode().into();
+        let value = value.compress().into();
+        write_table::<T, _>(&self.tables, |table| upsert_value::<T>(table, key, value));
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key = key.encode();
+        // Like in MDBX, the value is only used to select the duplicate of `DUPSORT` tables.
+        let value = value.filter(|_| T::DUPSORT).map(Compress::compress);
+
+        Ok(write_table::<T, _>(&self.tables, |table| match value {
+            Some(value) => remove_value(table, key.as_ref(), value.as_ref()),
+            None => table.remove(key.as_ref()).is_some(),
+        }))
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.tables.write().remove(T::NAME);
+
+        Ok(())
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+}
@@ -1,2 +1,6 @@
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
+pub(crate) mod mem;
+
+#[cfg(test)]
+mod tests;
@@ -27,6 +27,7 @@ pub mod version;
 
 #[cfg(feature = "mdbx")]
 pub mod mdbx;
+pub mod mem;
 
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
@@ -35,6 +36,7 @@ pub use utils::is_database_empty;
 
 #[cfg(feature = "mdbx")]
 pub use mdbx::{create_db, init_db, open_db, open_db_read_only, DatabaseEnv, DatabaseEnvKind};
+pub use mem::MemDatabase;
 
 pub use models::ClientVersion;
 pub use reth_db_api::*;
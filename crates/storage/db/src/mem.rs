@@ -0,0 +1,3 @@
+//! In-memory database, mostly useful for tests and ephemeral nodes.
+
+pub use crate::implementation::mem::*;
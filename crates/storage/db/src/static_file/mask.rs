@@ -33,16 +33,21 @@ pub trait ColumnSelectorThree {
 #[macro_export]
 /// Add mask to select `N` column values from a specific static file segment row.
 macro_rules! add_static_file_mask {
-    ($(#[$attr:meta])* $mask_struct:ident $(<$generic:ident>)?, $type1:ty, $mask:expr) => {
+    // The arms with more columns come first, a generic type can't be parsed as the mask.
+    ($(#[$attr:meta])* $mask_struct:ident $(<$generic:ident>)?, $type1:ty, $type2:ty, $type3:ty, $mask:expr) => {
         $(#[$attr])*
         #[derive(Debug)]
         pub struct $mask_struct$(<$generic>)?$((std::marker::PhantomData<$generic>))?;
 
-        impl$(<$generic>)? ColumnSelectorOne for $mask_struct$(<$generic>)?
+        impl$(<$generic>)? ColumnSelectorThree for $mask_struct$(<$generic>)?
         where
             $type1: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
+            $type2: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
+            $type3: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
         {
             type FIRST = $type1;
+            type SECOND = $type2;
+            type THIRD = $type3;
             const MASK: usize = $mask;
         }
     };
@@ -61,20 +66,16 @@ macro_rules! add_static_file_mask {
             const MASK: usize = $mask;
         }
     };
-    ($(#[$attr:meta])* $mask_struct:ident $(<$generic:ident>)?, $type1:ty, $type2:ty, $type3:ty, $mask:expr) => {
+    ($(#[$attr:meta])* $mask_struct:ident $(<$generic:ident>)?, $type1:ty, $mask:expr) => {
         $(#[$attr])*
         #[derive(Debug)]
         pub struct $mask_struct$(<$generic>)?$((std::marker::PhantomData<$generic>))?;
 
-        impl$(<$generic>)? ColumnSelectorThree for $mask_struct$(<$generic>)?
+        impl$(<$generic>)? ColumnSelectorOne for $mask_struct$(<$generic>)?
         where
             $type1: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
-            $type2: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
-            $type3: Send + Sync + std::fmt::Debug + reth_db_api::table::Decompress,
         {
             type FIRST = $type1;
-            type SECOND = $type2;
-            type THIRD = $type3;
             const MASK: usize = $mask;
         }
     };
@@ -1,11 +1,14 @@
 use crate::{
     add_static_file_mask,
-    static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo},
+    static_file::mask::{ColumnSelectorOne, ColumnSelectorThree, ColumnSelectorTwo},
     BlockBodyIndices, HeaderTerminalDifficulties,
 };
 use alloy_primitives::BlockHash;
 use reth_db_api::{
-    models::{StaticFileBlockWithdrawals, StoredBlockOmmers},
+    models::{
+        StaticFileAccountChangeSet, StaticFileBlockWithdrawals, StaticFileStorageChangeSet,
+        StoredBlockOmmers,
+    },
     table::Table,
 };
 
@@ -59,3 +62,21 @@ add_static_file_mask! {
     #[doc = "Mask for a `StaticFileBlockWithdrawals` from BlockMeta static file segment"]
     WithdrawalsMask, StaticFileBlockWithdrawals, 0b100
 }
+add_static_file_mask! {
+    #[doc = "Mask for selecting all columns from BlockMeta static file segment"]
+    BlockMetaMask<H>,
+    <BlockBodyIndices as Table>::Value,
+    StoredBlockOmmers<H>,
+    StaticFileBlockWithdrawals,
+    0b111
+}
+
+// CHANGESET MASKS
+add_static_file_mask! {
+    #[doc = "Mask for the account changes of a block from AccountChangeSets static file segment"]
+    AccountChangeSetMask, StaticFileAccountChangeSet, 0b1
+}
+add_static_file_mask! {
+    #[doc = "Mask for the storage changes of a block from StorageChangeSets static file segment"]
+    StorageChangeSetMask, StaticFileStorageChangeSet, 0b1
+}
@@ -16,7 +16,6 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
-#[cfg(feature = "mdbx")]
 pub(crate) mod utils;
 
 use alloy_consensus::Header;
@@ -443,6 +442,26 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks with logs emitted by each address.
+    ///
+    /// Sharded like [`AccountsHistory`], the last shard of an address is keyed with `u64::MAX`.
+    /// Only written by the optional `LogIndex` stage, which is used to serve `eth_getLogs` over
+    /// large block ranges.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks with logs containing each topic, at any topic position.
+    ///
+    /// Sharded like [`AccountsHistory`], the last shard of a topic is keyed with `u64::MAX`.
+    /// Only written by the optional `LogIndex` stage, which is used to serve `eth_getLogs` over
+    /// large block ranges.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -105,6 +105,9 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// The storage history of the account has too many slots to be searched.
+    #[error("storage history of {_0} has too many slots to be searched")]
+    StorageHistoryTooLarge(Address),
     /// Provider does not support this particular request.
     #[error("this provider does not support this request")]
     UnsupportedProvider,
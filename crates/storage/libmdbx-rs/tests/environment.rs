@@ -114,6 +114,26 @@ fn test_stat() {
     assert_eq!(stat.entries(), 64);
 }
 
+#[test]
+fn test_copy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let tx = env.begin_rw_txn().unwrap();
+    tx.put(tx.open_db(None).unwrap().dbi(), b"key", b"val", WriteFlags::default()).unwrap();
+    tx.commit().unwrap();
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+    // The destination file must not exist
+    assert!(env.copy(&copy_dir.path().join("mdbx.dat"), true).is_err());
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    let tx = copy.begin_ro_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.get(db.dbi(), b"key").unwrap(), Some(*b"val"));
+}
+
 #[test]
 fn test_info() {
     let map_size = 1024 * 1024;
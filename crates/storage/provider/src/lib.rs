@@ -42,8 +42,8 @@ pub use revm::db::states::OriginalValuesKnown;
 pub mod writer;
 
 pub use reth_chain_state::{
-    CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    BadBlockProvider, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotificationStream, CanonStateNotifications, CanonStateSubscriptions,
 };
 
 // reexport traits to avoid breaking changes
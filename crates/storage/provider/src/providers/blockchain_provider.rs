@@ -4,7 +4,8 @@ use crate::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
     ChainSpecProvider, ChainStateBlockReader, ChangeSetReader, DatabaseProvider,
-    DatabaseProviderFactory, FullProvider, HashedPostStateProvider, HeaderProvider, ProviderError,
+    DatabaseProviderFactory, FullProvider, HashedAccountRange, HashedPostStateProvider,
+    HashedStateRangeReader, HashedStorageRange, HeaderProvider, HistoryReader, ProviderError,
     ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
     StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
@@ -14,11 +15,13 @@ use alloy_eips::{
     eip4895::{Withdrawal, Withdrawals},
     BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag,
 };
-use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
+use alloy_primitives::{
+    map::B256Map, Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256,
+};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
-    BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    BadBlockProvider, BlockState, CanonicalInMemoryState, ForkChoiceNotifications,
+    ForkChoiceSubscriptions, MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db::{models::BlockNumberAddress, transaction::DbTx, Database};
@@ -38,7 +41,7 @@ use reth_storage_api::{
     StateCommitmentProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::HashedPostState;
+use reth_trie::{HashedPostState, HashedStorage};
 use reth_trie_db::StateCommitment;
 use revm::{
     db::BundleState,
@@ -710,6 +713,14 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BadBlockProvider for BlockchainProvider<N> {
+    type Block = BlockTy<N>;
+
+    fn bad_blocks(&self) -> Vec<Arc<SealedBlock<Self::Block>>> {
+        self.canonical_in_memory_state.bad_blocks()
+    }
+}
+
 impl<N: ProviderNodeTypes> ForkChoiceSubscriptions for BlockchainProvider<N> {
     type Header = HeaderTy<N>;
 
@@ -742,6 +753,86 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HistoryReader for BlockchainProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        newest_first: bool,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.account_history_blocks(address, range, limit, newest_first)
+    }
+
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.consistent_provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.consistent_provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> HashedStateRangeReader for BlockchainProvider<N> {
+    fn hashed_account_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        with_storage: bool,
+        with_preimages: bool,
+    ) -> ProviderResult<HashedAccountRange> {
+        self.consistent_provider()?.hashed_account_range(
+            block_number,
+            start,
+            limit,
+            with_storage,
+            with_preimages,
+        )
+    }
+
+    fn hashed_storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        self.consistent_provider()?.hashed_storage_range(
+            block_number,
+            address,
+            overlay,
+            start,
+            limit,
+        )
+    }
+
+    fn account_preimages(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        self.consistent_provider()?.account_preimages(hashed_addresses)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<B256Map<B256>> {
+        self.consistent_provider()?.storage_preimages(address, hashed_slots)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
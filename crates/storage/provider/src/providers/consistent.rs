@@ -1,7 +1,8 @@
 use super::{DatabaseProviderRO, ProviderFactory, ProviderNodeTypes};
 use crate::{
     providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader, HeaderProvider,
+    BlockReader, BlockReaderIdExt, BlockSource, ChainSpecProvider, ChangeSetReader,
+    HashedAccountRange, HashedStateRangeReader, HashedStorageRange, HeaderProvider, HistoryReader,
     ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
     StageCheckpointReader, StateReader, StaticFileProviderFactory, TransactionVariant,
     TransactionsProvider, WithdrawalsProvider,
@@ -12,7 +13,8 @@ use alloy_eips::{
     BlockNumberOrTag, HashOrNumber,
 };
 use alloy_primitives::{
-    map::{hash_map, HashMap},
+    keccak256,
+    map::{hash_map, B256Map, HashMap},
     Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
 };
 use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProviderRef};
@@ -30,6 +32,7 @@ use reth_storage_api::{
     StateProvider, StorageChangeSetReader,
 };
 use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{HashedPostState, HashedStorage};
 use revm::db::states::PlainStorageRevert;
 use std::{
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
@@ -393,6 +396,21 @@ impl<N: ProviderNodeTypes> ConsistentProvider<N> {
         Ok(items)
     }
 
+    /// Returns the combined hashed state of the in-memory blocks up to and including the given
+    /// block, or `None` if the block is not in memory.
+    ///
+    /// Applied on top of the database state, this yields the state after the given block.
+    fn in_memory_hashed_state(&self, block_number: BlockNumber) -> Option<HashedPostState> {
+        let block = self.head_block.as_ref()?.block_on_chain(block_number.into())?;
+
+        // The chain is ordered from the highest block to the lowest.
+        let mut state = HashedPostState::default();
+        for block in block.chain().collect::<Vec<_>>().into_iter().rev() {
+            state.extend_ref(block.block_ref().hashed_state());
+        }
+        Some(state)
+    }
+
     /// This uses a given [`BlockState`] to initialize a state provider for that block.
     fn block_state_provider_ref(
         &self,
@@ -1440,6 +1458,199 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HistoryReader for ConsistentProvider<N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        newest_first: bool,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        // Blocks that are still in memory are not covered by the history indices yet, so they are
+        // checked against their execution outcome instead. In-memory blocks take precedence over
+        // any overlapping blocks in the database.
+        let in_memory_chain =
+            self.head_block.as_ref().map(|b| b.chain().collect::<Vec<_>>()).unwrap_or_default();
+        let lowest_memory_block =
+            in_memory_chain.last().map(|b| b.number()).unwrap_or(BlockNumber::MAX);
+
+        // The chain is ordered from the highest block to the lowest.
+        let in_memory_blocks = in_memory_chain
+            .into_iter()
+            .filter(|block| {
+                range.contains(&block.number()) &&
+                    block
+                        .block_ref()
+                        .execution_output
+                        .bundle
+                        .reverts
+                        .iter()
+                        .flatten()
+                        .any(|(changed, _)| *changed == address)
+            })
+            .map(|block| block.number())
+            .collect::<Vec<_>>();
+        let storage_blocks = |limit: usize| {
+            if *range.start() < lowest_memory_block && limit > 0 {
+                let storage_range = *range.start()..=(*range.end()).min(lowest_memory_block - 1);
+                self.storage_provider.account_history_blocks(
+                    address,
+                    storage_range,
+                    limit,
+                    newest_first,
+                )
+            } else {
+                Ok(Vec::new())
+            }
+        };
+
+        let mut blocks = if newest_first {
+            let mut blocks = in_memory_blocks;
+            blocks.truncate(limit);
+            blocks.extend(storage_blocks(limit - blocks.len())?);
+            blocks
+        } else {
+            let mut blocks = storage_blocks(limit)?;
+            blocks.extend(in_memory_blocks.into_iter().rev());
+            blocks
+        };
+        blocks.truncate(limit);
+
+        Ok(blocks)
+    }
+
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // In-memory blocks take precedence over any overlapping blocks in the database, so the
+        // index is only used below them.
+        let lowest_memory_block = self
+            .head_block
+            .as_ref()
+            .and_then(|b| b.chain().last())
+            .map(|b| b.number())
+            .unwrap_or(BlockNumber::MAX);
+
+        Ok(self.storage_provider.log_index_range()?.and_then(|range| {
+            let end = (*range.end()).min(lowest_memory_block.saturating_sub(1));
+            (*range.start() <= end).then(|| *range.start()..=end)
+        }))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.storage_provider.log_topic_blocks(topic, range)
+    }
+}
+
+impl<N: ProviderNodeTypes> HashedStateRangeReader for ConsistentProvider<N> {
+    fn hashed_account_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        with_storage: bool,
+        with_preimages: bool,
+    ) -> ProviderResult<HashedAccountRange> {
+        let Some(state) = self.in_memory_hashed_state(block_number) else {
+            return self.storage_provider.hashed_account_range(
+                block_number,
+                start,
+                limit,
+                with_storage,
+                with_preimages,
+            )
+        };
+
+        let mut range = self.storage_provider.hashed_account_range_with_overlay(
+            state,
+            start,
+            limit,
+            with_storage,
+        )?;
+        if with_preimages {
+            range.set_addresses(&self.account_preimages(&range.hashed_addresses())?);
+        }
+        Ok(range)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        if let Some(mut state) = self.in_memory_hashed_state(block_number) {
+            let hashed_address = keccak256(address);
+            let mut storage = state.storages.remove(&hashed_address).unwrap_or_default();
+            storage.extend(&overlay);
+            return self.storage_provider.hashed_storage_range_with_overlay(
+                hashed_address,
+                storage,
+                start,
+                limit,
+            )
+        }
+        self.storage_provider.hashed_storage_range(block_number, address, overlay, start, limit)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        let mut preimages = self.storage_provider.account_preimages(hashed_addresses)?;
+
+        // Accounts that were only touched by blocks that are not persisted yet.
+        if preimages.len() < hashed_addresses.len() {
+            for block in self.head_block.iter().flat_map(|block| block.chain()) {
+                for address in block.block_ref().execution_output.bundle.state.keys() {
+                    let hashed_address = keccak256(address);
+                    if hashed_addresses.contains(&hashed_address) {
+                        preimages.insert(hashed_address, *address);
+                    }
+                }
+            }
+        }
+
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<B256Map<B256>> {
+        let mut preimages = self.storage_provider.storage_preimages(address, hashed_slots)?;
+
+        // Slots that were only written by blocks that are not persisted yet.
+        if preimages.len() < hashed_slots.len() {
+            for block in self.head_block.iter().flat_map(|block| block.chain()) {
+                let Some(account) = block.block_ref().execution_output.bundle.account(&address)
+                else {
+                    continue
+                };
+                for slot in account.storage.keys() {
+                    let slot = B256::from(*slot);
+                    let hashed_slot = keccak256(slot);
+                    if hashed_slots.contains(&hashed_slot) {
+                        preimages.insert(hashed_slot, slot);
+                    }
+                }
+            }
+        }
+
+        Ok(preimages)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
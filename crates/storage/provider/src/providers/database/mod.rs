@@ -659,11 +659,16 @@ mod tests {
     use super::*;
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
-        test_utils::{blocks::TEST_BLOCK, create_test_provider_factory, MockNodeTypesWithDB},
-        BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
-        StorageLocation, TransactionsProvider,
+        test_utils::{
+            blocks::{BlockchainTestData, TEST_BLOCK},
+            create_test_provider_factory, MockNodeTypesWithDB,
+        },
+        writer::UnifiedStorageWriter,
+        BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockWriter, ChangeSetReader,
+        DBProvider, HashedStateRangeReader, HeaderSyncGapProvider, HistoryReader, HistoryWriter,
+        StorageChangeSetReader, StorageLocation, TransactionsProvider,
     };
-    use alloy_primitives::{TxNumber, B256, U256};
+    use alloy_primitives::{keccak256, Address, TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use rand::Rng;
     use reth_chainspec::ChainSpecBuilder;
@@ -672,11 +677,17 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_primitives::StaticFileSegment;
+    use reth_db_api::{
+        models::{StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{Account, StaticFileSegment, StorageEntry};
     use reth_primitives_traits::SignedTransaction;
     use reth_prune_types::{PruneMode, PruneModes};
+    use reth_storage_api::MAX_HISTORY_STORAGE_SLOTS;
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::generators::{self, random_block, random_header, BlockParams};
+    use reth_trie::HashedStorage;
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
@@ -834,4 +845,229 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn account_history_blocks_lookup() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        provider
+            .insert_account_history_index([(address, vec![1, 5, 9]), (other, vec![4])])
+            .unwrap();
+        provider
+            .insert_storage_history_index([
+                ((address, B256::with_last_byte(1)), vec![3, 5]),
+                ((address, B256::with_last_byte(2)), vec![7, 10]),
+                ((other, B256::with_last_byte(1)), vec![6]),
+            ])
+            .unwrap();
+
+        let all = |address, range, newest_first| {
+            provider.account_history_blocks(address, range, usize::MAX, newest_first).unwrap()
+        };
+        assert_eq!(all(address, 2..=8, false), vec![3, 5, 7]);
+        assert_eq!(all(address, 2..=8, true), vec![7, 5, 3]);
+        assert_eq!(all(address, 0..=u64::MAX, false), vec![1, 3, 5, 7, 9, 10]);
+        assert_eq!(all(address, 0..=u64::MAX, true), vec![10, 9, 7, 5, 3, 1]);
+        assert!(all(Address::ZERO, 0..=u64::MAX, false).is_empty());
+        assert!(all(Address::ZERO, 0..=u64::MAX, true).is_empty());
+
+        // the first blocks in the requested order are returned
+        assert_eq!(provider.account_history_blocks(address, 0..=8, 2, false).unwrap(), vec![1, 3]);
+        assert_eq!(provider.account_history_blocks(address, 0..=8, 2, true).unwrap(), vec![7, 5]);
+        assert!(provider.account_history_blocks(address, 0..=8, 0, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn account_history_blocks_multiple_shards() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // the history of both the account and the slot is split into multiple shards
+        let address = Address::with_last_byte(1);
+        provider.insert_account_history_index([(address, (1..=5000).map(|n| n * 2))]).unwrap();
+        provider
+            .insert_storage_history_index([(
+                (address, B256::with_last_byte(1)),
+                (1..=5000).map(|n| n * 2 + 1),
+            )])
+            .unwrap();
+
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=6000, 3, true).unwrap(),
+            vec![6000, 5999, 5998]
+        );
+        assert_eq!(
+            provider.account_history_blocks(address, 4001..=u64::MAX, 3, false).unwrap(),
+            vec![4001, 4002, 4003]
+        );
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=u64::MAX, 2, true).unwrap(),
+            vec![10001, 10000]
+        );
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=u64::MAX, usize::MAX, false).unwrap(),
+            (2..=10001).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn account_history_blocks_max_slots() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let slot_history = |slots: RangeInclusive<usize>| {
+            slots.map(move |slot| ((address, B256::from(U256::from(slot))), vec![slot as u64]))
+        };
+        provider.insert_storage_history_index(slot_history(1..=MAX_HISTORY_STORAGE_SLOTS)).unwrap();
+        assert_eq!(
+            provider.account_history_blocks(address, 0..=u64::MAX, 1, true).unwrap(),
+            vec![MAX_HISTORY_STORAGE_SLOTS as u64]
+        );
+
+        let slot = MAX_HISTORY_STORAGE_SLOTS + 1;
+        provider.insert_storage_history_index(slot_history(slot..=slot)).unwrap();
+        assert_matches!(
+            provider.account_history_blocks(address, 0..=u64::MAX, 1, true),
+            Err(ProviderError::StorageHistoryTooLarge(a)) if a == address
+        );
+    }
+
+    #[test]
+    fn hashed_state_range_paging() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        let address = Address::with_last_byte(1);
+        let hashed_address = keccak256(address);
+        let mut hashed_addresses =
+            [hashed_address, B256::with_last_byte(2), B256::with_last_byte(3)];
+        hashed_addresses.sort();
+        for (nonce, hashed_address) in hashed_addresses.iter().enumerate() {
+            let account = Account { nonce: nonce as u64, ..Default::default() };
+            provider.tx_ref().put::<tables::HashedAccounts>(*hashed_address, account).unwrap();
+        }
+        for slot in 1..=3 {
+            let entry = StorageEntry { key: B256::with_last_byte(slot), value: U256::from(slot) };
+            provider.tx_ref().put::<tables::HashedStorages>(hashed_address, entry).unwrap();
+        }
+
+        let page = provider.hashed_account_range(0, B256::ZERO, 2, false, false).unwrap();
+        assert_eq!(
+            page.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+            hashed_addresses[..2]
+        );
+        assert_eq!(page.next_key, Some(hashed_addresses[2]));
+
+        let page = provider.hashed_account_range(0, hashed_addresses[2], 2, true, false).unwrap();
+        assert_eq!(page.accounts.len(), 1);
+        assert_eq!(page.accounts[0].address, None);
+        assert_eq!(page.next_key, None);
+
+        // The overlay clears the first slot and adds a fourth one.
+        let overlay = HashedStorage::from_iter(
+            false,
+            [(B256::with_last_byte(1), U256::ZERO), (B256::with_last_byte(4), U256::from(4))],
+        );
+        let page = provider.hashed_storage_range(0, address, overlay, B256::ZERO, 2).unwrap();
+        assert_eq!(
+            page.slots,
+            vec![
+                (B256::with_last_byte(2), U256::from(2)),
+                (B256::with_last_byte(3), U256::from(3))
+            ]
+        );
+        assert_eq!(page.next_key, Some(B256::with_last_byte(4)));
+    }
+
+    /// Returns a factory with the test blocks, with their changesets moved to static files if
+    /// `move_change_sets` is set.
+    fn factory_with_change_sets(move_change_sets: bool) -> ProviderFactory<MockNodeTypesWithDB> {
+        let factory = create_test_provider_factory();
+        let data = BlockchainTestData::default();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_block(data.genesis.clone().try_recover().unwrap(), StorageLocation::Database)
+            .unwrap();
+        let mut execution_outcome = data.blocks[0].1.clone();
+        execution_outcome.extend(data.blocks[1].1.clone());
+        provider_rw
+            .append_blocks_with_state(
+                data.blocks[..2].iter().map(|(block, _)| block.clone()).collect(),
+                &execution_outcome,
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        if move_change_sets {
+            let provider_rw = factory.provider_rw().unwrap();
+            let static_file_provider = factory.static_file_provider();
+            let mut accounts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            let mut storages_writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block_number in 0..=2 {
+                let changes = provider_rw.account_block_changeset(block_number).unwrap();
+                accounts_writer
+                    .append_account_changeset(&StaticFileAccountChangeSet { changes }, block_number)
+                    .unwrap();
+                let changes = provider_rw
+                    .storage_changeset(block_number)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(key, entry)| StorageBeforeTx { address: key.address(), entry })
+                    .collect();
+                storages_writer
+                    .append_storage_changeset(&StaticFileStorageChangeSet { changes }, block_number)
+                    .unwrap();
+            }
+            accounts_writer.commit().unwrap();
+            storages_writer.commit().unwrap();
+            provider_rw.tx_ref().clear::<tables::AccountChangeSets>().unwrap();
+            provider_rw.tx_ref().clear::<tables::StorageChangeSets>().unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        factory
+    }
+
+    #[test]
+    fn take_block_and_execution_above_restores_change_sets() {
+        let expected = {
+            let factory = factory_with_change_sets(false);
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.take_block_and_execution_above(0, StorageLocation::Database).unwrap()
+        };
+
+        let factory = factory_with_change_sets(true);
+        assert_eq!(
+            factory.provider().unwrap().tx_ref().entries::<tables::AccountChangeSets>().unwrap(),
+            0
+        );
+
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let chain =
+            provider_rw.take_block_and_execution_above(0, StorageLocation::Database).unwrap();
+        UnifiedStorageWriter::commit_unwind(provider_rw).unwrap();
+
+        // The unwound state is the same as if the changesets were never moved.
+        assert_eq!(chain.execution_outcome(), expected.execution_outcome());
+        assert!(!chain.execution_outcome().bundle.reverts.is_empty());
+
+        // Changesets of the unwound blocks are removed from static files as well.
+        let static_file_provider = factory.static_file_provider();
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            assert_eq!(static_file_provider.get_highest_static_file_block(segment), Some(0));
+        }
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSets>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::StorageChangeSets>().unwrap(), 0);
+    }
 }
@@ -11,12 +11,13 @@ use crate::{
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
-    DBProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter, LatestStateProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
-    PruneCheckpointWriter, RevertsInit, StageCheckpointReader, StateCommitmentProvider,
-    StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation,
-    StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
+    DBProvider, HashedAccountRange, HashedAccountRangeEntry, HashedStateRangeReader,
+    HashedStorageRange, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HistoricalStateProvider, HistoricalStateProviderRef, HistoryReader, HistoryWriter,
+    LatestStateProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, RevertsInit, StageCheckpointReader,
+    StateCommitmentProvider, StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader,
+    StorageLocation, StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
     TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader, Header, TxReceipt};
@@ -24,7 +25,7 @@ use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -58,14 +59,21 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
     BlockBodyIndicesProvider, BlockBodyReader, NodePrimitivesProvider, OmmersProvider,
     StateProvider, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    MAX_HISTORY_STORAGE_SLOTS,
 };
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    metrics::{TrieRootMetrics, TrieType},
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
     updates::{StorageTrieUpdates, TrieUpdates},
-    HashedPostStateSorted, Nibbles, StateRoot, StoredNibbles,
+    HashedPostState, HashedPostStateSorted, HashedStorage, Nibbles, StateRoot, StorageRoot,
+    StoredNibbles,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseStateRoot, DatabaseStorageTrieCursor,
+    DatabaseTrieCursorFactory,
 };
-use reth_trie_db::{DatabaseStateRoot, DatabaseStorageTrieCursor};
 use revm::db::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
 };
@@ -254,6 +262,80 @@ impl<TX, N: NodeTypes> AsRef<Self> for DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Moves the account and storage changesets above the given block from static files back to
+    /// the database, so they can be unwound.
+    ///
+    /// The static file rows are only removed once the static files are committed. Returns `true`
+    /// if any changesets were restored.
+    pub fn restore_change_sets_above(&self, block: BlockNumber) -> ProviderResult<bool> {
+        let mut restored = false;
+        for (segment, prune_segment) in [
+            (StaticFileSegment::AccountChangeSets, PruneSegment::AccountChangeSets),
+            (StaticFileSegment::StorageChangeSets, PruneSegment::StorageChangeSets),
+        ] {
+            let Some(highest_block) = self
+                .static_file_provider
+                .get_highest_static_file_block(segment)
+                .filter(|highest_block| *highest_block > block)
+            else {
+                continue
+            };
+
+            debug!(target: "providers::db", ?segment, from = highest_block, to = block, "Restoring changesets from static files");
+            for number in block + 1..=highest_block {
+                if segment == StaticFileSegment::AccountChangeSets {
+                    for account_before in
+                        self.static_file_provider.account_block_changeset(number)?
+                    {
+                        self.tx.put::<tables::AccountChangeSets>(number, account_before)?;
+                    }
+                } else {
+                    for (key, entry) in self.static_file_provider.storage_changeset(number)? {
+                        self.tx.put::<tables::StorageChangeSets>(key, entry)?;
+                    }
+                }
+            }
+
+            self.static_file_provider
+                .latest_writer(segment)?
+                .prune_change_sets(highest_block - block)?;
+
+            // The restored rows must not be pruned from the database again.
+            if let Some(mut checkpoint) = self
+                .get_prune_checkpoint(prune_segment)?
+                .filter(|checkpoint| checkpoint.block_number.is_some_and(|number| number > block))
+            {
+                checkpoint.block_number = Some(block);
+                self.save_prune_checkpoint(prune_segment, checkpoint)?;
+            }
+
+            restored = true;
+        }
+
+        Ok(restored)
+    }
+
+    /// Unwinds the log index above the given block, if it has been built.
+    ///
+    /// Reads the receipts of the unwound blocks, so it has to be called before they are removed.
+    fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let Some(checkpoint) = self
+            .get_stage_checkpoint(StageId::LogIndex)?
+            .filter(|checkpoint| checkpoint.block_number > block)
+        else {
+            return Ok(())
+        };
+
+        for number in block + 1..=checkpoint.block_number {
+            let receipts = self.receipts_by_block(number.into())?.unwrap_or_default();
+            self.unwind_log_index(
+                receipts.iter().flat_map(|receipt| receipt.logs()).map(|log| (number, log)),
+            )?;
+        }
+
+        self.save_stage_checkpoint(StageId::LogIndex, StageCheckpoint::new(block))
+    }
+
     /// Unwinds trie state for the given range.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -909,6 +991,14 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        if self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .is_some_and(|highest| highest >= block_number)
+        {
+            return self.static_file_provider.storage_changeset(block_number)
+        }
+
         let range = block_number..=block_number;
         let storage_range = BlockNumberAddress::range(range);
         self.tx
@@ -924,6 +1014,14 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        if self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .is_some_and(|highest| highest >= block_number)
+        {
+            return self.static_file_provider.account_block_changeset(block_number)
+        }
+
         let range = block_number..=block_number;
         self.tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -1820,12 +1918,24 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             .then(|| self.tx.cursor_write::<tables::Receipts<Self::Receipt>>())
             .transpose()?;
 
+        // Receipts of blocks that are already in static files aren't written again. They're
+        // imported from era1 archives, or written by an execution that wasn't committed to the
+        // database.
+        let highest_static_file_receipts_block =
+            self.static_file_provider.get_highest_static_file_block(StaticFileSegment::Receipts);
+        let first_static_file_receipts_block = highest_static_file_receipts_block
+            .map_or(first_block, |block| first_block.max(block + 1));
+
         // Prepare receipts static writer if we are going to write receipts to static files
         //
         // We are writing to static files if requested and if there's no receipt pruning configured
         let mut receipts_static_writer = (write_receipts_to.static_files() &&
-            !has_receipts_pruning)
-            .then(|| self.static_file_provider.get_writer(first_block, StaticFileSegment::Receipts))
+            !has_receipts_pruning &&
+            first_static_file_receipts_block <= last_block)
+            .then(|| {
+                self.static_file_provider
+                    .get_writer(first_static_file_receipts_block, StaticFileSegment::Receipts)
+            })
             .transpose()?;
 
         let has_contract_log_filter = !self.prune_modes.receipts_log_filter.is_empty();
@@ -1846,9 +1956,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             execution_outcome.receipts.iter().zip(block_indices).enumerate()
         {
             let block_number = first_block + idx as u64;
+            let mut static_file_writer = receipts_static_writer
+                .as_mut()
+                .filter(|_| block_number >= first_static_file_receipts_block);
 
             // Increment block number for receipts static file writer
-            if let Some(writer) = receipts_static_writer.as_mut() {
+            if let Some(writer) = static_file_writer.as_mut() {
                 writer.increment_block(block_number)?;
             }
 
@@ -1877,7 +1990,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                     continue
                 }
 
-                if let Some(writer) = &mut receipts_static_writer {
+                if let Some(writer) = static_file_writer.as_mut() {
                     writer.append_receipt(receipt_idx, receipt)?;
                 }
 
@@ -2090,6 +2203,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             return Ok(());
         }
 
+        // The log index is built from the receipts, so it's unwound before they are removed.
+        self.unwind_log_index_above(block)?;
+
         // We are not removing block meta as it is used to get block changesets.
         let block_bodies = self.block_body_indices_range(range.clone())?;
 
@@ -2184,6 +2300,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         if range.is_empty() {
             return Ok(ExecutionOutcome::default())
         }
+
+        // The log index is built from the receipts, so it's unwound before they are removed.
+        self.unwind_log_index_above(block)?;
         let start_block_number = *range.start();
 
         // We are not removing block meta as it is used to get block changesets.
@@ -2608,6 +2727,474 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns an error if the account or storage history needed to revert the state to the given
+    /// block was pruned.
+    fn ensure_history_available(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            if self
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| block_number < pruned)
+            {
+                return Err(ProviderError::StateAtBlockPruned(block_number))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the hashed state that reverts the latest state to the state after the given block.
+    fn hashed_state_reverts(&self, block_number: BlockNumber) -> ProviderResult<HashedPostState> {
+        if block_number >= self.best_block_number()? {
+            return Ok(HashedPostState::default())
+        }
+        self.ensure_history_available(block_number)?;
+
+        HistoricalStateProviderRef::new(self, block_number + 1).revert_state()
+    }
+
+    /// Returns the hashed storage that reverts the latest storage of `address` to the storage
+    /// after the given block.
+    fn hashed_storage_reverts(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<HashedStorage> {
+        if block_number >= self.best_block_number()? {
+            return Ok(HashedStorage::default())
+        }
+        self.ensure_history_available(block_number)?;
+
+        HistoricalStateProviderRef::new(self, block_number + 1).revert_storage(address)
+    }
+
+    /// Returns at most `limit` accounts of the latest state with `overlay` applied on top,
+    /// starting at the hashed address `start`.
+    pub(crate) fn hashed_account_range_with_overlay(
+        &self,
+        overlay: HashedPostState,
+        start: B256,
+        limit: usize,
+        with_storage: bool,
+    ) -> ProviderResult<HashedAccountRange> {
+        let prefix_sets = overlay.construct_prefix_sets().freeze();
+        let state_sorted = overlay.into_sorted();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &state_sorted,
+        );
+
+        let mut range = HashedAccountRange::default();
+        let mut account_cursor = hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = account_cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if range.accounts.len() >= limit {
+                range.next_key = Some(hashed_address);
+                break
+            }
+
+            let storage_root = StorageRoot::new_hashed(
+                DatabaseTrieCursorFactory::new(&self.tx),
+                hashed_cursor_factory.clone(),
+                hashed_address,
+                prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default(),
+                TrieRootMetrics::new(TrieType::Storage),
+            )
+            .root()
+            .map_err(reth_db::DatabaseError::from)?;
+
+            let storage = with_storage
+                .then(|| {
+                    hashed_storage_page(
+                        &hashed_cursor_factory,
+                        hashed_address,
+                        B256::ZERO,
+                        usize::MAX,
+                    )
+                })
+                .transpose()?
+                .map(|page| page.slots);
+
+            range.accounts.push(HashedAccountRangeEntry {
+                hashed_address,
+                address: None,
+                account,
+                storage_root,
+                storage,
+            });
+            entry = account_cursor.next()?;
+        }
+
+        Ok(range)
+    }
+
+    /// Returns at most `limit` storage slots of the account with the given hashed address, starting
+    /// at the hashed slot `start`.
+    ///
+    /// The storage is taken from the latest state with `overlay` applied on top.
+    pub(crate) fn hashed_storage_range_with_overlay(
+        &self,
+        hashed_address: B256,
+        overlay: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        let state_sorted =
+            HashedPostState::from_hashed_storage(hashed_address, overlay).into_sorted();
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(&self.tx),
+            &state_sorted,
+        );
+        Ok(hashed_storage_page(&hashed_cursor_factory, hashed_address, start, limit)?)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> HashedStateRangeReader for DatabaseProvider<TX, N> {
+    fn hashed_account_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        with_storage: bool,
+        with_preimages: bool,
+    ) -> ProviderResult<HashedAccountRange> {
+        let reverts = self.hashed_state_reverts(block_number)?;
+        let mut range =
+            self.hashed_account_range_with_overlay(reverts, start, limit, with_storage)?;
+        if with_preimages {
+            range.set_addresses(&self.account_preimages(&range.hashed_addresses())?);
+        }
+        Ok(range)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        let mut storage = self.hashed_storage_reverts(block_number, address)?;
+        storage.extend(&overlay);
+        self.hashed_storage_range_with_overlay(keccak256(address), storage, start, limit)
+    }
+
+    fn account_preimages(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        let mut remaining = hashed_addresses.iter().copied().collect::<HashSet<_>>();
+        let mut preimages = B256Map::default();
+
+        // Accounts that exist in the latest state.
+        let mut plain_account_cursor = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        for entry in plain_account_cursor.walk(None)? {
+            if remaining.is_empty() {
+                return Ok(preimages)
+            }
+            let (address, _) = entry?;
+            let hashed_address = keccak256(address);
+            if remaining.remove(&hashed_address) {
+                preimages.insert(hashed_address, address);
+            }
+        }
+
+        // Accounts that were changed at some point, including the ones that were destroyed since.
+        let mut account_history_cursor = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        for entry in account_history_cursor.walk(None)? {
+            if remaining.is_empty() {
+                break
+            }
+            let (key, _) = entry?;
+            let hashed_address = keccak256(key.key);
+            if remaining.remove(&hashed_address) {
+                preimages.insert(hashed_address, key.key);
+            }
+        }
+
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<B256Map<B256>> {
+        let mut remaining = hashed_slots.iter().copied().collect::<HashSet<_>>();
+        let mut preimages = B256Map::default();
+
+        // Slots that are set in the latest state.
+        let mut plain_storage_cursor = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        for entry in plain_storage_cursor.walk_dup(Some(address), None)? {
+            if remaining.is_empty() {
+                return Ok(preimages)
+            }
+            let (_, StorageEntry { key, .. }) = entry?;
+            let hashed_slot = keccak256(key);
+            if remaining.remove(&hashed_slot) {
+                preimages.insert(hashed_slot, key);
+            }
+        }
+
+        // Slots that were written at some point, including the ones that were cleared since.
+        let mut storage_history_cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        for entry in
+            storage_history_cursor.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))?
+        {
+            if remaining.is_empty() {
+                break
+            }
+            let (key, _) = entry?;
+            if key.address != address {
+                break
+            }
+            let slot = key.sharded_key.key;
+            let hashed_slot = keccak256(slot);
+            if remaining.remove(&hashed_slot) {
+                preimages.insert(hashed_slot, slot);
+            }
+        }
+
+        Ok(preimages)
+    }
+}
+
+/// Collects at most `limit` storage slots of the account with the given hashed address, starting at
+/// the hashed slot `start`.
+fn hashed_storage_page(
+    hashed_cursor_factory: &impl HashedCursorFactory,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> Result<HashedStorageRange, DatabaseError> {
+    let mut range = HashedStorageRange::default();
+    let mut storage_cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+    let mut entry = storage_cursor.seek(start)?;
+    while let Some((hashed_slot, value)) = entry {
+        if range.slots.len() >= limit {
+            range.next_key = Some(hashed_slot);
+            break
+        }
+        range.slots.push((hashed_slot, value));
+        entry = storage_cursor.next()?;
+    }
+    Ok(range)
+}
+
+/// The first blocks of a history index lookup, in the requested order.
+#[derive(Debug)]
+struct HistoryBlocks {
+    blocks: BTreeSet<BlockNumber>,
+    limit: usize,
+    newest_first: bool,
+}
+
+impl HistoryBlocks {
+    const fn new(limit: usize, newest_first: bool) -> Self {
+        Self { blocks: BTreeSet::new(), limit, newest_first }
+    }
+
+    /// Inserts a block, keeping at most `limit` blocks.
+    ///
+    /// Returns `false` if the block comes after all kept blocks while the limit is reached, in
+    /// which case no later block in the requested order can be kept either.
+    fn insert(&mut self, block: BlockNumber) -> bool {
+        if self.blocks.len() >= self.limit {
+            let last = if self.newest_first { self.blocks.first() } else { self.blocks.last() };
+            let Some(&last) = last else { return false };
+            if (self.newest_first && block <= last) || (!self.newest_first && block >= last) {
+                return false
+            }
+            if self.newest_first {
+                self.blocks.pop_first();
+            } else {
+                self.blocks.pop_last();
+            }
+        }
+        self.blocks.insert(block);
+        true
+    }
+
+    /// Returns the blocks in the requested order.
+    fn into_vec(self) -> Vec<BlockNumber> {
+        if self.newest_first {
+            self.blocks.into_iter().rev().collect()
+        } else {
+            self.blocks.into_iter().collect()
+        }
+    }
+}
+
+/// Walks the history shards of a single account or storage slot and inserts the blocks in `range`
+/// into `blocks`, in the order requested by `blocks`.
+///
+/// `shard_key` returns the key of the shard with the given highest block number, and `in_shards`
+/// returns whether a key belongs to the walked account or storage slot. The walk stops once
+/// `blocks` doesn't accept any more blocks.
+fn walk_history_shards<T, C>(
+    cursor: &mut C,
+    range: &RangeInclusive<BlockNumber>,
+    shard_key: impl Fn(BlockNumber) -> T::Key,
+    in_shards: impl Fn(&T::Key) -> bool,
+    blocks: &mut HistoryBlocks,
+) -> ProviderResult<()>
+where
+    T: Table<Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    // Shards are keyed by their highest block number, so the shard containing a block is the
+    // first one with a key that is not lower.
+    if blocks.newest_first {
+        let mut entry = match cursor.seek(shard_key(*range.end()))? {
+            Some(entry) if in_shards(&entry.0) => Some(entry),
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        while let Some((_, list)) = entry.filter(|(key, _)| in_shards(key)) {
+            for block in list.iter().collect::<Vec<_>>().into_iter().rev() {
+                if block > *range.end() {
+                    continue
+                }
+                if block < *range.start() || !blocks.insert(block) {
+                    return Ok(())
+                }
+            }
+            entry = cursor.prev()?;
+        }
+    } else {
+        let mut entry = cursor.seek(shard_key(*range.start()))?;
+        while let Some((_, list)) = entry.filter(|(key, _)| in_shards(key)) {
+            for block in list.iter() {
+                if block < *range.start() {
+                    continue
+                }
+                if block > *range.end() || !blocks.insert(block) {
+                    return Ok(())
+                }
+            }
+            entry = cursor.next()?;
+        }
+    }
+
+    Ok(())
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> HistoryReader for DatabaseProvider<TX, N> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        newest_first: bool,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut blocks = HistoryBlocks::new(limit, newest_first);
+
+        let mut account_cursor = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        walk_history_shards::<tables::AccountsHistory, _>(
+            &mut account_cursor,
+            &range,
+            |highest_block_number| ShardedKey::new(address, highest_block_number),
+            |key| key.key == address,
+            &mut blocks,
+        )?;
+
+        // Storage shards are ordered by slot first, so the shards of every slot of the account are
+        // walked separately, up to a bounded number of slots. Once enough blocks were found, only
+        // the part of each slot's history that can still contribute is read.
+        let mut storage_cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut next_slot = Some(B256::ZERO);
+        let mut slots = 0;
+        while let Some(start_slot) = next_slot {
+            let Some((key, _)) = storage_cursor
+                .seek(StorageShardedKey::new(address, start_slot, 0))?
+                .filter(|(key, _)| key.address == address)
+            else {
+                break
+            };
+            let slot = key.sharded_key.key;
+
+            slots += 1;
+            if slots > MAX_HISTORY_STORAGE_SLOTS {
+                return Err(ProviderError::StorageHistoryTooLarge(address))
+            }
+
+            walk_history_shards::<tables::StoragesHistory, _>(
+                &mut storage_cursor,
+                &range,
+                |highest_block_number| StorageShardedKey::new(address, slot, highest_block_number),
+                |key| key.address == address && key.sharded_key.key == slot,
+                &mut blocks,
+            )?;
+
+            next_slot = U256::from_be_bytes(slot.0).checked_add(U256::from(1)).map(B256::from);
+        }
+
+        Ok(blocks.into_vec())
+    }
+
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        // The stage checkpoint is only moved past genesis once the index has been built.
+        let Some(end) = self
+            .get_stage_checkpoint(StageId::LogIndex)?
+            .map(|checkpoint| checkpoint.block_number)
+            .filter(|block_number| *block_number > 0)
+        else {
+            return Ok(None)
+        };
+        let start = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+
+        Ok((start <= end).then_some(start..=end))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<tables::LogAddressIndex, _>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<tables::LogTopicIndex, _>(topic, range)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the blocks in the range from the shards of the key in the given log index table.
+    fn log_index_blocks<T, K>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: PartialEq + Clone,
+    {
+        let mut blocks = Vec::new();
+
+        // Shards are keyed by their highest block number, so start at the first shard that can
+        // contain the start of the range.
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(key.clone(), *range.start())))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != key {
+                break
+            }
+            blocks.extend(list.iter().filter(|block| range.contains(block)));
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+        }
+
+        Ok(blocks)
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvider<TX, N> {
     fn unwind_account_history_indices<'a>(
         &self,
@@ -2740,6 +3327,93 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         Ok(())
     }
+
+    fn unwind_log_index<'a>(
+        &self,
+        logs: impl Iterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<usize> {
+        // The lowest block number of every address and topic, everything from it is removed.
+        let mut addresses = BTreeMap::new();
+        let mut topics = BTreeMap::new();
+        let mut walked = 0;
+        for (block_number, log) in logs {
+            let lowest = addresses.entry(log.address).or_insert(block_number);
+            *lowest = (*lowest).min(block_number);
+            for topic in log.topics() {
+                let lowest = topics.entry(*topic).or_insert(block_number);
+                *lowest = (*lowest).min(block_number);
+            }
+            walked += 1;
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressIndex>()?;
+        for (address, rem_index) in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressIndex, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                rem_index,
+                |sharded_key| sharded_key.key == address,
+            )?;
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicIndex>()?;
+        for (topic, rem_index) in topics {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicIndex, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                rem_index,
+                |sharded_key| sharded_key.key == topic,
+            )?;
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(walked)
+    }
+
+    fn update_log_index<'a>(
+        &self,
+        block_number: BlockNumber,
+        logs: impl IntoIterator<Item = &'a Log>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self
+            .get_stage_checkpoint(StageId::LogIndex)?
+            .filter(|checkpoint| checkpoint.block_number > 0)
+        else {
+            return Ok(())
+        };
+        if checkpoint.block_number + 1 != block_number {
+            return Ok(())
+        }
+
+        let mut addresses = BTreeSet::new();
+        let mut topics = BTreeSet::new();
+        for log in logs {
+            addresses.insert(log.address);
+            topics.extend(log.topics().iter().copied());
+        }
+
+        self.append_history_index::<_, tables::LogAddressIndex>(
+            addresses.into_iter().map(|address| (address, [block_number])),
+            ShardedKey::new,
+        )?;
+        self.append_history_index::<_, tables::LogTopicIndex>(
+            topics.into_iter().map(|topic| (topic, [block_number])),
+            ShardedKey::new,
+        )?;
+
+        self.save_stage_checkpoint(StageId::LogIndex, StageCheckpoint::new(block_number))
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
@@ -2752,6 +3426,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<Chain<Self::Primitives>> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.restore_change_sets_above(block)?;
         self.unwind_trie_state_range(range.clone())?;
 
         // get execution res
@@ -2776,6 +3451,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<()> {
         let range = block + 1..=self.last_block_number()?;
 
+        self.restore_change_sets_above(block)?;
         self.unwind_trie_state_range(range)?;
 
         // remove execution res
@@ -3051,6 +3727,15 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             self.static_file_provider
                 .latest_writer(StaticFileSegment::Transactions)?
                 .prune_transactions(to_delete, block)?;
+
+            // Receipts in static files can be ahead of the execution stage, up to the last body.
+            if self
+                .static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::Receipts)
+                .is_some_and(|highest| highest > block)
+            {
+                self.remove_receipts_from(unwind_tx_from, block, StorageLocation::StaticFiles)?;
+            }
         }
 
         Ok(())
@@ -3091,6 +3776,12 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
         self.update_history_indices(first_number..=last_block_number)?;
+        for block_number in first_number..=last_block_number {
+            self.update_log_index(
+                block_number,
+                execution_outcome.logs(block_number).into_iter().flatten(),
+            )?;
+        }
         durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
 
         // Update pipeline progress
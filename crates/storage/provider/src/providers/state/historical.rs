@@ -1,27 +1,35 @@
 use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{map::B256Map, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Map},
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
+};
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     transaction::DbTx,
 };
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StaticFileSegment};
 use reth_storage_api::{
-    BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, ChangeSetReader, DBProvider, StateCommitmentProvider, StateProofProvider,
+    StorageChangeSetReader, StorageRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
@@ -40,6 +48,9 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets that have been moved to the [`StaticFileSegment::AccountChangeSets`] and
+/// [`StaticFileSegment::StorageChangeSets`] static files are read from there.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, Provider> {
     /// Database provider
@@ -58,8 +69,10 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProviderRef<'b, Provider>
+impl<
+        'b,
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProviderRef<'b, Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
@@ -118,7 +131,7 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
     }
 
     /// Retrieve revert hashed state for this history provider.
-    fn revert_state(&self) -> ProviderResult<HashedPostState> {
+    pub(crate) fn revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -133,13 +146,65 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
+        let accounts_end = self.static_file_changesets_end(StaticFileSegment::AccountChangeSets);
+        let storages_end = self.static_file_changesets_end(StaticFileSegment::StorageChangeSets);
+
+        // The database only needs to be read above the changesets in static files.
+        let db_from = accounts_end
+            .map_or(self.block_number, |end| end + 1)
+            .min(storages_end.map_or(self.block_number, |end| end + 1));
+        let mut state = HashedPostState::from_reverts::<
             <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        >(self.tx(), db_from)?;
+
+        // Static files hold the earliest changes, so their values take precedence.
+        let static_file_provider = self.provider.static_file_provider();
+        if let Some(end) = accounts_end {
+            let mut accounts = AddressMap::default();
+            for block_number in self.block_number..=end {
+                for AccountBeforeTx { address, info } in
+                    static_file_provider.account_block_changeset(block_number)?
+                {
+                    accounts.entry(address).or_insert(info);
+                }
+            }
+            state.accounts.extend(accounts.into_iter().map(|(address, info)| {
+                (<Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(address), info)
+            }));
+        }
+        if let Some(end) = storages_end {
+            let mut storages = AddressMap::<B256Map<U256>>::default();
+            for block_number in self.block_number..=end {
+                for (BlockNumberAddress((_, address)), entry) in
+                    static_file_provider.storage_changeset(block_number)?
+                {
+                    storages.entry(address).or_default().entry(entry.key).or_insert(entry.value);
+                }
+            }
+            for (address, storage) in storages {
+                state
+                    .storages
+                    .entry(<Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(
+                        address,
+                    ))
+                    .or_insert_with(|| HashedStorage::new(false))
+                    .storage
+                    .extend(storage.into_iter().map(|(slot, value)| {
+                        (
+                            <Provider::StateCommitment as StateCommitment>::KeyHasher::hash_key(
+                                slot,
+                            ),
+                            value,
+                        )
+                    }));
+            }
+        }
+
+        Ok(state)
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
-    fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
+    pub(crate) fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
         if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
@@ -152,7 +217,49 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let Some(end) = self.static_file_changesets_end(StaticFileSegment::StorageChangeSets)
+        else {
+            return Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        };
+
+        // Static files hold the earliest changes, so their values take precedence.
+        let mut storage = HashedStorage::from_reverts(self.tx(), address, end + 1)?;
+        let static_file_provider = self.provider.static_file_provider();
+        let mut slots = B256Map::default();
+        for block_number in self.block_number..=end {
+            for (BlockNumberAddress((_, storage_address)), entry) in
+                static_file_provider.storage_changeset(block_number)?
+            {
+                if storage_address == address {
+                    slots.entry(keccak256(entry.key)).or_insert(entry.value);
+                }
+            }
+        }
+        storage.storage.extend(slots);
+
+        Ok(storage)
+    }
+
+    /// Returns the highest block of the changesets static files of the segment, if the
+    /// changesets of this provider's block have been moved to them.
+    fn static_file_changesets_end(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .filter(|end| *end >= self.block_number)
+    }
+
+    /// Returns `true` if the changesets of the given block have been moved to the static files of
+    /// the segment.
+    fn is_changeset_in_static_files(
+        &self,
+        segment: StaticFileSegment,
+        block_number: BlockNumber,
+    ) -> bool {
+        self.provider
+            .static_file_provider()
+            .get_highest_static_file_block(segment)
+            .is_some_and(|end| end >= block_number)
     }
 
     fn history_info<T, K>(
@@ -243,23 +350,42 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(*address)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, *address)?
-                .filter(|acc| &acc.address == address)
-                .ok_or(ProviderError::AccountChangesetNotFound {
-                    block_number: changeset_block_number,
-                    address: *address,
-                })?
-                .info),
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let account_before = if self.is_changeset_in_static_files(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                ) {
+                    // Changes of a block are sorted by address.
+                    let mut changeset = self
+                        .provider
+                        .static_file_provider()
+                        .account_block_changeset(changeset_block_number)?;
+                    changeset
+                        .binary_search_by_key(address, |acc| acc.address)
+                        .ok()
+                        .map(|index| changeset.swap_remove(index))
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::AccountChangeSets>()?
+                        .seek_by_key_subkey(changeset_block_number, *address)?
+                        .filter(|acc| &acc.address == address)
+                };
+
+                Ok(account_before
+                    .ok_or(ProviderError::AccountChangesetNotFound {
+                        block_number: changeset_block_number,
+                        address: *address,
+                    })?
+                    .info)
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
                 Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
             }
@@ -284,8 +410,9 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
         let mut revert_state = self.revert_state()?;
@@ -320,8 +447,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StorageRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
         &self,
@@ -359,8 +487,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateProofProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
     fn proof(
@@ -402,8 +531,13 @@ impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider>
-    StateProvider for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + StaticFileProviderFactory
+            + BlockHashReader
+            + StateCommitmentProvider,
+    > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -413,18 +547,39 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
     ) -> ProviderResult<Option<StorageValue>> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
-            HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
-                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
-                        block_number: changeset_block_number,
-                        address,
-                        storage_key: Box::new(storage_key),
-                    })?
-                    .value,
-            )),
+            HistoryInfo::InChangeset(changeset_block_number) => {
+                let entry = if self.is_changeset_in_static_files(
+                    StaticFileSegment::StorageChangeSets,
+                    changeset_block_number,
+                ) {
+                    // Changes of a block are sorted by address and slot.
+                    let changeset = self
+                        .provider
+                        .static_file_provider()
+                        .storage_changeset(changeset_block_number)?;
+                    changeset
+                        .binary_search_by(|(BlockNumberAddress((_, changed_address)), entry)| {
+                            (*changed_address, entry.key).cmp(&(address, storage_key))
+                        })
+                        .ok()
+                        .map(|index| changeset[index].1)
+                } else {
+                    self.tx()
+                        .cursor_dup_read::<tables::StorageChangeSets>()?
+                        .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
+                        .filter(|entry| entry.key == storage_key)
+                };
+
+                Ok(Some(
+                    entry
+                        .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                            block_number: changeset_block_number,
+                            address,
+                            storage_key: Box::new(storage_key),
+                        })?
+                        .value,
+                ))
+            }
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
                 .tx()
                 .cursor_dup_read::<tables::PlainStorageState>()?
@@ -459,8 +614,9 @@ pub struct HistoricalStateProvider<Provider> {
     lowest_available_blocks: LowestAvailableBlocks,
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProvider<Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProvider<Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
@@ -503,7 +659,7 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + BlockHashReader + StateCommitmentProvider]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -536,17 +692,24 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
-        test_utils::create_test_provider_factory,
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, ProviderFactory,
+        StateProvider, StaticFileProviderFactory,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey,
+            StaticFileAccountChangeSet, StaticFileStorageChangeSet, StorageBeforeTx,
+        },
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{Account, StorageEntry};
+    use reth_primitives::{Account, StaticFileSegment, StorageEntry};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
         StateCommitmentProvider,
@@ -560,7 +723,11 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[allow(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider,
+        T: DBProvider
+            + BlockNumReader
+            + StaticFileProviderFactory
+            + BlockHashReader
+            + StateCommitmentProvider,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
@@ -835,4 +1002,147 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         ));
     }
+
+    /// Writes the same account and storage history to the database, with the changesets either
+    /// in the database or in static files.
+    fn history_with_changesets(static_files: bool) -> ProviderFactory<MockNodeTypesWithDB> {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3, 7, 10]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: HIGHER_ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([4]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3, 7, 10]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(
+            ADDRESS,
+            Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(
+            HIGHER_ADDRESS,
+            Account { nonce: 4, balance: U256::ZERO, bytecode_hash: None },
+        )
+        .unwrap();
+        tx.put::<tables::PlainStorageState>(
+            ADDRESS,
+            StorageEntry { key: STORAGE, value: U256::from(100) },
+        )
+        .unwrap();
+
+        let account_changes = |block_number: u64| match block_number {
+            1 => vec![AccountBeforeTx { address: ADDRESS, info: None }],
+            4 => vec![AccountBeforeTx { address: HIGHER_ADDRESS, info: None }],
+            3 | 7 | 10 => vec![AccountBeforeTx {
+                address: ADDRESS,
+                info: Some(Account {
+                    nonce: block_number,
+                    balance: U256::ZERO,
+                    bytecode_hash: None,
+                }),
+            }],
+            _ => Vec::new(),
+        };
+        let storage_changes = |block_number: u64| match block_number {
+            3 | 7 | 10 => vec![StorageBeforeTx {
+                address: ADDRESS,
+                entry: StorageEntry { key: STORAGE, value: U256::from(block_number) },
+            }],
+            _ => Vec::new(),
+        };
+
+        if static_files {
+            let static_file_provider = factory.static_file_provider();
+            let mut accounts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            let mut storages_writer =
+                static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block_number in 0..=12 {
+                accounts_writer
+                    .append_account_changeset(
+                        &StaticFileAccountChangeSet { changes: account_changes(block_number) },
+                        block_number,
+                    )
+                    .unwrap();
+                storages_writer
+                    .append_storage_changeset(
+                        &StaticFileStorageChangeSet { changes: storage_changes(block_number) },
+                        block_number,
+                    )
+                    .unwrap();
+            }
+            accounts_writer.commit().unwrap();
+            storages_writer.commit().unwrap();
+        } else {
+            for block_number in 0..=12 {
+                for change in account_changes(block_number) {
+                    tx.put::<tables::AccountChangeSets>(block_number, change).unwrap();
+                }
+                for change in storage_changes(block_number) {
+                    tx.put::<tables::StorageChangeSets>(
+                        (block_number, change.address).into(),
+                        change.entry,
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        tx.commit().unwrap();
+
+        factory
+    }
+
+    #[test]
+    fn history_provider_static_file_changesets() {
+        let database_factory = history_with_changesets(false);
+        let static_file_factory = history_with_changesets(true);
+        let database = database_factory.provider().unwrap();
+        let static_file = static_file_factory.provider().unwrap();
+
+        for block_number in 0..=13 {
+            let from_database = HistoricalStateProviderRef::new(&database, block_number);
+            let from_static_file = HistoricalStateProviderRef::new(&static_file, block_number);
+
+            for address in [ADDRESS, HIGHER_ADDRESS] {
+                assert_eq!(
+                    from_database.basic_account(&address).unwrap(),
+                    from_static_file.basic_account(&address).unwrap(),
+                    "account {address} at block {block_number}"
+                );
+            }
+            assert_eq!(
+                from_database.storage(ADDRESS, STORAGE).unwrap(),
+                from_static_file.storage(ADDRESS, STORAGE).unwrap(),
+                "storage at block {block_number}"
+            );
+            assert_eq!(
+                from_database.revert_state().unwrap(),
+                from_static_file.revert_state().unwrap(),
+                "revert state at block {block_number}"
+            );
+        }
+
+        // A sanity check that the static file changesets are actually read.
+        assert_eq!(
+            HistoricalStateProviderRef::new(&static_file, 5).basic_account(&ADDRESS).unwrap(),
+            Some(Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None })
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&static_file, 8).storage(ADDRESS, STORAGE).unwrap(),
+            Some(U256::from(10))
+        );
+    }
 }
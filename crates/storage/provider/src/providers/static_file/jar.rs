@@ -11,17 +11,21 @@ use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chainspec::ChainInfo;
 use reth_db::{
-    models::StoredBlockBodyIndices,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
     static_file::{
-        BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask, OmmersMask, ReceiptMask,
-        StaticFileCursor, TDWithHashMask, TotalDifficultyMask, TransactionMask, WithdrawalsMask,
+        AccountChangeSetMask, BlockHashMask, BodyIndicesMask, HeaderMask, HeaderWithHashMask,
+        OmmersMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask,
+        TotalDifficultyMask, TransactionMask, WithdrawalsMask,
     },
     table::{Decompress, Value},
 };
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
-use reth_primitives::SealedHeader;
-use reth_primitives_traits::SignedTransaction;
-use reth_storage_api::{BlockBodyIndicesProvider, OmmersProvider, WithdrawalsProvider};
+use reth_primitives::{SealedHeader, StaticFileSegment};
+use reth_primitives_traits::{SignedTransaction, StorageEntry};
+use reth_storage_api::{
+    BlockBodyIndicesProvider, ChangeSetReader, OmmersProvider, StorageChangeSetReader,
+    WithdrawalsProvider,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     fmt::Debug,
@@ -404,3 +408,42 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileJarProvider<'_, N
         Ok(indices)
     }
 }
+
+impl<N: NodePrimitives> ChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        // Every block in the segment range has a row, so a missing one is a corrupt jar.
+        self.cursor()?
+            .get_one::<AccountChangeSetMask>(block_number.into())?
+            .map(|changeset| changeset.changes)
+            .ok_or(ProviderError::MissingStaticFileBlock(
+                StaticFileSegment::AccountChangeSets,
+                block_number,
+            ))
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .map(|changeset| {
+                changeset
+                    .changes
+                    .into_iter()
+                    .map(|change| {
+                        (BlockNumberAddress((block_number, change.address)), change.entry)
+                    })
+                    .collect()
+            })
+            .ok_or(ProviderError::MissingStaticFileBlock(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+            ))
+    }
+}
@@ -26,7 +26,10 @@ use reth_db::{
     tables,
 };
 use reth_db_api::{
-    cursor::DbCursorRO, models::StoredBlockBodyIndices, table::Table, transaction::DbTx,
+    cursor::DbCursorRO,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
+    table::Table,
+    transaction::DbTx,
 };
 use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
@@ -37,9 +40,11 @@ use reth_primitives::{
     },
     Receipt, RecoveredBlock, SealedBlock, SealedHeader, StaticFileSegment, TransactionSigned,
 };
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{SignedTransaction, StorageEntry};
 use reth_stages_types::{PipelineTarget, StageId};
-use reth_storage_api::{BlockBodyIndicesProvider, DBProvider, OmmersProvider};
+use reth_storage_api::{
+    BlockBodyIndicesProvider, ChangeSetReader, DBProvider, OmmersProvider, StorageChangeSetReader,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     collections::{hash_map::Entry, BTreeMap, HashMap},
@@ -739,6 +744,32 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 update_unwind_target(highest_block.unwrap_or_default());
             }
 
+            // Changesets are only copied to static files once they're older than the execution
+            // checkpoint, and the database keeps no rows for blocks without changes, so the tables
+            // can't be compared. Only the rows above the checkpoint need to be removed, they're
+            // left behind by an unwind that failed to commit to the database.
+            if segment.is_change_sets() {
+                let checkpoint_block_number = provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number;
+                if let Some(highest_block) =
+                    highest_block.filter(|block| *block > checkpoint_block_number)
+                {
+                    info!(
+                        target: "reth::providers",
+                        ?segment,
+                        from = highest_block,
+                        to = checkpoint_block_number,
+                        "Unwinding static file segment."
+                    );
+                    let mut writer = self.latest_writer(segment)?;
+                    writer.prune_change_sets(highest_block - checkpoint_block_number)?;
+                    writer.commit()?;
+                }
+                continue
+            }
+
             // Only applies to transaction-based static files. (Receipts & Transactions)
             //
             // Make sure the last transaction matches the last block from its indices, since a heal
@@ -804,6 +835,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         highest_block,
                         highest_block,
                     )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    unreachable!("changesets are checked above")
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -893,7 +927,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions | StaticFileSegment::BlockMeta => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -910,6 +946,17 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             return Ok(Some(highest_static_file_block))
         }
 
+        // Receipts ahead of the execution stage are kept as long as their blocks have bodies.
+        // They're imported from era1 archives, or written by an execution that wasn't committed to
+        // the database, and the execution stage doesn't write them again.
+        let checkpoint_block_number = if segment.is_receipts() {
+            let bodies_block_number =
+                provider.get_stage_checkpoint(StageId::Bodies)?.unwrap_or_default().block_number;
+            checkpoint_block_number.max(bodies_block_number)
+        } else {
+            checkpoint_block_number
+        };
+
         // If the checkpoint is behind, then we failed to do a database commit **but committed** to
         // static files on executing a stage, or the reverse on unwinding a stage.
         // All we need to do is to prune the extra static file rows.
@@ -965,6 +1012,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
             block_meta: self.get_highest_static_file_block(StaticFileSegment::BlockMeta),
+            account_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -1763,6 +1814,34 @@ impl<N: NodePrimitives> BlockBodyIndicesProvider for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.account_block_changeset(block_number))
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )
+        .and_then(|provider| provider.storage_changeset(block_number))
+    }
+}
+
 impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
@@ -2,8 +2,8 @@ use crate::{
     traits::{BlockSource, ReceiptProvider},
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     ChainSpecProvider, ChangeSetReader, DatabaseProvider, EthStorage, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
-    StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    HistoryReader, ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory,
+    StateReader, StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_consensus::{
     constants::EMPTY_ROOT_HASH, transaction::TransactionMeta, Header, Transaction,
@@ -17,18 +17,19 @@ use alloy_primitives::{
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db::mock::{DatabaseMock, TxMock};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices};
 use reth_execution_types::ExecutionOutcome;
 use reth_node_types::NodeTypes;
 use reth_primitives::{
     Account, Block, Bytecode, EthPrimitives, GotExpected, Receipt, RecoveredBlock, SealedBlock,
-    SealedHeader, TransactionSigned,
+    SealedHeader, StorageEntry, TransactionSigned,
 };
-use reth_primitives_traits::SignedTransaction;
+use reth_primitives_traits::{Block as _, SignedTransaction};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, HashedPostStateProvider, OmmersProvider,
-    StageCheckpointReader, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
+    BlockBodyIndicesProvider, DatabaseProviderFactory, HashedAccountRange, HashedPostStateProvider,
+    HashedStateRangeReader, HashedStorageRange, OmmersProvider, StageCheckpointReader,
+    StateCommitmentProvider, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -556,18 +557,20 @@ impl<T: SignedTransaction, ChainSpec: EthChainSpec> BlockReader for MockEthProvi
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.block(id)?
+            .map(|block| block.try_into_recovered().map_err(|_| ProviderError::SenderRecoveryError))
+            .transpose()
     }
 
     fn sealed_block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
-        _transaction_kind: TransactionVariant,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.block_with_senders(id, transaction_kind)
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
@@ -857,6 +860,87 @@ impl<T: Transaction, ChainSpec: EthChainSpec> ChangeSetReader for MockEthProvide
     }
 }
 
+impl<T: Transaction, ChainSpec: EthChainSpec> StorageChangeSetReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: Transaction, ChainSpec: EthChainSpec> HistoryReader for MockEthProvider<T, ChainSpec> {
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+        _newest_first: bool,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: Transaction, ChainSpec: EthChainSpec> HashedStateRangeReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn hashed_account_range(
+        &self,
+        _block_number: BlockNumber,
+        _start: B256,
+        _limit: usize,
+        _with_storage: bool,
+        _with_preimages: bool,
+    ) -> ProviderResult<HashedAccountRange> {
+        Ok(HashedAccountRange::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _block_number: BlockNumber,
+        _address: Address,
+        _overlay: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStorageRange> {
+        Ok(HashedStorageRange::default())
+    }
+
+    fn account_preimages(&self, _hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>> {
+        Ok(B256Map::default())
+    }
+
+    fn storage_preimages(
+        &self,
+        _address: Address,
+        _hashed_slots: &[B256],
+    ) -> ProviderResult<B256Map<B256>> {
+        Ok(B256Map::default())
+    }
+}
+
 impl<T: Transaction, ChainSpec: EthChainSpec> StateReader for MockEthProvider<T, ChainSpec> {
     type Receipt = Receipt;
 
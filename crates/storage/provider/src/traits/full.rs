@@ -2,10 +2,10 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    HeaderProvider, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
-    TransactionsProvider,
+    HashedStateRangeReader, HeaderProvider, HistoryReader, StageCheckpointReader,
+    StateProviderFactory, StaticFileProviderFactory, StorageChangeSetReader, TransactionsProvider,
 };
-use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
+use reth_chain_state::{BadBlockProvider, CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_chainspec::EthereumHardforks;
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_storage_api::NodePrimitivesProvider;
@@ -24,8 +24,12 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + StateProviderFactory
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
+    + StorageChangeSetReader
+    + HistoryReader
+    + HashedStateRangeReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
+    + BadBlockProvider<Block = BlockTy<N>>
     + StageCheckpointReader
     + Clone
     + Unpin
@@ -46,8 +50,12 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + StateProviderFactory
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
+        + StorageChangeSetReader
+        + HistoryReader
+        + HashedStateRangeReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
+        + BadBlockProvider<Block = BlockTy<N>>
         + StageCheckpointReader
         + Clone
         + Unpin
@@ -176,6 +176,13 @@ where
                 StorageLocation::StaticFiles,
             )?;
 
+            // Receipts written to static files can't be read back before they are committed, so
+            // the log index is updated from the execution outcome.
+            self.database().update_log_index(
+                execution_output.first_block(),
+                execution_output.logs(execution_output.first_block()).into_iter().flatten(),
+            )?;
+
             // insert hashes and intermediate merkle nodes
             self.database()
                 .write_hashed_state(&Arc::unwrap_or_clone(hashed_state).into_sorted())?;
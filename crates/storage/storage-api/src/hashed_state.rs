@@ -0,0 +1,105 @@
+use alloc::vec::Vec;
+use alloy_primitives::{map::B256Map, Address, BlockNumber, B256, U256};
+use auto_impl::auto_impl;
+use reth_primitives_traits::Account;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::HashedStorage;
+
+/// An account of a [`HashedAccountRange`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedAccountRangeEntry {
+    /// Hashed address of the account.
+    pub hashed_address: B256,
+    /// Address of the account, if preimages were requested and it is known.
+    pub address: Option<Address>,
+    /// The account.
+    pub account: Account,
+    /// Storage root of the account.
+    pub storage_root: B256,
+    /// Non-zero storage slots of the account ordered by hashed slot, if requested.
+    pub storage: Option<Vec<(B256, U256)>>,
+}
+
+/// A page of accounts ordered by hashed address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashedAccountRange {
+    /// Accounts of this page.
+    pub accounts: Vec<HashedAccountRangeEntry>,
+    /// Hashed address of the first account after this page, if any.
+    pub next_key: Option<B256>,
+}
+
+impl HashedAccountRange {
+    /// Returns the hashed addresses of the accounts of this page.
+    pub fn hashed_addresses(&self) -> Vec<B256> {
+        self.accounts.iter().map(|entry| entry.hashed_address).collect()
+    }
+
+    /// Sets the addresses of the accounts of this page from the given preimages.
+    pub fn set_addresses(&mut self, preimages: &B256Map<Address>) {
+        for entry in &mut self.accounts {
+            entry.address = preimages.get(&entry.hashed_address).copied();
+        }
+    }
+}
+
+/// A page of non-zero storage slots ordered by hashed slot.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashedStorageRange {
+    /// Hashed slots and values of this page.
+    pub slots: Vec<(B256, U256)>,
+    /// Hashed slot of the first slot after this page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// Hashed state range reader
+///
+/// Enumerates the state of a block in the order of the hashed state tables. The state of
+/// historical blocks is reconstructed from the latest state and the changesets.
+#[auto_impl(&, Arc, Box)]
+pub trait HashedStateRangeReader: Send + Sync {
+    /// Returns at most `limit` accounts of the state after the given block, starting at the
+    /// hashed address `start`.
+    ///
+    /// If `with_storage` is set, the full storage of every returned account is included. If
+    /// `with_preimages` is set, the addresses of the returned accounts are looked up with
+    /// [`Self::account_preimages`] in the same view of the state.
+    fn hashed_account_range(
+        &self,
+        block_number: BlockNumber,
+        start: B256,
+        limit: usize,
+        with_storage: bool,
+        with_preimages: bool,
+    ) -> ProviderResult<HashedAccountRange>;
+
+    /// Returns at most `limit` storage slots of `address`, starting at the hashed slot `start`.
+    ///
+    /// The storage is taken from the state after the given block, with `overlay` applied on top.
+    fn hashed_storage_range(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        overlay: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStorageRange>;
+
+    /// Looks up the preimages of the given hashed addresses.
+    ///
+    /// Reth does not record preimages, so they are recovered from the accounts in the plain state
+    /// and the account history index, which requires walking both tables. Accounts that only
+    /// existed in pruned history are not found.
+    fn account_preimages(&self, hashed_addresses: &[B256]) -> ProviderResult<B256Map<Address>>;
+
+    /// Looks up the preimages of the given hashed storage slots of `address`.
+    ///
+    /// Reth does not record preimages, so they are recovered from the slots in the plain state
+    /// and the storage history index. Slots that were only written in pruned history are not
+    /// found.
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<B256Map<B256>>;
+}
@@ -1,10 +1,69 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, Log, B256};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
 use reth_db::models::{AccountBeforeTx, BlockNumberAddress};
 use reth_primitives_traits::StorageEntry;
 use reth_storage_errors::provider::ProviderResult;
 
+/// Maximum number of storage slots of an account whose history is searched by
+/// [`HistoryReader::account_history_blocks`].
+pub const MAX_HISTORY_STORAGE_SLOTS: usize = 10_000;
+
+/// History Reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send + Sync {
+    /// Returns the numbers of at most `limit` blocks in the given range in which the account or
+    /// any of its storage slots changed.
+    ///
+    /// The blocks are returned in ascending order, starting at the start of the range, or in
+    /// descending order, starting at the end of the range, if `newest_first` is set.
+    ///
+    /// This is answered from the account and storage history indices, so blocks that are not
+    /// covered by them (e.g. because they were pruned) are not returned. An account that was only
+    /// read or called without modifying any state is not recorded.
+    ///
+    /// The storage history is indexed per slot, so the history of every slot the account ever
+    /// wrote is searched. For accounts that wrote more than [`MAX_HISTORY_STORAGE_SLOTS`] slots,
+    /// [`StorageHistoryTooLarge`](reth_storage_errors::provider::ProviderError::StorageHistoryTooLarge)
+    /// is returned instead.
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        newest_first: bool,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the range of blocks covered by the log index, or `None` if the log index hasn't
+    /// been built.
+    ///
+    /// The log index is built by the optional `LogIndex` stage, and starts after the blocks that
+    /// were pruned from it.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the blocks in the given range with logs emitted by the address, in
+    /// ascending order.
+    ///
+    /// This is answered from the log index, so blocks outside of [`Self::log_index_range`] are not
+    /// returned.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the numbers of the blocks in the given range with logs containing the topic at any
+    /// position, in ascending order.
+    ///
+    /// This is answered from the log index, so blocks outside of [`Self::log_index_range`] are not
+    /// returned.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
 /// History Writer
 #[auto_impl(&, Arc, Box)]
 pub trait HistoryWriter: Send + Sync {
@@ -54,4 +113,22 @@ pub trait HistoryWriter: Send + Sync {
 
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Unwind and clear the log index entries of the given logs.
+    ///
+    /// Returns number of logs walked.
+    fn unwind_log_index<'a>(
+        &self,
+        logs: impl Iterator<Item = (BlockNumber, &'a Log)>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert the addresses and topics of the logs of a block into the log index.
+    ///
+    /// The log index is optional, so this is a no-op unless it has been built up to the previous
+    /// block. Otherwise, the log index checkpoint is moved to the block.
+    fn update_log_index<'a>(
+        &self,
+        block_number: BlockNumber,
+        logs: impl IntoIterator<Item = &'a Log>,
+    ) -> ProviderResult<()>;
 }
@@ -71,6 +71,10 @@ pub use history::*;
 
 mod hashing;
 pub use hashing::*;
+
+mod hashed_state;
+pub use hashed_state::*;
+
 mod stats;
 pub use stats::*;
 
@@ -0,0 +1,152 @@
+#![allow(missing_docs)]
+use alloy_primitives::{Address, B256, U256};
+use criterion::{criterion_group, criterion_main, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+use proptest::{prelude::*, strategy::ValueTree, test_runner::TestRunner};
+use reth_transaction_pool::{
+    record::{
+        read_pool_trace, PoolTraceEvent, RecordedAccount, RecordedBestTransactions,
+        RecordedCanonicalUpdate, RecordedTransaction,
+    },
+    test_utils::PoolTraceReplay,
+    PoolConfig, SubPoolLimit,
+};
+use std::hint::black_box;
+
+/// Environment variable pointing to a trace recorded with `--txpool.record-trace`.
+///
+/// If unset, a synthetic trace is generated.
+const TRACE_ENV: &str = "TXPOOL_TRACE";
+
+/// Generates a synthetic trace of `blocks` blocks, each preceded by `txs_per_block` transactions
+/// of random senders and a best transactions request.
+///
+/// Transactions of a sender arrive out of order, and every block includes the first transaction
+/// of every sender that has one pending.
+fn generate_trace(blocks: u64, txs_per_block: usize, senders: usize) -> Vec<PoolTraceEvent> {
+    let mut runner = TestRunner::deterministic();
+    let senders = (0..senders).map(|idx| Address::with_last_byte(idx as u8)).collect::<Vec<_>>();
+    let mut next_nonce = vec![0u64; senders.len()];
+    let mut mined_nonce = vec![0u64; senders.len()];
+
+    let mut events = Vec::new();
+    let mut timestamp = 0;
+    for number in 1..=blocks {
+        for _ in 0..txs_per_block {
+            timestamp += 1_000;
+            let idx = any::<usize>().new_tree(&mut runner).unwrap().current() % senders.len();
+            // occasionally skip a nonce, which parks the transaction until the gap is filled
+            let gap = u64::from(any::<u8>().new_tree(&mut runner).unwrap().current() % 8 == 0);
+            let nonce = next_nonce[idx] + gap;
+            next_nonce[idx] = nonce + 1;
+
+            let max_fee = any::<u64>().new_tree(&mut runner).unwrap().current() % 100 + 1;
+            events.push(PoolTraceEvent::AddTransaction(RecordedTransaction {
+                timestamp,
+                origin: 1,
+                hash: B256::random(),
+                sender: senders[idx],
+                nonce,
+                tx_type: 2,
+                max_fee_per_gas: max_fee.into(),
+                max_priority_fee_per_gas: (max_fee / 2).into(),
+                max_fee_per_blob_gas: 0,
+                gas_limit: 21_000,
+                value: U256::ZERO,
+                size: 120,
+                valid: true,
+                balance: U256::MAX,
+                state_nonce: mined_nonce[idx],
+            }));
+        }
+
+        timestamp += 1_000;
+        events.push(PoolTraceEvent::BestTransactions(RecordedBestTransactions {
+            timestamp,
+            basefee: 10,
+            blob_fee: 0,
+        }));
+
+        timestamp += 10_000;
+        let changed_accounts = senders
+            .iter()
+            .zip(mined_nonce.iter_mut())
+            .zip(&next_nonce)
+            .filter(|((_, mined), next)| **mined < **next)
+            .map(|((address, mined), _)| {
+                *mined += 1;
+                RecordedAccount { address: *address, nonce: *mined, balance: U256::MAX }
+            })
+            .collect();
+        events.push(PoolTraceEvent::CanonicalStateUpdate(RecordedCanonicalUpdate {
+            timestamp,
+            number,
+            hash: B256::random(),
+            gas_limit: 30_000_000,
+            pending_basefee: 10,
+            pending_blob_fee: 0,
+            reorg: false,
+            mined_transactions: vec![],
+            changed_accounts,
+        }));
+    }
+
+    events
+}
+
+/// Loads the trace from [`TRACE_ENV`] or generates a synthetic one.
+fn load_trace() -> Vec<PoolTraceEvent> {
+    match std::env::var(TRACE_ENV) {
+        Ok(path) => {
+            println!("Loading transaction pool trace from {path}...");
+            read_pool_trace(&path).expect("failed to read transaction pool trace")
+        }
+        Err(_) => {
+            println!("Generating synthetic transaction pool trace, set {TRACE_ENV} to replay a recorded trace...");
+            generate_trace(100, 500, 200)
+        }
+    }
+}
+
+/// Pool configurations the trace is replayed against.
+fn configs() -> Vec<(&'static str, PoolConfig)> {
+    let small = PoolConfig {
+        pending_limit: SubPoolLimit { max_txs: 1_000, max_size: 1024 * 1024 },
+        basefee_limit: SubPoolLimit { max_txs: 500, max_size: 512 * 1024 },
+        queued_limit: SubPoolLimit { max_txs: 500, max_size: 512 * 1024 },
+        ..Default::default()
+    };
+    let large = PoolConfig {
+        pending_limit: SubPoolLimit::default() * 4,
+        basefee_limit: SubPoolLimit::default() * 4,
+        queued_limit: SubPoolLimit::default() * 4,
+        max_account_slots: 64,
+        ..Default::default()
+    };
+    vec![("default", PoolConfig::default()), ("small", small), ("large", large)]
+}
+
+fn txpool_replay(c: &mut Criterion) {
+    let trace = load_trace();
+    let mut group = c.benchmark_group("Transaction Pool Replay");
+    group.sample_size(10);
+
+    for (name, config) in configs() {
+        let replay = PoolTraceReplay::new(config);
+
+        let report = replay.replay(&trace);
+        println!("Replayed {} events against the {name} pool config:\n{report}\n", trace.len());
+
+        let group_id = format!("txpool | total events: {} | config: {name}", trace.len());
+        group.bench_function(group_id, |b| {
+            b.iter(|| black_box(replay.replay(black_box(&trace))));
+        });
+    }
+}
+
+criterion_group! {
+    name = replay;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = txpool_replay
+}
+criterion_main!(replay);
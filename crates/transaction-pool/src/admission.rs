@@ -0,0 +1,579 @@
+//! Admission policies that are applied to transactions before they are validated.
+//!
+//! An [`AdmissionPolicy`] decides whether a transaction is admitted for validation at all, based
+//! on its origin and contents. This allows running public endpoints with custom rejection rules
+//! without wrapping the [`TransactionValidator`](crate::TransactionValidator).
+//!
+//! Policies are composed in an [`AdmissionPolicyStack`], which rejects a transaction if any of its
+//! policies rejects it. The built-in policies can be configured with an [`AdmissionPolicyConfig`].
+
+use crate::{error::AdmissionPolicyError, PoolTransaction, TransactionOrigin};
+use alloy_primitives::{map::HashMap, Address};
+use parking_lot::Mutex;
+use reth_metrics::{metrics::Counter, Metrics};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Default interval of the rate limit policies.
+pub const DEFAULT_RATE_LIMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of tracked rate limit windows above which expired windows are pruned.
+const RATE_LIMIT_PRUNE_THRESHOLD: usize = 10_000;
+
+/// A policy that decides whether a transaction is admitted to the pool before it is validated.
+pub trait AdmissionPolicy<T: PoolTransaction>: fmt::Debug + Send + Sync + 'static {
+    /// Checks whether the transaction from the given origin is admitted.
+    ///
+    /// Returns the reason of the rejection if the transaction isn't admitted.
+    fn check(&self, origin: TransactionOrigin, transaction: &T)
+        -> Result<(), AdmissionPolicyError>;
+}
+
+impl<T: PoolTransaction, P: AdmissionPolicy<T>> AdmissionPolicy<T> for Arc<P> {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        (**self).check(origin, transaction)
+    }
+}
+
+/// A stack of [`AdmissionPolicy`]s.
+///
+/// A transaction is admitted if it is admitted by all policies, which are checked in the order
+/// they were added.
+pub struct AdmissionPolicyStack<T: PoolTransaction> {
+    policies: Vec<Box<dyn AdmissionPolicy<T>>>,
+    metrics: Mutex<HashMap<&'static str, AdmissionPolicyMetrics>>,
+}
+
+impl<T: PoolTransaction> AdmissionPolicyStack<T> {
+    /// Creates a stack of the built-in policies enabled in the given config.
+    pub fn from_config(config: &AdmissionPolicyConfig) -> Self {
+        let mut stack = Self::default();
+        if !config.deny_from.is_empty() || !config.deny_to.is_empty() {
+            stack.push(DenyListPolicy::new(
+                config.deny_from.iter().copied(),
+                config.deny_to.iter().copied(),
+            ));
+        }
+        if config.no_contract_creation {
+            stack.push(NoContractCreationPolicy);
+        }
+        if let Some(max_size) = config.max_calldata_size {
+            stack.push(MaxCalldataSizePolicy::new(max_size));
+        }
+        if config.min_tip.is_some() || !config.min_tip_by_type.is_empty() {
+            stack.push(MinTipPolicy::new(
+                config.min_tip.map(u128::from),
+                config
+                    .min_tip_by_type
+                    .iter()
+                    .map(|min_tip| (min_tip.tx_type, u128::from(min_tip.min_tip)))
+                    .collect(),
+            ));
+        }
+        let interval = config.rate_limit_interval.unwrap_or(DEFAULT_RATE_LIMIT_INTERVAL);
+        if let Some(max_txs) = config.origin_rate_limit {
+            stack.push(OriginRateLimitPolicy::new(max_txs, interval));
+        }
+        if let Some(max_txs) = config.sender_rate_limit {
+            stack.push(SenderRateLimitPolicy::new(max_txs, interval));
+        }
+        stack
+    }
+
+    /// Adds a policy to the stack.
+    pub fn push(&mut self, policy: impl AdmissionPolicy<T>) {
+        self.policies.push(Box::new(policy));
+    }
+
+    /// Adds a policy to the stack and returns the stack.
+    pub fn with_policy(mut self, policy: impl AdmissionPolicy<T>) -> Self {
+        self.push(policy);
+        self
+    }
+
+    /// Returns the number of policies in the stack.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Returns `true` if the stack has no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Records a rejection in the metrics of the rejection reason.
+    fn on_rejected(&self, err: &AdmissionPolicyError) {
+        let label = err.label();
+        self.metrics
+            .lock()
+            .entry(label)
+            .or_insert_with(|| AdmissionPolicyMetrics::new_with_labels(&[("reason", label)]))
+            .rejected_transactions
+            .increment(1);
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for AdmissionPolicyStack<T> {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        for policy in &self.policies {
+            if let Err(err) = policy.check(origin, transaction) {
+                self.on_rejected(&err);
+                return Err(err)
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: PoolTransaction> Default for AdmissionPolicyStack<T> {
+    fn default() -> Self {
+        Self { policies: Vec::new(), metrics: Default::default() }
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for AdmissionPolicyStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionPolicyStack").field("policies", &self.policies).finish()
+    }
+}
+
+/// Metrics of the transactions rejected by admission policies, labeled by the rejection reason.
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.admission")]
+struct AdmissionPolicyMetrics {
+    /// Number of transactions rejected by admission policies
+    rejected_transactions: Counter,
+}
+
+/// Rejects transactions from or to deny-listed addresses.
+#[derive(Debug, Clone, Default)]
+pub struct DenyListPolicy {
+    /// Senders whose transactions are rejected.
+    senders: HashSet<Address>,
+    /// Recipients whose transactions are rejected.
+    recipients: HashSet<Address>,
+}
+
+impl DenyListPolicy {
+    /// Creates a new policy that rejects transactions from the given senders and to the given
+    /// recipients.
+    pub fn new(
+        senders: impl IntoIterator<Item = Address>,
+        recipients: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            senders: senders.into_iter().collect(),
+            recipients: recipients.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for DenyListPolicy {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        let sender = transaction.sender_ref();
+        if self.senders.contains(sender) {
+            return Err(AdmissionPolicyError::DeniedSender(*sender))
+        }
+        if let Some(recipient) = transaction.to().filter(|to| self.recipients.contains(to)) {
+            return Err(AdmissionPolicyError::DeniedRecipient(recipient))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions that create contracts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoContractCreationPolicy;
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for NoContractCreationPolicy {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        if transaction.is_create() {
+            return Err(AdmissionPolicyError::ContractCreation)
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with a calldata size above a maximum.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataSizePolicy {
+    /// The maximum calldata size in bytes.
+    max_size: usize,
+}
+
+impl MaxCalldataSizePolicy {
+    /// Creates a new policy with the given maximum calldata size in bytes.
+    pub const fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for MaxCalldataSizePolicy {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        let size = transaction.input().len();
+        if size > self.max_size {
+            return Err(AdmissionPolicyError::CalldataTooLarge { size, max_size: self.max_size })
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with a priority fee below a minimum, which can be configured per
+/// transaction type.
+///
+/// The priority fee of legacy and EIP-2930 transactions is their gas price.
+#[derive(Debug, Clone, Default)]
+pub struct MinTipPolicy {
+    /// The minimum priority fee of transaction types without a specific minimum.
+    default: Option<u128>,
+    /// The minimum priority fee per transaction type.
+    by_type: BTreeMap<u8, u128>,
+}
+
+impl MinTipPolicy {
+    /// Creates a new policy with the given default minimum priority fee and minimum priority fees
+    /// per transaction type.
+    pub const fn new(default: Option<u128>, by_type: BTreeMap<u8, u128>) -> Self {
+        Self { default, by_type }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for MinTipPolicy {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        let Some(min_tip) = self.by_type.get(&transaction.ty()).copied().or(self.default) else {
+            return Ok(())
+        };
+        let tip = transaction.priority_fee_or_price();
+        if tip < min_tip {
+            return Err(AdmissionPolicyError::TipTooLow { tip, min_tip })
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions of senders that submitted more than a maximum number of transactions
+/// within an interval.
+#[derive(Debug)]
+pub struct SenderRateLimitPolicy {
+    limiter: RateLimiter<Address>,
+}
+
+impl SenderRateLimitPolicy {
+    /// Creates a new policy that admits at most `max_txs` transactions per sender and interval.
+    pub fn new(max_txs: u32, interval: Duration) -> Self {
+        Self { limiter: RateLimiter::new(max_txs, interval) }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderRateLimitPolicy {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        let sender = transaction.sender();
+        if !self.limiter.try_acquire(sender) {
+            return Err(AdmissionPolicyError::SenderRateLimited(sender))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions of an origin once more than a maximum number of transactions of that
+/// origin were submitted within an interval.
+///
+/// Each [`TransactionOrigin`] is limited separately.
+#[derive(Debug)]
+pub struct OriginRateLimitPolicy {
+    limiter: RateLimiter<TransactionOrigin>,
+}
+
+impl OriginRateLimitPolicy {
+    /// Creates a new policy that admits at most `max_txs` transactions per origin and interval.
+    pub fn new(max_txs: u32, interval: Duration) -> Self {
+        Self { limiter: RateLimiter::new(max_txs, interval) }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for OriginRateLimitPolicy {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        _transaction: &T,
+    ) -> Result<(), AdmissionPolicyError> {
+        if !self.limiter.try_acquire(origin) {
+            return Err(AdmissionPolicyError::OriginRateLimited)
+        }
+        Ok(())
+    }
+}
+
+/// A fixed window rate limiter per key.
+#[derive(Debug)]
+struct RateLimiter<K> {
+    /// Maximum number of acquisitions per key and window.
+    max: u32,
+    /// Length of a window.
+    interval: Duration,
+    /// Start and number of acquisitions of the current window per key.
+    windows: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    fn new(max: u32, interval: Duration) -> Self {
+        Self { max, interval, windows: Default::default() }
+    }
+
+    /// Returns `true` if the key hasn't exceeded its limit in the current window.
+    fn try_acquire(&self, key: K) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        if windows.len() >= RATE_LIMIT_PRUNE_THRESHOLD {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < self.interval);
+        }
+
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= self.interval {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max {
+            return false
+        }
+        *count += 1;
+        true
+    }
+}
+
+/// Configuration of the built-in [`AdmissionPolicy`]s.
+///
+/// See [`AdmissionPolicyStack::from_config`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdmissionPolicyConfig {
+    /// Maximum number of transactions per sender and rate limit interval.
+    pub sender_rate_limit: Option<u32>,
+    /// Maximum number of transactions per origin and rate limit interval.
+    pub origin_rate_limit: Option<u32>,
+    /// Interval of the rate limits, [`DEFAULT_RATE_LIMIT_INTERVAL`] if not set.
+    pub rate_limit_interval: Option<Duration>,
+    /// Senders whose transactions are rejected.
+    pub deny_from: Vec<Address>,
+    /// Recipients whose transactions are rejected.
+    pub deny_to: Vec<Address>,
+    /// Whether contract creations are rejected.
+    pub no_contract_creation: bool,
+    /// Minimum priority fee in wei of all transaction types without a minimum in
+    /// `min_tip_by_type`.
+    pub min_tip: Option<u64>,
+    /// Minimum priority fees of specific transaction types.
+    pub min_tip_by_type: Vec<MinTipByType>,
+    /// Maximum calldata size in bytes.
+    pub max_calldata_size: Option<usize>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Merges the given config into this config.
+    ///
+    /// Settings of this config take precedence, deny-lists and minimum tips per type are
+    /// combined.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.sender_rate_limit = self.sender_rate_limit.or(other.sender_rate_limit);
+        self.origin_rate_limit = self.origin_rate_limit.or(other.origin_rate_limit);
+        self.rate_limit_interval = self.rate_limit_interval.or(other.rate_limit_interval);
+        self.deny_from.extend(other.deny_from);
+        self.deny_to.extend(other.deny_to);
+        self.no_contract_creation |= other.no_contract_creation;
+        self.min_tip = self.min_tip.or(other.min_tip);
+        for min_tip in other.min_tip_by_type {
+            if !self.min_tip_by_type.iter().any(|existing| existing.tx_type == min_tip.tx_type) {
+                self.min_tip_by_type.push(min_tip);
+            }
+        }
+        self.max_calldata_size = self.max_calldata_size.or(other.max_calldata_size);
+        self
+    }
+}
+
+/// Minimum priority fee of a transaction type, see [`AdmissionPolicyConfig::min_tip_by_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinTipByType {
+    /// The EIP-2718 transaction type.
+    pub tx_type: u8,
+    /// The minimum priority fee in wei.
+    pub min_tip: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionPool,
+    };
+    use alloy_primitives::TxKind;
+
+    fn eip1559_with_to(kind: TxKind) -> MockTransaction {
+        let mut tx = MockTransaction::eip1559();
+        if let MockTransaction::Eip1559 { to, .. } = &mut tx {
+            *to = kind;
+        }
+        tx
+    }
+
+    #[test]
+    fn deny_list() {
+        let tx = MockTransaction::eip1559();
+        let policy = DenyListPolicy::new([tx.sender()], []);
+        assert!(matches!(
+            policy.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::DeniedSender(_))
+        ));
+
+        let recipient = Address::random();
+        let tx = eip1559_with_to(TxKind::Call(recipient));
+        let policy = DenyListPolicy::new([], [recipient]);
+        assert!(matches!(
+            policy.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::DeniedRecipient(_))
+        ));
+        assert!(policy.check(TransactionOrigin::External, &MockTransaction::eip1559()).is_ok());
+    }
+
+    #[test]
+    fn min_tip_by_type() {
+        let policy = MinTipPolicy::new(Some(10), std::iter::once((0, 100)).collect());
+
+        let tx = MockTransaction::legacy().with_gas_price(50);
+        assert!(matches!(
+            policy.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::TipTooLow { tip: 50, min_tip: 100 })
+        ));
+
+        let tx = MockTransaction::eip1559().with_priority_fee(50).with_max_fee(100);
+        assert!(policy.check(TransactionOrigin::External, &tx).is_ok());
+    }
+
+    #[test]
+    fn rate_limits() {
+        let stack = AdmissionPolicyStack::default()
+            .with_policy(SenderRateLimitPolicy::new(2, Duration::from_secs(60)));
+
+        let tx = MockTransaction::eip1559();
+        assert!(stack.check(TransactionOrigin::External, &tx).is_ok());
+        assert!(stack.check(TransactionOrigin::External, &tx.next()).is_ok());
+        assert!(matches!(
+            stack.check(TransactionOrigin::External, &tx.next().next()),
+            Err(AdmissionPolicyError::SenderRateLimited(_))
+        ));
+        // other senders aren't limited
+        assert!(stack.check(TransactionOrigin::External, &MockTransaction::eip1559()).is_ok());
+
+        let policy = OriginRateLimitPolicy::new(1, Duration::from_secs(60));
+        assert!(policy.check(TransactionOrigin::External, &tx).is_ok());
+        assert!(policy.check(TransactionOrigin::Local, &tx).is_ok());
+        assert!(matches!(
+            policy.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::OriginRateLimited)
+        ));
+    }
+
+    #[test]
+    fn stack_from_config() {
+        let config = AdmissionPolicyConfig {
+            no_contract_creation: true,
+            max_calldata_size: Some(4),
+            ..Default::default()
+        };
+        let stack = AdmissionPolicyStack::<MockTransaction>::from_config(&config);
+        assert_eq!(stack.len(), 2);
+
+        let tx = eip1559_with_to(TxKind::Create);
+        assert!(matches!(
+            stack.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::ContractCreation)
+        ));
+        let tx = MockTransaction::eip1559().with_input(vec![0; 5].into());
+        assert!(matches!(
+            stack.check(TransactionOrigin::External, &tx),
+            Err(AdmissionPolicyError::CalldataTooLarge { size: 5, max_size: 4 })
+        ));
+
+        assert!(
+            AdmissionPolicyStack::<MockTransaction>::from_config(&Default::default()).is_empty()
+        );
+    }
+
+    #[test]
+    fn merge_config() {
+        let cli = AdmissionPolicyConfig {
+            rate_limit_interval: Some(Duration::from_secs(1)),
+            min_tip_by_type: vec![MinTipByType { tx_type: 2, min_tip: 10 }],
+            ..Default::default()
+        };
+        let file = AdmissionPolicyConfig {
+            sender_rate_limit: Some(5),
+            rate_limit_interval: Some(Duration::from_secs(2)),
+            min_tip_by_type: vec![
+                MinTipByType { tx_type: 2, min_tip: 20 },
+                MinTipByType { tx_type: 0, min_tip: 30 },
+            ],
+            ..Default::default()
+        };
+
+        let merged = cli.merge(file.clone());
+        // an explicitly set interval takes precedence, even if it's the default
+        assert_eq!(merged.rate_limit_interval, Some(Duration::from_secs(1)));
+        assert_eq!(merged.sender_rate_limit, Some(5));
+        assert_eq!(
+            merged.min_tip_by_type,
+            vec![
+                MinTipByType { tx_type: 2, min_tip: 10 },
+                MinTipByType { tx_type: 0, min_tip: 30 }
+            ]
+        );
+
+        let merged = AdmissionPolicyConfig::default().merge(file);
+        assert_eq!(merged.rate_limit_interval, Some(Duration::from_secs(2)));
+    }
+
+    #[tokio::test]
+    async fn reinjected_transactions_skip_policies() {
+        let pool = testing_pool();
+        pool.add_admission_policy(NoContractCreationPolicy);
+
+        let tx = eip1559_with_to(TxKind::Create);
+        assert!(pool.add_transaction(TransactionOrigin::External, tx.clone()).await.is_err());
+
+        let results = pool.reinject_transactions(TransactionOrigin::External, vec![tx]).await;
+        assert!(results[0].is_ok());
+    }
+}
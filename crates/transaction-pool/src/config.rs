@@ -1,6 +1,7 @@
 use crate::{
+    admission::AdmissionPolicyConfig,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
-    pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    pool::{state::SubPool, NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
 };
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
@@ -11,6 +12,10 @@ use std::{collections::HashSet, ops::Mul, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// Max in-flight transactions of an EIP-7702 delegated account or an account with a pending
+/// authorization, compatible with geth.
+pub const TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS: usize = 1;
+
 /// The default maximum allowed number of transactions in the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 
@@ -44,6 +49,9 @@ pub struct PoolConfig {
     pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max number of in-flight transactions of an EIP-7702 delegated account or an account with a
+    /// pending authorization in the pool.
+    pub max_inflight_delegated_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// Minimum base fee required by the protocol.
@@ -59,8 +67,10 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
-    /// Maximum lifetime for transactions in the pool
-    pub max_queued_lifetime: Duration,
+    /// Maximum lifetimes for transactions in the pool
+    pub lifetimes: TransactionLifetimes,
+    /// Built-in admission policies that are checked before transactions are validated.
+    pub admission: AdmissionPolicyConfig,
 }
 
 impl PoolConfig {
@@ -82,6 +92,7 @@ impl Default for PoolConfig {
             queued_limit: Default::default(),
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_inflight_delegated_slots: TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS,
             price_bumps: Default::default(),
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -89,7 +100,8 @@ impl Default for PoolConfig {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-            max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            lifetimes: Default::default(),
+            admission: Default::default(),
         }
     }
 }
@@ -164,6 +176,83 @@ impl Default for PriceBumpConfig {
     }
 }
 
+/// Maximum amount of time transactions are kept in the pool before they are evicted by the
+/// maintenance task.
+///
+/// The sub-pool lifetimes only apply to [`TransactionOrigin::External`] transactions. Transactions
+/// of an origin with a lifetime override are evicted after that lifetime, regardless of the
+/// sub-pool they are in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionLifetimes {
+    /// Maximum lifetime of transactions in the pending sub-pool.
+    pub pending: Option<Duration>,
+    /// Maximum lifetime of transactions in the basefee sub-pool.
+    pub basefee: Option<Duration>,
+    /// Maximum lifetime of transactions in the queued sub-pool.
+    pub queued: Option<Duration>,
+    /// Maximum lifetime of transactions in the blob sub-pool.
+    pub blob: Option<Duration>,
+    /// Lifetime override for [`TransactionOrigin::Local`] transactions.
+    pub local: Option<Duration>,
+    /// Lifetime override for [`TransactionOrigin::External`] transactions.
+    pub external: Option<Duration>,
+    /// Lifetime override for [`TransactionOrigin::Private`] transactions.
+    pub private: Option<Duration>,
+}
+
+impl TransactionLifetimes {
+    /// Returns the maximum lifetime of external transactions in the given sub-pool.
+    pub const fn subpool(&self, pool: SubPool) -> Option<Duration> {
+        match pool {
+            SubPool::Pending => self.pending,
+            SubPool::BaseFee => self.basefee,
+            SubPool::Queued => self.queued,
+            SubPool::Blob => self.blob,
+        }
+    }
+
+    /// Returns the lifetime override for transactions of the given origin.
+    pub const fn origin(&self, origin: TransactionOrigin) -> Option<Duration> {
+        match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        }
+    }
+
+    /// Returns the maximum lifetime of a transaction with the given origin in the given sub-pool.
+    pub fn lifetime(&self, pool: SubPool, origin: TransactionOrigin) -> Option<Duration> {
+        self.origin(origin).or_else(|| origin.is_external().then(|| self.subpool(pool)).flatten())
+    }
+
+    /// Returns the shortest lifetime of transactions with the given origin in any sub-pool.
+    pub fn min_lifetime(&self, origin: TransactionOrigin) -> Option<Duration> {
+        self.origin(origin).or_else(|| {
+            origin
+                .is_external()
+                .then(|| {
+                    [self.pending, self.basefee, self.queued, self.blob].into_iter().flatten().min()
+                })
+                .flatten()
+        })
+    }
+}
+
+impl Default for TransactionLifetimes {
+    fn default() -> Self {
+        // non-executable transactions of the queued and basefee sub-pools are evicted after 3 hours
+        Self {
+            pending: None,
+            basefee: Some(MAX_QUEUED_TRANSACTION_LIFETIME),
+            queued: Some(MAX_QUEUED_TRANSACTION_LIFETIME),
+            blob: None,
+            local: None,
+            external: None,
+            private: None,
+        }
+    }
+}
+
 /// Configuration options for the locally received transactions:
 /// [`TransactionOrigin::Local`](TransactionOrigin)
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -337,6 +426,38 @@ mod tests {
         assert!(!new_config.propagate_local_transactions);
     }
 
+    #[test]
+    fn transaction_lifetimes() {
+        let lifetimes = TransactionLifetimes {
+            pending: Some(Duration::from_secs(60)),
+            private: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            lifetimes.lifetime(SubPool::Pending, TransactionOrigin::External),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            lifetimes.lifetime(SubPool::Queued, TransactionOrigin::External),
+            Some(MAX_QUEUED_TRANSACTION_LIFETIME)
+        );
+        assert_eq!(lifetimes.lifetime(SubPool::Blob, TransactionOrigin::External), None);
+
+        // sub-pool lifetimes don't apply to local transactions
+        assert_eq!(lifetimes.lifetime(SubPool::Queued, TransactionOrigin::Local), None);
+
+        // origin overrides apply to all sub-pools
+        assert_eq!(
+            lifetimes.lifetime(SubPool::Pending, TransactionOrigin::Private),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            lifetimes.lifetime(SubPool::Blob, TransactionOrigin::Private),
+            Some(Duration::from_secs(10))
+        );
+    }
+
     #[test]
     fn scale_pool_limit() {
         let limit = SubPoolLimit::default();
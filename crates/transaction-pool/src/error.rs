@@ -3,7 +3,7 @@
 use std::any::Any;
 
 use alloy_eips::eip4844::BlobTransactionValidationError;
-use alloy_primitives::{Address, TxHash, U256};
+use alloy_primitives::{Address, Bytes, TxHash, U256};
 use reth_primitives::InvalidTransactionError;
 
 /// Transaction pool result type.
@@ -185,6 +185,96 @@ pub enum Eip7702PoolTransactionError {
     /// Thrown if the transaction has no items in its authorization list
     #[error("no items in authorization list for EIP7702 transaction")]
     MissingEip7702AuthorizationList,
+    /// Thrown if the sender is a delegated account or has a pending authorization and already
+    /// has the maximum number of in-flight transactions in the pool.
+    ///
+    /// Replacing an existing in-flight transaction is still allowed.
+    #[error("in-flight transaction limit reached for delegated accounts")]
+    InflightTxLimitReached,
+    /// Thrown if an authority of the authorization list has more than one transaction in the
+    /// pool, which the authorization could invalidate.
+    #[error("authority already reserved")]
+    AuthorityReserved,
+}
+
+/// Represents the reasons an [`AdmissionPolicy`](crate::admission::AdmissionPolicy) can reject a
+/// transaction before it is validated.
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionPolicyError {
+    /// Thrown if the sender submitted too many transactions within the rate limit interval.
+    #[error("sender {0} exceeded the transaction rate limit")]
+    SenderRateLimited(Address),
+    /// Thrown if too many transactions of the same origin were submitted within the rate limit
+    /// interval.
+    #[error("transaction rate limit exceeded")]
+    OriginRateLimited,
+    /// Thrown if the sender is deny-listed.
+    #[error("sender {0} is denied")]
+    DeniedSender(Address),
+    /// Thrown if the recipient is deny-listed.
+    #[error("recipient {0} is denied")]
+    DeniedRecipient(Address),
+    /// Thrown if the transaction creates a contract but contract creations aren't admitted.
+    #[error("contract creation is not allowed")]
+    ContractCreation,
+    /// Thrown if the priority fee of the transaction is below the minimum for its type.
+    #[error("transaction tip {tip} is below the minimum of {min_tip}")]
+    TipTooLow {
+        /// The priority fee of the transaction.
+        tip: u128,
+        /// The minimum priority fee for the transaction type.
+        min_tip: u128,
+    },
+    /// Thrown if the calldata of the transaction exceeds the maximum size.
+    #[error("calldata size {size} exceeds the maximum of {max_size}")]
+    CalldataTooLarge {
+        /// The calldata size of the transaction.
+        size: usize,
+        /// The maximum calldata size.
+        max_size: usize,
+    },
+    /// Rejection by a custom policy.
+    #[error(transparent)]
+    Other(Box<dyn PoolTransactionError>),
+}
+
+impl AdmissionPolicyError {
+    /// Returns the label of the rejection reason, used in metrics.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::SenderRateLimited(_) => "sender_rate_limited",
+            Self::OriginRateLimited => "origin_rate_limited",
+            Self::DeniedSender(_) => "denied_sender",
+            Self::DeniedRecipient(_) => "denied_recipient",
+            Self::ContractCreation => "contract_creation",
+            Self::TipTooLow { .. } => "tip_too_low",
+            Self::CalldataTooLarge { .. } => "calldata_too_large",
+            Self::Other(_) => "other",
+        }
+    }
+}
+
+/// Represents the reasons a transaction failed the pre-execution simulation of a
+/// [`TransactionSimulator`](crate::validate::TransactionSimulator).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SimulationError {
+    /// Thrown if the transaction reverted when executed on top of the latest state.
+    #[error("transaction reverted in simulation, gas used: {gas_used}")]
+    Reverted {
+        /// Gas used by the transaction until it reverted.
+        gas_used: u64,
+        /// The revert data.
+        output: Bytes,
+    },
+    /// Thrown if the execution of the transaction halted, e.g. due to an invalid opcode.
+    #[error("transaction halted in simulation: {reason}")]
+    Halted {
+        /// Gas used by the transaction until it halted.
+        gas_used: u64,
+        /// The reason execution halted.
+        reason: String,
+    },
 }
 
 /// Represents errors that can happen when validating transactions for the pool
@@ -232,6 +322,12 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the transaction was rejected by an admission policy before validation.
+    #[error(transparent)]
+    Admission(#[from] AdmissionPolicyError),
+    /// Thrown if the transaction failed the pre-execution simulation.
+    #[error(transparent)]
+    Simulation(#[from] SimulationError),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -316,8 +412,22 @@ impl InvalidPoolTransactionError {
                     }
                 }
             }
+            Self::Admission(err) => match err {
+                // admission policies are local settings
+                AdmissionPolicyError::Other(err) => err.is_bad_transaction(),
+                _ => false,
+            },
+            Self::Simulation(_) => {
+                // the outcome depends on the current state and isn't the sender's fault
+                false
+            }
             Self::Eip7702(eip7702_err) => match eip7702_err {
                 Eip7702PoolTransactionError::MissingEip7702AuthorizationList => false,
+                Eip7702PoolTransactionError::InflightTxLimitReached |
+                Eip7702PoolTransactionError::AuthorityReserved => {
+                    // these depend on the other transactions in the pool
+                    false
+                }
             },
         }
     }
@@ -155,16 +155,17 @@
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, TransactionLifetimes,
+        DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+        MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_INFLIGHT_DELEGATED_SLOTS,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, EvictionReason,
+        FullTransactionEvent, TransactionEvent, TransactionEvents,
     },
     traits::*,
     validate::{
@@ -182,15 +183,17 @@ use reth_execution_types::ChangedAccount;
 use reth_primitives::Recovered;
 use reth_primitives_traits::Block;
 use reth_storage_api::StateProviderFactory;
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, sync::Arc, time::Instant};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod admission;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
 pub mod pool;
+pub mod record;
 pub mod validate;
 
 pub mod blobstore;
@@ -240,6 +243,22 @@ where
         self.inner().config()
     }
 
+    /// Adds an [`AdmissionPolicy`](admission::AdmissionPolicy) that is checked before
+    /// transactions are validated.
+    ///
+    /// The policies enabled in [`PoolConfig::admission`] are added on construction.
+    pub fn add_admission_policy(&self, policy: impl admission::AdmissionPolicy<V::Transaction>) {
+        self.pool.add_admission_policy(policy)
+    }
+
+    /// Sets the [`PoolRecorder`](record::PoolRecorder) all added transactions, canonical state
+    /// updates and best transactions requests are recorded with.
+    ///
+    /// Passing `None` stops an active recording.
+    pub fn set_recorder(&self, recorder: Option<record::PoolRecorder>) {
+        self.pool.set_recorder(recorder)
+    }
+
     /// Returns future that validates all transactions in the given iterator.
     ///
     /// This returns the validated transactions in the iterator's order.
@@ -260,6 +279,10 @@ where
     ) -> (TxHash, TransactionValidationOutcome<V::Transaction>) {
         let hash = *transaction.hash();
 
+        if let Err(err) = self.pool.check_admission(origin, &transaction) {
+            return (hash, TransactionValidationOutcome::Invalid(transaction, err.into()))
+        }
+
         let outcome = self.pool.validator().validate_transaction(origin, transaction).await;
 
         (hash, outcome)
@@ -367,6 +390,18 @@ where
         results.pop().expect("result length is the same as the input")
     }
 
+    async fn add_transaction_with_deadline(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        deadline: Instant,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(origin, transaction).await;
+        let mut results =
+            self.pool.add_transactions_with_deadline(origin, std::iter::once(tx), Some(deadline));
+        results.pop().expect("result length is the same as the input")
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -380,6 +415,24 @@ where
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
+    async fn reinject_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let validated = futures_util::future::join_all(
+            transactions
+                .into_iter()
+                .map(|tx| self.pool.validator().validate_transaction(origin, tx)),
+        )
+        .await;
+
+        self.pool.add_transactions(origin, validated)
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
@@ -646,6 +699,10 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn evict_expired_transactions(&self, lifetimes: &TransactionLifetimes) -> Vec<TxHash> {
+        self.pool.evict_expired_transactions(lifetimes).iter().map(|tx| *tx.hash()).collect()
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
@@ -4,12 +4,18 @@ use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    traits::{
+        AllPoolTransactions, CanonicalStateUpdate, EthPoolTransaction, TransactionPool,
+        TransactionPoolExt,
+    },
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionLifetimes, TransactionOrigin,
 };
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
+use alloy_eips::{
+    eip2718::{Decodable2718, Eip2718Error, Encodable2718},
+    BlockNumberOrTag,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes};
 use alloy_rlp::Encodable;
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
@@ -39,6 +45,9 @@ use tracing::{debug, error, info, trace, warn};
 /// Maximum amount of time non-executable transaction are queued.
 pub const MAX_QUEUED_TRANSACTION_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
 
+/// Default interval at which expired transactions are evicted from the pool.
+pub const DEFAULT_EVICTION_INTERVAL: Duration = Duration::from_secs(12);
+
 /// Additional settings for maintaining the transaction pool
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MaintainPoolConfig {
@@ -52,9 +61,14 @@ pub struct MaintainPoolConfig {
     /// Default: 100
     pub max_reload_accounts: usize,
 
-    /// Maximum amount of time non-executable, non local transactions are queued.
-    /// Default: 3 hours
-    pub max_tx_lifetime: Duration,
+    /// Maximum amount of time transactions are kept in the pool.
+    ///
+    /// Default: 3 hours for non-executable, non local transactions
+    pub lifetimes: TransactionLifetimes,
+    /// Interval at which transactions that exceeded their lifetime or deadline are evicted.
+    ///
+    /// Default: 12 seconds
+    pub eviction_interval: Duration,
 }
 
 impl Default for MaintainPoolConfig {
@@ -62,7 +76,8 @@ impl Default for MaintainPoolConfig {
         Self {
             max_update_depth: 64,
             max_reload_accounts: 100,
-            max_tx_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            lifetimes: Default::default(),
+            eviction_interval: DEFAULT_EVICTION_INTERVAL,
         }
     }
 }
@@ -81,6 +96,51 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Version of the on-disk format written by [`snapshot_transactions_task`].
+pub const TRANSACTION_POOL_SNAPSHOT_VERSION: u8 = 1;
+
+/// Magic bytes the transaction pool snapshot file starts with.
+const TRANSACTION_POOL_SNAPSHOT_MAGIC: &[u8; 4] = b"rtxp";
+
+/// Default number of snapshotted transactions that are revalidated and inserted at once.
+pub const DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE: usize = 1024;
+
+/// Settings for the transaction pool snapshot task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionPoolSnapshotConfig {
+    /// Path to the snapshot file
+    pub snapshot_path: PathBuf,
+    /// Interval at which the snapshot is written in addition to shutdown, if any
+    pub interval: Option<Duration>,
+    /// Number of snapshotted transactions that are revalidated and inserted at once on startup
+    ///
+    /// Default: [`DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE`]
+    pub restore_batch_size: usize,
+}
+
+impl TransactionPoolSnapshotConfig {
+    /// Creates a new config that writes the snapshot to the given path on shutdown.
+    pub const fn new(snapshot_path: PathBuf) -> Self {
+        Self {
+            snapshot_path,
+            interval: None,
+            restore_batch_size: DEFAULT_SNAPSHOT_RESTORE_BATCH_SIZE,
+        }
+    }
+
+    /// Sets the interval at which the snapshot is written in addition to shutdown.
+    pub const fn with_interval(mut self, interval: Option<Duration>) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the number of transactions that are revalidated and inserted at once on startup.
+    pub const fn with_restore_batch_size(mut self, restore_batch_size: usize) -> Self {
+        self.restore_batch_size = restore_batch_size;
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<N, Client, P, St, Tasks>(
     client: Client,
@@ -153,8 +213,8 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
-    // eviction interval for stale non local txs
-    let mut stale_eviction_interval = time::interval(config.max_tx_lifetime);
+    // eviction interval for expired txs
+    let mut stale_eviction_interval = time::interval(config.eviction_interval);
 
     // toggle for the first notification
     let mut first_event = true;
@@ -252,17 +312,11 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 }
             }
             _ = stale_eviction_interval.tick() => {
-                let stale_txs: Vec<_> = pool
-                    .queued_transactions()
-                    .into_iter()
-                    .filter(|tx| {
-                        // filter stale external txs
-                        tx.origin.is_external() && tx.timestamp.elapsed() > config.max_tx_lifetime
-                    })
-                    .map(|tx| *tx.hash())
-                    .collect();
-                debug!(target: "txpool", count=%stale_txs.len(), "removing stale transactions");
-                pool.remove_transactions(stale_txs);
+                let evicted = pool.evict_expired_transactions(&config.lifetimes);
+                if !evicted.is_empty() {
+                    debug!(target: "txpool", count=%evicted.len(), "evicted expired transactions");
+                    metrics.inc_evicted_transactions(evicted.len());
+                }
             }
         }
         // handle the result of the account reload
@@ -403,7 +457,9 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                let _ = pool
+                    .reinject_transactions(TransactionOrigin::External, pruned_old_transactions)
+                    .await;
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
@@ -622,7 +678,8 @@ where
         })
         .collect();
 
-    let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
+    let outcome =
+        pool.reinject_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
 
     info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
     reth_fs_util::remove_file(file_path)?;
@@ -660,12 +717,205 @@ where
     }
 }
 
+/// Encodes the origin of a snapshotted transaction.
+const fn encode_snapshot_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Decodes the origin of a snapshotted transaction.
+const fn decode_snapshot_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Encodes the given transactions in the snapshot format.
+///
+/// The snapshot consists of the magic bytes, the [`TRANSACTION_POOL_SNAPSHOT_VERSION`] and an RLP
+/// list with one entry per transaction: the encoded [`TransactionOrigin`] followed by the EIP-2718
+/// encoding of the pooled transaction, which includes the blob sidecar of blob transactions.
+fn encode_pool_snapshot<T: Encodable2718>(
+    transactions: impl IntoIterator<Item = (TransactionOrigin, T)>,
+) -> Vec<u8> {
+    let entries = transactions
+        .into_iter()
+        .map(|(origin, tx)| {
+            let mut entry = Vec::with_capacity(1 + tx.encode_2718_len());
+            entry.push(encode_snapshot_origin(origin));
+            tx.encode_2718(&mut entry);
+            Bytes::from(entry)
+        })
+        .collect::<Vec<_>>();
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(TRANSACTION_POOL_SNAPSHOT_MAGIC);
+    buf.push(TRANSACTION_POOL_SNAPSHOT_VERSION);
+    alloy_rlp::encode_list::<_, Bytes>(&entries, &mut buf);
+    buf
+}
+
+/// Decodes a snapshot written by [`encode_pool_snapshot`].
+///
+/// Entries that can't be decoded are logged and skipped.
+fn decode_pool_snapshot<T: Decodable2718>(
+    data: &[u8],
+) -> Result<Vec<(TransactionOrigin, T)>, TransactionsBackupError> {
+    let Some(data) = data.strip_prefix(TRANSACTION_POOL_SNAPSHOT_MAGIC) else {
+        return Err(TransactionsBackupError::InvalidSnapshot)
+    };
+    let Some((&version, mut data)) = data.split_first() else {
+        return Err(TransactionsBackupError::InvalidSnapshot)
+    };
+    if version != TRANSACTION_POOL_SNAPSHOT_VERSION {
+        return Err(TransactionsBackupError::UnsupportedSnapshotVersion(version))
+    }
+
+    let entries: Vec<Bytes> = alloy_rlp::Decodable::decode(&mut data)?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            let Some((&origin, mut tx)) = entry.split_first() else {
+                warn!(target: "txpool", "Skipping empty transaction pool snapshot entry");
+                return None
+            };
+            let Some(origin) = decode_snapshot_origin(origin) else {
+                warn!(target: "txpool", %origin, "Skipping transaction pool snapshot entry with unknown origin");
+                return None
+            };
+            match T::decode_2718(&mut tx) {
+                Ok(tx) => Some((origin, tx)),
+                Err(err) => {
+                    warn!(target: "txpool", %err, "Skipping undecodable transaction pool snapshot entry");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Loads the transactions of the snapshot file, if it exists, and inserts them into the pool in
+/// batches of `batch_size`.
+///
+/// All transactions are revalidated on insertion, transactions that are no longer valid are
+/// discarded. The snapshot file is removed afterwards.
+async fn load_and_reinsert_pool_snapshot<P>(
+    pool: P,
+    file_path: &Path,
+    batch_size: usize,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Loading transaction pool snapshot");
+    // reading and decoding a large snapshot is blocking work
+    let path = file_path.to_path_buf();
+    let transactions = tokio::task::spawn_blocking(move || {
+        let data = reth_fs_util::read(path)?;
+        decode_pool_snapshot::<<P::Transaction as PoolTransaction>::Pooled>(&data)
+    })
+    .await??;
+
+    let num_txs = transactions.len();
+    let mut num_inserted = 0;
+    for batch in transactions.chunks(batch_size.max(1)) {
+        let mut local = Vec::new();
+        let mut external = Vec::new();
+        let mut private = Vec::new();
+        for (origin, tx) in batch {
+            let Ok(tx) = tx.try_clone_into_recovered() else { continue };
+            let tx = <P::Transaction as PoolTransaction>::from_pooled(tx);
+            match origin {
+                TransactionOrigin::Local => local.push(tx),
+                TransactionOrigin::External => external.push(tx),
+                TransactionOrigin::Private => private.push(tx),
+            }
+        }
+
+        for (origin, txs) in [
+            (TransactionOrigin::Local, local),
+            (TransactionOrigin::External, external),
+            (TransactionOrigin::Private, private),
+        ] {
+            if txs.is_empty() {
+                continue
+            }
+            num_inserted += pool
+                .reinject_transactions(origin, txs)
+                .await
+                .into_iter()
+                .filter(Result::is_ok)
+                .count();
+        }
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, %num_txs, %num_inserted, "Restored transaction pool snapshot");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Writes all pending, basefee and queued transactions of the pool, including the sidecars of
+/// blob transactions, to the snapshot file.
+fn save_pool_snapshot<P>(pool: &P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let transactions = pending
+        .into_iter()
+        .chain(queued)
+        .filter_map(|tx| {
+            // blob sidecars are looked up in the blob store
+            let pooled = pool.get_pooled_transaction_element(*tx.hash())?;
+            Some((tx.origin, pooled.into_tx()))
+        })
+        .collect::<Vec<_>>();
+
+    let num_txs = transactions.len();
+    let buf = encode_pool_snapshot(transactions);
+    info!(target: "txpool", snapshot_file =?file_path, %num_txs, "Saving transaction pool snapshot");
+
+    let res = file_path.parent().map(reth_fs_util::create_dir_all).transpose().and_then(|_| {
+        reth_fs_util::atomic_write_file(file_path, |file| std::io::Write::write_all(file, &buf))
+    });
+    match res {
+        Ok(_) => {
+            info!(target: "txpool", snapshot_file =?file_path, "Wrote transaction pool snapshot to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file =?file_path, "Failed to write transaction pool snapshot to file");
+        }
+    }
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
     /// Error during RLP decoding of transactions
     #[error("failed to apply transactions backup. Encountered RLP decode error: {0}")]
     Decode(#[from] alloy_rlp::Error),
+    /// Error during EIP-2718 decoding of a snapshotted transaction
+    #[error("failed to apply transactions snapshot. Encountered decode error: {0}")]
+    Decode2718(#[from] Eip2718Error),
+    /// The snapshot file is malformed
+    #[error("failed to apply transactions snapshot. Invalid snapshot file")]
+    InvalidSnapshot,
+    /// The snapshot file was written in an unsupported version of the format
+    #[error("failed to apply transactions snapshot. Unsupported snapshot version: {0}")]
+    UnsupportedSnapshotVersion(u8),
+    /// The blocking task reading the snapshot file failed
+    #[error("failed to apply transactions snapshot. Blocking task failed: {0}")]
+    BlockingTask(#[from] tokio::task::JoinError),
     /// Error during file upload
     #[error("failed to apply transactions backup. Encountered file error: {0}")]
     FsPath(#[from] FsPathError),
@@ -700,6 +950,65 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Task which manages snapshots of the entire transaction pool.
+///
+/// Restores the snapshot on boot up by revalidating and inserting the snapshotted transactions in
+/// batches, then writes the snapshot at the configured interval, if any, and on shutdown.
+pub async fn snapshot_transactions_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionPoolSnapshotConfig,
+) where
+    P: TransactionPool + Clone + 'static,
+{
+    let TransactionPoolSnapshotConfig { snapshot_path, interval, restore_batch_size } = config;
+
+    // collecting, encoding and writing the snapshot is blocking work
+    let save_snapshot = || {
+        let pool = pool.clone();
+        let snapshot_path = snapshot_path.clone();
+        async move {
+            if let Err(err) =
+                tokio::task::spawn_blocking(move || save_pool_snapshot(&pool, &snapshot_path)).await
+            {
+                error!(target: "txpool", %err, "Failed to save transaction pool snapshot");
+            }
+        }
+    };
+
+    if let Err(err) =
+        load_and_reinsert_pool_snapshot(pool.clone(), &snapshot_path, restore_batch_size).await
+    {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let mut interval = interval.map(|interval| {
+        let mut interval = time::interval_at(time::Instant::now() + interval, interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        interval
+    });
+
+    let mut shutdown = std::pin::pin!(shutdown);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            _ = async {
+                match interval.as_mut() {
+                    Some(interval) => interval.tick().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                save_snapshot().await;
+            }
+        }
+    };
+
+    // write transactions to disk
+    save_snapshot().await;
+
+    drop(graceful_guard)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -772,6 +1081,90 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pool_snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("test_pool_snapshot");
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransaction::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider).build(blob_store.clone());
+
+        let txpool = Pool::new(
+            validator.clone(),
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        let handle = tokio::runtime::Handle::current();
+        let manager = TaskManager::new(handle);
+        let config = TransactionPoolSnapshotConfig::new(snapshot_path.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            snapshot_transactions_task(shutdown, txpool.clone(), config)
+        });
+
+        // shutdown the executor
+        manager.graceful_shutdown();
+
+        let data = fs::read(&snapshot_path).unwrap();
+        let txs = decode_pool_snapshot::<PooledTransaction>(&data).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].0, TransactionOrigin::External);
+        assert_eq!(txs[0].1.hash(), transaction.hash());
+
+        // restore into a fresh pool
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        load_and_reinsert_pool_snapshot(txpool.clone(), &snapshot_path, 1).await.unwrap();
+        assert!(txpool.contains(transaction.hash()));
+        assert!(!snapshot_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_pool_snapshot_unsupported_version() {
+        let mut data = encode_pool_snapshot::<PooledTransaction>([]);
+        data[TRANSACTION_POOL_SNAPSHOT_MAGIC.len()] = TRANSACTION_POOL_SNAPSHOT_VERSION + 1;
+        assert!(matches!(
+            decode_pool_snapshot::<PooledTransaction>(&data),
+            Err(TransactionsBackupError::UnsupportedSnapshotVersion(_))
+        ));
+        assert!(matches!(
+            decode_pool_snapshot::<PooledTransaction>(b"invalid"),
+            Err(TransactionsBackupError::InvalidSnapshot)
+        ));
+    }
+
+    #[test]
+    fn test_pool_snapshot_skips_undecodable_entries() {
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransaction::decode_2718(&mut &tx_bytes[..]).unwrap();
+
+        // an undecodable transaction, an unknown origin and an empty entry next to a valid entry
+        let mut valid = vec![1];
+        valid.extend_from_slice(&tx_bytes);
+        let entries = [
+            Bytes::from_static(&[1, 0x02, 0xff]),
+            Bytes::from_static(&[9]),
+            Bytes::new(),
+            Bytes::from(valid),
+        ];
+        let mut data = TRANSACTION_POOL_SNAPSHOT_MAGIC.to_vec();
+        data.push(TRANSACTION_POOL_SNAPSHOT_VERSION);
+        alloy_rlp::encode_list::<_, Bytes>(&entries, &mut data);
+
+        let txs = decode_pool_snapshot::<PooledTransaction>(&data).unwrap();
+        assert_eq!(txs, vec![(TransactionOrigin::External, tx)]);
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
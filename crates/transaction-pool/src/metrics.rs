@@ -1,7 +1,7 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 
@@ -82,6 +82,9 @@ pub struct MaintainPoolMetrics {
     pub(crate) reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of transactions evicted because they exceeded their lifetime or
+    /// deadline.
+    pub(crate) evicted_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -100,6 +103,11 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_evicted_transactions(&self, count: usize) {
+        self.evicted_transactions.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
@@ -123,3 +131,19 @@ pub struct AllTransactionsMetrics {
     /// The current base fee
     pub(crate) base_fee: Gauge,
 }
+
+/// Transaction simulation metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.simulation")]
+pub struct TransactionSimulationMetrics {
+    /// Number of transactions that were simulated
+    pub(crate) simulated_transactions: Counter,
+    /// Number of simulation results that were served from the cache
+    pub(crate) cache_hits: Counter,
+    /// Number of transactions that reverted or halted in simulation
+    pub(crate) failed_transactions: Counter,
+    /// Number of simulations that exceeded the gas or time budget or errored
+    pub(crate) inconclusive_simulations: Counter,
+    /// How long it took to simulate a transaction
+    pub(crate) simulation_duration: Histogram,
+}
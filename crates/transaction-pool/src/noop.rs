@@ -23,7 +23,7 @@ use alloy_eips::{
 use alloy_primitives::{Address, TxHash, B256, U256};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::Recovered;
-use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, marker::PhantomData, sync::Arc, time::Instant};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
 /// A [`TransactionPool`] implementation that does nothing.
@@ -69,6 +69,16 @@ impl TransactionPool for NoopTransactionPool {
         Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
     }
 
+    async fn add_transaction_with_deadline(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        _deadline: Instant,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     async fn add_transactions(
         &self,
         _origin: TransactionOrigin,
@@ -345,6 +355,8 @@ impl<T: EthPoolTransaction> TransactionValidator for MockTransactionValidator<T>
                 TransactionOrigin::Local => self.propagate_local,
                 TransactionOrigin::Private => false,
             },
+            bytecode_hash: None,
+            authorities: None,
         }
     }
 }
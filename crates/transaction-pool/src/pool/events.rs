@@ -1,4 +1,4 @@
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{error::SimulationError, traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
 use alloy_primitives::{TxHash, B256};
 use std::sync::Arc;
 
@@ -32,6 +32,20 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
     Discarded(TxHash),
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
+    /// Transaction failed the pre-execution simulation and is about to be discarded.
+    SimulationFailed {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// Why the simulation failed.
+        error: SimulationError,
+    },
+    /// Transaction was evicted from the pool by the maintenance task.
+    Evicted {
+        /// The hash of the evicted transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was evicted.
+        reason: EvictionReason,
+    },
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -49,6 +63,12 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             }
             Self::Discarded(hash) => Self::Discarded(*hash),
             Self::Invalid(hash) => Self::Invalid(*hash),
+            Self::SimulationFailed { tx_hash, error } => {
+                Self::SimulationFailed { tx_hash: *tx_hash, error: error.clone() }
+            }
+            Self::Evicted { tx_hash, reason } => {
+                Self::Evicted { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
@@ -72,6 +92,10 @@ pub enum TransactionEvent {
     Discarded,
     /// Transaction became invalid indefinitely.
     Invalid,
+    /// Transaction failed the pre-execution simulation and is about to be discarded.
+    SimulationFailed(SimulationError),
+    /// Transaction was evicted from the pool by the maintenance task.
+    Evicted(EvictionReason),
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -80,6 +104,16 @@ impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
     pub const fn is_final(&self) -> bool {
-        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
+        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded | Self::Evicted(_))
     }
 }
+
+/// Why a transaction was evicted from the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EvictionReason {
+    /// The transaction exceeded the lifetime configured for its sub-pool or origin.
+    LifetimeExceeded,
+    /// The deadline the transaction was submitted with passed.
+    DeadlineExceeded,
+}
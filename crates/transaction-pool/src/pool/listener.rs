@@ -1,7 +1,8 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    error::SimulationError,
+    pool::events::{EvictionReason, FullTransactionEvent, TransactionEvent},
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
@@ -127,7 +128,15 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Create a new subscription for all transactions.
     pub(crate) fn subscribe_all(&mut self) -> AllTransactionsEvents<T> {
-        let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
+        self.subscribe_all_with_capacity(TX_POOL_EVENT_CHANNEL_SIZE)
+    }
+
+    /// Create a new subscription for all transactions that buffers up to `capacity` events.
+    pub(crate) fn subscribe_all_with_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> AllTransactionsEvents<T> {
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
         self.all_events_broadcaster.senders.push(tx);
         AllTransactionsEvents::new(rx)
     }
@@ -172,6 +181,24 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
     }
 
+    /// Notify listeners about a transaction that failed the pre-execution simulation.
+    pub(crate) fn simulation_failed(&mut self, tx: &TxHash, error: SimulationError) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::SimulationFailed(error.clone()),
+            FullTransactionEvent::SimulationFailed { tx_hash: *tx, error },
+        );
+    }
+
+    /// Notify listeners about a transaction that was evicted by the maintenance task.
+    pub(crate) fn evicted(&mut self, tx: &TxHash, reason: EvictionReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Evicted(reason),
+            FullTransactionEvent::Evicted { tx_hash: *tx, reason },
+        );
+    }
+
     /// Notify listeners that the transaction was mined
     pub(crate) fn mined(&mut self, tx: &TxHash, block_hash: B256) {
         self.broadcast_event(
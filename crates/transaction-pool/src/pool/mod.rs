@@ -66,20 +66,24 @@
 //!    category (2.) and become pending.
 
 use crate::{
-    error::{PoolError, PoolErrorKind, PoolResult},
+    admission::{AdmissionPolicy, AdmissionPolicyStack},
+    error::{
+        AdmissionPolicyError, InvalidPoolTransactionError, PoolError, PoolErrorKind, PoolResult,
+    },
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
         listener::PoolEventBroadcast,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
     },
+    record::PoolRecorder,
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
         PoolTransaction, PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
-    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
-    TransactionValidator,
+    CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionLifetimes,
+    TransactionOrdering, TransactionValidator,
 };
 use alloy_primitives::{Address, TxHash, B256};
 use best::BestTransactions;
@@ -103,7 +107,7 @@ use crate::{
 };
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{EvictionReason, FullTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -151,6 +155,10 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Policies that are checked before transactions are validated.
+    admission: RwLock<AdmissionPolicyStack<T::Transaction>>,
+    /// Records the operations of the pool to a trace file, if enabled.
+    recorder: RwLock<Option<Arc<PoolRecorder>>>,
 }
 
 // === impl PoolInner ===
@@ -171,12 +179,45 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            admission: RwLock::new(AdmissionPolicyStack::from_config(&config.admission)),
+            recorder: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
         }
     }
 
+    /// Adds an [`AdmissionPolicy`] that is checked before transactions are validated.
+    pub fn add_admission_policy(&self, policy: impl AdmissionPolicy<T::Transaction>) {
+        self.admission.write().push(policy);
+    }
+
+    /// Checks whether the transaction is admitted by all [`AdmissionPolicy`]s.
+    pub(crate) fn check_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T::Transaction,
+    ) -> Result<(), AdmissionPolicyError> {
+        self.admission.read().check(origin, transaction)
+    }
+
+    /// Sets the [`PoolRecorder`] the operations of the pool are recorded with.
+    ///
+    /// Passing `None` stops an active recording and flushes its trace.
+    pub fn set_recorder(&self, recorder: Option<PoolRecorder>) {
+        let previous = std::mem::replace(&mut *self.recorder.write(), recorder.map(Arc::new));
+        if let Some(previous) = previous {
+            if let Err(err) = previous.flush() {
+                warn!(target: "txpool", %err, path=?previous.path(), "Failed to flush pool trace");
+            }
+        }
+    }
+
+    /// Returns the active [`PoolRecorder`], if any.
+    fn recorder(&self) -> Option<Arc<PoolRecorder>> {
+        self.recorder.read().clone()
+    }
+
     /// Returns the configured blob store.
     pub const fn blob_store(&self) -> &S {
         &self.blob_store
@@ -201,6 +242,12 @@ where
         self.identifiers.write().sender_id_or_create(addr)
     }
 
+    /// Returns the internal [`SenderId`]s for the given addresses.
+    pub fn get_sender_ids(&self, addrs: impl IntoIterator<Item = Address>) -> Vec<SenderId> {
+        let mut identifiers = self.identifiers.write();
+        addrs.into_iter().map(|addr| identifiers.sender_id_or_create(addr)).collect()
+    }
+
     /// Returns all senders in the pool
     pub fn unique_senders(&self) -> HashSet<Address> {
         self.get_pool_data().unique_senders()
@@ -273,6 +320,15 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for all transaction events that buffers up to `capacity` events.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub(crate) fn add_all_transactions_event_listener_with_capacity(
+        &self,
+        capacity: usize,
+    ) -> AllTransactionsEvents<T::Transaction> {
+        self.event_listener.write().subscribe_all_with_capacity(capacity)
+    }
+
     /// Returns a read lock to the pool's data.
     pub fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
@@ -383,6 +439,10 @@ where
     {
         trace!(target: "txpool", ?update, "updating pool on canonical state change");
 
+        if let Some(recorder) = self.recorder() {
+            recorder.record_canonical_state_update(&update);
+        }
+
         let block_info = update.block_info();
         let CanonicalStateUpdate {
             new_tip, changed_accounts, mined_transactions, update_kind, ..
@@ -410,6 +470,10 @@ where
     ///
     /// This will either promote or discard transactions based on the new account state.
     pub fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
+        if let Some(recorder) = self.recorder() {
+            recorder.record_update_accounts(&accounts);
+        }
+
         let changed_senders = self.changed_senders(accounts.into_iter());
         let UpdateOutcome { promoted, discarded } =
             self.pool.write().update_accounts(changed_senders);
@@ -425,13 +489,14 @@ where
 
     /// Add a single validated transaction into the pool.
     ///
-    /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
-    /// come in through that function, either as a batch or `std::iter::once`.
+    /// Note: this is only used internally by [`Self::add_transactions_with_deadline()`], all new
+    /// transaction(s) come in through that function, either as a batch or `std::iter::once`.
     fn add_transaction(
         &self,
         pool: &mut RwLockWriteGuard<'_, TxPool<T>>,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
+        deadline: Option<Instant>,
     ) -> PoolResult<TxHash> {
         match tx {
             TransactionValidationOutcome::Valid {
@@ -439,6 +504,8 @@ where
                 state_nonce,
                 transaction,
                 propagate,
+                bytecode_hash,
+                authorities,
             } => {
                 let sender_id = self.get_sender_id(transaction.sender());
                 let transaction_id = TransactionId::new(sender_id, transaction.nonce());
@@ -461,9 +528,11 @@ where
                     propagate,
                     timestamp: Instant::now(),
                     origin,
+                    deadline,
+                    authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
                 };
 
-                let added = pool.add_transaction(tx, balance, state_nonce)?;
+                let added = pool.add_transaction(tx, balance, state_nonce, bytecode_hash)?;
                 let hash = *added.hash();
 
                 // transaction was successfully inserted into the pool
@@ -499,6 +568,9 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
+                if let InvalidPoolTransactionError::Simulation(simulation_err) = &err {
+                    listener.simulation_failed(tx.hash(), simulation_err.clone());
+                }
                 listener.discarded(tx.hash());
                 Err(PoolError::new(*tx.hash(), err))
             }
@@ -535,12 +607,32 @@ where
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
     ) -> Vec<PoolResult<TxHash>> {
+        self.add_transactions_with_deadline(origin, transactions, None)
+    }
+
+    /// Adds all transactions in the iterator to the pool with an optional deadline after which
+    /// they are evicted by the maintenance task, returning a list of results.
+    ///
+    /// See also [`Self::add_transactions`].
+    pub fn add_transactions_with_deadline(
+        &self,
+        origin: TransactionOrigin,
+        transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
+        deadline: Option<Instant>,
+    ) -> Vec<PoolResult<TxHash>> {
+        let recorder = self.recorder();
+
         // Add the transactions and enforce the pool size limits in one write lock
         let (mut added, discarded) = {
             let mut pool = self.pool.write();
             let added = transactions
                 .into_iter()
-                .map(|tx| self.add_transaction(&mut pool, origin, tx))
+                .map(|tx| {
+                    if let Some(recorder) = &recorder {
+                        recorder.record_add_transaction(origin, &tx);
+                    }
+                    self.add_transaction(&mut pool, origin, tx, deadline)
+                })
                 .collect::<Vec<_>>();
 
             // Enforce the pool size limits if at least one transaction was added successfully
@@ -685,7 +777,13 @@ where
 
     /// Returns an iterator that yields transactions that are ready to be included in the block.
     pub fn best_transactions(&self) -> BestTransactions<T> {
-        self.get_pool_data().best_transactions()
+        let pool = self.get_pool_data();
+        if let Some(recorder) = self.recorder() {
+            recorder.record_best_transactions(BestTransactionsAttributes::base_fee(
+                pool.block_info().pending_basefee,
+            ));
+        }
+        pool.best_transactions()
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -695,6 +793,9 @@ where
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
     {
+        if let Some(recorder) = self.recorder() {
+            recorder.record_best_transactions(best_transactions_attributes);
+        }
         self.get_pool_data().best_transactions_with_attributes(best_transactions_attributes)
     }
 
@@ -742,6 +843,28 @@ where
         removed
     }
 
+    /// Removes and returns all transactions that exceeded their deadline or the lifetime
+    /// configured for their sub-pool and origin.
+    pub fn evict_expired_transactions(
+        &self,
+        lifetimes: &TransactionLifetimes,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let evicted = self.pool.write().remove_expired_transactions(lifetimes, Instant::now());
+        if evicted.is_empty() {
+            return Vec::new()
+        }
+
+        {
+            let mut listener = self.event_listener.write();
+            evicted.iter().for_each(|(tx, reason)| listener.evicted(tx.hash(), *reason));
+        }
+
+        let evicted = evicted.into_iter().map(|(tx, _)| tx).collect::<Vec<_>>();
+        self.delete_discarded_blobs(evicted.iter());
+
+        evicted
+    }
+
     /// Removes and returns all matching transactions and their dependent transactions from the
     /// pool.
     pub fn remove_transactions_and_descendants(
@@ -1261,9 +1384,11 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 mod tests {
     use crate::{
         blobstore::{BlobStore, InMemoryBlobStore},
+        error::SimulationError,
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, FullTransactionEvent, PoolConfig, SubPoolLimit, TransactionOrigin,
+        TransactionValidationOutcome, U256,
     };
     use alloy_eips::eip4844::BlobTransactionSidecar;
     use reth_primitives::kzg::Blob;
@@ -1330,6 +1455,8 @@ mod tests {
                         sidecar: sidecar.clone(),
                     },
                     propagate: true,
+                    bytecode_hash: None,
+                    authorities: None,
                 }],
             );
         }
@@ -1343,4 +1470,28 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn test_simulation_failed_event() {
+        let test_pool = &TestPoolBuilder::default().pool;
+        let mut events = test_pool.add_all_transactions_event_listener();
+
+        let tx = MockTransaction::eip1559();
+        let hash = *tx.get_hash();
+        let error = SimulationError::Reverted { gas_used: 21_000, output: Default::default() };
+        let res = test_pool.add_transactions(
+            TransactionOrigin::Private,
+            [TransactionValidationOutcome::Invalid(tx, error.clone().into())],
+        );
+        assert!(res[0].is_err());
+
+        assert!(matches!(
+            events.events.try_recv().unwrap(),
+            FullTransactionEvent::SimulationFailed { tx_hash, error: err } if tx_hash == hash && err == error
+        ));
+        assert!(matches!(
+            events.events.try_recv().unwrap(),
+            FullTransactionEvent::Discarded(tx_hash) if tx_hash == hash
+        ));
+    }
 }
@@ -2,7 +2,10 @@
 
 use crate::{
     config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
-    error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        PoolError, PoolErrorKind,
+    },
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
     pool::{
@@ -12,14 +15,14 @@ use crate::{
         pending::PendingPool,
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate},
-        AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
+        AddedPendingTransaction, AddedTransaction, EvictionReason, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
-    ValidPoolTransaction, U256,
+    traits::{BestTransactionsAttributes, BlockInfo, PoolSize, TransactionOrigin},
+    PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionLifetimes,
+    TransactionOrdering, ValidPoolTransaction, U256,
 };
 use alloy_consensus::constants::{
-    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
+    EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID, KECCAK_EMPTY,
     LEGACY_TX_TYPE_ID,
 };
 use alloy_eips::{
@@ -32,10 +35,11 @@ use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
     cmp::Ordering,
-    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet},
+    collections::{btree_map::Entry, hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Instant,
 };
 use tracing::trace;
 
@@ -619,11 +623,14 @@ impl<T: TransactionOrdering> TxPool<T> {
         tx: ValidPoolTransaction<T::Transaction>,
         on_chain_balance: U256,
         on_chain_nonce: u64,
+        on_chain_code_hash: Option<B256>,
     ) -> PoolResult<AddedTransaction<T::Transaction>> {
         if self.contains(tx.hash()) {
             return Err(PoolError::new(*tx.hash(), PoolErrorKind::AlreadyImported))
         }
 
+        self.validate_auth(&tx, on_chain_code_hash)?;
+
         // Update sender info with balance and nonce
         self.sender_info
             .entry(tx.sender_id())
@@ -715,6 +722,48 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Enforces the limits for accounts that are delegated via EIP-7702.
+    ///
+    /// The nonce and balance of a delegated account can change through calls into its code, which
+    /// invalidates its pooled transactions without a transaction of the account being mined. This
+    /// is why accounts with code, or with a pending authorization in the pool, are limited to
+    /// [`PoolConfig::max_inflight_delegated_slots`] transactions, replacements excluded.
+    ///
+    /// The authorities of the transaction must not have more than one transaction in the pool,
+    /// because the authorization increments their nonce.
+    fn validate_auth(
+        &self,
+        transaction: &ValidPoolTransaction<T::Transaction>,
+        on_chain_code_hash: Option<B256>,
+    ) -> Result<(), PoolError> {
+        let sender_id = transaction.sender_id();
+        let has_code = on_chain_code_hash.is_some_and(|hash| hash != KECCAK_EMPTY);
+        if (has_code || self.all_transactions.has_auth(sender_id)) &&
+            self.all_transactions.get(transaction.id()).is_none() &&
+            self.all_transactions.tx_count(sender_id) >= self.config.max_inflight_delegated_slots
+        {
+            return Err(PoolError::new(
+                *transaction.hash(),
+                PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                    Eip7702PoolTransactionError::InflightTxLimitReached,
+                )),
+            ))
+        }
+
+        for authority in transaction.authority_ids.iter().flatten() {
+            if *authority != sender_id && self.all_transactions.tx_count(*authority) > 1 {
+                return Err(PoolError::new(
+                    *transaction.hash(),
+                    PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                        Eip7702PoolTransactionError::AuthorityReserved,
+                    )),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
     /// Maintenance task to apply a series of updates.
     ///
     /// This will move/discard the given transaction according to the `PoolUpdate`
@@ -773,6 +822,27 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs
     }
 
+    /// Removes and returns all transactions that exceeded their deadline or the lifetime
+    /// configured for their sub-pool and origin at the given instant, together with the reason
+    /// they were evicted.
+    pub(crate) fn remove_expired_transactions(
+        &mut self,
+        lifetimes: &TransactionLifetimes,
+        now: Instant,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, EvictionReason)> {
+        let expired = self.all_transactions.expired_transactions(lifetimes, now);
+        if expired.is_empty() {
+            return Vec::new()
+        }
+
+        let evicted = expired
+            .into_iter()
+            .filter_map(|(hash, reason)| Some((self.remove_transaction_by_hash(&hash)?, reason)))
+            .collect();
+        self.update_size_metrics();
+        evicted
+    }
+
     /// Removes and returns all matching transactions and their descendants from the pool.
     pub(crate) fn remove_transactions_and_descendants(
         &mut self,
@@ -1104,6 +1174,12 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
     tx_counter: FxHashMap<SenderId, usize>,
+    /// Hashes of the pooled transactions that carry an EIP-7702 authorization of the account.
+    auths: FxHashMap<SenderId, HashSet<TxHash>>,
+    /// _All_ transactions of an origin sorted by the time they were added to the pool.
+    by_timestamp: HashMap<TransactionOrigin, BTreeSet<(Instant, TxHash)>>,
+    /// Transactions that were submitted with a deadline, sorted by their deadline.
+    by_deadline: BTreeSet<(Instant, TxHash)>,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1154,6 +1230,94 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.txs.get(id)
     }
 
+    /// This function retrieves the number of transactions stored in the pool for a specific sender.
+    ///
+    /// If there are no transactions for the given sender, it returns zero by default.
+    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
+        self.tx_counter.get(&sender).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` if a pooled transaction carries an EIP-7702 authorization of the account.
+    pub(crate) fn has_auth(&self, authority: SenderId) -> bool {
+        self.auths.contains_key(&authority)
+    }
+
+    /// Tracks the EIP-7702 authorizations of the transaction.
+    fn add_auths(&mut self, tx: &ValidPoolTransaction<T>) {
+        for authority in tx.authority_ids.iter().flatten() {
+            self.auths.entry(*authority).or_default().insert(*tx.hash());
+        }
+    }
+
+    /// Removes the EIP-7702 authorizations of the transaction.
+    fn remove_auths(&mut self, tx: &ValidPoolTransaction<T>) {
+        for authority in tx.authority_ids.iter().flatten() {
+            if let hash_map::Entry::Occupied(mut entry) = self.auths.entry(*authority) {
+                entry.get_mut().remove(tx.hash());
+                if entry.get().is_empty() {
+                    entry.remove();
+                }
+            }
+        }
+    }
+
+    /// Tracks the time the transaction was added and its deadline.
+    fn add_expiry(&mut self, tx: &ValidPoolTransaction<T>) {
+        self.by_timestamp.entry(tx.origin).or_default().insert((tx.timestamp, *tx.hash()));
+        if let Some(deadline) = tx.deadline {
+            self.by_deadline.insert((deadline, *tx.hash()));
+        }
+    }
+
+    /// Removes the time the transaction was added and its deadline.
+    fn remove_expiry(&mut self, tx: &ValidPoolTransaction<T>) {
+        if let Some(txs) = self.by_timestamp.get_mut(&tx.origin) {
+            txs.remove(&(tx.timestamp, *tx.hash()));
+        }
+        if let Some(deadline) = tx.deadline {
+            self.by_deadline.remove(&(deadline, *tx.hash()));
+        }
+    }
+
+    /// Returns the hashes of all transactions that exceeded their deadline or the lifetime
+    /// configured for their sub-pool and origin at the given instant, together with the reason
+    /// they expired.
+    ///
+    /// Only transactions that are older than the shortest lifetime of their origin are checked.
+    pub(crate) fn expired_transactions(
+        &self,
+        lifetimes: &TransactionLifetimes,
+        now: Instant,
+    ) -> Vec<(TxHash, EvictionReason)> {
+        let mut expired = self
+            .by_deadline
+            .iter()
+            .take_while(|(deadline, _)| *deadline <= now)
+            .map(|(_, hash)| (*hash, EvictionReason::DeadlineExceeded))
+            .collect::<Vec<_>>();
+
+        for (origin, txs) in &self.by_timestamp {
+            let Some(min_lifetime) = lifetimes.min_lifetime(*origin) else { continue };
+            for (timestamp, hash) in txs {
+                let age = now.saturating_duration_since(*timestamp);
+                if age <= min_lifetime {
+                    break
+                }
+                let Some(tx) = self.by_hash.get(hash) else { continue };
+                let Some(internal) = self.txs.get(&tx.transaction_id) else { continue };
+                if !tx.is_deadline_exceeded(now) &&
+                    lifetimes
+                        .lifetime(internal.subpool, *origin)
+                        .is_some_and(|lifetime| age > lifetime)
+                {
+                    expired.push((*hash, EvictionReason::LifetimeExceeded));
+                }
+            }
+        }
+
+        expired
+    }
+
     /// Increments the transaction counter for the sender
     pub(crate) fn tx_inc(&mut self, sender: SenderId) {
         let count = self.tx_counter.entry(sender).or_default();
@@ -1453,6 +1617,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(&tx.transaction_id)?;
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
+        self.remove_auths(&tx);
+        self.remove_expiry(&tx);
         self.update_size_metrics();
         Some((tx, internal.subpool))
     }
@@ -1470,6 +1636,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
+        self.remove_auths(&internal.transaction);
+        self.remove_expiry(&internal.transaction);
 
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
@@ -1718,9 +1886,13 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 self.by_hash.remove(replaced.transaction.hash());
                 self.by_hash.insert(new_hash, new_transaction);
                 // also remove the hash
+                self.remove_auths(&replaced.transaction);
+                self.remove_expiry(&replaced.transaction);
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
             }
         }
+        self.add_auths(&transaction);
+        self.add_expiry(&transaction);
 
         // The next transaction of this sender
         let on_chain_id = TransactionId::new(transaction.sender_id(), on_chain_nonce);
@@ -1817,16 +1989,6 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 }
 
-#[cfg(test)]
-impl<T: PoolTransaction> AllTransactions<T> {
-    /// This function retrieves the number of transactions stored in the pool for a specific sender.
-    ///
-    /// If there are no transactions for the given sender, it returns zero by default.
-    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
-        self.tx_counter.get(&sender).copied().unwrap_or_default()
-    }
-}
-
 impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
@@ -1836,6 +1998,9 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
+            auths: Default::default(),
+            by_timestamp: Default::default(),
+            by_deadline: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
@@ -1976,12 +2141,12 @@ mod tests {
     use super::*;
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
-        traits::TransactionOrigin,
         SubPoolLimit,
     };
     use alloy_consensus::Transaction;
     use alloy_primitives::address;
     use reth_primitives::TxType;
+    use std::time::Duration;
 
     #[test]
     fn test_insert_blob() {
@@ -2067,7 +2232,7 @@ mod tests {
 
         let validated = f.validated(tx.clone());
         let id = *validated.id();
-        pool.add_transaction(validated, on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(validated, on_chain_balance, on_chain_nonce, None).unwrap();
 
         // assert pool lengths
         assert!(pool.blob_pool.is_empty());
@@ -2107,7 +2272,7 @@ mod tests {
 
         let validated = f.validated(tx.clone());
         let id = *validated.id();
-        pool.add_transaction(validated, on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(validated, on_chain_balance, on_chain_nonce, None).unwrap();
 
         // assert pool lengths
         assert!(pool.pending_pool.is_empty());
@@ -2310,7 +2475,7 @@ mod tests {
 
             let validated = f.validated(tx.clone());
             let id = *validated.id();
-            pool.add_transaction(validated, on_chain_balance, on_chain_nonce).unwrap();
+            pool.add_transaction(validated, on_chain_balance, on_chain_nonce, None).unwrap();
 
             // assert pool lengths
             promotion_test.assert_single_tx_starting_subpool(&pool);
@@ -2412,8 +2577,8 @@ mod tests {
         let mut pool = TxPool::new(MockOrdering::default(), Default::default());
         let tx = MockTransaction::eip1559().inc_price().inc_limit();
         let tx = f.validated(tx);
-        pool.add_transaction(tx.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        match pool.add_transaction(tx, on_chain_balance, on_chain_nonce).unwrap_err().kind {
+        pool.add_transaction(tx.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        match pool.add_transaction(tx, on_chain_balance, on_chain_nonce, None).unwrap_err().kind {
             PoolErrorKind::AlreadyImported => {}
             _ => unreachable!(),
         }
@@ -2450,10 +2615,12 @@ mod tests {
 
         let tx = MockTransaction::eip1559().inc_price().inc_limit();
         let first = f.validated(tx.clone());
-        let first_added = pool.add_transaction(first, on_chain_balance, on_chain_nonce).unwrap();
+        let first_added =
+            pool.add_transaction(first, on_chain_balance, on_chain_nonce, None).unwrap();
         let replacement = f.validated(tx.rng_hash().inc_price());
-        let replacement_added =
-            pool.add_transaction(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        let replacement_added = pool
+            .add_transaction(replacement.clone(), on_chain_balance, on_chain_nonce, None)
+            .unwrap();
 
         // // ensure replaced tx removed
         assert!(!pool.contains(first_added.hash()));
@@ -2745,7 +2912,7 @@ mod tests {
         let tx = MockTransaction::eip1559().inc_price_by(10);
         let validated = f.validated(tx.clone());
         let id = *validated.id();
-        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
         assert_eq!(pool.pending_pool.len(), 1);
 
@@ -2765,7 +2932,7 @@ mod tests {
         let tx = MockTransaction::eip1559().inc_price_by(10);
         let validated = f.validated(tx.clone());
         let id = *validated.id();
-        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
         assert_eq!(pool.pending_pool.len(), 1);
 
@@ -2788,14 +2955,14 @@ mod tests {
 
         // Create a mock transaction and add it to the pool.
         let tx = MockTransaction::eip1559();
-        pool.add_transaction(f.validated(tx.clone()), U256::from(1_000), 0).unwrap();
+        pool.add_transaction(f.validated(tx.clone()), U256::from(1_000), 0, None).unwrap();
 
         // Create another mock transaction with an incremented price.
         let tx1 = tx.inc_price().next();
 
         // Validate the second mock transaction and add it to the pool.
         let tx1_validated = f.validated(tx1.clone());
-        pool.add_transaction(tx1_validated, U256::from(1_000), 0).unwrap();
+        pool.add_transaction(tx1_validated, U256::from(1_000), 0, None).unwrap();
 
         // Ensure that the calculated next nonce for the sender matches the expected value.
         assert_eq!(
@@ -2827,7 +2994,7 @@ mod tests {
             mock_tx.set_nonce(nonce);
 
             let validated_tx = f.validated(mock_tx);
-            pool.add_transaction(validated_tx, U256::from(1000), 0).unwrap();
+            pool.add_transaction(validated_tx, U256::from(1000), 0, None).unwrap();
         }
 
         // Get last consecutive transaction
@@ -2861,11 +3028,11 @@ mod tests {
         let tx = MockTransaction::eip1559().inc_price_by(10);
         let validated = f.validated(tx.clone());
         let id = *validated.id();
-        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
         let next = tx.next();
         let validated = f.validated(next.clone());
-        pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+        pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
         assert_eq!(pool.pending_pool.len(), 2);
 
@@ -2913,7 +3080,7 @@ mod tests {
 
         // add all the transactions to the parked pool
         for tx in a_txs {
-            pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap();
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
         }
 
         // truncate the pool, it should remove at least one transaction
@@ -2951,7 +3118,7 @@ mod tests {
 
         // add all the transactions to the parked pool
         for tx in a_txs {
-            pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap();
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0, None).unwrap();
         }
 
         // truncate the pool, it should remove at least one transaction
@@ -2971,7 +3138,7 @@ mod tests {
             let tx = MockTransaction::eip1559().inc_price_by(10).inc_nonce();
             let validated = f.validated(tx.clone());
             let _id = *validated.id();
-            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
         }
 
         let size = pool.size();
@@ -2981,7 +3148,7 @@ mod tests {
             let tx = MockTransaction::eip1559().inc_price_by(10).inc_nonce();
             let validated = f.validated(tx.clone());
             let _id = *validated.id();
-            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
             pool.discard_worst();
             pool.assert_invariants();
@@ -3001,7 +3168,7 @@ mod tests {
             let tx = MockTransaction::eip4844().inc_price_by(100).with_blob_fee(100);
             let validated = f.validated(tx.clone());
             let _id = *validated.id();
-            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
         }
 
         let size = pool.size();
@@ -3011,7 +3178,7 @@ mod tests {
             let tx = MockTransaction::eip4844().inc_price_by(100).with_blob_fee(100);
             let validated = f.validated(tx.clone());
             let _id = *validated.id();
-            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            pool.add_transaction(validated, U256::from(1_000), 0, None).unwrap();
 
             pool.discard_worst();
             pool.assert_invariants();
@@ -3036,8 +3203,9 @@ mod tests {
         let v2 = f.validated(tx_2);
 
         // Add first 2 to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert!(pool.queued_transactions().is_empty());
         assert_eq!(2, pool.pending_transactions().len());
@@ -3046,7 +3214,7 @@ mod tests {
         pool.prune_transaction_by_hash(v0.hash());
 
         // Now add transaction with nonce 2
-        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce).unwrap();
+        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce, None).unwrap();
 
         // v2 is in the queue now. v1 is still in 'pending'.
         assert_eq!(1, pool.queued_transactions().len());
@@ -3080,8 +3248,10 @@ mod tests {
         let v1 = f.validated(tx_1);
 
         // Add them to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(2, pool.pending_transactions().len());
@@ -3111,10 +3281,14 @@ mod tests {
         let v3 = f.validated(tx_3);
 
         // Add them to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v3.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v3.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(4, pool.pending_transactions().len());
@@ -3127,6 +3301,69 @@ mod tests {
         assert!(pool.contains(v3.hash()));
     }
 
+    #[test]
+    fn test_remove_expired_transactions() {
+        let on_chain_balance = U256::from(10_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let pending = f.validated(MockTransaction::eip1559().set_gas_price(100).inc_limit());
+        let queued =
+            f.validated(MockTransaction::eip1559().set_gas_price(100).inc_limit().with_nonce(1));
+        let local = f.validated_with_origin(
+            TransactionOrigin::Local,
+            MockTransaction::eip1559().set_gas_price(100).inc_limit(),
+        );
+        let mut with_deadline = f.validated_with_origin(
+            TransactionOrigin::Local,
+            MockTransaction::eip1559().set_gas_price(100).inc_limit(),
+        );
+        let now = Instant::now();
+        with_deadline.deadline = Some(now + Duration::from_secs(10));
+
+        for tx in [&pending, &queued, &local, &with_deadline] {
+            pool.add_transaction(tx.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        }
+        assert_eq!(1, pool.queued_transactions().len());
+        assert_eq!(3, pool.pending_transactions().len());
+
+        let lifetimes = TransactionLifetimes {
+            pending: Some(Duration::from_secs(60)),
+            queued: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+
+        // nothing expired yet
+        assert!(pool.remove_expired_transactions(&lifetimes, now).is_empty());
+
+        // the deadline passed
+        let evicted = pool.remove_expired_transactions(&lifetimes, now + Duration::from_secs(20));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0.hash(), with_deadline.hash());
+        assert_eq!(evicted[0].1, EvictionReason::DeadlineExceeded);
+
+        // the queued lifetime is exceeded
+        let evicted = pool.remove_expired_transactions(&lifetimes, now + Duration::from_secs(40));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0.hash(), queued.hash());
+        assert_eq!(evicted[0].1, EvictionReason::LifetimeExceeded);
+
+        // the pending lifetime is exceeded, local transactions are exempt
+        let evicted = pool.remove_expired_transactions(&lifetimes, now + Duration::from_secs(70));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0.hash(), pending.hash());
+        assert!(pool.contains(local.hash()));
+        assert_eq!(1, pool.pending_transactions().len());
+
+        // evicted transactions are removed from the expiry index
+        assert!(pool.all_transactions.by_deadline.is_empty());
+        assert_eq!(
+            pool.all_transactions.by_timestamp.values().map(BTreeSet::len).sum::<usize>(),
+            pool.len()
+        );
+    }
+
     #[test]
     fn test_remove_transactions_and_descendants() {
         let on_chain_balance = U256::from(10_000);
@@ -3148,11 +3385,13 @@ mod tests {
         let v4 = f.validated(tx_4);
 
         // Add them to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v4, on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v4, on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(5, pool.pending_transactions().len());
@@ -3181,10 +3420,11 @@ mod tests {
         let v3 = f.validated(tx_3);
 
         // Add them to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(4, pool.pending_transactions().len());
@@ -3218,11 +3458,14 @@ mod tests {
         let v4 = f.validated(tx_4);
 
         // Add them to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v4, on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v1.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res =
+            pool.add_transaction(v2.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v4, on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(5, pool.pending_transactions().len());
@@ -3253,8 +3496,9 @@ mod tests {
         let v3 = f.validated(tx_3);
 
         // Add first 2 to the pool
-        let _res = pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce).unwrap();
-        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce).unwrap();
+        let _res =
+            pool.add_transaction(v0.clone(), on_chain_balance, on_chain_nonce, None).unwrap();
+        let _res = pool.add_transaction(v1, on_chain_balance, on_chain_nonce, None).unwrap();
 
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(2, pool.pending_transactions().len());
@@ -3263,7 +3507,7 @@ mod tests {
         pool.remove_transaction(v0.id());
 
         // Now add transaction with nonce 2
-        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce).unwrap();
+        let _res = pool.add_transaction(v2, on_chain_balance, on_chain_nonce, None).unwrap();
 
         // v2 is in the queue now. v1 is still in 'pending'.
         assert_eq!(1, pool.queued_transactions().len());
@@ -3284,7 +3528,7 @@ mod tests {
         assert_eq!(2, pool.pending_transactions().len());
 
         // Add transaction v3 - it 'unclogs' everything.
-        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce).unwrap();
+        let _res = pool.add_transaction(v3, on_chain_balance, on_chain_nonce, None).unwrap();
         assert_eq!(0, pool.queued_transactions().len());
         assert_eq!(3, pool.pending_transactions().len());
 
@@ -3308,11 +3552,11 @@ mod tests {
         let v1 = f.validated(tx_1);
 
         // nonce gap, tx should be queued
-        pool.add_transaction(v0.clone(), U256::MAX, 0).unwrap();
+        pool.add_transaction(v0.clone(), U256::MAX, 0, None).unwrap();
         assert_eq!(1, pool.queued_transactions().len());
 
         // nonce gap is closed on-chain, both transactions should be moved to pending
-        pool.add_transaction(v1, U256::MAX, 1).unwrap();
+        pool.add_transaction(v1, U256::MAX, 1, None).unwrap();
 
         assert_eq!(2, pool.pending_transactions().len());
         assert_eq!(0, pool.queued_transactions().len());
@@ -3341,7 +3585,7 @@ mod tests {
         for tx_nonce in 40..48 {
             let tx = f.validated(template.clone().with_nonce(tx_nonce).rng_hash());
             submitted_txs.push(*tx.id());
-            pool.add_transaction(tx, on_chain_balance, on_chain_nonce).unwrap();
+            pool.add_transaction(tx, on_chain_balance, on_chain_nonce, None).unwrap();
         }
 
         // A block is mined with two txs (so nonce is changed from 40 to 42).
@@ -3357,6 +3601,7 @@ mod tests {
                 f.validated(template.clone().with_nonce(tx_nonce).rng_hash()),
                 on_chain_balance,
                 on_chain_nonce,
+                None,
             )
             .unwrap();
         }
@@ -3366,4 +3611,79 @@ mod tests {
 
         assert_eq!(pool.pending_pool.independent().len(), 1);
     }
+
+    #[test]
+    fn delegated_account_inflight_limit() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::mock();
+        let code_hash = Some(B256::random());
+
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        pool.add_transaction(f.validated(tx.clone()), U256::MAX, 0, code_hash).unwrap();
+
+        // the sender is delegated, so only a single transaction can be in-flight
+        let err =
+            pool.add_transaction(f.validated(tx.next()), U256::MAX, 0, code_hash).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                Eip7702PoolTransactionError::InflightTxLimitReached
+            ))
+        ));
+
+        // but the in-flight transaction can be replaced
+        pool.add_transaction(f.validated(tx.rng_hash().inc_price()), U256::MAX, 0, code_hash)
+            .unwrap();
+        assert_eq!(pool.len(), 1);
+
+        // accounts without code are not limited
+        let tx = MockTransaction::eip1559().inc_price().inc_limit();
+        pool.add_transaction(f.validated(tx.clone()), U256::MAX, 0, Some(KECCAK_EMPTY)).unwrap();
+        pool.add_transaction(f.validated(tx.next()), U256::MAX, 0, Some(KECCAK_EMPTY)).unwrap();
+        assert_eq!(pool.len(), 3);
+    }
+
+    #[test]
+    fn pending_authorization_limits() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::mock();
+
+        let authority_tx = MockTransaction::eip1559().inc_price().inc_limit();
+        let authority = f.ids.sender_id_or_create(authority_tx.sender());
+        let first = f.validated(authority_tx.clone());
+        let second = f.validated(authority_tx.next());
+        pool.add_transaction(first, U256::MAX, 0, None).unwrap();
+        pool.add_transaction(second.clone(), U256::MAX, 0, None).unwrap();
+
+        // the authorization would invalidate the queued transactions of the authority
+        let mut auth_tx = f.validated(MockTransaction::eip7702().inc_price().inc_limit());
+        auth_tx.authority_ids = Some(vec![authority]);
+        let err = pool.add_transaction(auth_tx.clone(), U256::MAX, 0, None).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                Eip7702PoolTransactionError::AuthorityReserved
+            ))
+        ));
+
+        pool.remove_transaction(second.id());
+        pool.add_transaction(auth_tx.clone(), U256::MAX, 0, None).unwrap();
+        assert!(pool.all_transactions.has_auth(authority));
+
+        // the authority is limited while the authorization is pending
+        let err = pool.add_transaction(second.clone(), U256::MAX, 0, None).unwrap_err();
+        assert!(matches!(
+            err.kind,
+            PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Eip7702(
+                Eip7702PoolTransactionError::InflightTxLimitReached
+            ))
+        ));
+
+        // removing the authorization lifts the limit
+        pool.remove_transaction_by_hash(auth_tx.hash());
+        assert!(!pool.all_transactions.has_auth(authority));
+        pool.add_transaction(second, U256::MAX, 0, None).unwrap();
+        assert_eq!(pool.all_transactions.tx_count(authority), 2);
+        pool.all_transactions.assert_invariants();
+    }
 }
@@ -0,0 +1,554 @@
+//! Support for recording the operations a transaction pool sees to a trace file.
+//!
+//! A recorded trace contains every transaction that was added to the pool together with its
+//! validation outcome, every canonical state and account update and every request for the best
+//! transactions, in order and with the time they happened at. Transactions are recorded with the
+//! fields the pool uses for ordering and accounting only, which keeps the trace compact.
+//!
+//! Traces can be replayed against pools with different configurations, see
+//! `test_utils::PoolTraceReplay`.
+
+use crate::{
+    traits::{BestTransactionsAttributes, CanonicalStateUpdate, PoolUpdateKind},
+    validate::TransactionValidationOutcome,
+    PoolTransaction, TransactionOrigin,
+};
+use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_execution_types::ChangedAccount;
+use reth_fs_util::FsPathError;
+use reth_primitives_traits::Block;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// Version of the on-disk format written by [`PoolRecorder`].
+pub const POOL_TRACE_VERSION: u8 = 1;
+
+/// Magic bytes the pool trace file starts with.
+const POOL_TRACE_MAGIC: &[u8; 4] = b"rtxr";
+
+/// Interval after which the writer flushes buffered events if no new events were recorded.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Identifiers of the recorded events in the trace file.
+const ADD_TRANSACTION: u8 = 0;
+const CANONICAL_STATE_UPDATE: u8 = 1;
+const UPDATE_ACCOUNTS: u8 = 2;
+const BEST_TRANSACTIONS: u8 = 3;
+
+/// Encodes the origin of a recorded transaction.
+const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+/// Decodes the origin of a recorded transaction.
+const fn decode_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// A transaction that was added to the pool and the outcome of its validation.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct RecordedTransaction {
+    /// Microseconds since the recording started.
+    pub timestamp: u64,
+    /// The encoded [`TransactionOrigin`] of the transaction.
+    pub origin: u8,
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The EIP-2718 type of the transaction.
+    pub tx_type: u8,
+    /// The max fee per gas, or the gas price of legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// The max priority fee per gas, zero for transactions without one.
+    pub max_priority_fee_per_gas: u128,
+    /// The max fee per blob gas, zero for non-blob transactions.
+    pub max_fee_per_blob_gas: u128,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The value transferred by the transaction.
+    pub value: U256,
+    /// The encoded length of the transaction.
+    pub size: u64,
+    /// Whether the transaction passed validation.
+    pub valid: bool,
+    /// The balance of the sender at validation, zero for invalid transactions.
+    pub balance: U256,
+    /// The on-chain nonce of the sender at validation, zero for invalid transactions.
+    pub state_nonce: u64,
+}
+
+impl RecordedTransaction {
+    /// Returns the [`TransactionOrigin`] of the transaction.
+    pub const fn origin(&self) -> Option<TransactionOrigin> {
+        decode_origin(self.origin)
+    }
+
+    fn new<T: PoolTransaction>(
+        timestamp: u64,
+        origin: TransactionOrigin,
+        outcome: &TransactionValidationOutcome<T>,
+    ) -> Option<Self> {
+        let (transaction, valid, balance, state_nonce) = match outcome {
+            TransactionValidationOutcome::Valid { balance, state_nonce, transaction, .. } => {
+                (transaction.transaction(), true, *balance, *state_nonce)
+            }
+            TransactionValidationOutcome::Invalid(transaction, _) => {
+                (transaction, false, U256::ZERO, 0)
+            }
+            TransactionValidationOutcome::Error(..) => return None,
+        };
+
+        Some(Self {
+            timestamp,
+            origin: encode_origin(origin),
+            hash: *transaction.hash(),
+            sender: transaction.sender(),
+            nonce: transaction.nonce(),
+            tx_type: transaction.ty(),
+            max_fee_per_gas: transaction.max_fee_per_gas(),
+            max_priority_fee_per_gas: transaction.max_priority_fee_per_gas().unwrap_or_default(),
+            max_fee_per_blob_gas: transaction.max_fee_per_blob_gas().unwrap_or_default(),
+            gas_limit: transaction.gas_limit(),
+            value: transaction.value(),
+            size: transaction.encoded_length() as u64,
+            valid,
+            balance,
+            state_nonce,
+        })
+    }
+}
+
+/// The state of an account after a canonical state or account update.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct RecordedAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+}
+
+impl From<&ChangedAccount> for RecordedAccount {
+    fn from(account: &ChangedAccount) -> Self {
+        Self { address: account.address, nonce: account.nonce, balance: account.balance }
+    }
+}
+
+impl From<RecordedAccount> for ChangedAccount {
+    fn from(account: RecordedAccount) -> Self {
+        Self { address: account.address, nonce: account.nonce, balance: account.balance }
+    }
+}
+
+/// A canonical state update the pool was notified about.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct RecordedCanonicalUpdate {
+    /// Microseconds since the recording started.
+    pub timestamp: u64,
+    /// The number of the new tip.
+    pub number: u64,
+    /// The hash of the new tip.
+    pub hash: B256,
+    /// The gas limit of the new tip.
+    pub gas_limit: u64,
+    /// The base fee of the pending block.
+    pub pending_basefee: u64,
+    /// The blob fee of the pending block, zero before Cancun.
+    pub pending_blob_fee: u128,
+    /// Whether the update was caused by a reorg.
+    pub reorg: bool,
+    /// The transactions mined in the update.
+    pub mined_transactions: Vec<TxHash>,
+    /// The accounts changed in the update.
+    pub changed_accounts: Vec<RecordedAccount>,
+}
+
+/// Accounts that were reloaded from state and updated in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct RecordedAccountsUpdate {
+    /// Microseconds since the recording started.
+    pub timestamp: u64,
+    /// The updated accounts.
+    pub accounts: Vec<RecordedAccount>,
+}
+
+/// A request for the best transactions of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct RecordedBestTransactions {
+    /// Microseconds since the recording started.
+    pub timestamp: u64,
+    /// The base fee the best transactions were requested for.
+    pub basefee: u64,
+    /// The blob fee the best transactions were requested for, zero if none was given.
+    pub blob_fee: u64,
+}
+
+impl RecordedBestTransactions {
+    /// Returns the [`BestTransactionsAttributes`] the best transactions were requested with.
+    pub const fn attributes(&self) -> BestTransactionsAttributes {
+        BestTransactionsAttributes {
+            basefee: self.basefee,
+            blob_fee: if self.blob_fee == 0 { None } else { Some(self.blob_fee) },
+        }
+    }
+}
+
+/// An operation of the pool recorded by a [`PoolRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolTraceEvent {
+    /// A transaction was added to the pool.
+    AddTransaction(RecordedTransaction),
+    /// The pool was updated after a canonical state change.
+    CanonicalStateUpdate(RecordedCanonicalUpdate),
+    /// Accounts were updated in the pool.
+    UpdateAccounts(RecordedAccountsUpdate),
+    /// The best transactions of the pool were requested.
+    BestTransactions(RecordedBestTransactions),
+}
+
+impl PoolTraceEvent {
+    /// Returns the time since the recording started at which the event happened.
+    pub const fn timestamp(&self) -> Duration {
+        let timestamp = match self {
+            Self::AddTransaction(event) => event.timestamp,
+            Self::CanonicalStateUpdate(event) => event.timestamp,
+            Self::UpdateAccounts(event) => event.timestamp,
+            Self::BestTransactions(event) => event.timestamp,
+        };
+        Duration::from_micros(timestamp)
+    }
+
+    /// Encodes the event as the event identifier followed by the RLP encoded event.
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::AddTransaction(event) => {
+                out.push(ADD_TRANSACTION);
+                event.encode(out);
+            }
+            Self::CanonicalStateUpdate(event) => {
+                out.push(CANONICAL_STATE_UPDATE);
+                event.encode(out);
+            }
+            Self::UpdateAccounts(event) => {
+                out.push(UPDATE_ACCOUNTS);
+                event.encode(out);
+            }
+            Self::BestTransactions(event) => {
+                out.push(BEST_TRANSACTIONS);
+                event.encode(out);
+            }
+        }
+    }
+
+    /// Decodes an event written by [`Self::encode`] and advances the buffer.
+    fn decode(buf: &mut &[u8]) -> Result<Self, PoolTraceError> {
+        let Some((&kind, mut data)) = buf.split_first() else {
+            return Err(PoolTraceError::InvalidTrace)
+        };
+        let event = match kind {
+            ADD_TRANSACTION => Self::AddTransaction(Decodable::decode(&mut data)?),
+            CANONICAL_STATE_UPDATE => Self::CanonicalStateUpdate(Decodable::decode(&mut data)?),
+            UPDATE_ACCOUNTS => Self::UpdateAccounts(Decodable::decode(&mut data)?),
+            BEST_TRANSACTIONS => Self::BestTransactions(Decodable::decode(&mut data)?),
+            kind => return Err(PoolTraceError::UnknownEvent(kind)),
+        };
+        *buf = data;
+        Ok(event)
+    }
+}
+
+/// Records the operations of a transaction pool to a trace file.
+///
+/// The trace consists of the magic bytes, the [`POOL_TRACE_VERSION`] and the recorded
+/// [`PoolTraceEvent`]s. Recording an event only sends it to a dedicated writer thread, so the pool
+/// never performs file I/O while recording. The writer buffers events and flushes them when no new
+/// events arrive for a second, on [`PoolRecorder::flush`] and when the recorder is dropped.
+#[derive(Debug)]
+pub struct PoolRecorder {
+    /// Path to the trace file.
+    path: PathBuf,
+    /// When the recording started.
+    started_at: Instant,
+    /// Sends messages to the writer thread.
+    to_writer: mpsc::Sender<RecorderMessage>,
+}
+
+impl PoolRecorder {
+    /// Creates a new recorder that writes the trace to the given path.
+    ///
+    /// An existing file at the path is truncated.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, PoolTraceError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        let file = reth_fs_util::create_file(&path)?;
+
+        let mut writer = BufWriter::new(file);
+        writer.write_all(POOL_TRACE_MAGIC)?;
+        writer.write_all(&[POOL_TRACE_VERSION])?;
+
+        let (to_writer, from_recorder) = mpsc::channel();
+        let writer_path = path.clone();
+        std::thread::Builder::new()
+            .name("txpool-recorder".to_string())
+            .spawn(move || write_pool_trace(writer, &writer_path, from_recorder))?;
+
+        Ok(Self { path, started_at: Instant::now(), to_writer })
+    }
+
+    /// Returns the path of the trace file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Waits until all recorded events are written and flushes them to the trace file.
+    pub fn flush(&self) -> io::Result<()> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let _ = self.to_writer.send(RecorderMessage::Flush(tx));
+        rx.recv().unwrap_or_else(|_| {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "pool trace writer stopped"))
+        })
+    }
+
+    /// Microseconds since the recording started.
+    fn elapsed(&self) -> u64 {
+        self.started_at.elapsed().as_micros() as u64
+    }
+
+    /// Sends the event to the writer thread.
+    fn record(&self, event: PoolTraceEvent) {
+        let _ = self.to_writer.send(RecorderMessage::Event(Box::new(event)));
+    }
+
+    /// Records a transaction that is added to the pool.
+    pub(crate) fn record_add_transaction<T: PoolTransaction>(
+        &self,
+        origin: TransactionOrigin,
+        outcome: &TransactionValidationOutcome<T>,
+    ) {
+        if let Some(tx) = RecordedTransaction::new(self.elapsed(), origin, outcome) {
+            self.record(PoolTraceEvent::AddTransaction(tx));
+        }
+    }
+
+    /// Records a canonical state update.
+    pub(crate) fn record_canonical_state_update<B: Block>(
+        &self,
+        update: &CanonicalStateUpdate<'_, B>,
+    ) {
+        let block_info = update.block_info();
+        self.record(PoolTraceEvent::CanonicalStateUpdate(RecordedCanonicalUpdate {
+            timestamp: self.elapsed(),
+            number: block_info.last_seen_block_number,
+            hash: block_info.last_seen_block_hash,
+            gas_limit: block_info.block_gas_limit,
+            pending_basefee: block_info.pending_basefee,
+            pending_blob_fee: block_info.pending_blob_fee.unwrap_or_default(),
+            reorg: matches!(update.update_kind, PoolUpdateKind::Reorg),
+            mined_transactions: update.mined_transactions.clone(),
+            changed_accounts: update.changed_accounts.iter().map(Into::into).collect(),
+        }));
+    }
+
+    /// Records accounts that are updated in the pool.
+    pub(crate) fn record_update_accounts(&self, accounts: &[ChangedAccount]) {
+        self.record(PoolTraceEvent::UpdateAccounts(RecordedAccountsUpdate {
+            timestamp: self.elapsed(),
+            accounts: accounts.iter().map(Into::into).collect(),
+        }));
+    }
+
+    /// Records a request for the best transactions of the pool.
+    pub(crate) fn record_best_transactions(&self, attributes: BestTransactionsAttributes) {
+        self.record(PoolTraceEvent::BestTransactions(RecordedBestTransactions {
+            timestamp: self.elapsed(),
+            basefee: attributes.basefee,
+            blob_fee: attributes.blob_fee.unwrap_or_default(),
+        }));
+    }
+}
+
+impl Drop for PoolRecorder {
+    fn drop(&mut self) {
+        // the writer exits once the channel is closed
+        if let Err(err) = self.flush() {
+            warn!(target: "txpool", %err, path=?self.path, "Failed to flush pool trace");
+        }
+    }
+}
+
+/// Messages sent from the [`PoolRecorder`] to its writer thread.
+#[derive(Debug)]
+enum RecorderMessage {
+    /// An event to append to the trace.
+    Event(Box<PoolTraceEvent>),
+    /// Flush all buffered events and report the result.
+    Flush(mpsc::SyncSender<io::Result<()>>),
+}
+
+/// Appends the received events to the trace until the [`PoolRecorder`] is dropped.
+///
+/// Buffered events are flushed when no new events arrive within the [`FLUSH_INTERVAL`].
+fn write_pool_trace(
+    mut writer: BufWriter<File>,
+    path: &Path,
+    from_recorder: mpsc::Receiver<RecorderMessage>,
+) {
+    let mut buf = Vec::new();
+    let mut unflushed = false;
+    loop {
+        match from_recorder.recv_timeout(FLUSH_INTERVAL) {
+            Ok(RecorderMessage::Event(event)) => {
+                buf.clear();
+                event.encode(&mut buf);
+                if let Err(err) = writer.write_all(&buf) {
+                    warn!(target: "txpool", %err, ?path, "Failed to record pool event");
+                }
+                unflushed = true;
+            }
+            Ok(RecorderMessage::Flush(tx)) => {
+                unflushed = false;
+                let _ = tx.send(writer.flush());
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if unflushed {
+                    unflushed = false;
+                    if let Err(err) = writer.flush() {
+                        warn!(target: "txpool", %err, ?path, "Failed to flush pool trace");
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    if let Err(err) = writer.flush() {
+        warn!(target: "txpool", %err, ?path, "Failed to flush pool trace");
+    }
+}
+
+/// Reads all events of the trace file written by a [`PoolRecorder`].
+pub fn read_pool_trace(path: impl AsRef<Path>) -> Result<Vec<PoolTraceEvent>, PoolTraceError> {
+    decode_pool_trace(&reth_fs_util::read(path)?)
+}
+
+/// Decodes all events of a trace written by a [`PoolRecorder`].
+pub fn decode_pool_trace(data: &[u8]) -> Result<Vec<PoolTraceEvent>, PoolTraceError> {
+    let Some(data) = data.strip_prefix(POOL_TRACE_MAGIC) else {
+        return Err(PoolTraceError::InvalidTrace)
+    };
+    let Some((&version, mut data)) = data.split_first() else {
+        return Err(PoolTraceError::InvalidTrace)
+    };
+    if version != POOL_TRACE_VERSION {
+        return Err(PoolTraceError::UnsupportedVersion(version))
+    }
+
+    let mut events = Vec::new();
+    while !data.is_empty() {
+        events.push(PoolTraceEvent::decode(&mut data)?);
+    }
+    Ok(events)
+}
+
+/// Errors that can occur while recording or reading a pool trace.
+#[derive(Debug, thiserror::Error)]
+pub enum PoolTraceError {
+    /// Error during RLP decoding of an event
+    #[error("failed to decode pool trace event: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+    /// The trace file is malformed
+    #[error("invalid pool trace file")]
+    InvalidTrace,
+    /// The trace file was written in an unsupported version of the format
+    #[error("unsupported pool trace version: {0}")]
+    UnsupportedVersion(u8),
+    /// The trace file contains an unknown event
+    #[error("unknown pool trace event: {0}")]
+    UnknownEvent(u8),
+    /// Error accessing the trace file
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// Error writing the trace file
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionPool,
+    };
+
+    #[tokio::test]
+    async fn records_pool_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txpool-trace.rlp");
+
+        let pool = testing_pool();
+        pool.set_recorder(Some(PoolRecorder::create(&path).unwrap()));
+
+        let tx = MockTransaction::eip1559().with_gas_limit(21_000).with_size(100);
+        pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        let _ = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(7, None));
+
+        // stop recording and flush the trace
+        pool.set_recorder(None);
+
+        let events = read_pool_trace(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        let PoolTraceEvent::AddTransaction(recorded) = &events[0] else {
+            panic!("expected added transaction, got {:?}", events[0])
+        };
+        assert_eq!(recorded.hash, *tx.get_hash());
+        assert_eq!(recorded.sender, *tx.get_sender());
+        assert_eq!(recorded.origin(), Some(TransactionOrigin::External));
+        assert_eq!(recorded.gas_limit, 21_000);
+        assert_eq!(recorded.size, 100);
+        assert!(recorded.valid);
+        assert_eq!(recorded.balance, U256::MAX);
+        assert_eq!(
+            events[1],
+            PoolTraceEvent::BestTransactions(RecordedBestTransactions {
+                timestamp: events[1].timestamp().as_micros() as u64,
+                basefee: 7,
+                blob_fee: 0,
+            })
+        );
+        assert!(events[0].timestamp() <= events[1].timestamp());
+    }
+
+    #[test]
+    fn invalid_trace() {
+        assert!(matches!(decode_pool_trace(b"rtxp\x01"), Err(PoolTraceError::InvalidTrace)));
+        assert!(matches!(
+            decode_pool_trace(b"rtxr\x02"),
+            Err(PoolTraceError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(decode_pool_trace(b"rtxr\x01\x07"), Err(PoolTraceError::UnknownEvent(7))));
+        assert!(decode_pool_trace(b"rtxr\x01").unwrap().is_empty());
+    }
+}
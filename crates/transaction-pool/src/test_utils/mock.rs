@@ -1237,6 +1237,8 @@ impl MockTransactionFactory {
             transaction,
             timestamp: Instant::now(),
             origin,
+            deadline: None,
+            authority_ids: None,
         }
     }
 
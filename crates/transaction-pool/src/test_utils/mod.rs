@@ -11,6 +11,9 @@ pub use mock::*;
 
 mod pool;
 
+mod replay;
+pub use replay::*;
+
 /// A [Pool] used for testing
 pub type TestPool =
     Pool<MockTransactionValidator<MockTransaction>, MockOrdering, InMemoryBlobStore>;
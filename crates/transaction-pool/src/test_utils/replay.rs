@@ -0,0 +1,343 @@
+//! Replays recorded pool traces against a test pool.
+
+use crate::{
+    record::{PoolTraceEvent, RecordedCanonicalUpdate, RecordedTransaction},
+    test_utils::{MockTransaction, TestPool, TestPoolBuilder},
+    traits::{CanonicalStateUpdate, PoolUpdateKind},
+    validate::{TransactionValidationOutcome, ValidTransaction},
+    AllTransactionsEvents, FullTransactionEvent, PoolConfig, TransactionPool, TransactionPoolExt,
+};
+use alloy_primitives::TxHash;
+use reth_primitives::{Block, Header, SealedBlock, TxType};
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// The number of pool events buffered while replaying a trace.
+///
+/// Events are drained after every replayed operation, so this only needs to hold the events of a
+/// single operation.
+const REPLAY_EVENT_CHANNEL_SIZE: usize = 1 << 20;
+
+/// Replays a recorded pool trace against a fresh [`TestPool`].
+///
+/// Recorded transactions are rebuilt as [`MockTransaction`]s and inserted with their recorded
+/// validation outcome, so the replay does not depend on any state.
+#[derive(Debug, Clone)]
+pub struct PoolTraceReplay {
+    /// The configuration of the pool the trace is replayed against.
+    config: PoolConfig,
+}
+
+impl PoolTraceReplay {
+    /// Creates a new replay against a pool with the given configuration.
+    pub const fn new(config: PoolConfig) -> Self {
+        Self { config }
+    }
+
+    /// Replays all events against a fresh pool and returns the collected statistics.
+    pub fn replay(&self, events: &[PoolTraceEvent]) -> ReplayReport {
+        let pool: TestPool = TestPoolBuilder::default().with_config(self.config.clone()).into();
+        let mut listener = pool
+            .inner()
+            .add_all_transactions_event_listener_with_capacity(REPLAY_EVENT_CHANNEL_SIZE);
+
+        let mut report = ReplayReport::default();
+        // transactions that were not pending yet and the time they were added at
+        let mut parked = HashMap::new();
+
+        for event in events {
+            let now = event.timestamp();
+            match event {
+                PoolTraceEvent::AddTransaction(tx) => {
+                    let Some(origin) = tx.origin() else { continue };
+                    if !tx.valid {
+                        report.invalid += 1;
+                        continue
+                    }
+                    let outcome = TransactionValidationOutcome::Valid {
+                        balance: tx.balance,
+                        state_nonce: tx.state_nonce,
+                        transaction: ValidTransaction::Valid(mock_transaction(tx)),
+                        propagate: true,
+                        bytecode_hash: None,
+                        authorities: None,
+                    };
+                    for res in pool.inner().add_transactions(origin, [outcome]) {
+                        if res.is_ok() {
+                            report.added += 1;
+                        } else {
+                            report.rejected += 1;
+                        }
+                    }
+                }
+                PoolTraceEvent::CanonicalStateUpdate(update) => {
+                    let tip = sealed_block(update);
+                    pool.on_canonical_state_change(CanonicalStateUpdate {
+                        new_tip: &tip,
+                        pending_block_base_fee: update.pending_basefee,
+                        pending_block_blob_fee: (update.pending_blob_fee != 0)
+                            .then_some(update.pending_blob_fee),
+                        changed_accounts: update
+                            .changed_accounts
+                            .iter()
+                            .cloned()
+                            .map(Into::into)
+                            .collect(),
+                        mined_transactions: update.mined_transactions.clone(),
+                        update_kind: if update.reorg {
+                            PoolUpdateKind::Reorg
+                        } else {
+                            PoolUpdateKind::Commit
+                        },
+                    });
+                }
+                PoolTraceEvent::UpdateAccounts(update) => {
+                    pool.update_accounts(update.accounts.iter().cloned().map(Into::into).collect());
+                }
+                PoolTraceEvent::BestTransactions(best) => {
+                    let start = Instant::now();
+                    let yielded = pool.best_transactions_with_attributes(best.attributes()).count();
+                    report.best_transactions_elapsed += start.elapsed();
+                    report.best_transactions_calls += 1;
+                    report.best_transactions_yielded += yielded;
+                }
+            }
+
+            report.drain_events(&mut listener, &mut parked, now);
+
+            let size = pool.pool_size();
+            report.peak_size = report
+                .peak_size
+                .max(size.pending_size + size.basefee_size + size.queued_size + size.blob_size);
+            report.peak_transactions = report.peak_transactions.max(size.total);
+        }
+
+        report
+    }
+}
+
+/// Rebuilds the recorded transaction as a [`MockTransaction`].
+fn mock_transaction(tx: &RecordedTransaction) -> MockTransaction {
+    let tx_type = TxType::try_from(tx.tx_type).unwrap_or(TxType::Eip1559);
+    let mut mock = MockTransaction::new_from_type(tx_type)
+        .with_hash(tx.hash)
+        .with_sender(tx.sender)
+        .with_nonce(tx.nonce)
+        .with_gas_limit(tx.gas_limit)
+        .with_value(tx.value)
+        .with_size(tx.size as usize);
+    if mock.is_legacy() || mock.is_eip2930() {
+        mock.set_gas_price(tx.max_fee_per_gas);
+    } else {
+        mock.set_max_fee(tx.max_fee_per_gas)
+            .set_priority_fee(tx.max_priority_fee_per_gas)
+            .set_blob_fee(tx.max_fee_per_blob_gas);
+    }
+    mock
+}
+
+/// Returns an empty block with the recorded number, hash and gas limit of the new tip.
+fn sealed_block(update: &RecordedCanonicalUpdate) -> SealedBlock<Block> {
+    let header =
+        Header { number: update.number, gas_limit: update.gas_limit, ..Default::default() };
+    SealedBlock::from_parts_unchecked(header, Default::default(), update.hash)
+}
+
+/// Statistics collected while replaying a pool trace.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Number of transactions that were added to the pool.
+    pub added: usize,
+    /// Number of valid transactions the pool rejected.
+    pub rejected: usize,
+    /// Number of recorded transactions that failed validation.
+    pub invalid: usize,
+    /// Number of transactions that were discarded from the pool after they were added.
+    pub evicted: usize,
+    /// Number of transactions that were mined.
+    pub mined: usize,
+    /// Trace time between adding a transaction that was not pending and its promotion to the
+    /// pending pool, in the order the promotions happened.
+    pub promotion_latencies: Vec<Duration>,
+    /// The maximum reported size of all transactions in the pool.
+    pub peak_size: usize,
+    /// The maximum number of transactions in the pool.
+    pub peak_transactions: usize,
+    /// Number of replayed best transactions requests.
+    pub best_transactions_calls: usize,
+    /// Number of transactions yielded by all best transactions requests.
+    pub best_transactions_yielded: usize,
+    /// Time spent iterating the best transactions.
+    pub best_transactions_elapsed: Duration,
+}
+
+impl ReplayReport {
+    /// Returns the average promotion latency, if any transaction was promoted.
+    pub fn mean_promotion_latency(&self) -> Option<Duration> {
+        let count = self.promotion_latencies.len() as u32;
+        (count > 0).then(|| self.promotion_latencies.iter().sum::<Duration>() / count)
+    }
+
+    /// Returns the number of best transactions yielded per second.
+    pub fn best_transactions_throughput(&self) -> f64 {
+        let secs = self.best_transactions_elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0
+        }
+        self.best_transactions_yielded as f64 / secs
+    }
+
+    /// Drains all buffered pool events and updates the statistics.
+    fn drain_events(
+        &mut self,
+        listener: &mut AllTransactionsEvents<MockTransaction>,
+        parked: &mut HashMap<TxHash, Duration>,
+        now: Duration,
+    ) {
+        while let Ok(event) = listener.events.try_recv() {
+            match event {
+                FullTransactionEvent::Queued(hash) => {
+                    parked.entry(hash).or_insert(now);
+                }
+                FullTransactionEvent::Pending(hash) => {
+                    if let Some(added) = parked.remove(&hash) {
+                        self.promotion_latencies.push(now.saturating_sub(added));
+                    }
+                }
+                FullTransactionEvent::Discarded(hash) => {
+                    parked.remove(&hash);
+                    self.evicted += 1;
+                }
+                FullTransactionEvent::Evicted { tx_hash, .. } => {
+                    parked.remove(&tx_hash);
+                    self.evicted += 1;
+                }
+                FullTransactionEvent::Mined { tx_hash, .. } => {
+                    parked.remove(&tx_hash);
+                    self.mined += 1;
+                }
+                FullTransactionEvent::Replaced { transaction, .. } => {
+                    parked.remove(transaction.hash());
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "added: {}, rejected: {}, invalid: {}, evicted: {}, mined: {}",
+            self.added, self.rejected, self.invalid, self.evicted, self.mined
+        )?;
+        writeln!(
+            f,
+            "promotions: {}, mean promotion latency: {:?}",
+            self.promotion_latencies.len(),
+            self.mean_promotion_latency().unwrap_or_default()
+        )?;
+        writeln!(
+            f,
+            "peak pool size: {} bytes, peak transactions: {}",
+            self.peak_size, self.peak_transactions
+        )?;
+        write!(
+            f,
+            "best transactions: {} calls, {} yielded, {:.0} tx/s",
+            self.best_transactions_calls,
+            self.best_transactions_yielded,
+            self.best_transactions_throughput()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        record::{RecordedAccount, RecordedBestTransactions},
+        SubPoolLimit, TransactionOrigin,
+    };
+    use alloy_primitives::{Address, B256, U256};
+
+    fn recorded(sender: Address, nonce: u64, timestamp: u64) -> PoolTraceEvent {
+        PoolTraceEvent::AddTransaction(RecordedTransaction {
+            timestamp,
+            origin: 1,
+            hash: B256::random(),
+            sender,
+            nonce,
+            tx_type: TxType::Eip1559 as u8,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 1,
+            max_fee_per_blob_gas: 0,
+            gas_limit: 21_000,
+            value: U256::ZERO,
+            size: 100,
+            valid: true,
+            balance: U256::MAX,
+            state_nonce: 0,
+        })
+    }
+
+    #[test]
+    fn replay_trace() {
+        let sender = Address::random();
+        let events = vec![
+            // nonce gap, queued until the first transaction arrives
+            recorded(sender, 1, 0),
+            recorded(sender, 0, 1_000),
+            recorded(Address::random(), 5, 2_000),
+            PoolTraceEvent::BestTransactions(RecordedBestTransactions {
+                timestamp: 3_000,
+                basefee: 0,
+                blob_fee: 0,
+            }),
+            PoolTraceEvent::CanonicalStateUpdate(RecordedCanonicalUpdate {
+                timestamp: 4_000,
+                number: 1,
+                hash: B256::random(),
+                gas_limit: 30_000_000,
+                pending_basefee: 0,
+                pending_blob_fee: 0,
+                reorg: false,
+                mined_transactions: vec![],
+                changed_accounts: vec![RecordedAccount {
+                    address: sender,
+                    nonce: 2,
+                    balance: U256::MAX,
+                }],
+            }),
+        ];
+        assert_eq!(events[0].timestamp(), Duration::ZERO);
+        assert!(
+            matches!(&events[0], PoolTraceEvent::AddTransaction(tx) if tx.origin() == Some(TransactionOrigin::External))
+        );
+
+        let report = PoolTraceReplay::new(Default::default()).replay(&events);
+        assert_eq!(report.added, 3);
+        assert_eq!(report.promotion_latencies, vec![Duration::from_millis(1)]);
+        assert_eq!(report.best_transactions_calls, 1);
+        assert_eq!(report.best_transactions_yielded, 2);
+        assert_eq!(report.peak_transactions, 3);
+        assert_eq!(report.peak_size, 300);
+        // both transactions of the sender are outdated after the update
+        assert_eq!(report.evicted, 2);
+
+        // a pool without room for queued transactions discards them on insert
+        let config = PoolConfig {
+            queued_limit: SubPoolLimit { max_txs: 0, max_size: usize::MAX },
+            ..Default::default()
+        };
+        let report = PoolTraceReplay::new(config).replay(&events);
+        assert_eq!(report.added, 1);
+        assert_eq!(report.rejected, 2);
+        assert_eq!(report.peak_transactions, 1);
+        assert!(report.promotion_latencies.is_empty());
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     error::{InvalidPoolTransactionError, PoolResult},
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
-    AllTransactionsEvents,
+    AllTransactionsEvents, TransactionLifetimes,
 };
 use alloy_consensus::{BlockHeader, Signed, Typed2718};
 use alloy_eips::{
@@ -31,6 +31,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::sync::mpsc::Receiver;
 
@@ -110,6 +111,17 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> impl Future<Output = PoolResult<TxHash>> + Send;
 
+    /// Adds an _unvalidated_ transaction into the pool that is evicted if it is still in the pool
+    /// once the given deadline has passed.
+    ///
+    /// Consumer: RPC
+    fn add_transaction_with_deadline(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        deadline: Instant,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -121,6 +133,22 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
 
+    /// Re-adds the given _unvalidated_ transactions that were admitted to the pool before, e.g.
+    /// transactions of reorged blocks or restored from a snapshot.
+    ///
+    /// Unlike [`TransactionPool::add_transactions`], this skips the
+    /// [`AdmissionPolicy`](crate::admission::AdmissionPolicy)s, which only apply to new
+    /// submissions.
+    ///
+    /// Consumer: Utility
+    fn reinject_transactions(
+        &self,
+        origin: TransactionOrigin,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send {
+        self.add_transactions(origin, transactions)
+    }
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -531,6 +559,10 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Evicts all transactions that exceeded their deadline or the given lifetime configured for
+    /// their sub-pool and origin, and returns their hashes.
+    fn evict_expired_transactions(&self, lifetimes: &TransactionLifetimes) -> Vec<TxHash>;
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -668,7 +700,7 @@ pub struct NewBlobSidecar {
 ///
 /// Depending on where the transaction was picked up, it affects how the transaction is handled
 /// internally, e.g. limits for simultaneous transaction of one sender.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
 pub enum TransactionOrigin {
     /// Transaction is coming from a local source.
     #[default]
@@ -7,7 +7,7 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{TransactionSimulator, ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -41,9 +41,21 @@ use tokio::sync::Mutex;
 pub struct EthTransactionValidator<Client, T> {
     /// The type that performs the actual validation.
     inner: Arc<EthTransactionValidatorInner<Client, T>>,
+    /// Optional simulation of transactions that passed validation.
+    simulator: Option<Arc<dyn TransactionSimulator<T>>>,
 }
 
 impl<Client, Tx> EthTransactionValidator<Client, Tx> {
+    /// Installs a [`TransactionSimulator`] that is invoked for all transactions that passed
+    /// validation.
+    ///
+    /// Transactions that fail the simulation are rejected with
+    /// [`InvalidPoolTransactionError::Simulation`].
+    pub fn with_simulator(mut self, simulator: impl TransactionSimulator<Tx>) -> Self {
+        self.simulator = Some(Arc::new(simulator));
+        self
+    }
+
     /// Returns the configured chain spec
     pub fn chain_spec(&self) -> Arc<Client::ChainSpec>
     where
@@ -71,7 +83,8 @@ where
         origin: TransactionOrigin,
         transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
-        self.inner.validate_one(origin, transaction)
+        let outcome = self.inner.validate_one(origin, transaction);
+        self.simulate(origin, outcome)
     }
 
     /// Validates all given transactions.
@@ -83,7 +96,51 @@ where
         &self,
         transactions: Vec<(TransactionOrigin, Tx)>,
     ) -> Vec<TransactionValidationOutcome<Tx>> {
-        self.inner.validate_batch(transactions)
+        if self.simulator.is_none() {
+            return self.inner.validate_batch(transactions)
+        }
+        let origins = transactions.iter().map(|(origin, _)| *origin).collect::<Vec<_>>();
+        self.inner
+            .validate_batch(transactions)
+            .into_iter()
+            .zip(origins)
+            .map(|(outcome, origin)| self.simulate(origin, outcome))
+            .collect()
+    }
+
+    /// Simulates the transaction if it is valid and a [`TransactionSimulator`] is installed.
+    fn simulate(
+        &self,
+        origin: TransactionOrigin,
+        outcome: TransactionValidationOutcome<Tx>,
+    ) -> TransactionValidationOutcome<Tx> {
+        let Some(simulator) = &self.simulator else { return outcome };
+        match outcome {
+            TransactionValidationOutcome::Valid {
+                balance,
+                state_nonce,
+                transaction,
+                propagate,
+                bytecode_hash,
+                authorities,
+            } => {
+                if let Err(err) = simulator.simulate(origin, transaction.transaction()) {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction.into_transaction(),
+                        err.into(),
+                    )
+                }
+                TransactionValidationOutcome::Valid {
+                    balance,
+                    state_nonce,
+                    transaction,
+                    propagate,
+                    bytecode_hash,
+                    authorities,
+                }
+            }
+            outcome => outcome,
+        }
     }
 }
 
@@ -476,6 +533,12 @@ where
             }
         }
 
+        // Recover the authorities of the authorization list, so the pool can track transactions
+        // whose nonce or code an authorization changes
+        let authorities = transaction.authorization_list().map(|auths| {
+            auths.iter().flat_map(|auth| auth.recover_authority()).collect::<Vec<_>>()
+        });
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
@@ -489,6 +552,8 @@ where
                 }
                 TransactionOrigin::Private => false,
             },
+            bytecode_hash: account.bytecode_hash,
+            authorities,
         }
     }
 
@@ -797,7 +862,7 @@ impl<Client> EthTransactionValidatorBuilder<Client> {
             _marker: Default::default(),
         };
 
-        EthTransactionValidator { inner: Arc::new(inner) }
+        EthTransactionValidator { inner: Arc::new(inner), simulator: None }
     }
 
     /// Builds a [`EthTransactionValidator`] and spawns validation tasks via the
@@ -14,11 +14,18 @@ use std::{fmt, future::Future, time::Instant};
 
 mod constants;
 mod eth;
+mod simulate;
 mod task;
 
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// Pre-execution simulation of valid transactions.
+pub use simulate::{
+    EvmTransactionSimulator, SimulationConfig, TransactionSimulator, DEFAULT_SIMULATION_CACHE_SIZE,
+    DEFAULT_SIMULATION_GAS_LIMIT, DEFAULT_SIMULATION_TIMEOUT,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -46,6 +53,15 @@ pub enum TransactionValidationOutcome<T: PoolTransaction> {
         transaction: ValidTransaction<T>,
         /// Whether to propagate the transaction to the network.
         propagate: bool,
+        /// The bytecode hash of the sender, if the sender has code.
+        ///
+        /// Since Prague, this is the hash of the EIP-7702 delegation designator of a delegated
+        /// account.
+        bytecode_hash: Option<B256>,
+        /// The authorities of the EIP-7702 authorization list of the transaction, if any.
+        ///
+        /// Authorizations whose signer can't be recovered are skipped.
+        authorities: Option<Vec<Address>>,
     },
     /// The transaction is considered invalid indefinitely: It violates constraints that prevent
     /// this transaction from ever becoming valid.
@@ -270,6 +286,10 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub timestamp: Instant,
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
+    /// Optional deadline after which the transaction is evicted from the pool.
+    pub deadline: Option<Instant>,
+    /// The senders of the EIP-7702 authorizations of the transaction, if any.
+    pub authority_ids: Option<Vec<SenderId>>,
 }
 
 // === impl ValidPoolTransaction ===
@@ -367,6 +387,12 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.origin.is_local()
     }
 
+    /// Returns `true` if the transaction was submitted with a deadline that passed at the given
+    /// instant.
+    pub fn is_deadline_exceeded(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+
     /// Whether the transaction is an EIP-4844 blob transaction.
     #[inline]
     pub fn is_eip4844(&self) -> bool {
@@ -456,6 +482,8 @@ impl<T: PoolTransaction> Clone for ValidPoolTransaction<T> {
             propagate: self.propagate,
             timestamp: self.timestamp,
             origin: self.origin,
+            deadline: self.deadline,
+            authority_ids: self.authority_ids.clone(),
         }
     }
 }
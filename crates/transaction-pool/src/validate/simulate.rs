@@ -0,0 +1,438 @@
+//! Pre-execution simulation of transactions.
+
+use crate::{
+    error::SimulationError, metrics::TransactionSimulationMetrics, traits::TransactionOrigin,
+    PoolTransaction,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, TxHash, B256, U256};
+use parking_lot::Mutex;
+use reth_evm::{ConfigureEvm, Evm, TransactionEnv};
+use reth_primitives_traits::SealedHeader;
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    interpreter::{InstructionResult, Interpreter},
+    primitives::ExecutionResult,
+    EvmContext, Inspector,
+};
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+/// Default gas budget of a single simulation.
+pub const DEFAULT_SIMULATION_GAS_LIMIT: u64 = 5_000_000;
+
+/// Default time budget of a single simulation.
+pub const DEFAULT_SIMULATION_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Default number of simulation results that are cached.
+pub const DEFAULT_SIMULATION_CACHE_SIZE: u32 = 10_000;
+
+/// Number of executed instructions after which the time budget is checked.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+/// Simulates transactions before they are admitted to the pool.
+///
+/// A simulator can be installed on the
+/// [`EthTransactionValidator`](crate::validate::EthTransactionValidator) with
+/// [`with_simulator`](crate::validate::EthTransactionValidator::with_simulator). It is only
+/// invoked for transactions that passed all other validation checks.
+pub trait TransactionSimulator<T>: fmt::Debug + Send + Sync + 'static {
+    /// Simulates the transaction and returns an error if it would fail.
+    ///
+    /// Simulations that can't reach a verdict, e.g. because they ran out of budget, must not
+    /// return an error.
+    fn simulate(&self, origin: TransactionOrigin, transaction: &T) -> Result<(), SimulationError>;
+}
+
+/// Configuration for the [`EvmTransactionSimulator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// Maximum amount of gas a single simulation can use.
+    ///
+    /// Transactions with a higher gas limit are simulated with this gas limit, if they fail the
+    /// simulation is inconclusive.
+    pub gas_limit: u64,
+    /// Maximum time a single simulation can take before it is aborted as inconclusive.
+    pub timeout: Duration,
+    /// Number of simulation results to cache.
+    pub cache_size: u32,
+    /// Whether only [`TransactionOrigin::Private`] transactions are simulated.
+    ///
+    /// This is the default, as simulating every transaction received from the network is costly.
+    pub private_only: bool,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            gas_limit: DEFAULT_SIMULATION_GAS_LIMIT,
+            timeout: DEFAULT_SIMULATION_TIMEOUT,
+            cache_size: DEFAULT_SIMULATION_CACHE_SIZE,
+            private_only: true,
+        }
+    }
+}
+
+/// A [`TransactionSimulator`] that executes transactions with the node's [`ConfigureEvm`] on top
+/// of the latest state.
+///
+/// Transactions are executed in the environment of the block following the latest block, with the
+/// base fee ignored. The nonce of the sender is set to the nonce of the transaction, as if all
+/// preceding transactions of the sender in the pool were executed before.
+///
+/// Results are cached by sender and nonce until the latest block changes.
+pub struct EvmTransactionSimulator<Client, EvmConfig> {
+    /// The client used to access the latest state.
+    client: Client,
+    /// The EVM configuration used to execute transactions.
+    evm_config: EvmConfig,
+    /// The gas and time budget of simulations.
+    config: SimulationConfig,
+    /// Cached simulation results by sender and nonce.
+    cache: Mutex<LruMap<(Address, u64), CachedSimulation>>,
+    /// Simulation metrics.
+    metrics: TransactionSimulationMetrics,
+}
+
+impl<Client, EvmConfig> EvmTransactionSimulator<Client, EvmConfig> {
+    /// Creates a new simulator with the given configuration.
+    pub fn new(client: Client, evm_config: EvmConfig, config: SimulationConfig) -> Self {
+        let cache = Mutex::new(LruMap::new(ByLength::new(config.cache_size)));
+        Self { client, evm_config, config, cache, metrics: Default::default() }
+    }
+
+    /// Returns the configuration of the simulator.
+    pub const fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+}
+
+impl<Client, EvmConfig> EvmTransactionSimulator<Client, EvmConfig>
+where
+    Client: StateProviderFactory + BlockReaderIdExt<Header = EvmConfig::Header>,
+    EvmConfig: ConfigureEvm,
+{
+    /// Executes the transaction on top of the state of the given latest block.
+    ///
+    /// Returns `None` if the simulation is inconclusive.
+    fn execute<T>(
+        &self,
+        header: &SealedHeader<EvmConfig::Header>,
+        transaction: &T,
+    ) -> Option<Result<(), SimulationError>>
+    where
+        T: PoolTransaction<Consensus = EvmConfig::Transaction>,
+    {
+        let state = match self.client.latest() {
+            Ok(state) => state,
+            Err(err) => {
+                debug!(target: "txpool", %err, "Failed to get latest state for simulation");
+                return None
+            }
+        };
+
+        let mut db = CacheDB::new(StateProviderDatabase::new(state));
+        // execute the transaction as if all preceding transactions of the sender were executed
+        match db.load_account(transaction.sender()) {
+            Ok(account) => account.info.nonce = transaction.nonce(),
+            Err(err) => {
+                debug!(target: "txpool", %err, "Failed to load sender for simulation");
+                return None
+            }
+        }
+
+        let mut evm_env = self.evm_config.evm_env(header.header());
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        evm_env.block_env.number = U256::from(header.number() + 1);
+        evm_env.block_env.timestamp = U256::from(now.max(header.timestamp() + 1));
+        // the fee requirements are enforced by the pool
+        evm_env.block_env.basefee = U256::ZERO;
+
+        let consensus = transaction.clone_into_consensus();
+        let mut tx_env = self.evm_config.tx_env(consensus.tx(), consensus.signer());
+        let capped = tx_env.gas_limit() > self.config.gas_limit;
+        if capped {
+            tx_env.set_gas_limit(self.config.gas_limit);
+        }
+
+        let mut inspector = TimeoutInspector::new(Instant::now() + self.config.timeout);
+        let result = {
+            let mut evm = self.evm_config.evm_with_env_and_inspector(db, evm_env, &mut inspector);
+            evm.transact(tx_env)
+        };
+
+        match result {
+            Ok(res) => match res.result {
+                ExecutionResult::Success { .. } => Some(Ok(())),
+                // the transaction may only have failed because it ran out of budget
+                _ if capped || inspector.timed_out => None,
+                ExecutionResult::Revert { gas_used, output } => {
+                    Some(Err(SimulationError::Reverted { gas_used, output }))
+                }
+                ExecutionResult::Halt { reason, gas_used } => {
+                    Some(Err(SimulationError::Halted { gas_used, reason: format!("{reason:?}") }))
+                }
+            },
+            Err(err) => {
+                debug!(target: "txpool", %err, tx=%transaction.hash(), "Failed to simulate transaction");
+                None
+            }
+        }
+    }
+}
+
+impl<T, Client, EvmConfig> TransactionSimulator<T> for EvmTransactionSimulator<Client, EvmConfig>
+where
+    T: PoolTransaction<Consensus = EvmConfig::Transaction>,
+    Client:
+        StateProviderFactory + BlockReaderIdExt<Header = EvmConfig::Header> + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm,
+{
+    fn simulate(&self, origin: TransactionOrigin, transaction: &T) -> Result<(), SimulationError> {
+        if self.config.private_only && !origin.is_private() {
+            return Ok(())
+        }
+
+        let header = match self.client.latest_header() {
+            Ok(Some(header)) => header,
+            Ok(None) => return Ok(()),
+            Err(err) => {
+                debug!(target: "txpool", %err, "Failed to get latest header for simulation");
+                return Ok(())
+            }
+        };
+
+        let key = (transaction.sender(), transaction.nonce());
+        if let Some(cached) = self.cache.lock().get(&key) {
+            if cached.hash == *transaction.hash() && cached.block_hash == header.hash() {
+                self.metrics.cache_hits.increment(1);
+                return cached.result.clone()
+            }
+        }
+
+        let start = Instant::now();
+        let result = self.execute(&header, transaction);
+        self.metrics.simulated_transactions.increment(1);
+        self.metrics.simulation_duration.record(start.elapsed());
+
+        let Some(result) = result else {
+            self.metrics.inconclusive_simulations.increment(1);
+            return Ok(())
+        };
+        if result.is_err() {
+            self.metrics.failed_transactions.increment(1);
+        }
+
+        self.cache.lock().insert(
+            key,
+            CachedSimulation {
+                hash: *transaction.hash(),
+                block_hash: header.hash(),
+                result: result.clone(),
+            },
+        );
+        result
+    }
+}
+
+impl<Client, EvmConfig> fmt::Debug for EvmTransactionSimulator<Client, EvmConfig> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EvmTransactionSimulator")
+            .field("config", &self.config)
+            .field("cached", &self.cache.lock().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A simulation result of a transaction.
+#[derive(Debug)]
+struct CachedSimulation {
+    /// The hash of the simulated transaction.
+    hash: TxHash,
+    /// The hash of the block the transaction was simulated on.
+    block_hash: B256,
+    /// The outcome of the simulation.
+    result: Result<(), SimulationError>,
+}
+
+/// An [`Inspector`] that aborts the execution once the deadline passed.
+#[derive(Debug)]
+struct TimeoutInspector {
+    deadline: Instant,
+    steps: u64,
+    timed_out: bool,
+}
+
+impl TimeoutInspector {
+    const fn new(deadline: Instant) -> Self {
+        Self { deadline, steps: 0, timed_out: false }
+    }
+}
+
+impl<DB: reth_revm::Database> Inspector<DB> for TimeoutInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.steps += 1;
+        if self.steps % TIMEOUT_CHECK_INTERVAL == 0 && Instant::now() >= self.deadline {
+            self.timed_out = true;
+            interp.instruction_result = InstructionResult::OutOfGas;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_consensus::Header;
+    use alloy_primitives::{Bytes, TxKind};
+    use reth_chainspec::MAINNET;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::Block;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    fn simulator() -> (MockEthProvider, EvmTransactionSimulator<MockEthProvider, EthEvmConfig>) {
+        let provider = MockEthProvider::default();
+        let header = Header {
+            number: 20_000_000,
+            timestamp: 1_720_000_000,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+        provider.add_block(B256::random(), Block { header, body: Default::default() });
+        let simulator = EvmTransactionSimulator::new(
+            provider.clone(),
+            EthEvmConfig::new(MAINNET.clone()),
+            SimulationConfig { private_only: false, ..Default::default() },
+        );
+        (provider, simulator)
+    }
+
+    fn call(sender: Address, nonce: u64, to: Address) -> MockTransaction {
+        let mut tx = MockTransaction::eip1559()
+            .with_sender(sender)
+            .with_nonce(nonce)
+            .with_gas_limit(100_000);
+        if let MockTransaction::Eip1559 { to: kind, .. } = &mut tx {
+            *kind = TxKind::Call(to);
+        }
+        tx
+    }
+
+    #[test]
+    fn rejects_reverting_transactions() {
+        let (provider, simulator) = simulator();
+        let sender = Address::random();
+        let reverting = Address::random();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+        // PUSH0 PUSH0 REVERT
+        provider.add_account(
+            reverting,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&[0x5f, 0x5f, 0xfd])),
+        );
+
+        let transfer = call(sender, 0, Address::random());
+        assert_eq!(simulator.simulate(TransactionOrigin::External, &transfer), Ok(()));
+
+        let tx = call(sender, 0, reverting);
+        assert!(matches!(
+            simulator.simulate(TransactionOrigin::External, &tx),
+            Err(SimulationError::Reverted { .. })
+        ));
+
+        // the sender's pending nonce is used
+        let tx = call(sender, 5, reverting);
+        assert!(matches!(
+            simulator.simulate(TransactionOrigin::External, &tx),
+            Err(SimulationError::Reverted { .. })
+        ));
+    }
+
+    #[test]
+    fn caches_by_sender_and_nonce() {
+        let (provider, simulator) = simulator();
+        let sender = Address::random();
+        let target = Address::random();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+        // STOP
+        provider.add_account(
+            target,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[0x00])),
+        );
+
+        let tx = call(sender, 0, target);
+        assert_eq!(simulator.simulate(TransactionOrigin::External, &tx), Ok(()));
+
+        // PUSH0 PUSH0 REVERT
+        provider.add_account(
+            target,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&[0x5f, 0x5f, 0xfd])),
+        );
+        // served from the cache
+        assert_eq!(simulator.simulate(TransactionOrigin::External, &tx), Ok(()));
+
+        // a replacement is simulated again
+        let replacement = call(sender, 0, target);
+        assert!(simulator.simulate(TransactionOrigin::External, &replacement).is_err());
+    }
+
+    #[test]
+    fn private_only() {
+        let (provider, _) = simulator();
+        let simulator = EvmTransactionSimulator::new(
+            provider.clone(),
+            EthEvmConfig::new(MAINNET.clone()),
+            SimulationConfig::default(),
+        );
+        let sender = Address::random();
+        let reverting = Address::random();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+        provider.add_account(
+            reverting,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&[0x5f, 0x5f, 0xfd])),
+        );
+
+        let tx = call(sender, 0, reverting);
+        assert_eq!(simulator.simulate(TransactionOrigin::External, &tx), Ok(()));
+        assert!(simulator.simulate(TransactionOrigin::Private, &tx).is_err());
+    }
+
+    #[test]
+    fn capped_failures_are_inconclusive() {
+        let (provider, _) = simulator();
+        let simulator = EvmTransactionSimulator::new(
+            provider.clone(),
+            EthEvmConfig::new(MAINNET.clone()),
+            SimulationConfig { gas_limit: 50_000, private_only: false, ..Default::default() },
+        );
+        let sender = Address::random();
+        let reverting = Address::random();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(10).pow(U256::from(18))));
+        // PUSH0 PUSH0 REVERT
+        provider.add_account(
+            reverting,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&[0x5f, 0x5f, 0xfd])),
+        );
+
+        // the revert may be caused by the capped gas limit
+        let tx = call(sender, 0, reverting);
+        assert_eq!(simulator.simulate(TransactionOrigin::External, &tx), Ok(()));
+
+        let tx = call(sender, 0, reverting).with_gas_limit(50_000);
+        assert!(matches!(
+            simulator.simulate(TransactionOrigin::External, &tx),
+            Err(SimulationError::Reverted { .. })
+        ));
+    }
+}
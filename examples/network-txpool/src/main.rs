@@ -89,6 +89,8 @@ impl TransactionValidator for OkValidator {
             state_nonce: transaction.nonce(),
             transaction: ValidTransaction::Valid(transaction),
             propagate: false,
+            bytecode_hash: None,
+            authorities: None,
         }
     }
 }